@@ -0,0 +1,181 @@
+import crypto from 'crypto';
+import fs from 'fs';
+import path from 'path';
+import { decodeText } from './utils/encoding.mjs';
+import { executeGitCommand, getBlobBytes, getFileHashAtCommit, resolveRepositoryPath } from './utils/git.mjs';
+import { FileNotFoundError } from './errors.mjs';
+
+/**
+ * Hex digits of the SHA-256 digest kept in an anchor ID
+ */
+export const ANCHOR_ID_LENGTH = 16;
+
+/**
+ * Runs of whitespace collapsed to one space when normalizing
+ */
+const WHITESPACE_PATTERN = /\s+/g;
+
+/**
+ * Computes the anchor ID of a sequence of lines
+ * @param {string[]} lines - Line contents without line terminators
+ * @param {boolean} [normalizeWhitespace=false] - Strip each line and collapse inner runs of whitespace to one space
+ *   before hashing
+ * @returns {string} 'sha256:' followed by ANCHOR_ID_LENGTH hex digits
+ */
+export function hashLines(lines, normalizeWhitespace = false) {
+  const content = normalizeWhitespace
+    ? lines.map((line) => line.replace(WHITESPACE_PATTERN, ' ').trim())
+    : lines;
+  const digest = crypto.createHash('sha256').update(content.join('\n'), 'utf8').digest('hex');
+  return `sha256:${digest.slice(0, ANCHOR_ID_LENGTH)}`;
+}
+
+/**
+ * Creates an anchor for a range of lines of a file
+ * Identifies the lines by a hash of their content rather than by line numbers alone, so an anchor (a review comment,
+ * a bookmark) can be found again with locateContentAnchor after edits elsewhere in the file shift the lines. Returns
+ * { anchorId, path, startLine, endLine, commit, normalizeWhitespace }: anchorId 'sha256:' and ANCHOR_ID_LENGTH hex
+ * digits, path relative to repo root and commit the SHA the lines were read at (null for the working tree).
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {number} startLine - First line of the range (1-based)
+ * @param {object} [options={}] - Options
+ * @param {number} [options.endLine] - Last line of the range (default: startLine)
+ * @param {string|null} [options.commit='HEAD'] - Revision to read the file at; null reads the working tree
+ * @param {boolean} [options.normalizeWhitespace=false] - Hash the lines with whitespace normalized, so reindenting
+ *   them keeps the ID
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object>} Anchor
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit
+ * @throws {FileNotFoundError} If the file does not exist at commit
+ * @throws {TypeError} If the range is empty or past the end of the file
+ */
+export async function createContentAnchor(filePath, startLine, options = {}) {
+  const { endLine = startLine, commit = 'HEAD', normalizeWhitespace = false, repoPath = process.cwd() } = options;
+
+  if (!Number.isInteger(startLine) || !Number.isInteger(endLine) || startLine < 1 || endLine < startLine) {
+    throw new TypeError(`Invalid line range: ${startLine}-${endLine}`);
+  }
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+  const oid = await resolveCommit(repoRoot, commit);
+  const lines = await readLines(repoRoot, relativePath, oid);
+
+  if (endLine > lines.length) {
+    throw new TypeError(
+      `Invalid line range: ${startLine}-${endLine}. ${relativePath} has ${lines.length} lines`
+    );
+  }
+
+  return {
+    anchorId: hashLines(lines.slice(startLine - 1, endLine), normalizeWhitespace),
+    path: relativePath,
+    startLine,
+    endLine,
+    commit: oid,
+    normalizeWhitespace
+  };
+}
+
+/**
+ * Finds the lines an anchor identifies in another version of its file
+ * Every run of lines as long as the anchor's whose hash matches is returned, closest to the anchor's original
+ * position first, so the first result is the best guess when the content occurs more than once.
+ * @param {object} anchor - Anchor from createContentAnchor, or a stored copy of one
+ * @param {object} [options={}] - Options
+ * @param {string|null} [options.commit='HEAD'] - Revision to search; null searches the working tree
+ * @param {string} [options.path] - File to search, if it was renamed since the anchor was created (absolute or
+ *   relative to repo root; default: anchor.path)
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object[]>} Anchors with the new line numbers, empty if the lines were changed or the file does not
+ *   exist at commit
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+export async function locateContentAnchor(anchor, options = {}) {
+  const { commit = 'HEAD', path: filePath = anchor.path, repoPath = process.cwd() } = options;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+  const oid = await resolveCommit(repoRoot, commit);
+
+  let lines;
+  try {
+    lines = await readLines(repoRoot, relativePath, oid);
+  } catch (error) {
+    if (error instanceof FileNotFoundError) {
+      return [];
+    }
+    throw error;
+  }
+
+  const count = anchor.endLine - anchor.startLine + 1;
+  const matches = [];
+  for (let start = 1; start + count - 1 <= lines.length; start++) {
+    if (hashLines(lines.slice(start - 1, start - 1 + count), anchor.normalizeWhitespace) === anchor.anchorId) {
+      matches.push({
+        anchorId: anchor.anchorId,
+        path: relativePath,
+        startLine: start,
+        endLine: start + count - 1,
+        commit: oid,
+        normalizeWhitespace: Boolean(anchor.normalizeWhitespace)
+      });
+    }
+  }
+
+  return matches.sort(
+    (a, b) => Math.abs(a.startLine - anchor.startLine) - Math.abs(b.startLine - anchor.startLine)
+  );
+}
+
+/**
+ * Resolves a revision to a commit SHA, passing null (the working tree) through
+ * @param {string} repoRoot - Repository root path
+ * @param {string|null} commit - Commit SHA, branch, tag or other revision, or null
+ * @returns {Promise<string | null>} Commit SHA, or null
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+async function resolveCommit(repoRoot, commit) {
+  if (commit === null) {
+    return null;
+  }
+  return executeGitCommand(['git', 'rev-parse', `${commit}^{commit}`], repoRoot);
+}
+
+/**
+ * Reads a file as lines, at a commit or from the working tree
+ * CRLF line endings are treated as LF, so a checkout with autocrlf hashes the same as the committed file.
+ * @param {string} repoRoot - Repository root path
+ * @param {string} relativePath - Path relative to repo root
+ * @param {string|null} commit - Commit SHA, or null for the working tree
+ * @returns {Promise<string[]>} Lines without terminators
+ * @throws {FileNotFoundError} If the file does not exist
+ */
+async function readLines(repoRoot, relativePath, commit) {
+  let data = null;
+  if (commit === null) {
+    try {
+      data = await fs.promises.readFile(path.join(repoRoot, relativePath));
+    } catch {
+      data = null;
+    }
+  } else {
+    const blobHash = await getFileHashAtCommit(repoRoot, relativePath, commit);
+    data = blobHash === null ? null : await getBlobBytes(repoRoot, blobHash);
+  }
+
+  if (data === null) {
+    throw new FileNotFoundError(
+      `File not found in repository: ${relativePath}`,
+      {
+        context: { repoPath: repoRoot, filePath: relativePath, commit }
+      }
+    );
+  }
+
+  const lines = decodeText(data).text.replace(/\r\n/g, '\n').split('\n');
+  if (lines[lines.length - 1] === '') {
+    lines.pop();
+  }
+  return lines;
+}
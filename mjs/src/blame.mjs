@@ -0,0 +1,256 @@
+import path from 'path';
+import { treeEntryAt } from './tree.mjs';
+import { DIFF_HUNK_PATTERN, renamedPath } from './diff.mjs';
+import { readAuthors } from './history.mjs';
+import { formatTimestamp } from './refs.mjs';
+import { executeGitCommand, getBlobBytes, getShallowBoundary, resolveRepositoryPath } from './utils/git.mjs';
+import { FileNotFoundError } from './errors.mjs';
+
+/**
+ * Attributes each line of a file to the commit that last changed it
+ * Lines are grouped into hunks of consecutive lines from the same commit, with the line numbers and path they had in
+ * that commit, so editor plugins can link a line to the version of the file it came from. Returns { oid, startLine,
+ * endLine, lineCount, origStartLine, origPath, authorName, authorEmail, authorDate, summary, boundary, shallowBoundary }
+ * hunks: oid all zeros for uncommitted lines, origPath the file's path in that commit (differs after a rename),
+ * authorDate ISO 8601, boundary true when the commit is a root commit or the boundary of the blamed range, so the
+ * lines may be older, and shallowBoundary true when the commit is where a shallow clone's history is cut off, so
+ * boundary does not mean a root commit.
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string|null} [options.rev='HEAD'] - Revision to blame; null blames the working tree file
+ * @param {number} [options.lineStart] - First line to blame (1-based; default: 1)
+ * @param {number} [options.lineEnd] - Last line to blame (default: end of file)
+ * @param {boolean} [options.ignoreWhitespace=false] - Ignore whitespace-only changes
+ * @param {boolean} [options.detectMoves=false] - Attribute lines moved or copied within the file, and from other files
+ * changed in the same commit, to where they came from (git blame -M -C)
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {boolean} [options.mailmap=true] - Map names and emails through .mailmap
+ * @returns {Promise<object[]>} Hunks ordered by startLine
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If rev does not name a commit, the file does not exist there, or the line range is past
+ * the end of the file
+ * @throws {TypeError} If lineStart or lineEnd is out of range
+ */
+export async function blameFile(filePath, options = {}) {
+  const {
+    rev = 'HEAD',
+    lineStart,
+    lineEnd,
+    ignoreWhitespace = false,
+    detectMoves = false,
+    repoPath = process.cwd(),
+    mailmap = true
+  } = options;
+
+  if (lineStart !== undefined && (!Number.isInteger(lineStart) || lineStart < 1)) {
+    throw new TypeError(`Invalid start line: ${lineStart}. Must be positive`);
+  }
+  if (lineEnd !== undefined && (!Number.isInteger(lineEnd) || lineEnd < (lineStart ?? 1))) {
+    throw new TypeError(`Invalid end line: ${lineEnd}. Must not be before the start line`);
+  }
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  const command = ['git', 'blame', '--incremental'];
+  if (ignoreWhitespace) {
+    command.push('-w');
+  }
+  if (detectMoves) {
+    command.push('-M', '-C');
+  }
+  if (lineStart !== undefined || lineEnd !== undefined) {
+    command.push(`-L${lineStart ?? 1},${lineEnd ?? ''}`);
+  }
+  if (rev !== null) {
+    command.push(rev);
+  }
+  command.push('--', relativePath);
+
+  const output = await executeGitCommand(command, repoRoot);
+  const shallowBoundary = new Set(await getShallowBoundary(repoRoot));
+
+  // Each hunk is "<oid> <orig line> <final line> <count>", then header lines for the commit (only the first time it
+  // appears), ending with "filename <path>"
+  const commits = new Map();
+  const hunks = [];
+  let header = null;
+  for (const line of output.split('\n')) {
+    if (header === null) {
+      header = line.split(' ');
+      continue;
+    }
+    const separator = line.indexOf(' ');
+    const key = separator === -1 ? line : line.slice(0, separator);
+    const value = separator === -1 ? '' : line.slice(separator + 1);
+    if (!commits.has(header[0])) {
+      commits.set(header[0], {});
+    }
+    const commit = commits.get(header[0]);
+    if (key !== 'filename') {
+      commit[key] = value;
+      continue;
+    }
+
+    const [oid, origLine, finalLine, count] = header;
+    const startLine = parseInt(finalLine, 10);
+    const lineCount = parseInt(count, 10);
+    hunks.push({
+      oid,
+      startLine,
+      endLine: startLine + lineCount - 1,
+      lineCount,
+      origStartLine: parseInt(origLine, 10),
+      origPath: value,
+      authorName: commit.author ?? '',
+      authorEmail: (commit['author-mail'] ?? '').replace(/^<|>$/g, ''),
+      authorDate: formatTimestamp(commit['author-time'] ?? '0', commit['author-tz'] ?? '+0000'),
+      summary: commit.summary ?? '',
+      boundary: 'boundary' in commit,
+      shallowBoundary: shallowBoundary.has(oid)
+    });
+    header = null;
+  }
+
+  if (!mailmap) {
+    // git blame always applies .mailmap, so read the recorded identities
+    const authors = await readAuthors(repoRoot, [...commits.keys()].filter((oid) => /[^0]/.test(oid)));
+    for (const hunk of hunks) {
+      [hunk.authorName, hunk.authorEmail] = authors.get(hunk.oid) ?? [hunk.authorName, hunk.authorEmail];
+    }
+  }
+
+  return hunks.sort((a, b) => a.startLine - b.startLine);
+}
+
+/**
+ * Finds where a line of a file at one commit is at another commit
+ * The line is carried through the diff between the two versions of the file, following a rename, so a stored
+ * line-anchored link can be moved onto a newer (or older) revision. Lines the diff changes or deletes have no
+ * counterpart and map to null rather than to nearby code. Returns { path, line, commit }: path at the target commit
+ * (differs after a rename), line there (1-based) and the target commit SHA.
+ * @param {string} filePath - File path at fromCommit (absolute or relative to repo root)
+ * @param {number} line - Line number at fromCommit (1-based)
+ * @param {string} fromCommit - Commit SHA, branch, tag or other revision the line number refers to
+ * @param {object} [options={}] - Options
+ * @param {string} [options.toCommit='HEAD'] - Revision to map the line onto
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object | null>} Mapping, or null if the line was changed or deleted, or the file no longer
+ *   exists at toCommit
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If fromCommit or toCommit does not name a commit
+ * @throws {FileNotFoundError} If the file does not exist at fromCommit
+ * @throws {TypeError} If line is not a positive integer or is past the end of the file at fromCommit
+ */
+export async function mapLine(filePath, line, fromCommit, options = {}) {
+  const { toCommit = 'HEAD', repoPath = process.cwd() } = options;
+
+  if (!Number.isInteger(line) || line < 1) {
+    throw new TypeError(`Invalid line: ${line}. Must be a positive integer`);
+  }
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+  const fromOid = await executeGitCommand(['git', 'rev-parse', `${fromCommit}^{commit}`], repoRoot);
+  const toOid = await executeGitCommand(['git', 'rev-parse', `${toCommit}^{commit}`], repoRoot);
+
+  const source = await treeEntryAt(repoRoot, fromOid, relativePath);
+  if (source === null || source.type !== 'blob') {
+    throw new FileNotFoundError(
+      `File not found at ${fromCommit}: ${relativePath}`,
+      {
+        context: { repoPath: repoRoot, filePath: relativePath, commit: fromCommit }
+      }
+    );
+  }
+
+  const content = await getBlobBytes(repoRoot, source.oid);
+  let lineCount = content.toString('latin1').split('\n').length - 1;
+  if (content.length > 0 && content.at(-1) !== 0x0a) {
+    // Last line without a newline
+    lineCount += 1;
+  }
+  if (line > lineCount) {
+    throw new TypeError(`Invalid line: ${line}. ${relativePath} has ${lineCount} lines at ${fromCommit}`);
+  }
+
+  const targetPath = await renamedPath(repoRoot, fromOid, toOid, relativePath);
+  const target = targetPath === null ? null : await treeEntryAt(repoRoot, toOid, targetPath);
+  if (target === null || target.type !== 'blob') {
+    return null;
+  }
+  if (target.oid === source.oid) {
+    return { path: targetPath, line, commit: toOid };
+  }
+
+  const patch = (
+    await executeGitCommand(
+      ['git', 'diff', '--no-ext-diff', '--no-textconv', '--text', '-U0', source.oid, target.oid],
+      repoRoot,
+      { encoding: 'buffer' }
+    )
+  ).toString('utf8');
+
+  let shift = 0;
+  for (const [, start, count, , newCount] of patch.matchAll(DIFF_HUNK_PATTERN)) {
+    const oldStart = parseInt(start, 10);
+    const oldCount = count === undefined ? 1 : parseInt(count, 10);
+    if (oldCount === 0) {
+      // Pure insertion after oldStart
+      if (line <= oldStart) {
+        break;
+      }
+    } else if (line < oldStart) {
+      break;
+    } else if (line < oldStart + oldCount) {
+      return null;
+    }
+    shift += (newCount === undefined ? 1 : parseInt(newCount, 10)) - oldCount;
+  }
+
+  return { path: targetPath, line: line + shift, commit: toOid };
+}
+
+/**
+ * Breaks down who wrote the current lines of a file
+ * Each line is credited to the author of the commit that last changed it, as blameFile reports; authors are told
+ * apart by email, ignoring case. Returns { name, email, lines, fraction } shares, most lines first: name as on the
+ * first of the author's lines and fraction the share of the file's lines (0 to 1).
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string|null} [options.rev='HEAD'] - Revision to blame; null uses the working tree file, crediting
+ *   uncommitted lines to 'Not Committed Yet'
+ * @param {boolean} [options.ignoreWhitespace=false] - Do not credit whitespace-only changes
+ * @param {boolean} [options.detectMoves=false] - Credit moved or copied lines to their original author
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {boolean} [options.mailmap=true] - Map authors through .mailmap, so lines written under several aliases are
+ *   credited to one person
+ * @returns {Promise<object[]>} Shares, empty for an empty file
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If rev does not name a commit or the file does not exist there
+ */
+export async function getFileOwnership(filePath, options = {}) {
+  const {
+    rev = 'HEAD',
+    ignoreWhitespace = false,
+    detectMoves = false,
+    repoPath = process.cwd(),
+    mailmap = true
+  } = options;
+
+  const hunks = await blameFile(filePath, { rev, ignoreWhitespace, detectMoves, repoPath, mailmap });
+
+  const shares = new Map();
+  for (const hunk of hunks) {
+    const key = hunk.authorEmail.toLowerCase();
+    if (!shares.has(key)) {
+      shares.set(key, { name: hunk.authorName, email: hunk.authorEmail, lines: 0, fraction: 0 });
+    }
+    shares.get(key).lines += hunk.lineCount;
+  }
+
+  const total = hunks.reduce((sum, hunk) => sum + hunk.lineCount, 0);
+  for (const share of shares.values()) {
+    share.fraction = share.lines / total;
+  }
+
+  return [...shares.values()].sort((a, b) => b.lines - a.lines || a.name.localeCompare(b.name));
+}
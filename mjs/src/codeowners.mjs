@@ -0,0 +1,143 @@
+import fs from 'fs';
+import path from 'path';
+import { executeGitCommand, getBlobBytes, getFileHashAtCommit, resolveRepositoryPath } from './utils/git.mjs';
+
+/**
+ * Where GitHub looks for the file, in order; the first one found is used
+ */
+export const CODEOWNERS_LOCATIONS = ['.github/CODEOWNERS', 'CODEOWNERS', 'docs/CODEOWNERS'];
+
+/**
+ * Parses the rules of a CODEOWNERS file
+ * Blank lines and comments ('#' at the start of a line or of a token) are skipped; a pattern starting with '#' is
+ * written '\#'. Returns [{ pattern, owners, line }] in file order: owners the '@user', '@org/team' or email owners,
+ * empty when the pattern marks paths as having no owner, and line the 1-based line number.
+ * @param {string} content - File content
+ * @returns {object[]} Rules
+ */
+export function parseCodeowners(content) {
+  const rules = [];
+  content.split(/\r?\n/).forEach((text, index) => {
+    const tokens = text.split(/\s+/).filter(Boolean);
+    if (tokens.length === 0 || tokens[0].startsWith('#')) {
+      return;
+    }
+
+    const pattern = tokens[0].startsWith('\\#') ? tokens[0].slice(1) : tokens[0];
+    const owners = [];
+    for (const token of tokens.slice(1)) {
+      if (token.startsWith('#')) {
+        break;
+      }
+      owners.push(token);
+    }
+    rules.push({ pattern, owners, line: index + 1 });
+  });
+  return rules;
+}
+
+/**
+ * Finds the owners of a file or directory from the repository's CODEOWNERS
+ * The file is looked for in CODEOWNERS_LOCATIONS order, and as on GitHub the last matching rule decides. Returns
+ * { path, owners, rule, source }: path relative to repo root, owners from the matching rule (empty if no rule matches
+ * or the rule lists none), rule the applying rule from parseCodeowners (null if none matches) and source the
+ * CODEOWNERS file used.
+ * @param {string} filePath - File or directory path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string|null} [options.commit='HEAD'] - Revision to read CODEOWNERS at; null reads the working tree
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object | null>} Owners, or null if the repository has no CODEOWNERS file
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+export async function getCodeOwners(filePath, options = {}) {
+  const { commit = 'HEAD', repoPath = process.cwd() } = options;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+  const oid = commit === null
+    ? null
+    : await executeGitCommand(['git', 'rev-parse', `${commit}^{commit}`], repoRoot);
+
+  for (const source of CODEOWNERS_LOCATIONS) {
+    const content = await readFile(repoRoot, source, oid);
+    if (content === null) {
+      continue;
+    }
+
+    let rule = null;
+    for (const candidate of parseCodeowners(content)) {
+      if (patternToRegex(candidate.pattern).test(relativePath)) {
+        rule = candidate;
+      }
+    }
+    return { path: relativePath, owners: rule ? rule.owners : [], rule, source };
+  }
+
+  return null;
+}
+
+/**
+ * Compiles a CODEOWNERS pattern to a regular expression over paths
+ * A pattern with a slash other than a trailing one is anchored at the repository root, otherwise it matches at any
+ * depth. A match also covers everything under a matched directory, except that a trailing '/*' only covers the
+ * directory's direct children. '**' spans directories. Paths are not checked against the file system, so 'apps/'
+ * matches the path 'apps' as the directory itself.
+ * @param {string} pattern - Path pattern
+ * @returns {RegExp} Regular expression
+ */
+function patternToRegex(pattern) {
+  const anchored = pattern.replace(/\/+$/, '').includes('/');
+  const directory = pattern.endsWith('/');
+  const glob = pattern.replace(/^\/+|\/+$/g, '');
+
+  let regex = '';
+  let index = 0;
+  while (index < glob.length) {
+    if (glob.startsWith('**/', index)) {
+      regex += '(?:.*/)?';
+      index += 3;
+    } else if (glob.startsWith('**', index)) {
+      regex += '.*';
+      index += 2;
+    } else if (glob[index] === '*') {
+      regex += '[^/]*';
+      index += 1;
+    } else if (glob[index] === '?') {
+      regex += '[^/]';
+      index += 1;
+    } else {
+      regex += glob[index].replace(/[.*+?^${}()|[\]\\/]/g, '\\$&');
+      index += 1;
+    }
+  }
+
+  const prefix = anchored ? '^' : '^(?:.*/)?';
+  let suffix = '(?:/.*)?$';
+  if (glob.endsWith('*') && !glob.endsWith('**') && !directory) {
+    suffix = '$';
+  }
+  return new RegExp(prefix + regex + suffix, 's');
+}
+
+/**
+ * Reads a text file at a commit or from the working tree
+ * @param {string} repoRoot - Repository root path
+ * @param {string} relativePath - Path relative to repo root
+ * @param {string|null} commit - Commit SHA, or null for the working tree
+ * @returns {Promise<string | null>} Content, or null if the file does not exist
+ */
+async function readFile(repoRoot, relativePath, commit) {
+  if (commit === null) {
+    try {
+      return await fs.promises.readFile(path.join(repoRoot, relativePath), 'utf8');
+    } catch {
+      return null;
+    }
+  }
+
+  const blobHash = await getFileHashAtCommit(repoRoot, relativePath, commit);
+  if (blobHash === null) {
+    return null;
+  }
+  return (await getBlobBytes(repoRoot, blobHash)).toString('utf8');
+}
@@ -0,0 +1,382 @@
+import fs from 'fs';
+import path from 'path';
+import { STATUS_CODES } from './status.mjs';
+import { treeEntryAt } from './tree.mjs';
+import { executeGitCommand, getRepositoryRoot, isHeadUnborn, resolveRepositoryPath } from './utils/git.mjs';
+import { resolveFilePath } from './utils/path.mjs';
+import { FileNotFoundError } from './errors.mjs';
+
+/**
+ * "@@ -<start>[,<count>] +<start>[,<count>] @@" unified diff hunk header
+ */
+export const DIFF_HUNK_PATTERN = /^@@ -(\d+)(?:,(\d+))? \+(\d+)(?:,(\d+))? @@/gm;
+
+/**
+ * Empty tree object per object format, the base of an unborn HEAD
+ */
+const EMPTY_TREE_OIDS = {
+  sha1: '4b825dc642cb6eb9a060e54bf8d69288fbee4904',
+  sha256: '6ef19b41225c5369f1c104d45d8d85efa9b057b53b14b4b9b939dd74decc5321'
+};
+
+/**
+ * Counts the lines a commit added and deleted, per file and in total, as in git show --stat
+ * Returns { commit, filesChanged, additions, deletions, files }, where each file is { path, additions, deletions,
+ * oldPath, binary }: path is the new path of a rename (oldPath the previous one, otherwise null) and binary files
+ * count 0 lines. The diff is taken against the first parent (against the empty tree for a root commit), so a merge
+ * reports what it brought into its branch.
+ * @param {string} [commit='HEAD'] - Commit SHA, branch, tag or other revision
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object>} Commit stats
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+export async function getCommitStats(commit = 'HEAD', options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const { oid, diffCommand } = await firstParentDiff(commit, repoRoot);
+  const tokens = (await executeGitCommand([...diffCommand, '--numstat', '-z'], repoRoot)).split('\0');
+
+  const files = [];
+  for (let i = 0; i < tokens.length; i++) {
+    if (!tokens[i]) {
+      continue;
+    }
+    // "<added>\t<deleted>\t<path>", or "<added>\t<deleted>\t" followed by the old and new paths of a rename
+    const [added, deleted, ...rest] = tokens[i].split('\t');
+    let filePath = rest.join('\t');
+    let oldPath = null;
+    if (!filePath) {
+      oldPath = tokens[++i];
+      filePath = tokens[++i];
+    }
+    const binary = added === '-';
+    files.push({
+      path: filePath,
+      additions: binary ? 0 : Number(added),
+      deletions: binary ? 0 : Number(deleted),
+      oldPath,
+      binary
+    });
+  }
+
+  return {
+    commit: oid,
+    filesChanged: files.length,
+    additions: files.reduce((total, file) => total + file.additions, 0),
+    deletions: files.reduce((total, file) => total + file.deletions, 0),
+    files
+  };
+}
+
+/**
+ * Lists the files a commit touched and how each changed
+ * Each entry is { path, change, oldPath }: change is 'added', 'modified', 'deleted', 'renamed', 'copied' or
+ * 'type-changed', path the new path of a rename or copy and oldPath its source (otherwise null). The diff is taken
+ * against the first parent (against the empty tree for a root commit); only paths are compared, so this is cheaper
+ * than getCommitStats for large commits.
+ * @param {string} [commit='HEAD'] - Commit SHA, branch, tag or other revision
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object[]>} Changed files in path order
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+export async function getCommitChangedFiles(commit = 'HEAD', options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const { diffCommand } = await firstParentDiff(commit, repoRoot);
+  const tokens = (await executeGitCommand([...diffCommand, '--name-status', '-z'], repoRoot)).split('\0');
+
+  const files = [];
+  for (let i = 0; i < tokens.length; i++) {
+    if (!tokens[i]) {
+      continue;
+    }
+    // "<status>\0<path>", or "R<score>\0<old path>\0<new path>" for renames and copies
+    const change = STATUS_CODES[tokens[i][0]] || 'modified';
+    const oldPath = change === 'renamed' || change === 'copied' ? tokens[++i] : null;
+    files.push({ path: tokens[++i], change, oldPath });
+  }
+
+  return files;
+}
+
+/**
+ * Gets the unified diff a commit introduced, as git show prints it
+ * The diff is taken against the first parent (against the empty tree for a root commit), with renames detected.
+ * Binary files are reported as differing without content.
+ * @param {string} [commit='HEAD'] - Commit SHA, branch, tag or other revision
+ * @param {object} [options={}] - Options
+ * @param {string} [options.path] - Only include changes to this file or directory (absolute or relative to repo root)
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<string>} Patch text, empty if the commit (or the path) has no changes
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+export async function getCommitPatch(commit = 'HEAD', options = {}) {
+  const { path: filePath, repoPath = process.cwd() } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const { diffCommand } = await firstParentDiff(commit, repoRoot);
+  const command = [...diffCommand, '-p'];
+  if (filePath !== undefined) {
+    command.push('--', resolveFilePath(repoRoot, filePath));
+  }
+
+  return (await executeGitCommand(command, repoRoot, { encoding: 'buffer' })).toString('utf8');
+}
+
+/**
+ * Diffs one file between two commits
+ * A rename between the commits is followed, so filePath is the path at commitA. Text conversion and external diff
+ * drivers are not applied. Returns { oldPath, newPath, change, binary, hunks, patch }: oldPath null if the file was
+ * added, newPath null if deleted, change 'added', 'deleted', 'modified', 'renamed' or 'unmodified', binary true when
+ * Git reports the file as binary (no hunks) and patch the unified diff as git diff prints it. Hunks are { oldStart,
+ * oldLines, newStart, newLines, section, lines, added, removed }: section the text after the header's closing '@@'
+ * (usually the enclosing function), lines the body prefixed with ' ', '-' or '+', and added and removed the changed
+ * lines without their prefix.
+ * @param {string} filePath - File path at commitA (absolute or relative to repo root)
+ * @param {string} commitA - Old side: commit SHA, branch, tag or other revision
+ * @param {object} [options={}] - Options
+ * @param {string} [options.commitB='HEAD'] - New side
+ * @param {number} [options.contextLines=3] - Unchanged lines shown around each change
+ * @param {boolean} [options.ignoreWhitespace=false] - Ignore whitespace-only changes
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object>} File diff
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commitA or commitB does not name a commit
+ * @throws {FileNotFoundError} If the file exists at neither commit
+ * @throws {TypeError} If contextLines is not a non-negative integer
+ */
+export async function diffFile(filePath, commitA, options = {}) {
+  const { commitB = 'HEAD', contextLines = 3, ignoreWhitespace = false, repoPath = process.cwd() } = options;
+
+  if (!Number.isInteger(contextLines) || contextLines < 0) {
+    throw new TypeError(`Invalid context lines: ${contextLines}. Must be a non-negative integer`);
+  }
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+  const oidA = await executeGitCommand(['git', 'rev-parse', `${commitA}^{commit}`], repoRoot);
+  const oidB = await executeGitCommand(['git', 'rev-parse', `${commitB}^{commit}`], repoRoot);
+
+  const newPath = await renamedPath(repoRoot, oidA, oidB, relativePath);
+  const pathspec = [relativePath];
+  if (newPath !== null && newPath !== relativePath) {
+    pathspec.push(newPath);
+  }
+
+  const command = diffCommand(contextLines, ignoreWhitespace);
+  const patch = (
+    await executeGitCommand([...command, '-M', oidA, oidB, '--', ...pathspec], repoRoot, { encoding: 'buffer' })
+  ).toString('utf8');
+
+  if (!patch && (await treeEntryAt(repoRoot, oidA, relativePath)) === null) {
+    throw new FileNotFoundError(
+      `File not found at ${commitA} or ${commitB}: ${relativePath}`,
+      {
+        context: { repoPath: repoRoot, filePath: relativePath }
+      }
+    );
+  }
+
+  return parseFileDiff(patch, relativePath, newPath);
+}
+
+/**
+ * Shows what changed locally in a file, against HEAD and against the index
+ * Complements getFileStatus, which tells whether a file changed, with the changes themselves. Returns { path, head,
+ * index }: head the working tree file compared with HEAD (staged and unstaged changes together, as git diff HEAD
+ * shows them) and index compared with the index (unstaged changes only, as git diff shows them), each in the form
+ * diffFile returns. A file deleted from the working tree is reported as deleted; with an unborn HEAD, the head side
+ * is compared with an empty tree.
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {number} [options.contextLines=3] - Unchanged lines shown around each change
+ * @param {boolean} [options.ignoreWhitespace=false] - Ignore whitespace-only changes
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object | null>} Local changes, or null if the file is untracked (neither in HEAD nor in the index)
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {FileNotFoundError} If the file is neither tracked nor on disk
+ * @throws {TypeError} If contextLines is not a non-negative integer
+ */
+export async function diffWorkdir(filePath, options = {}) {
+  const { contextLines = 3, ignoreWhitespace = false, repoPath = process.cwd() } = options;
+
+  if (!Number.isInteger(contextLines) || contextLines < 0) {
+    throw new TypeError(`Invalid context lines: ${contextLines}. Must be a non-negative integer`);
+  }
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  let base = 'HEAD';
+  let inHead = false;
+  if (await isHeadUnborn(repoRoot)) {
+    base = EMPTY_TREE_OIDS[await executeGitCommand('git rev-parse --show-object-format', repoRoot)];
+  } else {
+    inHead = (await treeEntryAt(repoRoot, base, relativePath)) !== null;
+  }
+  const inIndex = Boolean(await executeGitCommand(['git', 'ls-files', '--', relativePath], repoRoot));
+
+  if (!inHead && !inIndex) {
+    try {
+      fs.lstatSync(path.join(repoRoot, relativePath));
+      return null;
+    } catch {
+      throw new FileNotFoundError(
+        `File not found in repository: ${relativePath}`,
+        {
+          context: { repoPath: repoRoot, filePath: relativePath }
+        }
+      );
+    }
+  }
+
+  const command = diffCommand(contextLines, ignoreWhitespace);
+  const [head, index] = await Promise.all(
+    [[base], []].map(async (revision) =>
+      (await executeGitCommand([...command, ...revision, '--', relativePath], repoRoot, { encoding: 'buffer' })).toString(
+        'utf8'
+      )
+    )
+  );
+
+  return {
+    path: relativePath,
+    head: parseFileDiff(head, relativePath, relativePath),
+    index: parseFileDiff(index, relativePath, relativePath)
+  };
+}
+
+/**
+ * Builds a git diff command whose output does not depend on user config
+ * @param {number} contextLines - Unchanged lines shown around each change
+ * @param {boolean} ignoreWhitespace - Ignore whitespace-only changes
+ * @returns {string[]} Command without revisions or paths
+ */
+function diffCommand(contextLines, ignoreWhitespace) {
+  const command = ['git', 'diff', '--no-ext-diff', '--no-textconv', '--no-color', `-U${contextLines}`];
+  if (ignoreWhitespace) {
+    command.push('-w');
+  }
+  return command;
+}
+
+/**
+ * Builds a file diff from the git diff output for a single file
+ * @param {string} patch - Unified diff text, empty if the file is unchanged
+ * @param {string|null} oldPath - Path on the old side
+ * @param {string|null} newPath - Path on the new side
+ * @returns {object} File diff, as returned by diffFile, with oldPath or newPath cleared if the patch adds or deletes
+ *   the file
+ */
+function parseFileDiff(patch, oldPath, newPath) {
+  let change = patch ? 'modified' : 'unmodified';
+  let binary = false;
+  const hunks = [];
+  for (const line of patch.split('\n')) {
+    if (hunks.length) {
+      if ([' ', '-', '+'].includes(line[0])) {
+        hunks[hunks.length - 1].lines.push(line);
+        continue;
+      }
+      if (line.startsWith('\\')) {
+        // "\ No newline at end of file"
+        continue;
+      }
+    }
+
+    DIFF_HUNK_PATTERN.lastIndex = 0;
+    const match = DIFF_HUNK_PATTERN.exec(line);
+    if (match) {
+      const [header, oldStart, oldLines, newStart, newLines] = match;
+      hunks.push({
+        oldStart: parseInt(oldStart, 10),
+        oldLines: oldLines === undefined ? 1 : parseInt(oldLines, 10),
+        newStart: parseInt(newStart, 10),
+        newLines: newLines === undefined ? 1 : parseInt(newLines, 10),
+        section: line.slice(header.length).trim(),
+        lines: []
+      });
+    } else if (line.startsWith('new file mode')) {
+      oldPath = null;
+      change = 'added';
+    } else if (line.startsWith('deleted file mode')) {
+      newPath = null;
+      change = 'deleted';
+    } else if (line.startsWith('rename from')) {
+      change = 'renamed';
+    } else if (line.startsWith('Binary files')) {
+      binary = true;
+    }
+  }
+
+  return {
+    oldPath,
+    newPath,
+    change,
+    binary,
+    hunks: hunks.map((hunk) => ({
+      ...hunk,
+      added: hunk.lines.filter((line) => line.startsWith('+')).map((line) => line.slice(1)),
+      removed: hunk.lines.filter((line) => line.startsWith('-')).map((line) => line.slice(1))
+    })),
+    patch
+  };
+}
+
+/**
+ * Builds the diff-tree command comparing a commit with its first parent
+ * @param {string} commit - Commit SHA, branch, tag or other revision
+ * @param {string} repoRoot - Repository root path
+ * @returns {Promise<{oid: string, diffCommand: string}>} Commit SHA and command without output options
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+async function firstParentDiff(commit, repoRoot) {
+  const [oid, ...parents] = (
+    await executeGitCommand(['git', 'rev-list', '--parents', '-n', '1', `${commit}^{commit}`], repoRoot)
+  ).split(/\s+/);
+
+  if (parents.length > 0) {
+    return { oid, diffCommand: ['git', 'diff-tree', '-r', '-M', parents[0], oid] };
+  }
+  // Root commit: diff against the empty tree
+  return { oid, diffCommand: ['git', 'diff-tree', '-r', '-M', '--root', '--no-commit-id', oid] };
+}
+
+/**
+ * Follows a path from one commit to another through a rename
+ * @param {string} repoRoot - Repository root path
+ * @param {string} fromCommit - Commit SHA the path refers to
+ * @param {string} toCommit - Commit SHA to follow it to
+ * @param {string} entryPath - Path relative to repo root at fromCommit
+ * @returns {Promise<string | null>} Path at toCommit (entryPath itself if not renamed), or null if deleted
+ * @throws {GitCommandError} If either commit does not name a commit
+ */
+export async function renamedPath(repoRoot, fromCommit, toCommit, entryPath) {
+  // Renames are only paired when the diff is not limited to one path
+  const tokens = (
+    await executeGitCommand(['git', 'diff-tree', '-r', '-M', '--name-status', '-z', fromCommit, toCommit], repoRoot)
+  ).split('\0');
+  for (let i = 0; i < tokens.length; i++) {
+    const status = tokens[i];
+    if (!status) {
+      continue;
+    }
+    // "<status>\0<path>", or "R<score>\0<old path>\0<new path>"
+    const paths = [tokens[++i]];
+    if (status[0] === 'R' || status[0] === 'C') {
+      paths.push(tokens[++i]);
+    }
+    if (paths[0] === entryPath && status[0] !== 'C') {
+      return status[0] === 'D' ? null : paths[paths.length - 1];
+    }
+  }
+  return entryPath;
+}
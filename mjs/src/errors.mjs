@@ -93,6 +93,26 @@ export class AuthenticationError extends GitError {
   }
 }
 
+/**
+ * Error thrown when HEAD points at a branch that has no commits yet (fresh `git init` or orphan branch)
+ */
+export class UnbornHeadError extends GitError {
+  constructor(message, options = {}) {
+    super(message, {
+      ...options,
+      code: options.code || 'UNBORN_HEAD'
+    });
+    this.branch = options.branch || null;
+  }
+
+  toJSON() {
+    return {
+      ...super.toJSON(),
+      branch: this.branch
+    };
+  }
+}
+
 /**
  * Error thrown when a Git command execution fails
  */
@@ -116,3 +136,27 @@ export class GitCommandError extends GitError {
     };
   }
 }
+
+/**
+ * Error thrown when a Git object is missing from a partial clone and cannot be fetched
+ * Blobless (--filter=blob:none) and treeless clones fetch objects from their promisor remote on demand;
+ * this is thrown when that fetch fails, e.g. while offline.
+ */
+export class ObjectNotLocalError extends GitCommandError {
+  constructor(message, options = {}) {
+    super(message, {
+      ...options,
+      code: options.code || 'OBJECT_NOT_LOCAL'
+    });
+    this.objectId = options.objectId || null;
+    this.remoteUrl = options.remoteUrl || null;
+  }
+
+  toJSON() {
+    return {
+      ...super.toJSON(),
+      objectId: this.objectId,
+      remoteUrl: this.remoteUrl
+    };
+  }
+}
@@ -29,11 +29,161 @@ export {
   saveManifest
 } from './change-detection.mjs';
 
+// Permalink generation
+export {
+  generateGitHubUrl,
+  generateGitHubApiContentsUrl,
+  generateGitHubCompareUrl,
+  generateGitHubArchiveUrl,
+  findPullRequestForCommit,
+  canonicalizeGitHubUrl,
+  locateGitHubLink,
+  checkDrift,
+  REF_STRATEGIES,
+  UNPUSHED_POLICIES
+} from './permalink.mjs';
+
+// Repository layout
+export { listWorktrees, getSubmoduleIdentity, getRepoState, REPOSITORY_STATES } from './repository.mjs';
+
+// Refs and revisions
+export {
+  getHeadInfo,
+  getRepoFingerprint,
+  listRefs,
+  refsContaining,
+  getReflog,
+  listStashes,
+  getStashFileHash,
+  resolveRevspec,
+  describe,
+  getLatestTag,
+  resolveTag
+} from './refs.mjs';
+
+// Commit history
+export {
+  getCommitInfo,
+  logRange,
+  countCommits,
+  getFileHistory,
+  pickaxe,
+  searchCommits,
+  getFileLastModified,
+  getFileChurn,
+  getFileContributors,
+  getFileCreationCommit,
+  findCommitForBlob
+} from './history.mjs';
+
+// Line attribution
+export { blameFile, mapLine, getFileOwnership } from './blame.mjs';
+
+// Diffs
+export { getCommitChangedFiles, getCommitStats, getCommitPatch, diffFile, diffWorkdir } from './diff.mjs';
+
+// Trees and blobs
+export {
+  getTreeEntries,
+  walkTree,
+  findDuplicateFiles,
+  generateManifest,
+  diffManifests,
+  findPathsForBlob,
+  grepAt,
+  getSymlink,
+  getFileText,
+  isBinary,
+  getLfsInfo
+} from './tree.mjs';
+
+// Working-tree status
+export {
+  getFileStatus,
+  getRepoStatus,
+  getIndexEntries,
+  getIndexEntry,
+  getDirectoryStatus,
+  listUntracked,
+  listIgnored,
+  isPathIgnored,
+  explainIgnore,
+  getAttributes
+} from './status.mjs';
+
+// Commit signatures
+export { extractSignature, verifySignature, SIGNATURE_FORMATS } from './signature.mjs';
+
+// Content anchors
+export { createContentAnchor, locateContentAnchor, hashLines, ANCHOR_ID_LENGTH } from './anchor.mjs';
+
+// CODEOWNERS
+export { getCodeOwners, parseCodeowners, CODEOWNERS_LOCATIONS } from './codeowners.mjs';
+
+// Provenance
+export { createProvenanceRecord, PROVENANCE_SCHEMA } from './provenance.mjs';
+
 // Utility functions
 export { normalizeFilePath, resolveFilePath } from './utils/path.mjs';
-export { parseGitHubUrl, buildGitHubUrl } from './utils/url.mjs';
+export {
+  detectProvider,
+  parseGitHubUrl,
+  normalizeRemoteUrl,
+  parseGitHubLink,
+  buildGitHubUrl,
+  buildGitHubApiContentsUrl,
+  buildGitHubCompareUrl,
+  buildGitHubArchiveUrl,
+  LFS_MEDIA_URL_TEMPLATE,
+  buildGitHubLfsMediaUrl,
+  buildGitHubPullRequestUrl,
+  ARCHIVE_FORMATS,
+  buildLineAnchor,
+  encodeUrlPath,
+  isRenderedFile
+} from './utils/url.mjs';
 export { isValidGitHash, validateGitHash } from './utils/hash.mjs';
-export { executeGitCommand, isGitRepository, getRepositoryRoot } from './utils/git.mjs';
+export { resolveSshHost } from './utils/ssh.mjs';
+export { parseConventionalCommit } from './utils/conventional.mjs';
+export { TEXT_ENCODINGS, detectEncoding, decodeText } from './utils/encoding.mjs';
+export { LFS_POINTER_MAX_SIZE, parseLfsPointer } from './utils/lfs.mjs';
+export { formatLink, formatHtmlLink, formatOsc8Link, formatOrgLink, linkText, LINK_FORMATS } from './utils/format.mjs';
+export {
+  executeGitCommand,
+  setHonorGitEnv,
+  isGitRepository,
+  getRepositoryRoot,
+  findRepositoryWithCeiling,
+  resolveRepositoryPath,
+  getCurrentBranch,
+  isHeadUnborn,
+  getHeadCommit,
+  abbreviateOid,
+  expandOid,
+  listRemotes,
+  getTrackingBranch,
+  getUpstreamDivergence,
+  getDefaultRemote,
+  getRemoteUrl,
+  getDefaultBranch,
+  isCommitOnRemote,
+  getNearestPushedAncestor,
+  mergeBase,
+  isAncestor,
+  applyUrlRewrites,
+  getLastCommitForFile,
+  getPromisorRemotes,
+  isObjectLocal,
+  isShallow,
+  getShallowBoundary,
+  getBlobOid,
+  getBlobBytes,
+  getBlobContent,
+  getBlobSize,
+  streamBlob,
+  getFileHashAtCommit,
+  getHeadTag
+} from './utils/git.mjs';
 
 // Error classes
 export {
@@ -43,7 +193,9 @@ export {
   InvalidHashError,
   RateLimitError,
   AuthenticationError,
-  GitCommandError
+  UnbornHeadError,
+  GitCommandError,
+  ObjectNotLocalError
 } from './errors.mjs';
 
 // Helper function for complete repository info
@@ -0,0 +1,584 @@
+import path from 'path';
+import {
+  executeGitCommand,
+  getRepositoryRoot,
+  getShallowBoundary,
+  isHeadUnborn,
+  resolveRepositoryPath
+} from './utils/git.mjs';
+import { parseConventionalCommit } from './utils/conventional.mjs';
+import { validateGitHash } from './utils/hash.mjs';
+import { resolveFilePath } from './utils/path.mjs';
+
+/**
+ * Commit fields read by getCommitInfo and logRange, NUL-separated, with identities mapped through .mailmap
+ */
+const COMMIT_INFO_FORMAT = '%H%x00%P%x00%aN%x00%aE%x00%aI%x00%cN%x00%cE%x00%cI%x00%(trailers:only,unfold)%x00%B';
+
+/**
+ * COMMIT_INFO_FORMAT with identities as recorded in the commit
+ */
+const RAW_COMMIT_INFO_FORMAT = '%H%x00%P%x00%an%x00%ae%x00%aI%x00%cn%x00%ce%x00%cI%x00%(trailers:only,unfold)%x00%B';
+const COMMIT_INFO_FIELDS = 10;
+
+/**
+ * Commits read per git invocation by logRange and getFileHistory
+ */
+const LOG_PAGE_SIZE = 100;
+
+/**
+ * Reads the author, committer, message and trailers of a commit
+ * Returns { oid, parents, authorName, authorEmail, authorDate, committerName, committerEmail, committerDate,
+ * summary, body, message, trailers, conventional, shallowBoundary }. The author wrote the change and the committer last applied it (for example by rebasing),
+ * so the two can differ; identities are mapped through .mailmap unless disabled and dates are ISO 8601. summary is the first paragraph of the message joined into one line, as
+ * Git does, and body the rest ('' if there is none). trailers lists { key, value } pairs such as Signed-off-by from
+ * the message's final trailer block, in order, as parsed by Git (continuation lines unfolded). conventional is the
+ * parseConventionalCommit breakdown of the message, or null if it does not follow the Conventional Commits spec.
+ * shallowBoundary is true when a shallow clone's history is cut off at the commit, so parents is empty without it
+ * being a root commit.
+ * @param {string} [commit='HEAD'] - Commit SHA, branch, tag or other revision
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {boolean} [options.mailmap=true] - Map names and emails through .mailmap
+ * @returns {Promise<object>} Commit
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+export async function getCommitInfo(commit = 'HEAD', options = {}) {
+  const { repoPath = process.cwd(), mailmap = true } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const format = mailmap ? COMMIT_INFO_FORMAT : RAW_COMMIT_INFO_FORMAT;
+  const output = await executeGitCommand(['git', 'show', '-s', `--format=${format}`, `${commit}^{commit}`], repoRoot);
+
+  const fields = output.split('\0');
+  return parseCommitInfo(
+    [...fields.slice(0, COMMIT_INFO_FIELDS - 1), fields.slice(COMMIT_INFO_FIELDS - 1).join('\0')],
+    new Set(await getShallowBoundary(repoRoot))
+  );
+}
+
+/**
+ * Walks the commits in from..to, newest first
+ * Yields getCommitInfo results for the commits that git log from..to shows: reachable from to but not from from.
+ * Commits are read as they are consumed, a page of LOG_PAGE_SIZE at a time, so long ranges can be processed without
+ * loading every message.
+ * @param {string|null} from - Exclusive start of the range, e.g. the previous release tag (null walks all history
+ *   reachable from to)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.to='HEAD'] - Inclusive end of the range
+ * @param {string} [options.pathFilter] - Only include commits that changed this file or directory (absolute or
+ *   relative to repo root)
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {boolean} [options.mailmap=true] - Map names and emails through .mailmap
+ * @yields {object} Commit, as returned by getCommitInfo
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If from or to does not name a commit
+ */
+export async function* logRange(from, options = {}) {
+  const { to = 'HEAD', pathFilter, repoPath = process.cwd(), mailmap = true } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const command = ['git', 'rev-list'];
+  if (from !== null && from !== undefined) {
+    command.push(`^${from}^{commit}`);
+  }
+  command.push(`${to}^{commit}`);
+  if (pathFilter !== undefined) {
+    command.push('--', resolveFilePath(repoRoot, pathFilter));
+  }
+
+  const oids = (await executeGitCommand(command, repoRoot)).split('\n').filter(Boolean);
+
+  yield* readCommits(repoRoot, oids, mailmap);
+}
+
+/**
+ * Counts the commits in a revision range, like git rev-list --count
+ * Cheaper than walking the commits, for figures such as "this file has 213 commits" or repository size metrics. A
+ * branch with no commits yet counts 0 for 'HEAD'.
+ * @param {string} [revspecRange='HEAD'] - Revisions as git rev-list takes them, e.g. 'HEAD', 'v1.2.0..HEAD' or
+ *   'main...feature'
+ * @param {object} [options={}] - Options
+ * @param {string} [options.pathFilter] - Only count commits that changed this file or directory (absolute or relative
+ *   to repo root)
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<number>} Number of commits
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If revspecRange does not name commits
+ * @throws {TypeError} If revspecRange is empty
+ */
+export async function countCommits(revspecRange = 'HEAD', options = {}) {
+  const { pathFilter, repoPath = process.cwd() } = options;
+
+  const revisions = typeof revspecRange === 'string' ? revspecRange.split(/\s+/).filter(Boolean) : [];
+  if (revisions.length === 0) {
+    throw new TypeError('Invalid revspec range: must be a non-empty string');
+  }
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const command = ['git', 'rev-list', '--count', ...revisions];
+  if (pathFilter !== undefined) {
+    command.push('--', resolveFilePath(repoRoot, pathFilter));
+  }
+
+  try {
+    return parseInt(await executeGitCommand(command, repoRoot), 10);
+  } catch (error) {
+    if (revisions.length === 1 && revisions[0] === 'HEAD' && (await isHeadUnborn(repoRoot))) {
+      return 0;
+    }
+    throw error;
+  }
+}
+
+/**
+ * Walks the commits that changed a file or directory, newest first
+ * Lists the commits git log -- <path> shows, with Git's default history simplification. limit and offset page
+ * through long histories; commits are read as they are consumed, a page of LOG_PAGE_SIZE at a time. In a shallow
+ * clone the history stops at the shallow boundary, where a commit has shallowBoundary set rather than looking like a
+ * root commit that added the path. A branch with no commits yet has no history for 'HEAD'.
+ * @param {string} filePath - File or directory path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {number} [options.limit] - Most commits to yield (default: all)
+ * @param {number} [options.offset=0] - Commits to skip from the newest
+ * @param {string} [options.rev='HEAD'] - Revision whose history is searched
+ * @param {boolean} [options.follow=false] - Continue past renames of a single file, like git log --follow
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {boolean} [options.mailmap=true] - Map names and emails through .mailmap
+ * @yields {object} Commit, as returned by getCommitInfo
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If rev does not name a commit
+ * @throws {TypeError} If limit or offset is not a non-negative integer
+ */
+export async function* getFileHistory(filePath, options = {}) {
+  const { limit, offset = 0, rev = 'HEAD', follow = false, repoPath = process.cwd(), mailmap = true } = options;
+
+  if (limit !== undefined && (!Number.isInteger(limit) || limit < 0)) {
+    throw new TypeError(`Invalid limit: ${limit}. Must be a non-negative integer`);
+  }
+  if (!Number.isInteger(offset) || offset < 0) {
+    throw new TypeError(`Invalid offset: ${offset}. Must be a non-negative integer`);
+  }
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  const command = ['git', 'log', '--format=%H'];
+  if (offset) {
+    command.push(`--skip=${offset}`);
+  }
+  if (limit !== undefined) {
+    command.push(`--max-count=${limit}`);
+  }
+  if (follow) {
+    command.push('--follow');
+  }
+  command.push(`${rev}^{commit}`, '--', relativePath || '.');
+
+  let output;
+  try {
+    output = await executeGitCommand(command, repoRoot);
+  } catch (error) {
+    if (rev === 'HEAD' && (await isHeadUnborn(repoRoot))) {
+      return;
+    }
+    throw error;
+  }
+
+  yield* readCommits(repoRoot, output.split('\n').filter(Boolean), mailmap);
+}
+
+/**
+ * Walks the commits that changed how many times a string occurs, newest first
+ * Mirrors git log -S: a commit matches when it adds or removes an occurrence, so the results show when a token or call
+ * was introduced and when it went away, but not commits that only move it within a file. Merge commits are not
+ * searched.
+ * @param {string|null} pathFilter - Only search changes to this file or directory (absolute or relative to repo root;
+ *   null searches the whole repository)
+ * @param {string} needle - String to count
+ * @param {object} [options={}] - Options
+ * @param {string} [options.rev='HEAD'] - Revision whose history is searched
+ * @param {boolean} [options.regex=false] - Treat needle as an extended regular expression (--pickaxe-regex)
+ * @param {number} [options.limit] - Most commits to yield (default: all)
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @yields {object} Commit, as returned by getCommitInfo
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If rev does not name a commit or needle is not a valid regular expression
+ * @throws {TypeError} If needle is empty or limit is not a non-negative integer
+ */
+export async function* pickaxe(pathFilter, needle, options = {}) {
+  const { rev = 'HEAD', regex = false, limit, repoPath = process.cwd() } = options;
+
+  if (typeof needle !== 'string' || !needle) {
+    throw new TypeError('Invalid needle: must be a non-empty string');
+  }
+  if (limit !== undefined && (!Number.isInteger(limit) || limit < 0)) {
+    throw new TypeError(`Invalid limit: ${limit}. Must be a non-negative integer`);
+  }
+
+  const { repoRoot, relativePath } =
+    pathFilter === null || pathFilter === undefined
+      ? { repoRoot: await getRepositoryRoot(repoPath), relativePath: '' }
+      : await resolveRepositoryPath(repoPath, pathFilter);
+
+  const command = ['git', 'log', '--format=%H', `-S${needle}`];
+  if (regex) {
+    command.push('--pickaxe-regex');
+  }
+  if (limit !== undefined) {
+    command.push(`--max-count=${limit}`);
+  }
+  command.push(`${rev}^{commit}`, '--', relativePath || '.');
+
+  const oids = (await executeGitCommand(command, repoRoot)).split('\n').filter(Boolean);
+
+  yield* readCommits(repoRoot, oids);
+}
+
+/**
+ * Walks the commits whose message matches a pattern, newest first
+ * Useful for resolving a ticket ID mentioned in commit messages (e.g. 'PROJ-123') to the commits that implement it.
+ * @param {string} pattern - Substring to look for, or an extended regular expression with regex
+ * @param {object} [options={}] - Options
+ * @param {number} [options.limit] - Most commits to yield (default: all)
+ * @param {boolean} [options.regex=false] - Treat pattern as an extended regular expression instead of a substring
+ * @param {boolean} [options.ignoreCase=false] - Match case-insensitively
+ * @param {string} [options.rev='HEAD'] - Revision whose history is searched
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @yields {object} Commit, as returned by getCommitInfo
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If rev does not name a commit or pattern is not a valid regular expression
+ * @throws {TypeError} If pattern is empty or limit is not a non-negative integer
+ */
+export async function* searchCommits(pattern, options = {}) {
+  const { limit, regex = false, ignoreCase = false, rev = 'HEAD', repoPath = process.cwd() } = options;
+
+  if (typeof pattern !== 'string' || !pattern) {
+    throw new TypeError('Invalid pattern: must be a non-empty string');
+  }
+  if (limit !== undefined && (!Number.isInteger(limit) || limit < 0)) {
+    throw new TypeError(`Invalid limit: ${limit}. Must be a non-negative integer`);
+  }
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const command = ['git', 'log', '--format=%H', `--grep=${pattern}`, regex ? '-E' : '-F'];
+  if (ignoreCase) {
+    command.push('-i');
+  }
+  if (limit !== undefined) {
+    command.push(`--max-count=${limit}`);
+  }
+  command.push(`${rev}^{commit}`);
+
+  const oids = (await executeGitCommand(command, repoRoot)).split('\n').filter(Boolean);
+
+  yield* readCommits(repoRoot, oids);
+}
+
+/**
+ * Gets when a file or directory was last changed, according to history
+ * Returns the committer date of the most recent commit touching the path, the right "last modified" for generated
+ * documentation: unlike the filesystem mtime it does not change on checkout or clone.
+ * @param {string} filePath - File or directory path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.rev='HEAD'] - Revision whose history is searched
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<string | null>} ISO 8601 timestamp in the committer's time zone, or null if no commit touches
+ *   the path
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If rev does not name a commit
+ */
+export async function getFileLastModified(filePath, options = {}) {
+  const { rev = 'HEAD', repoPath = process.cwd() } = options;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  const output = await executeGitCommand(
+    ['git', 'log', '-1', '--format=%cI', `${rev}^{commit}`, '--', relativePath || '.'],
+    repoRoot
+  );
+  return output || null;
+}
+
+/**
+ * Measures how often and how much a file or directory changed
+ * Counts the non-merge commits in rev's history that touched the path and the lines they added and deleted there.
+ * Binary changes count as commits but add no lines. Returns { path, commits, additions, deletions, authors, since }:
+ * authors the number of distinct author emails (after .mailmap, unless disabled) and since the start of the period as given (null for
+ * all history).
+ * @param {string} filePath - File or directory path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string|Date} [options.since] - Only count commits after this date: a Date, or any date Git understands,
+ *   e.g. '2024-01-01' or '3 months ago' (default: all history)
+ * @param {string} [options.rev='HEAD'] - Revision whose history is searched
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {boolean} [options.mailmap=true] - Map author emails through .mailmap before counting distinct authors
+ * @returns {Promise<object>} Churn
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If rev does not name a commit
+ */
+export async function getFileChurn(filePath, options = {}) {
+  const { rev = 'HEAD', repoPath = process.cwd(), mailmap = true } = options;
+  const since = options.since instanceof Date ? options.since.toISOString() : options.since ?? null;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  const command = ['git', 'log', '--no-merges', '--numstat', `--format=%x00${mailmap ? '%aE' : '%ae'}`];
+  if (since !== null) {
+    command.push(`--since=${since}`);
+  }
+  command.push(`${rev}^{commit}`, '--', relativePath || '.');
+
+  const output = await executeGitCommand(command, repoRoot);
+
+  let commits = 0;
+  let additions = 0;
+  let deletions = 0;
+  const authors = new Set();
+  for (const line of output.split('\n')) {
+    if (line.startsWith('\0')) {
+      commits++;
+      authors.add(line.slice(1).toLowerCase());
+      continue;
+    }
+    // "<added>\t<deleted>\t<path>", "-" for binary files
+    const fields = line.split('\t');
+    if (fields.length === 3 && fields[0] !== '-') {
+      additions += parseInt(fields[0], 10);
+      deletions += parseInt(fields[1], 10);
+    }
+  }
+
+  return { path: relativePath, commits, additions, deletions, authors: authors.size, since };
+}
+
+/**
+ * Summarizes who committed to a file or directory, like git shortlog
+ * Authors are mapped through .mailmap and told apart by email, ignoring case, so one person committing under several
+ * names is counted once. Returns { name, email, commits, firstCommitDate, lastCommitDate } contributors, most commits
+ * first, then by name: name as on the author's latest commit and the dates ISO 8601 author dates of their earliest
+ * and latest commits touching the path.
+ * @param {string} filePath - File or directory path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.rev='HEAD'] - Revision whose history is searched
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {boolean} [options.mailmap=true] - Map names and emails through .mailmap
+ * @returns {Promise<object[]>} Contributors
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If rev does not name a commit
+ */
+export async function getFileContributors(filePath, options = {}) {
+  const { rev = 'HEAD', repoPath = process.cwd(), mailmap = true } = options;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  const identity = mailmap ? '%aN%x00%aE' : '%an%x00%ae';
+  const output = await executeGitCommand(
+    ['git', 'log', '-z', `--format=${identity}%x00%aI`, `${rev}^{commit}`, '--', relativePath || '.'],
+    repoRoot
+  );
+
+  const contributors = new Map();
+  const fields = output ? output.split('\0') : [];
+  for (let offset = 0; offset + 3 <= fields.length; offset += 3) {
+    const [name, email, date] = fields.slice(offset, offset + 3);
+    const key = email.toLowerCase();
+    const contributor = contributors.get(key);
+    if (!contributor) {
+      contributors.set(key, { name, email, commits: 1, firstCommitDate: date, lastCommitDate: date });
+      continue;
+    }
+    contributor.commits++;
+    if (Date.parse(date) < Date.parse(contributor.firstCommitDate)) {
+      contributor.firstCommitDate = date;
+    }
+    if (Date.parse(date) > Date.parse(contributor.lastCommitDate)) {
+      contributor.name = name;
+      contributor.lastCommitDate = date;
+    }
+  }
+
+  return [...contributors.values()].sort((a, b) => b.commits - a.commits || a.name.localeCompare(b.name));
+}
+
+/**
+ * Finds the commit that first added a file, following renames back
+ * Uses git log --follow, so renames are detected by content similarity; a file deleted and later re-added under the
+ * same name is traced to its first addition. Returns { commit, path }: commit as returned by getCommitInfo and path the
+ * path the file was added under, before any later renames.
+ * @param {string} filePath - File path at rev (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.rev='HEAD'] - Revision whose history is searched
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object | null>} Origin, or null if no commit in the history adds the file
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If rev does not name a commit
+ */
+export async function getFileCreationCommit(filePath, options = {}) {
+  const { rev = 'HEAD', repoPath = process.cwd() } = options;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  const output = await executeGitCommand(
+    [
+      'git', 'log', '--follow', '--diff-filter=A', '--name-only', '-z', '--format=%H', `${rev}^{commit}`,
+      '--', relativePath
+    ],
+    repoRoot
+  );
+
+  // "<commit>\0\n<path>\0" per addition, newest first
+  const tokens = output.split('\0');
+  if (tokens[tokens.length - 1] === '') {
+    tokens.pop();
+  }
+  if (tokens.length < 2) {
+    return null;
+  }
+  const [oid, originalPath] = tokens.slice(-2);
+
+  for await (const commit of readCommits(repoRoot, [oid])) {
+    return { commit, path: originalPath.replace(/^\n/, '') };
+  }
+  return null;
+}
+
+/**
+ * Reads commits in order, a page of LOG_PAGE_SIZE per git invocation
+ * @param {string} repoRoot - Repository root path
+ * @param {string[]} oids - Commit SHAs
+ * @param {boolean} [mailmap=true] - Map names and emails through .mailmap
+ * @yields {object} Commit, as returned by getCommitInfo
+ */
+async function* readCommits(repoRoot, oids, mailmap = true) {
+  const format = mailmap ? COMMIT_INFO_FORMAT : RAW_COMMIT_INFO_FORMAT;
+  const shallowBoundary = new Set(oids.length > 0 ? await getShallowBoundary(repoRoot) : []);
+  for (let start = 0; start < oids.length; start += LOG_PAGE_SIZE) {
+    const page = oids.slice(start, start + LOG_PAGE_SIZE);
+    const output = await executeGitCommand(
+      ['git', 'log', '--no-walk=unsorted', '-z', `--format=${format}`, ...page],
+      repoRoot
+    );
+    // -z separates commits with the same NUL that separates fields
+    const fields = output.split('\0');
+    for (let offset = 0; offset + COMMIT_INFO_FIELDS <= fields.length; offset += COMMIT_INFO_FIELDS) {
+      yield parseCommitInfo(fields.slice(offset, offset + COMMIT_INFO_FIELDS), shallowBoundary);
+    }
+  }
+}
+
+/**
+ * Reads the author name and email recorded in commits, ignoring .mailmap
+ * @param {string} repoRoot - Repository root path
+ * @param {string[]} oids - Commit SHAs
+ * @returns {Promise<Map<string, string[]>>} Map of commit SHA to [name, email]
+ */
+export async function readAuthors(repoRoot, oids) {
+  const authors = new Map();
+  for (let start = 0; start < oids.length; start += LOG_PAGE_SIZE) {
+    const page = oids.slice(start, start + LOG_PAGE_SIZE);
+    const output = await executeGitCommand(
+      ['git', 'log', '--no-walk=unsorted', '-z', '--format=%H%x00%an%x00%ae', ...page],
+      repoRoot
+    );
+    const fields = output.split('\0');
+    for (let offset = 0; offset + 3 <= fields.length; offset += 3) {
+      const [oid, name, email] = fields.slice(offset, offset + 3);
+      authors.set(oid, [name, email]);
+    }
+  }
+  return authors;
+}
+
+/**
+ * Builds a commit object from the fields of COMMIT_INFO_FORMAT
+ * @param {string[]} fields - The COMMIT_INFO_FIELDS values of one commit
+ * @param {Set<string>} shallowBoundary - Commits at which the clone's history is cut off
+ * @returns {object} Commit, as returned by getCommitInfo
+ */
+function parseCommitInfo(fields, shallowBoundary) {
+  const [oid, parents, authorName, authorEmail, authorDate, committerName, committerEmail, committerDate, trailerBlock] =
+    fields;
+  const message = fields[9].trimEnd();
+  const [paragraph, ...rest] = message.trim().split('\n\n');
+
+  return {
+    oid,
+    parents: parents.split(' ').filter(Boolean),
+    authorName,
+    authorEmail,
+    authorDate,
+    committerName,
+    committerEmail,
+    committerDate,
+    summary: paragraph.split('\n').map((line) => line.trim()).join(' '),
+    body: rest.join('\n\n').trim(),
+    message,
+    trailers: trailerBlock.split('\n').filter((line) => line.includes(':')).map((line) => {
+      const separator = line.indexOf(':');
+      return { key: line.slice(0, separator).trim(), value: line.slice(separator + 1).trim() };
+    }),
+    conventional: parseConventionalCommit(message),
+    shallowBoundary: shallowBoundary.has(oid)
+  };
+}
+
+/**
+ * Finds the commits that set a file to a given blob
+ * Answers which revision produced a copy of a file: hash it with git hash-object and look the blob up at the file's
+ * path. Each returned commit changed the path to exactly that content; the commits after it, up to the next change of
+ * the path, hold it too. A blob that only appears through a merge's conflict resolution is not found, as merges are
+ * not diffed.
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {string} blobOid - Blob SHA (full 40 hex characters)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.rev='HEAD'] - Revision whose history is searched
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object[]>} Commits as returned by getCommitInfo, newest first; empty if the path never held the
+ *   blob
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If rev does not name a commit
+ * @throws {InvalidHashError} If blobOid is not a valid Git hash
+ */
+export async function findCommitForBlob(filePath, blobOid, options = {}) {
+  const { rev = 'HEAD', repoPath = process.cwd() } = options;
+
+  validateGitHash(blobOid, 'blobOid');
+  const oid = blobOid.toLowerCase();
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  const output = await executeGitCommand(
+    [
+      'git', 'log', '--raw', '--no-abbrev', '--no-renames', '-z', '--format=%x01%H', `${rev}^{commit}`,
+      '--', relativePath
+    ],
+    repoRoot
+  );
+
+  // "\x01<commit>" headers, then ":<old mode> <new mode> <old oid> <new oid> <status>\0<path>" per changed file
+  const oids = [];
+  let commit = '';
+  const tokens = output.split('\0');
+  for (let i = 0; i < tokens.length; i++) {
+    const token = tokens[i].replace(/^\n+/, '');
+    if (token.startsWith('\x01')) {
+      commit = token.slice(1);
+    } else if (token.startsWith(':')) {
+      const entryPath = tokens[++i];
+      if (entryPath === relativePath && token.split(' ')[3] === oid && !oids.includes(commit)) {
+        oids.push(commit);
+      }
+    }
+  }
+
+  const commits = [];
+  for await (const info of readCommits(repoRoot, oids)) {
+    commits.push(info);
+  }
+  return commits;
+}
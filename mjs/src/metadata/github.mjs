@@ -1,5 +1,5 @@
 import { normalizeFilePath } from '../utils/path.mjs';
-import { buildGitHubUrl } from '../utils/url.mjs';
+import { buildGitHubUrl, buildGitHubApiContentsUrl } from '../utils/url.mjs';
 import {
   FileNotFoundError,
   RepositoryNotFoundError,
@@ -103,7 +103,7 @@ export async function getGitHubMetadata(owner, repo, filePath, branch = 'main')
   try {
     // Get file contents to retrieve file SHA
     // API: GET /repos/:owner/:repo/contents/:path
-    const contentsUrl = `https://api.github.com/repos/${owner}/${repo}/contents/${normalizedPath}?ref=${branch}`;
+    const contentsUrl = buildGitHubApiContentsUrl(owner, repo, normalizedPath, branch);
     const contentsData = await githubApiRequest(contentsUrl);
 
     // Get commit information for this file
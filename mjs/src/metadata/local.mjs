@@ -1,8 +1,14 @@
 import path from 'path';
-import { executeGitCommand, getRepositoryRoot } from '../utils/git.mjs';
-import { normalizeFilePath, resolveFilePath } from '../utils/path.mjs';
+import {
+  executeGitCommand,
+  getCurrentBranch,
+  getHeadCommit,
+  getRemoteUrl,
+  resolveRepositoryPath
+} from '../utils/git.mjs';
+import { normalizeFilePath } from '../utils/path.mjs';
 import { parseGitHubUrl, buildGitHubUrl } from '../utils/url.mjs';
-import { FileNotFoundError } from '../errors.mjs';
+import { FileNotFoundError, UnbornHeadError } from '../errors.mjs';
 
 /**
  * Retrieves file metadata from a local Git repository
@@ -15,18 +21,18 @@ export async function getLocalMetadata(repoPath, filePath) {
     throw new TypeError('repoPath and filePath are required');
   }
 
-  // Get repository root
-  const repoRoot = await getRepositoryRoot(repoPath);
-
-  // Resolve file path relative to repo root
-  const relativePath = resolveFilePath(repoRoot, filePath);
+  // Resolve file path relative to the owning repository (submodules included)
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
   const absoluteFilePath = path.join(repoRoot, relativePath);
 
   try {
+    // A file staged before the first commit has no history yet
+    await getHeadCommit(repoRoot);
+
     // Get last commit hash for this file
     // git log -1 --pretty=format:%H -- <file>
     const commitHash = await executeGitCommand(
-      `git log -1 --pretty=format:%H -- "${relativePath}"`,
+      ['git', 'log', '-1', '--pretty=format:%H', '--', relativePath],
       repoRoot
     );
 
@@ -42,24 +48,17 @@ export async function getLocalMetadata(repoPath, filePath) {
     // Get commit timestamp
     // git log -1 --pretty=format:%cI -- <file>
     const lastModified = await executeGitCommand(
-      `git log -1 --pretty=format:%cI -- "${relativePath}"`,
+      ['git', 'log', '-1', '--pretty=format:%cI', '--', relativePath],
       repoRoot
     );
 
-    // Get current branch
-    // git rev-parse --abbrev-ref HEAD
-    let branch;
-    try {
-      branch = await executeGitCommand('git rev-parse --abbrev-ref HEAD', repoRoot);
-    } catch {
-      // Fallback for detached HEAD
-      branch = 'HEAD';
-    }
+    // Get current branch ('HEAD' when detached)
+    const branch = await getCurrentBranch(repoRoot);
 
     // Get file blob hash
     // git ls-tree HEAD <file>
     const lsTreeOutput = await executeGitCommand(
-      `git ls-tree HEAD "${relativePath}"`,
+      ['git', 'ls-tree', 'HEAD', relativePath],
       repoRoot
     );
 
@@ -80,12 +79,7 @@ export async function getLocalMetadata(repoPath, filePath) {
     const fileHash = match[1];
 
     // Get remote URL (optional)
-    let remoteUrl = null;
-    try {
-      remoteUrl = await executeGitCommand('git config --get remote.origin.url', repoRoot);
-    } catch {
-      // No remote configured - that's OK
-    }
+    const remoteUrl = await getRemoteUrl(repoRoot);
 
     // Parse owner/repo from remote URL
     let owner = null;
@@ -131,7 +125,7 @@ export async function getLocalMetadata(repoPath, filePath) {
     return metadata;
   } catch (error) {
     // Re-throw known errors
-    if (error instanceof FileNotFoundError) {
+    if (error instanceof FileNotFoundError || error instanceof UnbornHeadError) {
       throw error;
     }
 
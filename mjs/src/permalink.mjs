@@ -0,0 +1,844 @@
+import { createHash } from 'crypto';
+import fs from 'fs';
+import path from 'path';
+import {
+  executeGitCommand,
+  getBlobBytes,
+  getRepositoryRoot,
+  getRemoteUrl,
+  getCurrentBranch,
+  getDefaultBranch,
+  getDefaultRemote,
+  getHeadCommit,
+  getHeadTag,
+  getLastCommitForFile,
+  getNearestPushedAncestor,
+  getTrackingBranch,
+  isAncestor,
+  isCommitOnRemote,
+  isHeadUnborn,
+  isShallow,
+  resolveRepositoryPath
+} from './utils/git.mjs';
+import {
+  parseGitHubUrl,
+  parseGitHubLink,
+  buildGitHubUrl,
+  buildGitHubApiContentsUrl,
+  buildGitHubCompareUrl,
+  buildGitHubArchiveUrl,
+  buildGitHubPullRequestUrl,
+  ARCHIVE_FORMATS,
+  GITHUB_LINK_PATTERN
+} from './utils/url.mjs';
+import { isValidGitHash } from './utils/hash.mjs';
+import { GitError, FileNotFoundError } from './errors.mjs';
+
+/**
+ * Ref strategies for the ref segment of a URL
+ * - commit: pinned HEAD commit SHA (permalink)
+ * - branch: current branch name ("living" link that follows the branch)
+ * - tag: tag pointing exactly at HEAD
+ * - tag-or-commit: HEAD tag when there is one, otherwise the commit SHA
+ * - last-commit: most recent commit that modified the path (stable across unrelated commits)
+ * - default-branch: the remote's default branch ("latest on main" link)
+ */
+export const REF_STRATEGIES = ['commit', 'branch', 'tag', 'tag-or-commit', 'last-commit', 'default-branch'];
+
+/**
+ * What to do when the linked commit is not on the remote
+ * - allow: link it anyway (the URL 404s until it is pushed)
+ * - warn: link it anyway and emit a process warning with code COMMIT_NOT_PUSHED
+ * - fallback: link the nearest pushed ancestor instead
+ * - error: throw GitError with code COMMIT_NOT_PUSHED
+ */
+export const UNPUSHED_POLICIES = ['allow', 'warn', 'fallback', 'error'];
+
+/**
+ * Tree entry mode of a symbolic link
+ */
+const SYMLINK_MODE = '120000';
+
+/**
+ * Fetched PR head refs: refs/pull/123/head, refs/remotes/origin/pull/123/head, refs/remotes/origin/pr/123
+ */
+const PULL_REQUEST_REF_PATTERN = /^refs\/(?:remotes\/[^/]+\/)?(?:pull\/(\d+)\/head|pr\/(\d+))$/;
+
+/**
+ * What a ref name cannot contain (see git check-ref-format); URL refs matching it are rejected rather than read as
+ * revision syntax such as main~1 or @{-1}
+ */
+const INVALID_REF_NAME_PATTERN = /[\x00-\x20\x7f~^:?*[\\]|\.\.|@\{|^[-/]|\/$|\/\//;
+
+/**
+ * PR references in commit subjects: squash merges "... (#123)" and merge commits "Merge pull request #123"
+ */
+const PULL_REQUEST_MESSAGE_PATTERN = /(?:\(#(\d+)\)\s*$|^Merge pull request #(\d+))/;
+
+/**
+ * Generates a GitHub permalink for a file or directory in a local repository
+ * By default the URL is pinned to the current HEAD commit; refStrategy selects the branch name or HEAD tag instead.
+ * Paths inside a submodule are linked against the submodule's remote and commit.
+ * @param {string} filePath - File or directory path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {boolean} [options.isDirectory] - Force directory (true) or file (false) links; detected from the working tree when omitted
+ * @param {number} [options.lineStart] - First line to anchor (files only)
+ * @param {number} [options.lineEnd] - Last line of the anchored range
+ * @param {boolean} [options.plain] - Force (true) or suppress (false) ?plain=1; detected from the file extension when omitted
+ * @param {'commit'|'branch'|'tag'|'tag-or-commit'|'last-commit'|'default-branch'} [options.refStrategy='commit'] - Ref used in the URL
+ *   ('branch' uses the upstream branch name when linking against the tracked remote)
+ * @param {boolean} [options.resolveSshAliases=true] - Resolve ~/.ssh/config host aliases in the remote URL
+ * @param {string} [options.remote] - Remote whose URL is used (default: the current branch's remote, so a branch
+ *   tracking upstream/main links to upstream; then origin, then the first remote)
+ * @param {boolean} [options.preferUpstream=false] - When no remote is named and both origin (a fork) and upstream exist, use upstream
+ * @param {'allow'|'warn'|'fallback'|'error'} [options.unpushed='allow'] - When the linked commit is not on the remote:
+ *   allow, warn through process.emitWarning (code COMMIT_NOT_PUSHED) and link it anyway, fall back to the nearest
+ *   pushed ancestor, or throw
+ * @returns {Promise<string>} GitHub permalink URL
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitError} If the repository has no GitHub remote, the ref strategy cannot be satisfied (detached HEAD, untagged HEAD, unknown default branch),
+ *   or the commit is not pushed and unpushed is 'error' (or 'fallback' with no pushed ancestor)
+ * @throws {UnbornHeadError} If the current branch has no commits yet (every strategy except 'branch' and 'default-branch')
+ * @throws {FileNotFoundError} If refStrategy is 'last-commit' and the path has never been committed
+ */
+export async function generateGitHubUrl(filePath, options = {}) {
+  const {
+    repoPath = process.cwd(),
+    lineStart,
+    lineEnd,
+    plain,
+    refStrategy = 'commit',
+    resolveSshAliases = true,
+    remote,
+    preferUpstream = false,
+    unpushed = 'allow'
+  } = options;
+  let { isDirectory } = options;
+
+  if (!REF_STRATEGIES.includes(refStrategy)) {
+    throw new TypeError(`Invalid ref strategy: ${refStrategy}. Must be 'commit', 'branch', 'tag', 'tag-or-commit', 'last-commit' or 'default-branch'`);
+  }
+
+  if (!UNPUSHED_POLICIES.includes(unpushed)) {
+    throw new TypeError(`Invalid unpushed policy: ${unpushed}. Must be 'allow', 'warn', 'fallback' or 'error'`);
+  }
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  if (isDirectory === undefined) {
+    try {
+      isDirectory = fs.statSync(path.join(repoRoot, relativePath)).isDirectory();
+    } catch {
+      isDirectory = false;
+    }
+  }
+
+  const parsed = await githubRemote(repoRoot, resolveSshAliases, remote, preferUpstream);
+  let ref = await resolveRef(repoRoot, refStrategy, relativePath, remote, preferUpstream);
+
+  if (unpushed !== 'allow' && isValidGitHash(ref)) {
+    ref = await pushedCommit(
+      repoRoot,
+      ref,
+      remote || (await getDefaultRemote(repoRoot, { preferUpstream })),
+      unpushed
+    );
+  }
+
+  return buildGitHubUrl(parsed.owner, parsed.repo, ref, relativePath, {
+    isDirectory,
+    lineStart,
+    lineEnd,
+    plain
+  });
+}
+
+/**
+ * Generates a GitHub REST API contents URL for a path at the HEAD commit
+ * Uses the same identification data as generateGitHubUrl so automation can fetch the file through the API
+ * instead of the web UI.
+ * @param {string} filePath - File or directory path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {boolean} [options.resolveSshAliases=true] - Resolve ~/.ssh/config host aliases in the remote URL
+ * @param {string} [options.remote] - Remote whose URL is used (default: the current branch's remote, then origin, then the first remote)
+ * @param {boolean} [options.preferUpstream=false] - When no remote is named and both origin (a fork) and upstream exist, use upstream
+ * @returns {Promise<string>} GitHub REST API contents URL pinned to the HEAD commit
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitError} If the repository has no GitHub remote
+ */
+export async function generateGitHubApiContentsUrl(filePath, options = {}) {
+  const { repoPath = process.cwd(), resolveSshAliases = true, remote, preferUpstream = false } = options;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+  const parsed = await githubRemote(repoRoot, resolveSshAliases, remote, preferUpstream);
+  const commitHash = await resolveRev(repoRoot, 'HEAD');
+
+  return buildGitHubApiContentsUrl(parsed.owner, parsed.repo, relativePath, commitHash);
+}
+
+/**
+ * Generates a GitHub compare URL between two revisions of a local repository
+ * Both revisions are resolved to commit SHAs in the local repository, so the URL keeps showing the same
+ * diff after the branches move.
+ * @param {string} base - Base revision (commit SHA, branch, tag or other rev)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.head='HEAD'] - Head revision
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {boolean} [options.resolveSshAliases=true] - Resolve ~/.ssh/config host aliases in the remote URL
+ * @param {string} [options.remote] - Remote whose URL is used (default: the current branch's remote, then origin, then the first remote)
+ * @param {boolean} [options.preferUpstream=false] - When no remote is named and both origin (a fork) and upstream exist, use upstream
+ * @returns {Promise<string>} GitHub compare URL
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitError} If the repository has no GitHub remote, or a revision cannot be resolved
+ */
+export async function generateGitHubCompareUrl(base, options = {}) {
+  const { head = 'HEAD', repoPath = process.cwd(), resolveSshAliases = true, remote, preferUpstream = false } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+  const parsed = await githubRemote(repoRoot, resolveSshAliases, remote, preferUpstream);
+
+  return buildGitHubCompareUrl(
+    parsed.owner,
+    parsed.repo,
+    await resolveRev(repoRoot, base),
+    await resolveRev(repoRoot, head)
+  );
+}
+
+/**
+ * Generates a GitHub source archive URL for a revision of a local repository
+ * The revision is resolved to its commit SHA so build tooling fetches the exact snapshot a file was identified at.
+ * @param {object} [options={}] - Options
+ * @param {string} [options.rev='HEAD'] - Revision to archive
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {'tar.gz'|'zip'} [options.archiveFormat='tar.gz'] - Archive format
+ * @param {boolean} [options.resolveSshAliases=true] - Resolve ~/.ssh/config host aliases in the remote URL
+ * @param {string} [options.remote] - Remote whose URL is used (default: the current branch's remote, then origin, then the first remote)
+ * @param {boolean} [options.preferUpstream=false] - When no remote is named and both origin (a fork) and upstream exist, use upstream
+ * @returns {Promise<string>} GitHub archive URL
+ * @throws {TypeError} If the archive format is invalid
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitError} If the repository has no GitHub remote, or the revision cannot be resolved
+ */
+export async function generateGitHubArchiveUrl(options = {}) {
+  const {
+    rev = 'HEAD',
+    repoPath = process.cwd(),
+    archiveFormat = 'tar.gz',
+    resolveSshAliases = true,
+    remote,
+    preferUpstream = false
+  } = options;
+
+  if (!ARCHIVE_FORMATS.includes(archiveFormat)) {
+    throw new TypeError(`Invalid archive format: ${archiveFormat}. Must be 'tar.gz' or 'zip'`);
+  }
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+  const parsed = await githubRemote(repoRoot, resolveSshAliases, remote, preferUpstream);
+  const commitHash = await resolveRev(repoRoot, rev);
+
+  return buildGitHubArchiveUrl(parsed.owner, parsed.repo, commitHash, archiveFormat);
+}
+
+/**
+ * Finds the pull request that introduced a commit
+ * Only local data is used, in this order:
+ * 1. The commit's own subject ("Fix bug (#123)" from squash merges, or "Merge pull request #123")
+ * 2. Fetched PR head refs (refs/pull/<n>/head or <remote>/pr/<n>) containing the commit; a ref pointing exactly
+ *    at the commit wins, otherwise the lowest PR number
+ * 3. The oldest first-parent merge commit on HEAD's history that contains the commit, if its subject references a PR
+ *    and the commit came in through the merged branch rather than being on the mainline already
+ * PR head refs are only present if the remote is fetched with a refspec such as +refs/pull/*\/head:refs/remotes/origin/pr/*.
+ * @param {string} [commit='HEAD'] - Commit SHA or other revision
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {boolean} [options.resolveSshAliases=true] - Resolve ~/.ssh/config host aliases in the remote URL
+ * @param {string} [options.remote] - Remote whose URL is used (default: the current branch's remote, then origin, then the first remote)
+ * @param {boolean} [options.preferUpstream=false] - When no remote is named and both origin (a fork) and upstream exist, use upstream
+ * @returns {Promise<object | null>} Pull request: { number, url, commitHash, source } where source is 'ref', 'message'
+ *   or 'merge', or null if no pull request could be found
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitError} If the repository has no GitHub remote, or the commit cannot be resolved
+ */
+export async function findPullRequestForCommit(commit = 'HEAD', options = {}) {
+  const { repoPath = process.cwd(), resolveSshAliases = true, remote, preferUpstream = false } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+  const parsed = await githubRemote(repoRoot, resolveSshAliases, remote, preferUpstream);
+  const commitHash = await resolveRev(repoRoot, commit);
+
+  let number = await pullRequestFromMessage(repoRoot, commitHash);
+  let source = 'message';
+
+  if (number === null) {
+    number = await pullRequestFromRefs(repoRoot, commitHash);
+    source = 'ref';
+  }
+
+  if (number === null) {
+    let merges = [];
+    try {
+      const output = await executeGitCommand(
+        ['git', 'rev-list', '--first-parent', '--merges', '--ancestry-path', '--reverse', `${commitHash}..HEAD`],
+        repoRoot
+      );
+      merges = output.split('\n').filter(Boolean);
+    } catch {
+      // No mainline merges
+    }
+    // A commit made directly on the mainline is already in the merge's first parent; the merge did not bring it in
+    if (merges.length > 0 && !(await isAncestor(repoRoot, commitHash, `${merges[0]}^1`))) {
+      number = await pullRequestFromMessage(repoRoot, merges[0]);
+      source = 'merge';
+    }
+  }
+
+  if (number === null) {
+    return null;
+  }
+
+  return {
+    number,
+    url: buildGitHubPullRequestUrl(parsed.owner, parsed.repo, number),
+    commitHash,
+    source
+  };
+}
+
+/**
+ * Rewrites a GitHub branch or tag URL into a commit permalink
+ * The programmatic equivalent of pressing "y" on GitHub: the ref is resolved in the local repository
+ * and replaced with its commit SHA. Remote-tracking branches (<remote>/<ref>) are preferred over local refs
+ * because they reflect what the link showed on GitHub. Query string and line anchors are preserved.
+ * @param {string} url - GitHub blob or tree URL
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within a local clone of the repository
+ * @param {string} [options.remote] - Remote whose tracking branches are preferred (default: the current branch's remote, then origin, then the first remote)
+ * @returns {Promise<string>} Commit-pinned GitHub URL (unchanged if already pinned to a SHA)
+ * @throws {TypeError} If url is not a GitHub blob or tree URL
+ * @throws {GitError} If the ref cannot be resolved in the local repository
+ */
+export async function canonicalizeGitHubUrl(url, options = {}) {
+  const { repoPath = process.cwd(), remote } = options;
+
+  const match = typeof url === 'string' ? url.match(GITHUB_LINK_PATTERN) : null;
+  if (!match) {
+    throw new TypeError(`Not a GitHub blob or tree URL: ${url}`);
+  }
+
+  const [, owner, repo, kind, refAndPath, query = '', fragment = ''] = match;
+  const segments = refAndPath.replace(/^\/+|\/+$/g, '').split('/');
+
+  // Already a permalink
+  if (isValidGitHash(segments[0])) {
+    return url;
+  }
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+  const { commitHash, rest } = await splitRef(repoRoot, segments, url, remote);
+
+  const canonical = `https://github.com/${owner}/${repo}/${kind}/${commitHash}`;
+  return (rest.length > 0 ? `${canonical}/${rest.join('/')}` : canonical) + query + fragment;
+}
+
+/**
+ * Resolves a GitHub link to a path in a local clone and checks it for drift
+ * The link's ref is resolved like canonicalizeGitHubUrl. For files, the blob SHA at the linked commit is
+ * compared with the Git hash of the working tree file; for directories, the working tree is diffed against
+ * the linked commit. This backs "open link locally" features.
+ * @param {string} url - GitHub blob or tree URL
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within a local clone of the repository
+ * @param {string} [options.remote] - Remote whose tracking branches are preferred (default: the current branch's remote, then origin, then the first remote)
+ * @returns {Promise<object>} Location: { kind, commitHash, path, absolutePath, lineRange, blobHash, workingHash, status }
+ *   where status is 'identical', 'modified' or 'missing'
+ * @throws {TypeError} If url is not a GitHub blob or tree URL
+ * @throws {GitError} If the ref cannot be resolved in the local repository
+ * @throws {FileNotFoundError} If the path does not exist at the linked commit
+ */
+export async function locateGitHubLink(url, options = {}) {
+  const { repoPath = process.cwd(), remote } = options;
+
+  const match = typeof url === 'string' ? url.match(GITHUB_LINK_PATTERN) : null;
+  const link = parseGitHubLink(url);
+  if (!match || !link) {
+    throw new TypeError(`Not a GitHub blob or tree URL: ${url}`);
+  }
+
+  const segments = match[4].replace(/^\/+|\/+$/g, '').split('/');
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const { commitHash, rest } = isValidGitHash(segments[0])
+    ? { commitHash: segments[0], rest: segments.slice(1) }
+    : await splitRef(repoRoot, segments, url, remote);
+
+  const relativePath = decodeURIComponent(rest.join('/'));
+  const absolutePath = relativePath ? path.join(repoRoot, relativePath) : repoRoot;
+
+  let objectHash;
+  try {
+    objectHash = await executeGitCommand(
+      ['git', 'rev-parse', '--verify', '--quiet', `${commitHash}:${relativePath}`],
+      repoRoot
+    );
+  } catch (error) {
+    throw new FileNotFoundError(
+      `Path not found at commit ${commitHash}: ${relativePath}`,
+      {
+        cause: error,
+        context: { repoPath: repoRoot, filePath: relativePath, commitHash }
+      }
+    );
+  }
+
+  let status;
+  let blobHash = null;
+  let workingHash = null;
+
+  if (link.kind === 'tree') {
+    if (!fs.existsSync(absolutePath) || !fs.statSync(absolutePath).isDirectory()) {
+      status = 'missing';
+    } else {
+      status = await isCleanAgainst(repoRoot, commitHash, relativePath) ? 'identical' : 'modified';
+    }
+  } else {
+    // A blob link can point at a submodule, whose entry is not a blob
+    const entry = await getBlobEntry(repoRoot, commitHash, relativePath);
+    blobHash = entry ? entry.blobHash : objectHash;
+    if (!isFileOrLink(absolutePath)) {
+      status = 'missing';
+    } else {
+      ({ status, workingHash } = await getWorkingDrift(repoRoot, relativePath, entry ? entry.mode : '', blobHash));
+    }
+  }
+
+  return {
+    kind: link.kind,
+    commitHash,
+    path: relativePath,
+    absolutePath,
+    lineRange: link.lineRange,
+    blobHash,
+    workingHash,
+    status
+  };
+}
+
+/**
+ * Compares a working tree file with its HEAD version
+ * The file is hashed as Git would store it, with the clean filters and line-ending conversion configured for its path,
+ * and the OID compared with the blob at HEAD. That is one git hash-object rather than a scan of the working tree as
+ * git status does, cheap enough to run before emitting a permalink so the link shows what is on disk. Only content is
+ * compared, not the executable bit.
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<string>} 'identical', 'modified', 'missing' (at HEAD but deleted from the working tree) or
+ *   'untracked' (not a file at HEAD)
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {FileNotFoundError} If the path is a file neither at HEAD nor in the working tree
+ */
+export async function checkDrift(filePath, options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+  const onDisk = isFileOrLink(path.join(repoRoot, relativePath));
+
+  let entry = null;
+  if (!(await isHeadUnborn(repoRoot))) {
+    entry = await getBlobEntry(repoRoot, 'HEAD', relativePath);
+  }
+
+  if (entry === null) {
+    if (!onDisk) {
+      throw new FileNotFoundError(
+        `File not found at HEAD or in the working tree: ${relativePath}`,
+        {
+          context: { repoPath: repoRoot, filePath: relativePath }
+        }
+      );
+    }
+    return 'untracked';
+  }
+  if (!onDisk) {
+    return 'missing';
+  }
+
+  return (await getWorkingDrift(repoRoot, relativePath, entry.mode, entry.blobHash)).status;
+}
+
+/**
+ * Checks whether a path is a file or a symbolic link, without following links
+ * @param {string} absolutePath - Absolute path
+ * @returns {boolean} True if the path is a file or a link
+ */
+function isFileOrLink(absolutePath) {
+  try {
+    const stats = fs.lstatSync(absolutePath);
+    return stats.isFile() || stats.isSymbolicLink();
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Looks up the mode and OID of a file at a revision
+ * @param {string} repoRoot - Repository root path
+ * @param {string} rev - Commit SHA or other revision
+ * @param {string} relativePath - Path relative to repo root
+ * @returns {Promise<{mode: string, blobHash: string}|null>} Entry, or null if the path is not a file at rev
+ */
+async function getBlobEntry(repoRoot, rev, relativePath) {
+  const output = await executeGitCommand(['git', 'ls-tree', '-z', rev, '--', relativePath], repoRoot);
+
+  for (const line of output.split('\0')) {
+    // "<mode> <type> <oid>\t<path>"
+    const separator = line.indexOf('\t');
+    const [mode, type, blobHash] = line.slice(0, separator).split(' ');
+    if (separator !== -1 && line.slice(separator + 1) === relativePath && type === 'blob') {
+      return { mode, blobHash };
+    }
+  }
+  return null;
+}
+
+/**
+ * Compares a file on disk with a blob
+ * Symbolic links are compared by target, as Git stores them, rather than hashed through git hash-object which would
+ * read the file they point to.
+ * @param {string} repoRoot - Repository root path
+ * @param {string} relativePath - Path of an existing file or link, relative to repo root
+ * @param {string} mode - Tree entry mode of the blob
+ * @param {string} blobHash - Blob SHA to compare with
+ * @returns {Promise<{status: string, workingHash: string}>} 'identical' or 'modified', and the blob SHA of the
+ *   working tree file
+ */
+async function getWorkingDrift(repoRoot, relativePath, mode, blobHash) {
+  const absolutePath = path.join(repoRoot, relativePath);
+
+  if (fs.lstatSync(absolutePath).isSymbolicLink()) {
+    // Git stores a link as a blob of its target path
+    const target = fs.readlinkSync(absolutePath, { encoding: 'buffer' });
+    if (mode === SYMLINK_MODE && (await getBlobBytes(repoRoot, blobHash)).equals(target)) {
+      return { status: 'identical', workingHash: blobHash };
+    }
+    const objectFormat = await executeGitCommand(['git', 'rev-parse', '--show-object-format'], repoRoot);
+    const workingHash = createHash(objectFormat)
+      .update(`blob ${target.length}\0`)
+      .update(target)
+      .digest('hex');
+    return { status: 'modified', workingHash };
+  }
+
+  const workingHash = await executeGitCommand(['git', 'hash-object', '--', relativePath], repoRoot);
+  const status = mode !== SYMLINK_MODE && workingHash === blobHash ? 'identical' : 'modified';
+  return { status, workingHash };
+}
+
+/**
+ * Gets the parsed GitHub remote of a repository
+ * @param {string} repoRoot - Repository root path
+ * @param {boolean} resolveSshAliases - Resolve ~/.ssh/config host aliases in the remote URL
+ * @param {string} [remote] - Remote name (default: getDefaultRemote)
+ * @param {boolean} [preferUpstream=false] - Passed to getDefaultRemote when no remote is named
+ * @returns {Promise<object>} Remote info from parseGitHubUrl
+ * @throws {GitError} If the remote does not exist or is not a GitHub remote
+ */
+async function githubRemote(repoRoot, resolveSshAliases, remote, preferUpstream = false) {
+  if (!remote && preferUpstream) {
+    remote = await getDefaultRemote(repoRoot, { preferUpstream });
+  }
+
+  const remoteUrl = await getRemoteUrl(repoRoot, remote);
+  const parsed = remoteUrl ? parseGitHubUrl(remoteUrl, { resolveSshAliases }) : null;
+  if (!parsed || (parsed.host && parsed.host !== 'github')) {
+    throw new GitError(
+      `No GitHub remote configured for repository: ${repoRoot}`,
+      {
+        code: 'REMOTE_NOT_FOUND',
+        context: remote ? { path: repoRoot, remoteUrl, remote } : { path: repoRoot, remoteUrl }
+      }
+    );
+  }
+  return parsed;
+}
+
+/**
+ * Extracts a PR number from a commit's subject line
+ * @param {string} repoRoot - Repository root path
+ * @param {string} commitHash - Commit SHA
+ * @returns {Promise<number | null>} PR number, or null if the subject does not reference a PR
+ */
+async function pullRequestFromMessage(repoRoot, commitHash) {
+  const subject = await executeGitCommand(['git', 'log', '-1', '--format=%s', commitHash], repoRoot);
+  const match = subject.match(PULL_REQUEST_MESSAGE_PATTERN);
+  return match ? Number(match[1] || match[2]) : null;
+}
+
+/**
+ * Finds a PR number from fetched PR head refs containing a commit
+ * @param {string} repoRoot - Repository root path
+ * @param {string} commitHash - Commit SHA
+ * @returns {Promise<number | null>} Number of the PR whose head is the commit, otherwise the lowest PR number containing it
+ */
+async function pullRequestFromRefs(repoRoot, commitHash) {
+  const output = await executeGitCommand(
+    ['git', 'for-each-ref', '--contains', commitHash, '--format=%(objectname) %(refname)', 'refs/pull', 'refs/remotes'],
+    repoRoot
+  );
+
+  const exact = [];
+  const containing = [];
+  for (const line of output.split('\n').filter(Boolean)) {
+    const [objectName, refName] = line.split(' ');
+    const match = refName.match(PULL_REQUEST_REF_PATTERN);
+    if (match) {
+      const number = Number(match[1] || match[2]);
+      (objectName === commitHash ? exact : containing).push(number);
+    }
+  }
+
+  if (exact.length > 0) {
+    return Math.min(...exact);
+  }
+  return containing.length > 0 ? Math.min(...containing) : null;
+}
+
+/**
+ * Splits URL segments into a resolved commit SHA and the remaining path segments
+ * Branch names may contain slashes, so the longest resolvable prefix wins
+ * @param {string} repoRoot - Repository root path
+ * @param {string[]} segments - URL segments following blob/ or tree/
+ * @param {string} url - Original URL (for error reporting)
+ * @param {string} [remote] - Remote whose tracking branches are preferred (default: getDefaultRemote)
+ * @returns {Promise<{commitHash: string, rest: string[]}>} Commit SHA and path segments
+ * @throws {GitError} If no prefix resolves to a commit
+ */
+async function splitRef(repoRoot, segments, url, remote) {
+  const trackingRemote = remote || (await getDefaultRemote(repoRoot));
+
+  for (let split = segments.length; split > 0; split--) {
+    const ref = decodeURIComponent(segments.slice(0, split).join('/'));
+    const commitHash = await resolveCommit(repoRoot, ref, trackingRemote);
+    if (commitHash) {
+      return { commitHash, rest: segments.slice(split) };
+    }
+  }
+
+  throw new GitError(
+    `Cannot resolve ref in URL: ${url}`,
+    {
+      code: 'REF_NOT_FOUND',
+      context: { path: repoRoot, url }
+    }
+  );
+}
+
+/**
+ * Checks whether tracked files under a path match a commit in the working tree
+ * @param {string} repoRoot - Repository root path
+ * @param {string} commitHash - Commit SHA to compare against
+ * @param {string} relativePath - Path relative to the repository root ('' for everything)
+ * @returns {Promise<boolean>} True if there are no differences
+ */
+async function isCleanAgainst(repoRoot, commitHash, relativePath) {
+  const pathspec = relativePath ? ['--', relativePath] : [];
+  try {
+    await executeGitCommand(['git', 'diff', '--quiet', commitHash, ...pathspec], repoRoot);
+    return true;
+  } catch (error) {
+    if (error.exitCode === 1) {
+      return false;
+    }
+    throw error;
+  }
+}
+
+/**
+ * Resolves a ref name to a commit SHA, preferring the remote-tracking branch
+ * @param {string} repoRoot - Repository root path
+ * @param {string} ref - Branch, tag or other ref name
+ * @param {string | null} remote - Remote whose tracking branch is tried first (null for local refs only)
+ * @returns {Promise<string | null>} Commit SHA, or null if the ref does not exist locally or is not a valid ref name
+ */
+async function resolveCommit(repoRoot, ref, remote) {
+  if (INVALID_REF_NAME_PATTERN.test(ref)) {
+    return null;
+  }
+
+  const candidates = remote ? [`refs/remotes/${remote}/${ref}`, ref] : [ref];
+  for (const candidate of candidates) {
+    try {
+      return await executeGitCommand(
+        ['git', 'rev-parse', '--verify', '--quiet', '--end-of-options', `${candidate}^{commit}`],
+        repoRoot
+      );
+    } catch {
+      // Try the next candidate
+    }
+  }
+  return null;
+}
+
+/**
+ * Resolves a revision in the local repository to a commit SHA
+ * @param {string} repoRoot - Repository root path
+ * @param {string} rev - Commit SHA, branch, tag or other revision expression
+ * @returns {Promise<string>} Commit SHA
+ * @throws {UnbornHeadError} If rev is HEAD and the current branch has no commits yet
+ * @throws {GitError} If the revision cannot be resolved (context.shallow is set when the repository is a shallow clone)
+ */
+async function resolveRev(repoRoot, rev) {
+  try {
+    return await executeGitCommand(['git', 'rev-parse', '--verify', '--quiet', `${rev}^{commit}`], repoRoot);
+  } catch (error) {
+    if (rev === 'HEAD') {
+      // Rejects with UnbornHeadError when the current branch has no commits yet
+      await getHeadCommit(repoRoot);
+    }
+
+    const context = { path: repoRoot, ref: rev };
+    let message = `Cannot resolve revision: ${rev}`;
+    if (await isShallow(repoRoot)) {
+      // The commit may exist upstream but predate the shallow boundary
+      context.shallow = true;
+      message += ' (repository is a shallow clone; fetch more history)';
+    }
+
+    throw new GitError(
+      message,
+      {
+        code: 'REF_NOT_FOUND',
+        cause: error,
+        context
+      }
+    );
+  }
+}
+
+/**
+ * Applies an unpushed policy to a commit about to be linked
+ * @param {string} repoRoot - Repository root path
+ * @param {string} commitHash - Commit SHA
+ * @param {string} remote - Remote the URL is built against
+ * @param {'warn'|'fallback'|'error'} unpushed - Unpushed policy
+ * @returns {Promise<string>} The commit itself if pushed or the policy is 'warn', otherwise its nearest pushed ancestor
+ *   (fallback)
+ * @throws {GitError} If the commit is not pushed and no fallback applies
+ */
+async function pushedCommit(repoRoot, commitHash, remote, unpushed) {
+  if (await isCommitOnRemote(repoRoot, commitHash, remote)) {
+    return commitHash;
+  }
+
+  if (unpushed === 'warn') {
+    process.emitWarning(
+      `Commit ${commitHash} is not on remote '${remote}'; the link 404s until it is pushed`,
+      { code: 'COMMIT_NOT_PUSHED' }
+    );
+    return commitHash;
+  }
+
+  if (unpushed === 'fallback') {
+    const ancestor = await getNearestPushedAncestor(repoRoot, commitHash, remote);
+    if (ancestor) {
+      return ancestor;
+    }
+  }
+
+  throw new GitError(
+    `Commit ${commitHash} is not on remote '${remote}'; push it before sharing the link`,
+    {
+      code: 'COMMIT_NOT_PUSHED',
+      context: { path: repoRoot, commit: commitHash, remote }
+    }
+  );
+}
+
+/**
+ * Resolves the URL ref segment for a ref strategy
+ * @param {string} repoRoot - Repository root path
+ * @param {'commit'|'branch'|'tag'|'tag-or-commit'|'last-commit'|'default-branch'} refStrategy - Ref strategy
+ * @param {string} relativePath - Linked path relative to the repository root
+ * @param {string} [remote] - Remote the URL is built against (default: getDefaultRemote)
+ * @param {boolean} [preferUpstream=false] - Passed to getDefaultRemote when no remote is named
+ * @returns {Promise<string>} Commit SHA, branch name or tag name
+ * @throws {GitError} If HEAD is detached (branch), not tagged (tag) or the default branch is unknown (default-branch)
+ * @throws {UnbornHeadError} If the current branch has no commits yet (commit, tag, tag-or-commit and last-commit)
+ * @throws {FileNotFoundError} If the path has never been committed (last-commit)
+ */
+async function resolveRef(repoRoot, refStrategy, relativePath, remote, preferUpstream = false) {
+  if (refStrategy === 'branch') {
+    // An unborn branch still has a name, so no commit is needed
+    const branch = await getCurrentBranch(repoRoot);
+    if (branch === 'HEAD') {
+      throw new GitError(
+        'Cannot build a branch URL: HEAD is detached',
+        {
+          code: 'DETACHED_HEAD',
+          context: { path: repoRoot }
+        }
+      );
+    }
+
+    // A branch tracking e.g. upstream/main is published there as main
+    const tracking = await getTrackingBranch(repoRoot);
+    if (tracking && tracking.remote === (remote || (await getDefaultRemote(repoRoot, { preferUpstream })))) {
+      return tracking.branch;
+    }
+    return branch;
+  }
+
+  if (refStrategy === 'default-branch') {
+    const defaultBranch = await getDefaultBranch(
+      repoRoot,
+      remote || (await getDefaultRemote(repoRoot, { preferUpstream }))
+    );
+    if (!defaultBranch) {
+      throw new GitError(
+        'Cannot build a default branch URL: the remote has no HEAD and no main, master, trunk or develop branch',
+        {
+          code: 'DEFAULT_BRANCH_NOT_FOUND',
+          context: { path: repoRoot }
+        }
+      );
+    }
+    return defaultBranch;
+  }
+
+  const headCommit = await getHeadCommit(repoRoot);
+
+  if (refStrategy === 'last-commit') {
+    const commitHash = await getLastCommitForFile(repoRoot, relativePath);
+    if (!commitHash) {
+      throw new FileNotFoundError(
+        `No commit modifies path: ${relativePath}`,
+        {
+          context: { repoPath: repoRoot, filePath: relativePath }
+        }
+      );
+    }
+    return commitHash;
+  }
+
+  if (refStrategy === 'tag' || refStrategy === 'tag-or-commit') {
+    const tag = await getHeadTag(repoRoot);
+    if (tag) {
+      return tag;
+    }
+    if (refStrategy === 'tag') {
+      throw new GitError(
+        'Cannot build a tag URL: no tag points at HEAD',
+        {
+          code: 'TAG_NOT_FOUND',
+          context: { path: repoRoot }
+        }
+      );
+    }
+  }
+
+  return headCommit;
+}
@@ -0,0 +1,90 @@
+import { getRepoFingerprint } from './refs.mjs';
+import { executeGitCommand, getFileHashAtCommit, getRemoteUrl, resolveRepositoryPath } from './utils/git.mjs';
+import { normalizeRemoteUrl } from './utils/url.mjs';
+import { FileNotFoundError } from './errors.mjs';
+
+/**
+ * Identifies the layout of provenance records; bumped on any change to it
+ */
+export const PROVENANCE_SCHEMA = 'git-file-identifiers/provenance/v1';
+
+/**
+ * Records the identity of a file at a commit for an attestation
+ * Returns { schema, repoFingerprint, remote, commit, tree, path, blobOid, dirty, timestamp }, always with the same
+ * keys and only plain strings and booleans, so it can be serialized with JSON.stringify and checked against
+ * PROVENANCE_SCHEMA by consumers: repoFingerprint the root commit SHA identifying the repository across clones (null
+ * for a shallow clone, see getRepoFingerprint), remote the canonical https://host/owner/repo URL (null without a
+ * remote or if the URL cannot be normalized), tree the commit's root tree SHA, blobOid the file's blob at the commit,
+ * dirty whether the working tree copy differs from it, and timestamp when the record was created as UTC ISO 8601
+ * ('2024-01-15T10:30:00Z').
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.commit='HEAD'] - Commit SHA, branch, tag or other revision
+ * @param {string} [options.remote] - Remote whose URL is recorded (default: the current branch's remote, then origin,
+ *   then the first remote)
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object>} Provenance record
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit
+ * @throws {FileNotFoundError} If the file does not exist at commit
+ */
+export async function createProvenanceRecord(filePath, options = {}) {
+  const { commit = 'HEAD', remote, repoPath = process.cwd() } = options;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+  const oid = await executeGitCommand(['git', 'rev-parse', `${commit}^{commit}`], repoRoot);
+  const tree = await executeGitCommand(['git', 'rev-parse', `${oid}^{tree}`], repoRoot);
+
+  const blobOid = await getFileHashAtCommit(repoRoot, relativePath, oid);
+  if (blobOid === null) {
+    throw new FileNotFoundError(
+      `File not found at ${commit}: ${relativePath}`,
+      {
+        context: { repoPath: repoRoot, filePath: relativePath, commit }
+      }
+    );
+  }
+
+  const remoteUrl = await getRemoteUrl(repoRoot, remote);
+  let normalizedRemote = null;
+  try {
+    normalizedRemote = remoteUrl ? normalizeRemoteUrl(remoteUrl) : null;
+  } catch {
+    normalizedRemote = null;
+  }
+
+  return {
+    schema: PROVENANCE_SCHEMA,
+    repoFingerprint: await getRepoFingerprint({ repoPath: repoRoot, rev: oid }),
+    remote: normalizedRemote,
+    commit: oid,
+    tree,
+    path: relativePath,
+    blobOid,
+    dirty: await differsFromCommit(repoRoot, relativePath, oid),
+    timestamp: new Date().toISOString().replace(/\.\d{3}Z$/, 'Z')
+  };
+}
+
+/**
+ * Checks whether the working tree copy of a file differs from a commit
+ * @param {string} repoRoot - Repository root path
+ * @param {string} relativePath - Path relative to repo root
+ * @param {string} commit - Commit SHA
+ * @returns {Promise<boolean>} True if the file was changed or deleted since commit
+ */
+async function differsFromCommit(repoRoot, relativePath, commit) {
+  try {
+    await executeGitCommand(
+      ['git', 'diff', '--quiet', '--no-ext-diff', '--no-textconv', commit, '--', relativePath],
+      repoRoot
+    );
+    return false;
+  } catch (error) {
+    // Exit code 1: differences found
+    if (error.exitCode === 1) {
+      return true;
+    }
+    throw error;
+  }
+}
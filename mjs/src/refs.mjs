@@ -0,0 +1,436 @@
+import fs from 'fs';
+import path from 'path';
+import {
+  executeGitCommand,
+  getCurrentBranch,
+  getFileHashAtCommit,
+  getHeadCommit,
+  getRepositoryRoot,
+  isHeadUnborn,
+  isShallow
+} from './utils/git.mjs';
+
+/**
+ * Ref namespaces and the kind of ref they hold
+ */
+const REF_KINDS = [
+  ['refs/heads/', 'branch'],
+  ['refs/tags/', 'tag'],
+  ['refs/remotes/', 'remote'],
+  ['refs/notes/', 'note']
+];
+
+/**
+ * "<old> <new> <name> <<email>> <time> <offset>\t<message>" reflog line
+ */
+const REFLOG_LINE_PATTERN = /^([0-9a-f]+) ([0-9a-f]+) (.*?) ?<([^>]*)> (\d+) ([+-]\d{4})(?:\t(.*))?$/;
+
+/**
+ * "WIP on <branch>: ..." or "On <branch>: ..." stash message
+ */
+const STASH_MESSAGE_PATTERN = /^(?:WIP on|On) ([^:]+):/;
+
+/**
+ * Tag name globs that look like semantic versions, with or without a "v" prefix
+ */
+const SEMVER_TAG_GLOBS = ['v[0-9]*.[0-9]*.[0-9]*', '[0-9]*.[0-9]*.[0-9]*'];
+
+/**
+ * Reports the branch, commit and upstream of HEAD
+ * Unlike getCurrentBranch, which returns the string 'HEAD' when detached, the branch and commit are kept apart. On a
+ * branch with no commits yet (a fresh git init or orphan branch) the branch is still reported and oid is null.
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<{branch: string|null, oid: string|null, detached: boolean, upstream: string|null}>} HEAD: branch
+ *   is null when detached, oid is null when the branch has no commits yet, upstream (e.g. 'origin/main') is null when
+ *   detached or not tracking
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function getHeadInfo(options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const oid = (await isHeadUnborn(repoRoot)) ? null : await getHeadCommit(repoRoot);
+  const branch = await getCurrentBranch(repoRoot);
+  if (branch === 'HEAD') {
+    return { branch: null, oid, detached: true, upstream: null };
+  }
+
+  let upstream = null;
+  try {
+    upstream = await executeGitCommand('git rev-parse --abbrev-ref --symbolic-full-name @{upstream}', repoRoot);
+  } catch {
+    // Branch has no upstream
+  }
+
+  return { branch, oid, detached: false, upstream };
+}
+
+/**
+ * Identifies a repository by the root commit of a revision's history
+ * The root commit is the same in every clone, fork and mirror and does not depend on remote URLs, so it serves as a
+ * stable cache or namespace key. When the history has several roots (unrelated histories merged together) the lowest
+ * SHA is used: commit dates can tie or be rewritten, so picking by date would not give every clone the same answer.
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {string} [options.rev='HEAD'] - Commit SHA, branch, tag or other revision whose history is searched
+ * @returns {Promise<string | null>} Root commit SHA, or null if rev is HEAD and it has no commits yet, or the clone is
+ *   shallow, where the root commit is not available
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If rev does not name a commit
+ */
+export async function getRepoFingerprint(options = {}) {
+  const { repoPath = process.cwd(), rev = 'HEAD' } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+  if ((rev === 'HEAD' && (await isHeadUnborn(repoRoot))) || (await isShallow(repoRoot))) {
+    return null;
+  }
+
+  const output = await executeGitCommand(['git', 'rev-list', '--max-parents=0', `${rev}^{commit}`], repoRoot);
+  return output.split('\n').filter(Boolean).sort()[0];
+}
+
+/**
+ * Lists references with their targets in one call
+ * Each entry is { name, shorthand, oid, kind, peeled, symref, commit }: name is the full ref name, shorthand the
+ * unambiguous short name ('main', 'origin/main'), oid the object the ref points at (the tag object for an annotated
+ * tag), kind 'branch', 'tag', 'remote', 'note' or 'other', peeled the commit an annotated tag points at, symref the
+ * target of a symbolic ref such as refs/remotes/origin/HEAD, and commit the peeled target or oid.
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {string} [options.pattern] - Only list refs under this prefix or matching this glob, e.g. 'refs/tags' or
+ *   'refs/remotes/origin/*'
+ * @returns {Promise<object[]>} Refs sorted by name
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function listRefs(options = {}) {
+  const { repoPath = process.cwd(), pattern } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  return forEachRef(repoRoot, pattern ? [pattern] : []);
+}
+
+/**
+ * Lists the branches, remote-tracking branches and tags that contain a commit
+ * Mirrors git branch -a --contains and git tag --contains, returning entries shaped as by listRefs. Symbolic refs such
+ * as refs/remotes/origin/HEAD are left out, since the branch they point at is listed itself.
+ * @param {string} commit - Commit SHA or revision
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object[]>} Refs sorted by name
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+export async function refsContaining(commit, options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const refs = await forEachRef(repoRoot, ['--contains', commit, 'refs/heads', 'refs/remotes', 'refs/tags']);
+  return refs.filter((ref) => ref.symref === null);
+}
+
+/**
+ * Reads refs with git for-each-ref
+ * @param {string} repoRoot - Repository root path
+ * @param {string[]} args - Filter options and patterns passed to for-each-ref
+ * @returns {Promise<object[]>} Refs sorted by name
+ */
+async function forEachRef(repoRoot, args) {
+  const formatString = '%(refname)%00%(refname:short)%00%(objectname)%00%(*objectname)%00%(symref)';
+  const output = await executeGitCommand(['git', 'for-each-ref', `--format=${formatString}`, ...args], repoRoot);
+  return output.split('\n').filter(Boolean).map((line) => {
+    const [name, shorthand, oid, peeled, symref] = line.split('\0');
+    const kind = REF_KINDS.find(([prefix]) => name.startsWith(prefix))?.[1] || 'other';
+    return {
+      name,
+      shorthand,
+      oid,
+      kind,
+      peeled: peeled || null,
+      symref: symref || null,
+      commit: peeled || oid
+    };
+  });
+}
+
+/**
+ * Names a commit by the nearest tag reachable from it, like git describe --tags --long
+ * Returns { tag, distance, oid, abbreviated, name }: distance is the number of commits between the tag and the commit
+ * (0 if tagged), abbreviated the unambiguous short SHA and name the describe string, e.g. 'v1.2.0-3-gabc123d'.
+ * Lightweight and annotated tags are both considered.
+ * @param {string} [commit='HEAD'] - Commit SHA, branch, tag or other revision
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object | null>} Description, or null if no tag is reachable from the commit
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+export async function describe(commit = 'HEAD', options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const oid = await executeGitCommand(['git', 'rev-parse', '--verify', `${commit}^{commit}`], repoRoot);
+
+  let output;
+  try {
+    output = await executeGitCommand(['git', 'describe', '--tags', '--long', oid], repoRoot);
+  } catch {
+    return null;
+  }
+
+  // "<tag>-<distance>-g<abbreviated>"; the tag itself may contain dashes
+  const match = output.match(/^(.*)-(\d+)-g([0-9a-f]+)$/);
+  return { tag: match[1], distance: Number(match[2]), oid, abbreviated: match[3], name: output };
+}
+
+/**
+ * Finds the nearest tag reachable from a commit: the release it belongs to
+ * "Nearest" is by history, as git describe counts it, so a tag on the commit itself wins over older ones.
+ * @param {string} [commit='HEAD'] - Commit SHA, branch, tag or other revision
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {boolean} [options.semver=false] - Only consider tags that look like semantic versions, such as 'v1.2.3' or
+ *   '1.2.3-rc.1'
+ * @returns {Promise<object | null>} Tag as returned by resolveTag, or null if no (matching) tag is reachable
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+export async function getLatestTag(commit = 'HEAD', options = {}) {
+  const { repoPath = process.cwd(), semver = false } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const oid = await executeGitCommand(['git', 'rev-parse', '--verify', `${commit}^{commit}`], repoRoot);
+
+  const command = ['git', 'describe', '--tags', '--abbrev=0'];
+  if (semver) {
+    command.push(...SEMVER_TAG_GLOBS.map((glob) => `--match=${glob}`));
+  }
+
+  let name;
+  try {
+    name = await executeGitCommand([...command, oid], repoRoot);
+  } catch {
+    return null;
+  }
+
+  return resolveTag(name, { repoPath: repoRoot });
+}
+
+/**
+ * Resolves a tag, annotated or lightweight, to the object it tags
+ * Returns { name, oid, target, targetType, annotated, taggerName, taggerEmail, taggerDate }: oid is what the tag ref
+ * points at (the tag object for an annotated tag), target the tagged object and targetType its type ('commit',
+ * 'tree', 'blob' or 'tag'). Annotated tags are peeled to their target and report the tagger (date as ISO 8601);
+ * lightweight tags resolve to the object the ref points at directly, with null tagger fields.
+ * @param {string} name - Tag name, with or without the 'refs/tags/' prefix
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object | null>} Tag, or null if there is no such tag
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function resolveTag(name, options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const refName = name.startsWith('refs/tags/') ? name : `refs/tags/${name}`;
+  const formatString = '%(refname)%00%(objectname)%00%(objecttype)%00%(*objectname)%00%(*objecttype)' +
+    '%00%(taggername)%00%(taggeremail:trim)%00%(taggerdate:iso-strict)';
+  const output = await executeGitCommand(['git', 'for-each-ref', `--format=${formatString}`, refName], repoRoot);
+
+  for (const line of output.split('\n').filter(Boolean)) {
+    const [refname, oid, objectType, target, targetType, tagger, email, date] = line.split('\0');
+    // The pattern also matches tags below refname/ as a directory
+    if (refname !== refName) {
+      continue;
+    }
+    // Only annotated tags have a peeled target and a tagger
+    return {
+      name: refname.slice('refs/tags/'.length),
+      oid,
+      target: target || oid,
+      targetType: targetType || objectType,
+      annotated: objectType === 'tag',
+      taggerName: tagger || null,
+      taggerEmail: email || null,
+      taggerDate: date || null
+    };
+  }
+
+  return null;
+}
+
+/**
+ * Reads the reflog of a reference, latest update first
+ * The reflog records every value a ref has held locally, so it answers what HEAD or a branch pointed at when a link
+ * was generated, even after a reset or rebase. HEAD's reflog is read per working tree. Returns { refname, index,
+ * selector, oldOid, newOid, committerName, committerEmail, timestamp, message } entries: refname is the full ref
+ * name, index 0 for the latest update, selector the revision naming the entry (e.g. 'HEAD@{2}'), oldOid all zeros
+ * when the ref was created, timestamp ISO 8601 in the committer's time zone and message the reason recorded for the
+ * update (e.g. 'checkout: moving from main to feature').
+ * @param {string} [refname='HEAD'] - 'HEAD', a full ref name or a branch, tag or remote name that resolves to one
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object[]>} Entries, empty if the ref has no reflog
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If refname does not resolve
+ * @throws {TypeError} If refname names an object rather than a ref
+ */
+export async function getReflog(refname = 'HEAD', options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  let fullName = refname;
+  if (refname !== 'HEAD' && !refname.startsWith('refs/')) {
+    fullName = await executeGitCommand(['git', 'rev-parse', '--symbolic-full-name', refname], repoRoot);
+    if (!fullName) {
+      throw new TypeError(`Not a ref: ${refname}`);
+    }
+  }
+
+  const logPath = await executeGitCommand(['git', 'rev-parse', '--git-path', `logs/${fullName}`], repoRoot);
+  let content;
+  try {
+    content = await fs.promises.readFile(path.resolve(repoRoot, logPath), 'utf8');
+  } catch {
+    return [];
+  }
+
+  const entries = [];
+  for (const line of content.split('\n').reverse()) {
+    const match = line.match(REFLOG_LINE_PATTERN);
+    if (!match) {
+      continue;
+    }
+    const [, oldOid, newOid, committerName, committerEmail, time, offset, message] = match;
+    const index = entries.length;
+    entries.push({
+      refname: fullName,
+      index,
+      selector: `${fullName}@{${index}}`,
+      oldOid,
+      newOid,
+      committerName,
+      committerEmail,
+      timestamp: formatTimestamp(time, offset),
+      message: message || ''
+    });
+  }
+
+  return entries;
+}
+
+/**
+ * Formats a Git raw date as ISO 8601 in its own time zone
+ * @param {string} seconds - Seconds since the epoch
+ * @param {string} offset - Time zone offset as Git records it, e.g. '+0200'
+ * @returns {string} Timestamp such as '2024-01-15T10:30:00+02:00'
+ */
+export function formatTimestamp(seconds, offset) {
+  const sign = offset.startsWith('-') ? -1 : 1;
+  const minutes = sign * (parseInt(offset.slice(1, 3), 10) * 60 + parseInt(offset.slice(3), 10));
+  // Shift to the zone, then replace toISOString's Z with the offset
+  const local = new Date((parseInt(seconds, 10) + minutes * 60) * 1000).toISOString().slice(0, 19);
+  return `${local}${offset.slice(0, 3)}:${offset.slice(3)}`;
+}
+
+/**
+ * Lists stash entries, latest first
+ * Returns { index, selector, oid, message, branch } entries: index 0 for the latest, selector the revision naming
+ * the entry (e.g. 'stash@{1}'), oid the stash commit whose tree holds the stashed working tree, message e.g.
+ * 'WIP on main: abc1234 Fix parser' and branch the branch the work was stashed from (null if detached or not
+ * recorded in the message).
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object[]>} Entries, empty if nothing is stashed
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function listStashes(options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const entries = await getReflog('refs/stash', { repoPath });
+  return entries.map((entry) => {
+    const match = entry.message.match(STASH_MESSAGE_PATTERN);
+    const branch = match && match[1] !== '(no branch)' ? match[1] : null;
+    return {
+      index: entry.index,
+      selector: `stash@{${entry.index}}`,
+      oid: entry.newOid,
+      message: entry.message,
+      branch
+    };
+  });
+}
+
+/**
+ * Gets the blob SHA of a file as saved in a stash entry
+ * Looks in the stashed working tree, then among the untracked files the entry saved (git stash -u), so unsaved work
+ * can be identified.
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {number} [options.index=0] - Stash entry, 0 for the latest
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<string | null>} Blob SHA, or null if the entry does not hold the file
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitError} If there is no stash entry at index
+ * @throws {TypeError} If index is not a non-negative integer
+ */
+export async function getStashFileHash(filePath, options = {}) {
+  const { index = 0, repoPath = process.cwd() } = options;
+
+  if (!Number.isInteger(index) || index < 0) {
+    throw new TypeError(`Invalid stash index: ${index}. Must be a non-negative integer`);
+  }
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+  const selector = `stash@{${index}}`;
+
+  const blobHash = await getFileHashAtCommit(repoRoot, filePath, selector);
+  if (blobHash !== null) {
+    return blobHash;
+  }
+
+  // Untracked files are saved in a third parent
+  try {
+    await executeGitCommand(['git', 'rev-parse', '--verify', '--quiet', `${selector}^3`], repoRoot);
+  } catch {
+    return null;
+  }
+  return getFileHashAtCommit(repoRoot, filePath, `${selector}^3`);
+}
+
+/**
+ * Resolves any revision expression Git understands to an object
+ * Accepts everything git rev-parse does: ref names, ancestry (HEAD~3, main^2), reflog and upstream forms
+ * (main@{upstream}), peeling (v1.0^{commit}) and paths in a tree (main:src/app.js). Returns { spec, oid, type, ref }:
+ * type is 'commit', 'tree', 'blob' or 'tag', and ref the full name of the ref the expression names directly (e.g.
+ * 'refs/remotes/origin/main' for main@{upstream}; null for expressions that navigate from a ref, such as HEAD~3).
+ * @param {string} spec - Revision expression
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object | null>} Resolved revision, or null if the expression names no object
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function resolveRevspec(spec, options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  let oid;
+  try {
+    oid = await executeGitCommand(['git', 'rev-parse', '--verify', '--quiet', spec], repoRoot);
+  } catch {
+    return null;
+  }
+
+  const type = await executeGitCommand(['git', 'cat-file', '-t', oid], repoRoot);
+  const ref = await executeGitCommand(['git', 'rev-parse', '--symbolic-full-name', spec], repoRoot);
+  return { spec, oid, type, ref: ref || null };
+}
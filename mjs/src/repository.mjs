@@ -0,0 +1,166 @@
+import fs from 'fs';
+import path from 'path';
+import { executeGitCommand, getRemoteUrl, getRepositoryRoot, resolveRepositoryPath } from './utils/git.mjs';
+import { resolveFilePath } from './utils/path.mjs';
+
+/**
+ * Repository states reported by getRepoState
+ */
+export const REPOSITORY_STATES = ['clean', 'merge', 'revert', 'cherry-pick', 'bisect', 'rebase', 'apply-mailbox'];
+
+/**
+ * Git directory markers for operations in progress, checked in order
+ */
+const STATE_MARKERS = [
+  ['rebase-merge', 'rebase'],
+  ['rebase-apply/applying', 'apply-mailbox'],
+  ['rebase-apply', 'rebase'],
+  ['MERGE_HEAD', 'merge'],
+  ['REVERT_HEAD', 'revert'],
+  ['CHERRY_PICK_HEAD', 'cherry-pick'],
+  ['BISECT_LOG', 'bisect']
+];
+
+/**
+ * Reports which multi-step operation, if any, is in progress
+ * While a merge, rebase, cherry-pick, revert or bisect is in progress HEAD is transient, so URLs generated
+ * against it may not survive the operation. State is read per working tree.
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<'clean'|'merge'|'revert'|'cherry-pick'|'bisect'|'rebase'|'apply-mailbox'>} 'clean' or the
+ *   operation in progress ('apply-mailbox' is git am)
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function getRepoState(options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const markers = STATE_MARKERS.flatMap(([marker]) => ['--git-path', marker]);
+  const paths = (await executeGitCommand(['git', 'rev-parse', ...markers], repoRoot)).split('\n');
+
+  for (const [index, [, state]] of STATE_MARKERS.entries()) {
+    if (fs.existsSync(path.resolve(repoRoot, paths[index]))) {
+      return state;
+    }
+  }
+
+  return 'clean';
+}
+
+/**
+ * Reports the superproject gitlink and submodule-level identity of a path
+ * Identification functions already resolve paths inside submodules against the submodule itself; this adds
+ * the superproject's view, i.e. which submodule commit the superproject pins.
+ * @param {string} filePath - File or directory path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the superproject
+ * @returns {Promise<object | null>} Identity: { superproject: { root, gitlinkPath, gitlinkCommit },
+ *   submodule: { root, headCommit, remoteUrl, filePath }, isModified }, or null if the path is not inside a submodule
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function getSubmoduleIdentity(filePath, options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const { repoRoot: submoduleRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  const superprojectRoot = await executeGitCommand('git rev-parse --show-superproject-working-tree', submoduleRoot);
+  if (!superprojectRoot) {
+    return null;
+  }
+
+  const gitlinkPath = resolveFilePath(superprojectRoot, submoduleRoot);
+
+  let gitlinkCommit = null;
+  try {
+    const lsTreeOutput = await executeGitCommand(['git', 'ls-tree', 'HEAD', '--', gitlinkPath], superprojectRoot);
+    // "160000 commit <hash>\t<path>"
+    const match = lsTreeOutput.match(/^160000 commit ([0-9a-f]{40})\t/);
+    if (match) {
+      gitlinkCommit = match[1];
+    }
+  } catch {
+    // Superproject has no commits yet
+  }
+
+  let headCommit = null;
+  try {
+    headCommit = await executeGitCommand('git rev-parse --verify --quiet HEAD', submoduleRoot);
+  } catch {
+    // Unborn submodule HEAD
+  }
+
+  return {
+    superproject: {
+      root: superprojectRoot,
+      gitlinkPath,
+      gitlinkCommit
+    },
+    submodule: {
+      root: submoduleRoot,
+      headCommit,
+      remoteUrl: await getRemoteUrl(submoduleRoot),
+      filePath: relativePath
+    },
+    isModified: headCommit !== gitlinkCommit
+  };
+}
+
+/**
+ * Lists the main and linked working trees of a repository
+ * Works from any of the repository's working trees; the main working tree is always listed first.
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object[]>} Worktrees: { path, head, branch, isMain, isBare, isDetached, isLocked, isPrunable }
+ *   where head is null for bare repositories and branch is null when detached or bare
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function listWorktrees(options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+  const output = await executeGitCommand('git worktree list --porcelain', repoRoot);
+
+  const worktrees = [];
+  for (const block of output.split('\n\n')) {
+    const lines = block.trim().split('\n').filter(Boolean);
+    if (lines.length === 0 || !lines[0].startsWith('worktree ')) {
+      continue;
+    }
+
+    const worktree = {
+      path: lines[0].slice('worktree '.length),
+      head: null,
+      branch: null,
+      isMain: worktrees.length === 0,
+      isBare: false,
+      isDetached: false,
+      isLocked: false,
+      isPrunable: false
+    };
+
+    for (const line of lines.slice(1)) {
+      const separator = line.indexOf(' ');
+      const key = separator === -1 ? line : line.slice(0, separator);
+      const value = separator === -1 ? '' : line.slice(separator + 1);
+
+      if (key === 'HEAD') {
+        worktree.head = value;
+      } else if (key === 'branch') {
+        worktree.branch = value.replace(/^refs\/heads\//, '');
+      } else if (key === 'bare') {
+        worktree.isBare = true;
+      } else if (key === 'detached') {
+        worktree.isDetached = true;
+      } else if (key === 'locked') {
+        worktree.isLocked = true;
+      } else if (key === 'prunable') {
+        worktree.isPrunable = true;
+      }
+    }
+
+    worktrees.push(worktree);
+  }
+
+  return worktrees;
+}
@@ -0,0 +1,150 @@
+import os from 'os';
+import { executeGitCommand, getRepositoryRoot } from './utils/git.mjs';
+
+/**
+ * Supported signature formats
+ */
+export const SIGNATURE_FORMATS = ['openpgp', 'ssh', 'x509'];
+
+/**
+ * Git's %G? verification codes and the status each reports
+ */
+const SIGNATURE_STATUS_CODES = {
+  G: 'good',
+  U: 'unknown-validity',
+  B: 'bad',
+  X: 'expired-signature',
+  Y: 'expired-key',
+  R: 'revoked-key',
+  E: 'unverifiable',
+  N: 'unsigned'
+};
+
+/**
+ * Armor headers identifying each signature format
+ */
+const SIGNATURE_ARMOR = [
+  ['-----BEGIN PGP SIGNATURE-----', 'openpgp'],
+  ['-----BEGIN SSH SIGNATURE-----', 'ssh'],
+  ['-----BEGIN SIGNED MESSAGE-----', 'x509']
+];
+
+/**
+ * Commit headers holding the signature (gpgsig-sha256 in SHA-256 repositories)
+ */
+const SIGNATURE_HEADERS = ['gpgsig', 'gpgsig-sha256'];
+
+/**
+ * Extracts the signature block and signed payload of a commit
+ * No keys are needed; the signature is returned as stored, so it can be checked with external tools
+ * (gpg --verify, ssh-keygen -Y verify) against the payload.
+ * @param {string} [commit='HEAD'] - Commit SHA, branch, tag or other revision
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<{format: string|null, signature: string, payload: string} | null>} format 'openpgp', 'ssh' or
+ *   'x509' (null if the armor is unrecognized), the ASCII-armored signature and the signed data (the commit object
+ *   without its signature header); null if the commit is not signed
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+export async function extractSignature(commit = 'HEAD', options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const content = (
+    await executeGitCommand(['git', 'cat-file', 'commit', `${commit}^{commit}`], repoRoot, { encoding: 'buffer' })
+  ).toString('utf8');
+
+  const separator = content.indexOf('\n\n');
+  const header = separator === -1 ? content : content.slice(0, separator);
+  const rest = separator === -1 ? '' : content.slice(separator);
+
+  const signatureLines = [];
+  const payloadLines = [];
+  let inSignature = false;
+  for (const line of header.split('\n')) {
+    // Multi-line header values continue on lines starting with a space
+    if (inSignature && line.startsWith(' ')) {
+      signatureLines.push(line.slice(1));
+      continue;
+    }
+    const space = line.indexOf(' ');
+    const name = space === -1 ? line : line.slice(0, space);
+    inSignature = SIGNATURE_HEADERS.includes(name) && signatureLines.length === 0;
+    if (inSignature) {
+      signatureLines.push(line.slice(space + 1));
+    } else {
+      payloadLines.push(line);
+    }
+  }
+
+  if (signatureLines.length === 0) {
+    return null;
+  }
+
+  const signature = signatureLines.join('\n');
+  const format = SIGNATURE_ARMOR.find(([armor]) => signature.startsWith(armor))?.[1] || null;
+  return { format, signature, payload: payloadLines.join('\n') + rest };
+}
+
+/**
+ * Verifies a commit signature with Git and reports the signer
+ * Requires gpg (OpenPGP), gpgsm (X.509) or ssh-keygen (SSH) as configured for Git. Keys come from the user's
+ * keyring unless another is given. status is 'good'; 'unknown-validity' (intact, but the key is not trusted or not
+ * an allowed signer); 'bad'; 'expired-signature'; 'expired-key'; 'revoked-key'; 'unverifiable' (e.g. the key is
+ * missing, or no SSH allowed signers file is configured) or 'unsigned'. valid is true only for 'good'.
+ * @param {string} [commit='HEAD'] - Commit SHA, branch, tag or other revision
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {string} [options.keyring] - For OpenPGP and X.509 signatures, a GnuPG home directory; for SSH
+ *   signatures, an allowed signers file (default: Git's configuration, i.e. ~/.gnupg or gpg.ssh.allowedSignersFile)
+ * @returns {Promise<{status: string, valid: boolean, signer: string|null, key: string|null,
+ *   fingerprint: string|null}>} Verification result; signer is e.g. 'Jane Doe <jane@example.com>' for OpenPGP or
+ *   the allowed-signers principal for SSH
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+export async function verifySignature(commit = 'HEAD', options = {}) {
+  const { repoPath = process.cwd(), keyring } = options;
+
+  const signature = await extractSignature(commit, { repoPath });
+  if (!signature) {
+    return { status: 'unsigned', valid: false, signer: null, key: null, fingerprint: null };
+  }
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  let config = [];
+  const env = {};
+  if (keyring !== undefined) {
+    const keyringPath = keyring.replace(/^~(?=$|\/)/, os.homedir());
+    if (signature.format === 'ssh') {
+      config = ['-c', `gpg.ssh.allowedSignersFile=${keyringPath}`];
+    } else {
+      env.GNUPGHOME = keyringPath;
+    }
+  }
+
+  const output = await executeGitCommand(
+    ['git', ...config, 'show', '-s', '--format=%G?%x00%GS%x00%GK%x00%GF', `${commit}^{commit}`],
+    repoRoot,
+    { env }
+  );
+
+  const [code, signer, key, fingerprint] = output.split('\0');
+  // Git reports N when it cannot check the signature at all, e.g. an SSH signature with no allowed signers file
+  // configured
+  let status = SIGNATURE_STATUS_CODES[code] || 'unverifiable';
+  if (status === 'unsigned') {
+    status = 'unverifiable';
+  }
+
+  return {
+    status,
+    valid: status === 'good',
+    signer: signer || null,
+    key: key || null,
+    fingerprint: fingerprint || null
+  };
+}
@@ -0,0 +1,499 @@
+import fs from 'fs';
+import os from 'os';
+import path from 'path';
+import { executeGitCommand, getRepositoryRoot, resolveRepositoryPath } from './utils/git.mjs';
+import { FileNotFoundError, GitCommandError } from './errors.mjs';
+
+/**
+ * git status --porcelain=v2 XY codes
+ */
+export const STATUS_CODES = {
+  '.': 'unmodified',
+  M: 'modified',
+  T: 'type-changed',
+  A: 'added',
+  D: 'deleted',
+  R: 'renamed',
+  C: 'copied',
+  U: 'unmerged'
+};
+
+/**
+ * Builds a file status entry
+ * @param {string} filePath - Path relative to the repository root
+ * @param {string} [index='unmodified'] - Change staged in the index
+ * @param {string} [worktree='unmodified'] - Change in the working tree
+ * @param {string|null} [renameSource=null] - Original path of a rename or copy
+ * @param {object} [flags={}] - Index entry bits
+ * @param {boolean} [flags.skipWorktree=false] - Skip-worktree bit
+ * @param {boolean} [flags.assumeUnchanged=false] - Assume-unchanged bit
+ * @returns {object} File status
+ */
+function fileStatus(filePath, index = 'unmodified', worktree = 'unmodified', renameSource = null, flags = {}) {
+  const { skipWorktree = false, assumeUnchanged = false } = flags;
+  const unchanged = ['unmodified', 'untracked', 'ignored', 'unmerged'];
+  return {
+    path: filePath,
+    index,
+    worktree,
+    renameSource,
+    isStaged: !unchanged.includes(index),
+    isUnstaged: !unchanged.includes(worktree),
+    isConflicted: index === 'unmerged',
+    skipWorktree,
+    assumeUnchanged,
+    mayBeStale: skipWorktree || assumeUnchanged
+  };
+}
+
+/**
+ * Reports the index and working-tree status of a file
+ * Staged and unstaged changes are reported separately, so a file that was staged and then edited again has both
+ * index and worktree set. Git does not compare files flagged skip-worktree (sparse checkout, git update-index
+ * --skip-worktree) or assume-unchanged against the working tree, so they can report 'unmodified' while edited on
+ * disk; mayBeStale flags them. Renames are detected across the whole repository, so a staged git mv old.js new.js
+ * reports new.js as renamed from old.js (querying old.js returns the same entry). To check many paths, use
+ * getRepoStatus once instead.
+ * Changes are 'unmodified', 'modified', 'type-changed', 'added', 'deleted', 'renamed', 'copied', 'unmerged',
+ * 'untracked' or 'ignored'; a conflicted file reports 'unmerged' on both sides, an untracked or ignored file
+ * reports 'untracked' or 'ignored' on both.
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object>} Status: { path, index, worktree, renameSource, isStaged, isUnstaged, isConflicted,
+ *   skipWorktree, assumeUnchanged, mayBeStale }
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {FileNotFoundError} If the file is neither tracked nor on disk
+ */
+export async function getFileStatus(filePath, options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  const match = (await readStatus(repoRoot)).find(
+    (status) => status.path === relativePath || status.renameSource === relativePath
+  );
+  if (match) {
+    return match;
+  }
+
+  try {
+    await executeGitCommand(['git', 'ls-files', '--error-unmatch', '--', relativePath], repoRoot);
+    const flags = (await readIndexFlags(repoRoot, relativePath)).get(relativePath);
+    return fileStatus(relativePath, 'unmodified', 'unmodified', null, flags);
+  } catch {
+    // Not tracked
+  }
+
+  try {
+    fs.lstatSync(path.join(repoRoot, relativePath));
+  } catch {
+    throw new FileNotFoundError(
+      `File not found in repository: ${relativePath}`,
+      {
+        context: { repoPath: repoRoot, filePath: relativePath }
+      }
+    );
+  }
+
+  // Ignored files are not listed by --untracked-files
+  return fileStatus(relativePath, 'ignored', 'ignored');
+}
+
+/**
+ * Lists the index entries of a path, including conflict stages
+ * Each entry is { path, oid, mode, stage }: oid is the blob SHA (a commit for a submodule gitlink), mode the octal
+ * file mode ('100644', '100755', '120000' for symlinks, '160000' for gitlinks) and stage 0 for a merged entry or
+ * 1 (common ancestor), 2 (ours) or 3 (theirs) while the path has a merge conflict.
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object[]>} Entries ordered by stage, empty if the path is not in the index
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function getIndexEntries(filePath, options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  const output = await executeGitCommand(['git', 'ls-files', '--stage', '-z', '--', relativePath], repoRoot);
+
+  const entries = [];
+  for (const line of output.split('\0')) {
+    // "<mode> <oid> <stage>\t<path>"
+    const separator = line.indexOf('\t');
+    if (separator === -1 || line.slice(separator + 1) !== relativePath) {
+      continue;
+    }
+    const [mode, oid, stage] = line.slice(0, separator).split(' ');
+    entries.push({ path: relativePath, oid, mode, stage: Number(stage) });
+  }
+
+  return entries.sort((a, b) => a.stage - b.stage);
+}
+
+/**
+ * Gets the staged entry of a path: what the next commit will contain
+ * Unlike the HEAD blob, this reflects git add since the last commit, so tools can identify exactly what is about
+ * to be committed.
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {number} [options.stage=0] - Index stage: 0 for a merged entry, or 1-3 to read a side of a merge conflict
+ * @returns {Promise<object | null>} Entry { path, oid, mode, stage }, or null if the path has no entry at that stage
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {TypeError} If stage is not 0-3
+ */
+export async function getIndexEntry(filePath, options = {}) {
+  const { stage = 0 } = options;
+
+  if (![0, 1, 2, 3].includes(stage)) {
+    throw new TypeError(`Invalid index stage: ${stage}. Must be 0, 1, 2 or 3`);
+  }
+
+  const entries = await getIndexEntries(filePath, options);
+  return entries.find((entry) => entry.stage === stage) || null;
+}
+
+/**
+ * Lists the status of every changed, untracked (and optionally ignored) file
+ * Runs git status once for the whole repository; unmodified tracked files are not listed. A pathspec such as
+ * src/**\/*.js limits the entries using Git's own matching: it is relative to the repository root and read with
+ * :(glob) magic (* stays within a directory, ** crosses them) unless it carries its own magic, e.g. :(icase)*.md.
+ * Renames are only paired when both paths match the pathspec.
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {boolean} [options.includeIgnored=false] - Also list ignored files
+ * @param {string} [options.pathspec] - Only list paths matching this pathspec (default: all)
+ * @returns {Promise<object[]>} Status entries (see getFileStatus) in git status order
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function getRepoStatus(options = {}) {
+  const { repoPath = process.cwd(), includeIgnored = false, pathspec } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+  return readStatus(repoRoot, includeIgnored, pathspec);
+}
+
+/**
+ * Summarizes the status of every file below a directory
+ * Uses one git status and one git ls-files run regardless of the number of files, so file-tree views can badge
+ * folders cheaply. A file that is staged and then edited again counts as both staged and modified; every other
+ * file is counted once.
+ * @param {string} dirPath - Directory path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object>} Summary: { path, clean, modified, staged, untracked, conflicted, isClean } where path is
+ *   relative to the repository root ('' for the root)
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function getDirectoryStatus(dirPath, options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  let { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, dirPath);
+  if (relativePath === '.') {
+    relativePath = '';
+  }
+
+  const prefix = relativePath ? `${relativePath}/` : '';
+  const pathspec = relativePath ? ['--', relativePath] : [];
+  const tracked = new Set(
+    (await executeGitCommand(['git', 'ls-files', '-z', ...pathspec], repoRoot)).split('\0').filter(Boolean)
+  );
+
+  const summary = { path: relativePath, clean: 0, modified: 0, staged: 0, untracked: 0, conflicted: 0 };
+  for (const status of await readStatus(repoRoot)) {
+    if (!status.path.startsWith(prefix)) {
+      continue;
+    }
+
+    tracked.delete(status.path);
+    if (status.index === 'untracked') {
+      summary.untracked++;
+    } else if (status.isConflicted) {
+      summary.conflicted++;
+    } else {
+      summary.staged += Number(status.isStaged);
+      summary.modified += Number(status.isUnstaged);
+    }
+  }
+
+  summary.clean = tracked.size;
+  summary.isClean = !(summary.modified || summary.staged || summary.untracked || summary.conflicted);
+  return summary;
+}
+
+/**
+ * Lists untracked files that are not ignored
+ * Such files have no commit, so no commit-pinned link can be produced for them until they are added.
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {string} [options.directory] - Only list files below this directory (absolute or relative to repo root)
+ * @param {boolean} [options.directories=false] - List a wholly untracked directory once, with a trailing slash,
+ *   instead of every file in it
+ * @returns {Promise<string[]>} Paths relative to the repository root
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function listUntracked(options = {}) {
+  return listOthers(options, false);
+}
+
+/**
+ * Lists untracked files excluded by ignore rules
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {string} [options.directory] - Only list files below this directory (absolute or relative to repo root)
+ * @param {boolean} [options.directories=false] - List a wholly ignored directory (e.g. node_modules) once, with a
+ *   trailing slash, instead of every file in it
+ * @returns {Promise<string[]>} Paths relative to the repository root
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function listIgnored(options = {}) {
+  return listOthers(options, true);
+}
+
+/**
+ * Lists untracked or ignored files with git ls-files --others
+ * @param {object} options - listUntracked / listIgnored options
+ * @param {boolean} ignored - List ignored instead of untracked files
+ * @returns {Promise<string[]>} Paths relative to the repository root
+ */
+async function listOthers(options, ignored) {
+  const { repoPath = process.cwd(), directory, directories = false } = options;
+
+  let repoRoot;
+  let relativePath = '';
+  if (directory === undefined) {
+    repoRoot = await getRepositoryRoot(repoPath);
+  } else {
+    ({ repoRoot, relativePath } = await resolveRepositoryPath(repoPath, directory));
+    if (relativePath === '.') {
+      relativePath = '';
+    }
+  }
+
+  const command = ['git', 'ls-files', '-z', '--others', '--exclude-standard'];
+  if (ignored) {
+    command.push('--ignored');
+  }
+  if (directories) {
+    command.push('--directory');
+  }
+  if (relativePath) {
+    command.push('--', relativePath);
+  }
+
+  const output = await executeGitCommand(command, repoRoot);
+  return output.split('\0').filter(Boolean);
+}
+
+/**
+ * Checks whether a path is excluded by an ignore rule
+ * Tracked files are never reported as ignored, since ignore rules only apply to untracked files.
+ * @param {string} filePath - File or directory path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<boolean>} True if the path is ignored
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function isPathIgnored(filePath, options = {}) {
+  return (await explainIgnore(filePath, options)) !== null;
+}
+
+/**
+ * Reports which ignore file and pattern exclude a path
+ * Useful to tell users why a file cannot be identified or linked: an ignored file is never committed, so it has
+ * no GitHub URL.
+ * @param {string} filePath - File or directory path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object | null>} Rule: { path, source, line, pattern } where source is the ignore file (.gitignore,
+ *   src/.gitignore, .git/info/exclude or the global core.excludesFile), or null if the path is not ignored
+ *   (including tracked files and paths re-included by a !pattern)
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function explainIgnore(filePath, options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  let output;
+  try {
+    output = await executeGitCommand(['git', 'check-ignore', '--verbose', '--', relativePath], repoRoot);
+  } catch (error) {
+    // Exit code 1: not ignored
+    if (error.exitCode === 1) {
+      return null;
+    }
+    throw error;
+  }
+
+  // "<source>:<line>:<pattern>\t<path>"
+  const rule = output.split('\t')[0];
+  const [source, line, ...rest] = rule.split(':');
+  const pattern = rest.join(':');
+
+  if (pattern.startsWith('!')) {
+    return null;
+  }
+
+  return { path: relativePath, source, line: Number(line), pattern };
+}
+
+/**
+ * Looks up the gitattributes that apply to a path
+ * Attributes such as linguist-generated, export-ignore, diff and eol tell consumers whether a file should be linked,
+ * diffed or skipped. Lookup is delegated to git check-attr, so every .gitattributes file, .git/info/attributes and
+ * macros such as binary are taken into account.
+ * With commit, the .gitattributes files are read from that commit's tree rather than the working tree, so attributes
+ * added or removed since then do not leak in. Git 2.40 and later read the tree with --source; older versions read it
+ * into a scratch index.
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {string[]} [options.names] - Attributes to look up (default: every attribute specified for the path)
+ * @param {string} [options.commit] - Read attributes as of this revision (default: the working tree)
+ * @returns {Promise<Object<string, boolean|string|null>>} Attribute name to true (set), false (unset), a string value,
+ *   or null (unspecified; only returned for requested names)
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit cannot be resolved
+ */
+export async function getAttributes(filePath, options = {}) {
+  const { repoPath = process.cwd(), names, commit = null } = options;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  const selector = names?.length ? names : ['--all'];
+  const output = await checkAttr(repoRoot, [...selector, '--', relativePath], commit);
+
+  // "<path>\0<attribute>\0<info>\0" per attribute
+  const fields = output.split('\0');
+  const attributes = {};
+  for (let index = 0; index + 2 < fields.length; index += 3) {
+    const name = fields[index + 1];
+    const info = fields[index + 2];
+    if (info === 'set') {
+      attributes[name] = true;
+    } else if (info === 'unset') {
+      attributes[name] = false;
+    } else if (info === 'unspecified') {
+      attributes[name] = null;
+    } else {
+      attributes[name] = info;
+    }
+  }
+
+  return attributes;
+}
+
+/**
+ * Runs git check-attr -z against the working tree or a commit's tree
+ * @param {string} repoRoot - Repository root path
+ * @param {string[]} args - Attribute selector, "--" and the path
+ * @param {string|null} commit - Revision whose tree holds the .gitattributes files, or null for the working tree
+ * @returns {Promise<string>} check-attr output
+ */
+async function checkAttr(repoRoot, args, commit) {
+  if (commit === null) {
+    return executeGitCommand(['git', 'check-attr', '-z', ...args], repoRoot);
+  }
+
+  const tree = `${commit}^{tree}`;
+  try {
+    return await executeGitCommand(['git', 'check-attr', '-z', `--source=${tree}`, ...args], repoRoot);
+  } catch (error) {
+    if (!(error instanceof GitCommandError)) {
+      throw error;
+    }
+  }
+
+  // Git before 2.40 has no --source; --cached reads a scratch index of the tree instead
+  const scratch = fs.mkdtempSync(path.join(os.tmpdir(), 'git-identify-'));
+  try {
+    const env = { GIT_INDEX_FILE: path.join(scratch, 'index') };
+    await executeGitCommand(['git', 'read-tree', tree], repoRoot, { env });
+    return await executeGitCommand(['git', 'check-attr', '-z', '--cached', ...args], repoRoot, { env });
+  } finally {
+    fs.rmSync(scratch, { recursive: true, force: true });
+  }
+}
+
+/**
+ * Runs git status --porcelain=v2 and parses its entries
+ * @param {string} repoRoot - Repository root path
+ * @param {boolean} [includeIgnored=false] - Also list ignored files
+ * @param {string} [pathspec] - Only list paths matching this pathspec (glob magic by default)
+ * @returns {Promise<object[]>} Status entries
+ */
+async function readStatus(repoRoot, includeIgnored = false, pathspec = null) {
+  const command = ['git', 'status', '--porcelain=v2', '-z', '--untracked-files=all'];
+  if (includeIgnored) {
+    command.push('--ignored=matching');
+  }
+  if (pathspec) {
+    if (!pathspec.startsWith(':')) {
+      pathspec = `:(glob)${pathspec}`;
+    }
+    command.push('--', pathspec);
+  }
+
+  const output = await executeGitCommand(command, repoRoot);
+
+  const statuses = [];
+  const entries = output.split('\0');
+  for (let i = 0; i < entries.length; i++) {
+    const kind = entries[i][0];
+    const rest = entries[i].slice(2);
+    const fields = rest.split(' ');
+
+    if (kind === '?' || kind === '!') {
+      const change = kind === '?' ? 'untracked' : 'ignored';
+      statuses.push(fileStatus(rest, change, change));
+    } else if (kind === '1') {
+      // "1 XY sub mH mI mW hH hI path"
+      statuses.push(fileStatus(fields.slice(7).join(' '), STATUS_CODES[fields[0][0]], STATUS_CODES[fields[0][1]]));
+    } else if (kind === '2') {
+      // "2 XY sub mH mI mW hH hI Xscore path", then the original path
+      statuses.push(
+        fileStatus(fields.slice(8).join(' '), STATUS_CODES[fields[0][0]], STATUS_CODES[fields[0][1]], entries[++i])
+      );
+    } else if (kind === 'u') {
+      // "u XY sub m1 m2 m3 mW h1 h2 h3 path"
+      statuses.push(fileStatus(fields.slice(9).join(' '), 'unmerged', 'unmerged'));
+    }
+  }
+
+  const flags = await readIndexFlags(repoRoot, pathspec);
+  return statuses.map((status) =>
+    flags.has(status.path)
+      ? fileStatus(status.path, status.index, status.worktree, status.renameSource, flags.get(status.path))
+      : status
+  );
+}
+
+/**
+ * Reads the skip-worktree and assume-unchanged bits of index entries
+ * @param {string} repoRoot - Repository root path
+ * @param {string} [pathspec] - Only read entries matching this pathspec
+ * @returns {Promise<Map<string, {skipWorktree: boolean, assumeUnchanged: boolean}>>} Bits of flagged entries only
+ */
+async function readIndexFlags(repoRoot, pathspec = null) {
+  const command = ['git', 'ls-files', '-v', '-z'];
+  if (pathspec) {
+    command.push('--', pathspec);
+  }
+
+  // "<tag> <path>": 'S' marks skip-worktree, a lowercase tag assume-unchanged
+  const flags = new Map();
+  for (const entry of (await executeGitCommand(command, repoRoot)).split('\0')) {
+    const tag = entry.slice(0, 1);
+    const entryPath = entry.slice(2);
+    const skipWorktree = tag.toUpperCase() === 'S';
+    const assumeUnchanged = tag !== tag.toUpperCase();
+    if (entryPath && (skipWorktree || assumeUnchanged)) {
+      flags.set(entryPath, { skipWorktree, assumeUnchanged });
+    }
+  }
+
+  return flags;
+}
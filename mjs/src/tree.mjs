@@ -0,0 +1,604 @@
+import path from 'path';
+import { getAttributes } from './status.mjs';
+import {
+  executeGitCommand,
+  getBlobBytes,
+  getBlobSize,
+  getFileHashAtCommit,
+  getRepositoryRoot,
+  resolveRepositoryPath
+} from './utils/git.mjs';
+import { decodeText, detectEncoding } from './utils/encoding.mjs';
+import { LFS_POINTER_MAX_SIZE, parseLfsPointer } from './utils/lfs.mjs';
+import { validateGitHash } from './utils/hash.mjs';
+import { normalizeFilePath } from './utils/path.mjs';
+import { FileNotFoundError } from './errors.mjs';
+
+/**
+ * Tree entry mode of a symbolic link
+ */
+const SYMLINK_MODE = '120000';
+
+/**
+ * Links followed before a target is treated as a loop, as on Linux
+ */
+const MAX_SYMLINK_HOPS = 40;
+
+/**
+ * Finds every path at which a commit's tree holds a blob
+ * Answers where a piece of content lives when only its blob SHA is known; identical files share one blob, so several
+ * paths can match.
+ * @param {string} blobOid - Blob SHA (full 40 hex characters)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.atCommit='HEAD'] - Commit SHA, branch, tag or other revision whose tree is searched
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<string[]>} Paths relative to repo root, in tree order; empty if the blob is not in the tree
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If atCommit does not exist
+ * @throws {InvalidHashError} If blobOid is not a valid Git hash
+ */
+export async function findPathsForBlob(blobOid, options = {}) {
+  const { atCommit = 'HEAD', repoPath = process.cwd() } = options;
+
+  validateGitHash(blobOid, 'blobOid');
+  const oid = blobOid.toLowerCase();
+
+  const paths = [];
+  for await (const entry of walkTree(atCommit, { repoPath })) {
+    if (entry.oid === oid) {
+      paths.push(entry.path);
+    }
+  }
+  return paths;
+}
+
+/**
+ * Searches the files of a commit without checking it out
+ * Runs git grep against the commit's tree, so the results reflect that exact snapshot regardless of the working tree.
+ * Binary files are skipped. Returns { path, lineNumber, line } matches ordered by path, then line number: path
+ * relative to repo root, lineNumber 1-based and line the text without its terminator.
+ * @param {string} commit - Commit SHA, branch, tag or other revision
+ * @param {string} pattern - Extended regular expression, or a literal string with fixedStrings
+ * @param {object} [options={}] - Options
+ * @param {string} [options.pathspec] - Only search matching paths, relative to repo root, in Git pathspec syntax (e.g.
+ *   'src/', '*.py'; default: all files)
+ * @param {boolean} [options.ignoreCase=false] - Match case-insensitively
+ * @param {boolean} [options.fixedStrings=false] - Treat pattern as a literal string
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object[]>} Matches
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit or pattern is not a valid regular expression
+ * @throws {TypeError} If pattern is empty
+ */
+export async function grepAt(commit, pattern, options = {}) {
+  const { pathspec, ignoreCase = false, fixedStrings = false, repoPath = process.cwd() } = options;
+
+  if (typeof pattern !== 'string' || !pattern) {
+    throw new TypeError('Invalid pattern: must be a non-empty string');
+  }
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+  const oid = await executeGitCommand(['git', 'rev-parse', `${commit}^{commit}`], repoRoot);
+
+  const command = ['git', 'grep', '-n', '-z', '-I', '--full-name', '--no-color', fixedStrings ? '-F' : '-E'];
+  if (ignoreCase) {
+    command.push('-i');
+  }
+  command.push('-e', pattern, oid);
+  if (pathspec !== undefined) {
+    command.push('--', pathspec);
+  }
+
+  let output;
+  try {
+    output = (await executeGitCommand(command, repoRoot, { encoding: 'buffer' })).toString('utf8');
+  } catch (error) {
+    // Exit code 1: no matches
+    if (error.exitCode === 1) {
+      return [];
+    }
+    throw error;
+  }
+
+  const matches = [];
+  for (const record of output.split('\n')) {
+    // "<commit>:<path>\0<line number>\0<text>"
+    const fields = record.split('\0');
+    if (fields.length >= 3) {
+      matches.push({
+        path: fields[0].slice(oid.length + 1),
+        lineNumber: parseInt(fields[1], 10),
+        line: fields.slice(2).join('\0')
+      });
+    }
+  }
+  return matches;
+}
+
+/**
+ * Lists the entries of a tree without recursing into subtrees
+ * Each entry is { name, mode, type, oid }: name is a single path component, mode the octal file mode (e.g.
+ * '100644', '040000' for a tree, '160000' for a gitlink) and type 'blob', 'tree' or 'commit' (a submodule gitlink).
+ * @param {string} [tree='HEAD'] - Tree SHA, or any revision naming a tree: a commit or tag (its root tree) or
+ *   <rev>:<dir> such as HEAD:src
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object[]>} Entries in tree order
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If tree does not exist or does not name a tree
+ */
+export async function getTreeEntries(tree = 'HEAD', options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const output = await executeGitCommand(['git', 'ls-tree', '-z', tree], repoRoot);
+
+  return output.split('\0').filter(Boolean).map((line) => {
+    // "<mode> <type> <oid>\t<name>"
+    const separator = line.indexOf('\t');
+    const [mode, type, oid] = line.slice(0, separator).split(' ');
+    const name = line.slice(separator + 1);
+    return { name, path: name, mode, type, oid };
+  });
+}
+
+/**
+ * Walks every blob below a tree, with full paths
+ * Yields { name, path, mode, type, oid } entries; submodule gitlinks are skipped. Pathspecs follow Git's default
+ * matching: a pattern matches a path it names, any path below a directory it names, or a path it matches as a
+ * wildcard (* also matches /).
+ * @param {string} [tree='HEAD'] - Tree SHA, or any revision naming a tree
+ * @param {object} [options={}] - Options
+ * @param {string} [options.prefix=''] - Directory prepended to each path, e.g. 'src' when walking HEAD:src so that
+ *   paths are relative to the repository root
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {number} [options.maxDepth] - Deepest directory level to descend into below the tree; 0 yields only the
+ *   tree's own blobs (default: unlimited)
+ * @param {string[]} [options.pathspec] - Only yield blobs whose full path matches one of these patterns
+ * @yields {object} Entry for each blob, in tree order
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If tree does not exist or does not name a tree
+ * @throws {TypeError} If maxDepth is negative
+ */
+export async function* walkTree(tree = 'HEAD', options = {}) {
+  const { prefix = '', repoPath = process.cwd(), maxDepth, pathspec } = options;
+
+  if (maxDepth !== undefined && !(maxDepth >= 0)) {
+    throw new TypeError(`Invalid max depth: ${maxDepth}. Must not be negative`);
+  }
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+  const base = prefix.replace(/^\/+|\/+$/g, '');
+  const patterns = pathspec?.map((pattern) => ({
+    directory: `${pattern.replace(/\/+$/, '')}/`,
+    regex: new RegExp(
+      `^${pattern.replace(/[.+^${}()|[\]\\]/g, '\\$&').replace(/\*/g, '.*').replace(/\?/g, '.')}$`
+    ),
+    pattern
+  }));
+
+  const output = await executeGitCommand(['git', 'ls-tree', '-r', '-z', tree], repoRoot);
+
+  for (const line of output.split('\0')) {
+    // "<mode> <type> <oid>\t<path>"
+    const separator = line.indexOf('\t');
+    if (separator === -1) {
+      continue;
+    }
+    const [mode, type, oid] = line.slice(0, separator).split(' ');
+    const relativePath = line.slice(separator + 1);
+    if (type !== 'blob') {
+      continue;
+    }
+    if (maxDepth !== undefined && relativePath.split('/').length - 1 > maxDepth) {
+      continue;
+    }
+
+    const fullPath = base ? `${base}/${relativePath}` : relativePath;
+    if (patterns && !patterns.some(({ directory, regex, pattern }) => (
+      fullPath === pattern || fullPath.startsWith(directory) || regex.test(fullPath)
+    ))) {
+      continue;
+    }
+
+    yield { name: path.posix.basename(relativePath), path: fullPath, mode, type, oid };
+  }
+}
+
+/**
+ * Finds files with identical content, by grouping paths on their blob OID
+ * Symbolic links are left out, since links with the same target are not copies of a file. Empty files all share one
+ * blob and are left out unless minSize is 0. Returns { oid, size, paths, redundantBytes } groups, most redundant bytes
+ * first, then by first path: size in bytes, paths relative to repo root in tree order and redundantBytes the bytes
+ * taken by the copies beyond the first.
+ * @param {string} [tree='HEAD'] - Tree SHA, or any revision naming a tree
+ * @param {object} [options={}] - Options
+ * @param {string[]} [options.pathspec] - Only consider files matching one of these patterns, as in walkTree
+ * @param {number} [options.minSize=1] - Smallest file size in bytes to report
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object[]>} Duplicate groups
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If tree does not exist or does not name a tree
+ * @throws {TypeError} If minSize is negative
+ */
+export async function findDuplicateFiles(tree = 'HEAD', options = {}) {
+  const { pathspec, minSize = 1, repoPath = process.cwd() } = options;
+
+  if (!(minSize >= 0)) {
+    throw new TypeError(`Invalid min size: ${minSize}. Must not be negative`);
+  }
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+
+  const pathsByOid = new Map();
+  for await (const entry of walkTree(tree, { repoPath: repoRoot, pathspec })) {
+    if (entry.mode === SYMLINK_MODE) {
+      continue;
+    }
+    if (!pathsByOid.has(entry.oid)) {
+      pathsByOid.set(entry.oid, []);
+    }
+    pathsByOid.get(entry.oid).push(entry.path);
+  }
+
+  const groups = [];
+  for (const [oid, paths] of pathsByOid) {
+    if (paths.length < 2) {
+      continue;
+    }
+    const size = await getBlobSize(repoRoot, oid);
+    if (size >= minSize) {
+      groups.push({ oid, size, paths, redundantBytes: size * (paths.length - 1) });
+    }
+  }
+
+  return groups.sort((a, b) => b.redundantBytes - a.redundantBytes || (a.paths[0] < b.paths[0] ? -1 : 1));
+}
+
+/**
+ * Lists the path, blob OID, mode and size of every tracked file at a commit
+ * The bulk form of file identification, read from the commit's tree in a single git invocation, for CI provenance
+ * and release records; the result is plain data, ready for JSON.stringify. Submodule gitlinks are not files of the
+ * repository and are left out. Returns { commit, tree, entries }: commit and root tree SHAs, and { path, blobOid,
+ * mode, size } entries sorted by path, with mode e.g. '100644', '100755' or '120000' (symlink) and size in bytes.
+ * @param {string} [commit='HEAD'] - Commit SHA, branch, tag or other revision
+ * @param {object} [options={}] - Options
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object>} Manifest
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+export async function generateManifest(commit = 'HEAD', options = {}) {
+  const { repoPath = process.cwd() } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+  const oid = await executeGitCommand(['git', 'rev-parse', `${commit}^{commit}`], repoRoot);
+  const tree = await executeGitCommand(['git', 'rev-parse', `${oid}^{tree}`], repoRoot);
+
+  const output = await executeGitCommand(['git', 'ls-tree', '-r', '-l', '-z', oid], repoRoot);
+
+  const entries = [];
+  for (const line of output.split('\0')) {
+    // "<mode> <type> <oid> <padded size>\t<path>"
+    const separator = line.indexOf('\t');
+    if (separator === -1) {
+      continue;
+    }
+    const [mode, type, blobOid, size] = line.slice(0, separator).split(/\s+/);
+    if (type === 'blob') {
+      entries.push({ path: line.slice(separator + 1), blobOid, mode, size: parseInt(size, 10) });
+    }
+  }
+
+  entries.sort((a, b) => (a.path < b.path ? -1 : a.path > b.path ? 1 : 0));
+  return { commit: oid, tree, entries };
+}
+
+/**
+ * Reports which file identities were added, removed or changed between two commits
+ * Compares the generateManifest of each commit path by path, so a renamed file shows up as removed under its old path
+ * and added under the new one, as a per-file change report between release tags should. Returns { fromCommit,
+ * toCommit, added, removed, changed }, each list sorted by path: added and removed are manifest entries, changed are
+ * { path, oldBlobOid, newBlobOid, oldMode, newMode } for files at both commits whose blob or mode differs.
+ * @param {string} fromCommit - Older commit SHA, branch, tag or other revision
+ * @param {object} [options={}] - Options
+ * @param {string} [options.toCommit='HEAD'] - Newer revision
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object>} Manifest diff
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If fromCommit or toCommit does not name a commit
+ */
+export async function diffManifests(fromCommit, options = {}) {
+  const { toCommit = 'HEAD', repoPath = process.cwd() } = options;
+
+  const before = await generateManifest(fromCommit, { repoPath });
+  const after = await generateManifest(toCommit, { repoPath });
+
+  const oldEntries = new Map(before.entries.map((entry) => [entry.path, entry]));
+  const newPaths = new Set(after.entries.map((entry) => entry.path));
+
+  const added = [];
+  const changed = [];
+  for (const entry of after.entries) {
+    const previous = oldEntries.get(entry.path);
+    if (!previous) {
+      added.push(entry);
+    } else if (previous.blobOid !== entry.blobOid || previous.mode !== entry.mode) {
+      changed.push({
+        path: entry.path,
+        oldBlobOid: previous.blobOid,
+        newBlobOid: entry.blobOid,
+        oldMode: previous.mode,
+        newMode: entry.mode
+      });
+    }
+  }
+  const removed = before.entries.filter((entry) => !newPaths.has(entry.path));
+
+  return { fromCommit: before.commit, toCommit: after.commit, added, removed, changed };
+}
+
+/**
+ * Reads a symbolic link at a commit and optionally resolves it in the tree
+ * Resolution follows the target through the commit's tree, including links in intermediate directories and chains of
+ * links, so URLs can point at the real file rather than the one-line blob holding the target path. Absolute targets
+ * and targets leaving the repository are not resolved. Returns { path, oid, target, resolvedPath, resolvedType,
+ * resolvedOid }: oid is the link's blob, target the link target as stored (e.g. '../shared/config.yml'), and the
+ * resolved fields describe the object the link leads to ('blob', 'tree' or 'commit'), or are null if it was not
+ * resolved, leads outside the repository, to a missing path or into a loop.
+ * @param {string} filePath - Path of the link (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.commit='HEAD'] - Commit SHA, branch, tag or other revision
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @param {boolean} [options.resolve=true] - Follow the link to the object it points at
+ * @returns {Promise<object | null>} Link, or null if the path is not a symbolic link at that commit
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+export async function getSymlink(filePath, options = {}) {
+  const { commit = 'HEAD', repoPath = process.cwd(), resolve = true } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+  // Not resolveFilePath: that follows links in the working tree
+  const relativePath = normalizeFilePath(path.relative(repoRoot, path.resolve(repoRoot, filePath)));
+
+  const entry = await treeEntryAt(repoRoot, commit, relativePath);
+  if (!entry || entry.mode !== SYMLINK_MODE) {
+    return null;
+  }
+
+  const target = (await getBlobBytes(repoRoot, entry.oid)).toString('utf8');
+  const link = {
+    path: relativePath,
+    oid: entry.oid,
+    target,
+    resolvedPath: null,
+    resolvedType: null,
+    resolvedOid: null
+  };
+  if (!resolve || target.startsWith('/')) {
+    return link;
+  }
+
+  // Walk the target one component at a time from the link's directory, splicing in the target of every link met on
+  // the way
+  const resolved = relativePath.split('/').slice(0, -1);
+  let remaining = target.split('/');
+  let hops = 1;
+  let resolvedEntry = null;
+  while (remaining.length) {
+    const name = remaining.shift();
+    if (name === '' || name === '.') {
+      continue;
+    }
+    if (name === '..') {
+      if (!resolved.length) {
+        // Leaves the repository
+        return link;
+      }
+      resolved.pop();
+      resolvedEntry = null;
+      continue;
+    }
+
+    resolvedEntry = await treeEntryAt(repoRoot, commit, [...resolved, name].join('/'));
+    if (!resolvedEntry) {
+      return link;
+    }
+    if (resolvedEntry.mode === SYMLINK_MODE) {
+      hops++;
+      const nextTarget = (await getBlobBytes(repoRoot, resolvedEntry.oid)).toString('utf8');
+      if (hops > MAX_SYMLINK_HOPS || nextTarget.startsWith('/')) {
+        return link;
+      }
+      remaining = [...nextTarget.split('/'), ...remaining];
+      resolvedEntry = null;
+      continue;
+    }
+    resolved.push(name);
+  }
+
+  if (!resolved.length) {
+    // The repository root itself
+    return link;
+  }
+  if (!resolvedEntry) {
+    resolvedEntry = await treeEntryAt(repoRoot, commit, resolved.join('/'));
+    if (!resolvedEntry) {
+      return link;
+    }
+  }
+
+  return {
+    ...link,
+    resolvedPath: resolved.join('/'),
+    resolvedType: resolvedEntry.type,
+    resolvedOid: resolvedEntry.oid
+  };
+}
+
+/**
+ * Looks up the tree entry for an exact path at a commit
+ * @param {string} repoRoot - Repository root path
+ * @param {string} commit - Commit SHA, branch, tag or other revision
+ * @param {string} entryPath - Path relative to repo root
+ * @returns {Promise<object | null>} Entry shaped as by getTreeEntries, or null if nothing exists at that path
+ * @throws {GitCommandError} If commit does not name a commit
+ */
+export async function treeEntryAt(repoRoot, commit, entryPath) {
+  const output = await executeGitCommand(['git', 'ls-tree', '-z', commit, '--', entryPath], repoRoot);
+
+  for (const line of output.split('\0')) {
+    // "<mode> <type> <oid>\t<path>"
+    const separator = line.indexOf('\t');
+    if (separator !== -1 && line.slice(separator + 1) === entryPath) {
+      const [mode, type, oid] = line.slice(0, separator).split(' ');
+      return { name: path.posix.basename(entryPath), path: entryPath, mode, type, oid };
+    }
+  }
+  return null;
+}
+
+/**
+ * Reads a file at a commit as text, detecting its encoding
+ * Files with a working-tree-encoding attribute are stored by Git as UTF-8 and only converted on checkout; the
+ * attribute, as set in the .gitattributes files at commit, is reported so the text can be re-encoded to match a
+ * checkout of that commit. Files committed in another encoding are recognized by their byte order mark or, for
+ * UTF-16, their NUL bytes, so UTF-16 content is not returned as mojibake. Returns { path, oid, text, encoding, workingTreeEncoding, binary }: encoding is the one the blob was
+ * decoded from ('utf-8', 'utf-16-le', 'utf-16-be', 'utf-32-le', 'utf-32-be' or 'latin-1'; null if the content looks
+ * binary, in which case the text is decoded as UTF-8 with U+FFFD replacements), workingTreeEncoding the attribute
+ * value, e.g. 'UTF-16LE-BOM' (null if unset), and binary whether Git treats the file as binary (see isBinary).
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.commit='HEAD'] - Commit SHA, branch, tag or other revision
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object | null>} File text, or null if the path is not a file at that commit
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitError} If the commit cannot be resolved
+ */
+export async function getFileText(filePath, options = {}) {
+  const { commit = 'HEAD', repoPath = process.cwd() } = options;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  const blobHash = await getFileHashAtCommit(repoRoot, relativePath, commit);
+  if (blobHash === null) {
+    return null;
+  }
+
+  const { text, encoding } = decodeText(await getBlobBytes(repoRoot, blobHash));
+  const attributes = await getAttributes(relativePath, {
+    repoPath: repoRoot,
+    names: ['working-tree-encoding', 'diff'],
+    commit
+  });
+  const attribute = attributes['working-tree-encoding'];
+  const binary = await binaryFromAttributes(repoRoot, attributes);
+
+  return {
+    path: relativePath,
+    oid: blobHash,
+    text,
+    encoding,
+    workingTreeEncoding: typeof attribute === 'string' ? attribute : null,
+    binary: binary === null ? encoding === null : binary
+  };
+}
+
+/**
+ * Reads the Git LFS pointer stored for a file at a commit
+ * For a file tracked by Git LFS the blob SHA identifies the pointer, not the content; the LFS object ID and size
+ * identify the content and locate it on the LFS server (see buildGitHubLfsMediaUrl). Returns { oid, size,
+ * pointerOid, path } as described for parseLfsPointer, with pointerOid the pointer's blob SHA. Blobs larger than a
+ * pointer can be are not read.
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.commit='HEAD'] - Commit SHA, branch, tag or other revision
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<object | null>} Pointer, or null if the path is not a file at that commit or its blob is not an
+ *   LFS pointer
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitError} If the commit cannot be resolved
+ */
+export async function getLfsInfo(filePath, options = {}) {
+  const { commit = 'HEAD', repoPath = process.cwd() } = options;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  const blobHash = await getFileHashAtCommit(repoRoot, relativePath, commit);
+  if (blobHash === null || (await getBlobSize(repoRoot, blobHash)) > LFS_POINTER_MAX_SIZE) {
+    return null;
+  }
+
+  const info = parseLfsPointer(await getBlobBytes(repoRoot, blobHash));
+  if (!info) {
+    return null;
+  }
+
+  return { ...info, pointerOid: blobHash, path: relativePath };
+}
+
+/**
+ * Tells whether Git treats a file as binary, as git diff does
+ * gitattributes decide first, as in Git: -diff (which the binary macro sets) marks a file binary and diff marks it
+ * text, while a diff=<driver> is binary if diff.<driver>.binary is configured true. text and -text only control
+ * line-ending conversion, so they leave the decision to the content. Attributes are read from the .gitattributes
+ * files at commit, not the working tree. Content is binary if it has NUL bytes, except that UTF-16 and UTF-32 text is
+ * recognized as text (see detectEncoding).
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {string} [options.commit='HEAD'] - Commit SHA, branch, tag or other revision
+ * @param {string} [options.repoPath=process.cwd()] - Path within the repository
+ * @returns {Promise<boolean>} True if the file is binary
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {FileNotFoundError} If the path is not a file at that commit
+ * @throws {GitError} If the commit cannot be resolved
+ */
+export async function isBinary(filePath, options = {}) {
+  const { commit = 'HEAD', repoPath = process.cwd() } = options;
+
+  const { repoRoot, relativePath } = await resolveRepositoryPath(repoPath, filePath);
+
+  const blobHash = await getFileHashAtCommit(repoRoot, relativePath, commit);
+  if (blobHash === null) {
+    throw new FileNotFoundError(
+      `File not found in repository: ${relativePath}`,
+      {
+        context: { repoPath: repoRoot, filePath: relativePath, commit }
+      }
+    );
+  }
+
+  const attributes = await getAttributes(relativePath, { repoPath: repoRoot, names: ['diff'], commit });
+  const binary = await binaryFromAttributes(repoRoot, attributes);
+  if (binary !== null) {
+    return binary;
+  }
+
+  return detectEncoding(await getBlobBytes(repoRoot, blobHash)) === null;
+}
+
+/**
+ * Classifies a file as binary or text from its diff attribute
+ * @param {string} repoRoot - Repository root path
+ * @param {object} attributes - Attributes of the file, including 'diff'
+ * @returns {Promise<boolean | null>} True (binary), false (text), or null if the content decides
+ */
+async function binaryFromAttributes(repoRoot, attributes) {
+  const diff = attributes.diff;
+  if (typeof diff === 'boolean') {
+    return !diff;
+  }
+  if (diff === null || diff === undefined) {
+    return null;
+  }
+
+  try {
+    const binary = await executeGitCommand(['git', 'config', '--type=bool', '--get', `diff.${diff}.binary`], repoRoot);
+    return binary === 'true';
+  } catch {
+    // Driver does not set binary
+    return null;
+  }
+}
@@ -0,0 +1,40 @@
+/**
+ * "<type>[(<scope>)][!]: <description>" on the first line
+ */
+const HEADER_PATTERN = /^([A-Za-z][\w-]*)(?:\(([^()\r\n]*)\))?(!)?: +(\S.*)$/;
+
+/**
+ * "BREAKING CHANGE: <text>" footer; the text runs to the end of its paragraph
+ */
+const BREAKING_FOOTER_PATTERN = /^BREAKING[ -]CHANGE: (.+(?:\n(?!\n).+)*)/m;
+
+/**
+ * Parses a commit message written to the Conventional Commits specification (https://www.conventionalcommits.org/)
+ * Returns { type, scope, breaking, description, breakingChange }: type as written (e.g. 'feat'), scope from the
+ * parentheses (null if absent), breaking when the header has '!' or a BREAKING CHANGE footer, the header text after
+ * the colon, and the BREAKING CHANGE footer text (null if absent).
+ * @param {string} message - Full commit message
+ * @returns {object | null} Parsed message, or null if the first line is not a conventional commit header
+ */
+export function parseConventionalCommit(message) {
+  const trimmed = message.trim();
+  const newline = trimmed.indexOf('\n');
+  const header = newline === -1 ? trimmed : trimmed.slice(0, newline);
+  const rest = newline === -1 ? '' : trimmed.slice(newline + 1);
+
+  const match = header.trim().match(HEADER_PATTERN);
+  if (!match) {
+    return null;
+  }
+
+  const footer = rest.match(BREAKING_FOOTER_PATTERN);
+  const breakingChange = footer ? footer[1].trim() : null;
+
+  return {
+    type: match[1],
+    scope: match[2] || null,
+    breaking: Boolean(match[3]) || breakingChange !== null,
+    description: match[4].trim(),
+    breakingChange
+  };
+}
@@ -0,0 +1,147 @@
+/**
+ * Byte order marks, longest first so UTF-32 LE is not taken for UTF-16 LE
+ */
+const BYTE_ORDER_MARKS = [
+  [[0xff, 0xfe, 0x00, 0x00], 'utf-32-le'],
+  [[0x00, 0x00, 0xfe, 0xff], 'utf-32-be'],
+  [[0xef, 0xbb, 0xbf], 'utf-8'],
+  [[0xff, 0xfe], 'utf-16-le'],
+  [[0xfe, 0xff], 'utf-16-be']
+];
+
+/**
+ * Encodings detectEncoding reports
+ */
+export const TEXT_ENCODINGS = ['utf-8', 'utf-16-le', 'utf-16-be', 'utf-32-le', 'utf-32-be', 'latin-1'];
+
+/**
+ * Bytes inspected when guessing, as Git does when deciding a file is binary
+ */
+const DETECTION_SAMPLE_SIZE = 8000;
+
+/**
+ * Guesses the encoding of text content
+ * A byte order mark decides; otherwise the pattern of NUL bytes identifies UTF-16 (mostly ASCII text has a NUL in
+ * every other byte). Other content is UTF-8 if it decodes as such, else Latin-1, unless it contains NUL bytes, which
+ * marks it binary.
+ * @param {Buffer|Uint8Array} data - Content to inspect
+ * @returns {string | null} One of TEXT_ENCODINGS, or null if the content looks binary
+ */
+export function detectEncoding(data) {
+  for (const [bom, encoding] of BYTE_ORDER_MARKS) {
+    if (data.length >= bom.length && bom.every((byte, index) => data[index] === byte)) {
+      return encoding;
+    }
+  }
+
+  const sample = data.subarray(0, DETECTION_SAMPLE_SIZE);
+  if (sample.includes(0)) {
+    return detectUtf16(data, sample);
+  }
+
+  return isDecodable(data, 'utf-8') ? 'utf-8' : 'latin-1';
+}
+
+/**
+ * Tells BOM-less UTF-16 from binary content by where its NUL bytes fall
+ * @param {Buffer|Uint8Array} data - Content to inspect
+ * @param {Buffer|Uint8Array} sample - Leading bytes of data to count NUL bytes in
+ * @returns {string | null} 'utf-16-le' or 'utf-16-be', or null if the content looks binary
+ */
+function detectUtf16(data, sample) {
+  const pairs = Math.floor(sample.length / 2);
+  if (data.length % 2 || !pairs) {
+    return null;
+  }
+
+  let evenNuls = 0;
+  let oddNuls = 0;
+  for (let index = 0; index < pairs * 2; index++) {
+    if (sample[index] === 0) {
+      if (index % 2) {
+        oddNuls++;
+      } else {
+        evenNuls++;
+      }
+    }
+  }
+
+  let encoding;
+  if (oddNuls >= pairs * 0.3 && evenNuls <= pairs * 0.05) {
+    encoding = 'utf-16-le';
+  } else if (evenNuls >= pairs * 0.3 && oddNuls <= pairs * 0.05) {
+    encoding = 'utf-16-be';
+  } else {
+    return null;
+  }
+
+  return isDecodable(data, encoding) ? encoding : null;
+}
+
+/**
+ * Checks that content is valid in a TextDecoder-supported encoding
+ * @param {Buffer|Uint8Array} data - Content to check
+ * @param {string} encoding - 'utf-8', 'utf-16-le' or 'utf-16-be'
+ * @returns {boolean} True if the content decodes without errors
+ */
+function isDecodable(data, encoding) {
+  try {
+    new TextDecoder(encoding.replace('-le', 'le').replace('-be', 'be'), { fatal: true }).decode(data);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Decodes UTF-32 content; invalid code points become U+FFFD
+ * @param {Buffer|Uint8Array} data - Content to decode
+ * @param {boolean} littleEndian - Byte order
+ * @returns {string} Decoded text
+ */
+function decodeUtf32(data, littleEndian) {
+  const view = new DataView(data.buffer, data.byteOffset, data.byteLength);
+  const codePoints = [];
+  for (let offset = 0; offset + 4 <= data.length; offset += 4) {
+    const codePoint = view.getUint32(offset, littleEndian);
+    const valid = codePoint <= 0x10ffff && (codePoint < 0xd800 || codePoint > 0xdfff);
+    codePoints.push(valid ? codePoint : 0xfffd);
+  }
+  if (data.length % 4) {
+    codePoints.push(0xfffd);
+  }
+  return codePoints.map((codePoint) => String.fromCodePoint(codePoint)).join('');
+}
+
+/**
+ * Decodes text content, detecting its encoding unless one is given
+ * A leading byte order mark is removed. Content that looks binary is decoded as UTF-8 with invalid sequences
+ * replaced by U+FFFD.
+ * @param {Buffer|Uint8Array} data - Content to decode
+ * @param {string} [encoding] - One of TEXT_ENCODINGS to decode with (default: detect)
+ * @returns {{text: string, encoding: string|null}} Decoded text and the encoding used, null for binary content
+ * @throws {TypeError} If encoding is not one of TEXT_ENCODINGS
+ */
+export function decodeText(data, encoding) {
+  if (encoding !== undefined && !TEXT_ENCODINGS.includes(encoding)) {
+    throw new TypeError(`Unsupported encoding: ${encoding}. Must be one of ${TEXT_ENCODINGS.join(', ')}`);
+  }
+
+  const used = encoding === undefined ? detectEncoding(data) : encoding;
+
+  let text;
+  if (used === null) {
+    text = new TextDecoder('utf-8').decode(data);
+  } else if (used === 'latin-1') {
+    text = Buffer.from(data.buffer, data.byteOffset, data.byteLength).toString('latin1');
+  } else if (used.startsWith('utf-32')) {
+    text = decodeUtf32(data, used === 'utf-32-le');
+  } else {
+    text = new TextDecoder(used.replace('-le', 'le').replace('-be', 'be'), { ignoreBOM: true }).decode(data);
+  }
+
+  if (used !== null && text.startsWith('\ufeff')) {
+    text = text.slice(1);
+  }
+  return { text, encoding: used };
+}
@@ -0,0 +1,131 @@
+import { isValidGitHash } from './hash.mjs';
+import { parseGitHubLink } from './url.mjs';
+
+/**
+ * Supported link output formats
+ */
+export const LINK_FORMATS = ['url', 'html', 'osc8', 'org'];
+
+/**
+ * Abbreviated commit SHA length used in link text
+ */
+const SHORT_SHA_LENGTH = 7;
+
+/**
+ * C0/C1 control characters, which would break out of an OSC 8 sequence
+ */
+const CONTROL_CHARACTERS = /[\x00-\x1f\x7f-\x9f]/g;
+
+/**
+ * HTML entities for escaped characters
+ */
+const HTML_ESCAPES = {
+  '&': '&amp;',
+  '<': '&lt;',
+  '>': '&gt;',
+  '"': '&quot;',
+  "'": '&#x27;'
+};
+
+/**
+ * Derives path@shortsha link text from a GitHub blob or tree URL
+ * Commit SHAs are abbreviated; branch and tag refs are kept as-is. The repository root is labelled with the repository name.
+ * @param {string} url - GitHub URL
+ * @param {number} [shaLength=7] - Commit SHA characters to keep; pass the length of abbreviateOid for a prefix that is
+ *   unambiguous in the local repository
+ * @returns {string} Link text, or the URL itself if it is not a blob or tree URL
+ */
+export function linkText(url, shaLength = SHORT_SHA_LENGTH) {
+  const link = parseGitHubLink(url);
+  if (!link) {
+    return url;
+  }
+
+  const ref = isValidGitHash(link.ref) ? link.ref.slice(0, shaLength) : link.ref;
+  return `${link.path || link.repo}@${ref}`;
+}
+
+/**
+ * Formats a URL as an HTML anchor element
+ * Both the href attribute and the link text are HTML-escaped.
+ * @param {string} url - Link target
+ * @param {string} [text] - Link text (default: path@shortsha derived from the URL)
+ * @param {number} [shaLength=7] - Commit SHA characters in the default text
+ * @returns {string} HTML anchor string
+ */
+export function formatHtmlLink(url, text = undefined, shaLength = SHORT_SHA_LENGTH) {
+  if (text === undefined) {
+    text = linkText(url, shaLength);
+  }
+  return `<a href="${escapeHtml(url)}">${escapeHtml(text)}</a>`;
+}
+
+/**
+ * Formats a URL as an OSC 8 terminal hyperlink
+ * Terminals that support OSC 8 show the text as a clickable link; others print the text alone.
+ * Control characters are stripped from the URL and text so they cannot terminate the escape sequence early.
+ * @param {string} url - Link target
+ * @param {string} [text] - Link text (default: the path relative to the repository root)
+ * @returns {string} Escape-sequence wrapped link
+ */
+export function formatOsc8Link(url, text = undefined) {
+  if (text === undefined) {
+    const link = parseGitHubLink(url);
+    text = link ? (link.path || link.repo) : url;
+  }
+
+  const target = url.replace(CONTROL_CHARACTERS, '');
+  const label = text.replace(CONTROL_CHARACTERS, '');
+  return `\x1b]8;;${target}\x1b\\${label}\x1b]8;;\x1b\\`;
+}
+
+/**
+ * Formats a URL as an Org-mode link
+ * Square brackets in the URL are percent-encoded so they cannot terminate the link early. Org has no escape for
+ * brackets in the description, so there they become braces, as Org itself does when inserting a link.
+ * @param {string} url - Link target
+ * @param {string} [text] - Link description (default: path@shortsha derived from the URL)
+ * @param {number} [shaLength=7] - Commit SHA characters in the default text
+ * @returns {string} Org-mode link string
+ */
+export function formatOrgLink(url, text = undefined, shaLength = SHORT_SHA_LENGTH) {
+  if (text === undefined) {
+    text = linkText(url, shaLength);
+  }
+  const target = url.replace(/\[/g, '%5B').replace(/\]/g, '%5D');
+  const description = text.replace(/\[/g, '{').replace(/\]/g, '}');
+  return `[[${target}][${description}]]`;
+}
+
+/**
+ * Formats a URL in one of the supported output formats
+ * @param {string} url - Link target
+ * @param {'url'|'html'|'osc8'|'org'} [linkFormat='url'] - Output format
+ * @param {string} [text] - Link text (default: derived from the URL)
+ * @param {number} [shaLength=7] - Commit SHA characters in default path@shortsha text
+ * @returns {string} Formatted link
+ * @throws {TypeError} If the link format is invalid
+ */
+export function formatLink(url, linkFormat = 'url', text = undefined, shaLength = SHORT_SHA_LENGTH) {
+  switch (linkFormat) {
+    case 'url':
+      return url;
+    case 'html':
+      return formatHtmlLink(url, text, shaLength);
+    case 'osc8':
+      return formatOsc8Link(url, text);
+    case 'org':
+      return formatOrgLink(url, text, shaLength);
+    default:
+      throw new TypeError(`Invalid link format: ${linkFormat}. Must be 'url', 'html', 'osc8' or 'org'`);
+  }
+}
+
+/**
+ * Escapes HTML special characters
+ * @param {string} value - Raw text
+ * @returns {string} Escaped text
+ */
+function escapeHtml(value) {
+  return value.replace(/[&<>"']/g, (char) => HTML_ESCAPES[char]);
+}
@@ -1,34 +1,125 @@
-import { exec } from 'child_process';
+import { execFile, spawn } from 'child_process';
+import fs from 'fs';
+import path from 'path';
 import { promisify } from 'util';
-import { GitCommandError } from '../errors.mjs';
+import { GitCommandError, GitError, InvalidHashError, ObjectNotLocalError, UnbornHeadError } from '../errors.mjs';
+import { resolveFilePath } from './path.mjs';
 
-const execAsync = promisify(exec);
+const execFileAsync = promisify(execFile);
+
+/**
+ * Environment variables that select a repository instead of discovery from the working directory
+ */
+const REPOSITORY_ENV_VARS = [
+  'GIT_DIR',
+  'GIT_WORK_TREE',
+  'GIT_COMMON_DIR',
+  'GIT_INDEX_FILE',
+  'GIT_OBJECT_DIRECTORY',
+  'GIT_ALTERNATE_OBJECT_DIRECTORIES',
+  'GIT_IMPLICIT_WORK_TREE',
+  'GIT_PREFIX'
+];
+
+/**
+ * Repository environment variables holding a single path
+ */
+const REPOSITORY_PATH_ENV_VARS = [
+  'GIT_DIR',
+  'GIT_WORK_TREE',
+  'GIT_COMMON_DIR',
+  'GIT_INDEX_FILE',
+  'GIT_OBJECT_DIRECTORY'
+];
+
+/**
+ * stderr of an on-demand partial clone fetch that failed
+ */
+const PROMISOR_FETCH_FAILURE_PATTERN = /could not fetch ([0-9a-f]{40}) from promisor remote/;
+
+/**
+ * Abbreviated object ID: 4 (Git's minimum) to 40 hexadecimal characters
+ */
+const SHORT_OID_PATTERN = /^[0-9a-f]{4,40}$/i;
+
+/**
+ * Branch names tried when a remote has no HEAD ref
+ */
+const DEFAULT_BRANCH_CANDIDATES = ['main', 'master', 'trunk', 'develop'];
+
+/**
+ * Whether GIT_DIR / GIT_WORK_TREE from the environment are honored (see setHonorGitEnv)
+ */
+let honorGitEnv = true;
+
+/**
+ * git ls-tree -z entry: "<mode> blob <blob>\t<path>"
+ */
+const TREE_ENTRY_PATTERN = /^\d+ blob ([0-9a-f]{40})\t([\s\S]*)$/;
+
+/**
+ * git ls-files --stage -z entry for a merged file or symlink: "<mode> <blob> 0\t<path>"
+ */
+const INDEX_ENTRY_PATTERN = /^(?:100644|100755|120000) ([0-9a-f]{40}) 0\t([\s\S]*)$/;
 
 /**
  * Executes a Git command and returns the output
- * @param {string} command - Git command to execute
+ * No shell is involved. A string command is split on whitespace, so it suits fixed commands only; pass an argument
+ * array when any argument is a path, revision or other caller-supplied value, so it reaches Git as-is.
+ * @param {string|string[]} command - Git command to execute (e.g. 'git status' or ['git', 'log', '--', filePath])
  * @param {string} cwd - Working directory
- * @returns {Promise<string>} Command output (trimmed)
+ * @param {object} [options] - Options
+ * @param {object} [options.env] - Extra environment variables for this command
+ * @param {string} [options.encoding='utf8'] - 'buffer' returns stdout as an untrimmed Buffer (for binary content)
+ * @param {number} [options.maxBuffer=10485760] - Largest stdout accepted, in bytes
+ * @returns {Promise<string|Buffer>} Command output (trimmed string, or Buffer with encoding 'buffer')
  * @throws {GitCommandError} If command fails
+ * @throws {ObjectNotLocalError} If an object missing from a partial clone cannot be fetched from the promisor remote
  */
-export async function executeGitCommand(command, cwd = process.cwd()) {
+export async function executeGitCommand(command, cwd = process.cwd(), options = {}) {
+  const { env = {}, encoding = 'utf8', maxBuffer = 10 * 1024 * 1024 } = options;
+  const [file, ...args] = typeof command === 'string' ? command.split(/\s+/).filter(Boolean) : command;
+  // Error context keeps a readable form of the command
+  command = [file, ...args].join(' ');
+
   try {
-    const { stdout, stderr } = await execAsync(command, {
+    const { stdout } = await execFileAsync(file, args, {
       cwd,
-      maxBuffer: 10 * 1024 * 1024, // 10MB buffer
-      env: { ...process.env, LC_ALL: 'C' } // Force English output
+      maxBuffer,
+      encoding,
+      env: { ...gitEnvironment(), ...env }
     });
 
     // Git sometimes writes to stderr even on success
     // Only throw if the command actually failed (caught in catch block)
-    return stdout.trim();
+    return encoding === 'buffer' ? stdout : stdout.trim();
   } catch (error) {
+    const stderr = error.stderr?.toString();
+
+    // Object missing from a partial clone that could not be fetched on demand
+    const fetchFailure = stderr?.match(PROMISOR_FETCH_FAILURE_PATTERN);
+    if (fetchFailure) {
+      const [promisor] = await getPromisorRemotes(cwd);
+      throw new ObjectNotLocalError(
+        `Object not available locally (partial clone): ${fetchFailure[1]}`,
+        {
+          objectId: fetchFailure[1],
+          remoteUrl: promisor ? await getRemoteUrl(cwd, promisor) : null,
+          command,
+          exitCode: error.code,
+          stderr: stderr.trim(),
+          cause: error,
+          context: { cwd, objectId: fetchFailure[1] }
+        }
+      );
+    }
+
     throw new GitCommandError(
       `Git command failed: ${command}`,
       {
         command,
         exitCode: error.code,
-        stderr: error.stderr?.trim(),
+        stderr: stderr?.trim(),
         cause: error,
         context: { cwd }
       }
@@ -36,6 +127,39 @@ export async function executeGitCommand(command, cwd = process.cwd()) {
   }
 }
 
+/**
+ * Chooses whether repository environment variables are honored
+ * Git hooks and CI wrappers often set GIT_DIR / GIT_WORK_TREE, which Git uses instead of discovering the
+ * repository from the working directory. They are honored by default, with relative values resolved against
+ * the current directory so they keep working when commands run from another directory. Disabling this removes
+ * them (and related variables such as GIT_INDEX_FILE) so every path is discovered on its own.
+ * @param {boolean} enabled - Honor the environment (true, default) or ignore it (false)
+ */
+export function setHonorGitEnv(enabled) {
+  honorGitEnv = enabled;
+}
+
+/**
+ * Builds the environment for Git subprocesses
+ * @returns {object} Environment with LC_ALL=C (forces English output) and repository variables resolved or removed
+ */
+function gitEnvironment() {
+  const env = { ...process.env, LC_ALL: 'C' };
+
+  for (const name of REPOSITORY_ENV_VARS) {
+    if (!(name in env)) {
+      continue;
+    }
+    if (!honorGitEnv) {
+      delete env[name];
+    } else if (REPOSITORY_PATH_ENV_VARS.includes(name) && env[name]) {
+      env[name] = path.resolve(env[name]);
+    }
+  }
+
+  return env;
+}
+
 /**
  * Checks if a directory is a Git repository
  * @param {string} repoPath - Path to check
@@ -52,23 +176,821 @@ export async function isGitRepository(repoPath) {
 
 /**
  * Gets the repository root directory
+ * Discovery is delegated to Git, so linked worktrees and submodules (where .git is a file containing
+ * "gitdir: ...") resolve to their own working tree rather than the Git directory they point to.
+ * For bare repositories the Git directory itself is returned.
  * @param {string} path - Path within repository
  * @returns {Promise<string>} Absolute path to repository root
  * @throws {RepositoryNotFoundError} If not in a Git repository
  */
 export async function getRepositoryRoot(path) {
+  return discoverRepositoryRoot(path);
+}
+
+/**
+ * Finds the repository containing a path without walking above ceiling directories
+ * Discovery walks up from startPath but never looks in a ceiling directory or its parents, so scanning a
+ * vendored tree that is not itself a repository does not resolve to an unrelated parent repository.
+ * Ceilings from GIT_CEILING_DIRECTORIES in the environment are applied too.
+ * @param {string} startPath - Path to start discovery from
+ * @param {string[]} ceilingDirs - Directories discovery must not enter
+ * @returns {Promise<string|null>} Absolute path to repository root, or null if none is found below the ceilings
+ */
+export async function findRepositoryWithCeiling(startPath, ceilingDirs) {
+  const ceilings = ceilingDirs.map((ceiling) => path.resolve(ceiling));
+  if (process.env.GIT_CEILING_DIRECTORIES) {
+    ceilings.push(process.env.GIT_CEILING_DIRECTORIES);
+  }
+
+  try {
+    return await discoverRepositoryRoot(startPath, {
+      GIT_CEILING_DIRECTORIES: ceilings.join(path.delimiter)
+    });
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Runs Git repository discovery from a path
+ * @param {string} startPath - Path within the repository
+ * @param {object} [env] - Extra environment variables (e.g. GIT_CEILING_DIRECTORIES)
+ * @returns {Promise<string>} Absolute path to repository root (the Git directory for bare repositories)
+ * @throws {RepositoryNotFoundError} If not in a Git repository
+ */
+async function discoverRepositoryRoot(startPath, env = {}) {
   const { RepositoryNotFoundError } = await import('../errors.mjs');
 
   try {
-    const root = await executeGitCommand('git rev-parse --show-toplevel', path);
+    const [isBare, gitDir] = (
+      await executeGitCommand('git rev-parse --is-bare-repository --absolute-git-dir', startPath, { env })
+    ).split('\n');
+
+    // Bare repository - the Git directory IS the repo
+    if (isBare === 'true') {
+      return gitDir;
+    }
+
+    const root = await executeGitCommand('git rev-parse --show-toplevel', startPath, { env });
     return root;
   } catch (error) {
     throw new RepositoryNotFoundError(
-      `No Git repository found at path: ${path}`,
+      `No Git repository found at path: ${startPath}`,
+      {
+        cause: error,
+        context: { path: startPath }
+      }
+    );
+  }
+}
+
+/**
+ * Finds the repository that owns a path and the path relative to its root
+ * Relative paths are resolved against the root of the repository containing repoPath. Discovery then starts
+ * from the path itself, so a file inside a submodule (or a nested repository) resolves to that repository
+ * rather than the superproject. Paths that do not exist yet are resolved from their nearest existing parent.
+ * @param {string} repoPath - Path within the (super)repository
+ * @param {string} filePath - File or directory path (absolute or relative to repo root)
+ * @returns {Promise<{repoRoot: string, relativePath: string}>} Owning repository root and relative path
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function resolveRepositoryPath(repoPath, filePath) {
+  const baseRoot = await getRepositoryRoot(repoPath);
+  const absolutePath = path.resolve(baseRoot, filePath);
+
+  let probe = absolutePath;
+  while (!isDirectory(probe) && path.dirname(probe) !== probe) {
+    probe = path.dirname(probe);
+  }
+
+  let repoRoot;
+  try {
+    repoRoot = await getRepositoryRoot(probe);
+  } catch {
+    repoRoot = baseRoot;
+  }
+
+  return { repoRoot, relativePath: resolveFilePath(repoRoot, absolutePath) };
+}
+
+/**
+ * Checks whether a path is an existing directory
+ * @param {string} dirPath - Path to check
+ * @returns {boolean} True if the path is a directory
+ */
+function isDirectory(dirPath) {
+  try {
+    return fs.statSync(dirPath).isDirectory();
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Gets the current branch name
+ * HEAD is read as a symbolic ref, so this also works on an unborn branch (a fresh git init with no commits yet).
+ * @param {string} repoPath - Repository path
+ * @returns {Promise<string>} Current branch name, or 'HEAD' if HEAD is detached
+ * @throws {GitCommandError} If unable to determine branch
+ */
+export async function getCurrentBranch(repoPath) {
+  try {
+    return await executeGitCommand('git symbolic-ref --quiet --short HEAD', repoPath);
+  } catch (error) {
+    // Exit code 1: HEAD is not a symbolic ref (detached)
+    if (error instanceof GitCommandError && error.exitCode === 1) {
+      return 'HEAD';
+    }
+    throw error;
+  }
+}
+
+/**
+ * Checks whether HEAD points at a branch with no commits yet
+ * @param {string} repoPath - Repository path
+ * @returns {Promise<boolean>} True for a fresh git init or orphan branch, false otherwise
+ */
+export async function isHeadUnborn(repoPath) {
+  try {
+    await executeGitCommand('git rev-parse --verify --quiet HEAD', repoPath);
+    return false;
+  } catch {
+    return true;
+  }
+}
+
+/**
+ * Gets the commit SHA HEAD points at
+ * @param {string} repoPath - Repository path
+ * @returns {Promise<string>} Commit SHA
+ * @throws {UnbornHeadError} If the current branch has no commits yet
+ */
+export async function getHeadCommit(repoPath) {
+  try {
+    return await executeGitCommand('git rev-parse --verify --quiet HEAD', repoPath);
+  } catch (error) {
+    const branch = await getCurrentBranch(repoPath);
+    throw new UnbornHeadError(
+      `Branch ${branch} has no commits yet`,
       {
+        branch,
         cause: error,
-        context: { path }
+        context: { branch, path: repoPath }
       }
     );
   }
 }
+
+/**
+ * Abbreviates an object ID to the shortest prefix that is unambiguous in the repository
+ * @param {string} repoPath - Repository path
+ * @param {string} oid - Full object SHA (or any revision)
+ * @param {number} [minLength] - Shortest prefix to return (default: Git's core.abbrev, usually 7 or more depending on
+ *   repository size)
+ * @returns {Promise<string>} Unambiguous abbreviated SHA
+ * @throws {GitCommandError} If the object does not exist
+ * @throws {TypeError} If minLength is not between 4 and 40
+ */
+export async function abbreviateOid(repoPath, oid, minLength = undefined) {
+  if (minLength !== undefined && !(minLength >= 4 && minLength <= 40)) {
+    throw new TypeError(`Invalid min length: ${minLength}. Must be between 4 and 40`);
+  }
+
+  const option = minLength === undefined ? '--short' : `--short=${minLength}`;
+  // ^{object} makes a full SHA that names no object fail instead of being echoed
+  return executeGitCommand(['git', 'rev-parse', '--verify', option, `${oid}^{object}`], repoPath);
+}
+
+/**
+ * Expands an abbreviated object ID to the full SHA
+ * @param {string} repoPath - Repository path
+ * @param {string} prefix - Abbreviated SHA, at least 4 hexadecimal characters
+ * @returns {Promise<string>} Full object SHA
+ * @throws {InvalidHashError} If prefix is not 4-40 hexadecimal characters
+ * @throws {GitError} If no object matches (code OBJECT_NOT_FOUND) or several do (code AMBIGUOUS_OID, with the
+ *   candidates in the context)
+ */
+export async function expandOid(repoPath, prefix) {
+  if (!SHORT_OID_PATTERN.test(prefix)) {
+    throw new InvalidHashError(
+      'Invalid abbreviated hash: must be 4 to 40 hexadecimal characters',
+      { context: { hash: prefix } }
+    );
+  }
+
+  const output = await executeGitCommand(['git', 'rev-parse', `--disambiguate=${prefix.toLowerCase()}`], repoPath);
+  const candidates = output.split(/\s+/).filter(Boolean);
+
+  if (candidates.length === 0) {
+    throw new GitError(`No object matches ${prefix}`, {
+      code: 'OBJECT_NOT_FOUND',
+      context: { prefix, path: repoPath }
+    });
+  }
+  if (candidates.length > 1) {
+    throw new GitError(`Abbreviated hash ${prefix} is ambiguous: ${candidates.length} objects match`, {
+      code: 'AMBIGUOUS_OID',
+      context: { prefix, candidates }
+    });
+  }
+
+  return candidates[0];
+}
+
+/**
+ * Lists the configured remotes in configuration order
+ * @param {string} repoPath - Repository path
+ * @returns {Promise<string[]>} Remote names
+ */
+export async function listRemotes(repoPath) {
+  const output = await executeGitCommand('git remote', repoPath);
+  return output.split('\n').filter(Boolean);
+}
+
+/**
+ * Gets the remote and branch the current branch tracks
+ * Read from branch.<name>.remote and branch.<name>.merge, so a local feature branch tracking upstream/main
+ * yields { remote: 'upstream', branch: 'main' }.
+ * @param {string} repoPath - Repository path
+ * @returns {Promise<{remote: string, branch: string} | null>} Tracked remote and branch, or null if HEAD is
+ *   detached or the branch does not track a remote branch
+ */
+export async function getTrackingBranch(repoPath) {
+  const branch = await getCurrentBranch(repoPath);
+  if (branch === 'HEAD') {
+    return null;
+  }
+
+  let remote;
+  let merge;
+  try {
+    remote = await executeGitCommand(['git', 'config', '--get', `branch.${branch}.remote`], repoPath);
+    merge = await executeGitCommand(['git', 'config', '--get', `branch.${branch}.merge`], repoPath);
+  } catch {
+    return null;
+  }
+
+  // "." tracks a branch of the local repository
+  if (remote === '.') {
+    return null;
+  }
+
+  return { remote, branch: merge.replace(/^refs\/heads\//, '') };
+}
+
+/**
+ * Counts commits between HEAD and the current branch's upstream
+ * @param {string} repoPath - Repository path
+ * @returns {Promise<{ahead: number, behind: number} | null>} Commits on HEAD not yet on the upstream (ahead) and
+ *   upstream commits not yet on HEAD (behind), or null if HEAD is detached or the branch has no upstream
+ */
+export async function getUpstreamDivergence(repoPath) {
+  let output;
+  try {
+    output = await executeGitCommand('git rev-list --left-right --count HEAD...@{upstream}', repoPath);
+  } catch {
+    return null;
+  }
+
+  const [ahead, behind] = output.split(/\s+/).map(Number);
+  return { ahead, behind };
+}
+
+/**
+ * Picks the remote used when none is named
+ * The remote the current branch tracks (branch.<name>.remote) wins, then origin, then the first configured
+ * remote, so repositories without an origin remote still resolve.
+ * With preferUpstream, a clone of a fork (both origin and upstream remotes configured) resolves to upstream
+ * regardless of tracking, so shared links point at the canonical repository where everyone can see the commit.
+ * @param {string} repoPath - Repository path
+ * @param {object} [options={}] - Options
+ * @param {boolean} [options.preferUpstream=false] - Pick upstream when it exists alongside origin
+ * @returns {Promise<string | null>} Remote name, or null if no remotes are configured
+ */
+export async function getDefaultRemote(repoPath, options = {}) {
+  const { preferUpstream = false } = options;
+
+  const remotes = await listRemotes(repoPath);
+  if (remotes.length === 0) {
+    return null;
+  }
+
+  if (preferUpstream && remotes.includes('origin') && remotes.includes('upstream')) {
+    return 'upstream';
+  }
+
+  const branch = await getCurrentBranch(repoPath);
+  if (branch !== 'HEAD') {
+    try {
+      const tracked = await executeGitCommand(['git', 'config', '--get', `branch.${branch}.remote`], repoPath);
+      if (remotes.includes(tracked)) {
+        return tracked;
+      }
+    } catch {
+      // Branch has no upstream
+    }
+  }
+
+  return remotes.includes('origin') ? 'origin' : remotes[0];
+}
+
+/**
+ * Gets the URL of a Git remote
+ * url.<base>.insteadOf rewrites from git config are applied, so a remote stored as gh:owner/repo resolves
+ * to the real URL. With push, the push URL is returned instead (remote.<name>.pushurl if set, otherwise
+ * the fetch URL with pushInsteadOf rewrites).
+ * @param {string} repoPath - Repository path
+ * @param {string} [remote] - Remote name (default: getDefaultRemote, i.e. the current branch's remote, then origin,
+ *   then the first remote)
+ * @param {object} [options={}] - Options
+ * @param {boolean} [options.push=false] - Return the push URL instead of the fetch URL
+ * @returns {Promise<string | null>} Remote URL, or null if the remote doesn't exist
+ */
+export async function getRemoteUrl(repoPath, remote = null, options = {}) {
+  const { push = false } = options;
+
+  if (!remote) {
+    remote = await getDefaultRemote(repoPath);
+    if (!remote) {
+      return null;
+    }
+  }
+
+  if (push) {
+    try {
+      const pushUrl = await executeGitCommand(['git', 'config', '--get', `remote.${remote}.pushurl`], repoPath);
+      return applyUrlRewrites(pushUrl, repoPath);
+    } catch {
+      // No pushurl - fall back to the fetch URL
+    }
+  }
+
+  let url;
+  try {
+    url = await executeGitCommand(['git', 'config', '--get', `remote.${remote}.url`], repoPath);
+  } catch {
+    return null;
+  }
+
+  return applyUrlRewrites(url, repoPath, { push });
+}
+
+/**
+ * Gets the default branch of a remote
+ * Read from refs/remotes/<remote>/HEAD (set by git clone or git remote set-head). When that ref is missing, the
+ * first of main, master, trunk and develop that exists as a remote-tracking branch, then as a local branch, is used.
+ * @param {string} repoPath - Repository path
+ * @param {string} [remote] - Remote name (default: getDefaultRemote)
+ * @returns {Promise<string | null>} Branch name without the remote prefix, or null if it cannot be determined
+ */
+export async function getDefaultBranch(repoPath, remote = null) {
+  if (!remote) {
+    remote = await getDefaultRemote(repoPath);
+  }
+
+  if (remote) {
+    try {
+      const head = await executeGitCommand(
+        ['git', 'symbolic-ref', '--quiet', '--short', `refs/remotes/${remote}/HEAD`],
+        repoPath
+      );
+      return head.startsWith(`${remote}/`) ? head.slice(remote.length + 1) : head;
+    } catch {
+      // No remote HEAD - try the common names
+    }
+  }
+
+  const prefixes = remote ? [`refs/remotes/${remote}/`, 'refs/heads/'] : ['refs/heads/'];
+
+  for (const prefix of prefixes) {
+    for (const name of DEFAULT_BRANCH_CANDIDATES) {
+      try {
+        await executeGitCommand(['git', 'show-ref', '--verify', '--quiet', `${prefix}${name}`], repoPath);
+        return name;
+      } catch {
+        // Not present
+      }
+    }
+  }
+
+  return null;
+}
+
+/**
+ * Checks whether a commit is reachable from a remote's tracking branches
+ * A commit that only exists locally 404s for everyone else, so permalinks should only point at commits this
+ * returns true for. The check is made against refs/remotes/<remote>/* as of the last fetch or push.
+ * @param {string} repoPath - Repository path
+ * @param {string} commit - Commit SHA or revision
+ * @param {string} [remote] - Remote name (default: getDefaultRemote)
+ * @returns {Promise<boolean>} True if a remote-tracking branch of the remote contains the commit
+ * @throws {GitCommandError} If the commit does not exist
+ */
+export async function isCommitOnRemote(repoPath, commit, remote = null) {
+  if (!remote) {
+    remote = await getDefaultRemote(repoPath);
+    if (!remote) {
+      return false;
+    }
+  }
+
+  const output = await executeGitCommand(
+    ['git', 'for-each-ref', '--contains', commit, '--count=1', '--format=%(refname)', `refs/remotes/${remote}/`],
+    repoPath
+  );
+  return Boolean(output);
+}
+
+/**
+ * Gets the closest ancestor of a commit that is on a remote
+ * The commit itself is returned when it is already pushed. Otherwise the newest commit where the unpushed history
+ * branches off the remote's tracking branches is returned.
+ * @param {string} repoPath - Repository path
+ * @param {string} commit - Commit SHA or revision
+ * @param {string} [remote] - Remote name (default: getDefaultRemote)
+ * @returns {Promise<string | null>} Full SHA of the pushed ancestor, or null if no ancestor is pushed
+ * @throws {GitCommandError} If the commit does not exist
+ */
+export async function getNearestPushedAncestor(repoPath, commit, remote = null) {
+  if (!remote) {
+    remote = await getDefaultRemote(repoPath);
+    if (!remote) {
+      return null;
+    }
+  }
+
+  // Boundary commits ("-<sha>") are the pushed parents of the unpushed commits
+  const output = await executeGitCommand(
+    ['git', 'rev-list', '--boundary', commit, '--not', `--remotes=${remote}`],
+    repoPath
+  );
+  if (!output) {
+    return executeGitCommand(['git', 'rev-parse', '--verify', `${commit}^{commit}`], repoPath);
+  }
+
+  const boundary = output.split('\n').find((line) => line.startsWith('-'));
+  return boundary ? boundary.slice(1) : null;
+}
+
+/**
+ * Gets the best common ancestor of two commits
+ * With the default branch from getDefaultBranch this gives the point where a feature branch forked, which stays fixed
+ * while both branches move on.
+ * @param {string} repoPath - Repository path
+ * @param {string} a - Commit SHA or revision
+ * @param {string} b - Commit SHA or revision
+ * @returns {Promise<string | null>} Full SHA of the merge base, or null if the histories are unrelated
+ * @throws {GitCommandError} If either commit does not exist
+ */
+export async function mergeBase(repoPath, a, b) {
+  try {
+    return await executeGitCommand(['git', 'merge-base', a, b], repoPath);
+  } catch (error) {
+    // Exit code 1: no common ancestor
+    if (error instanceof GitCommandError && error.exitCode === 1) {
+      return null;
+    }
+    throw error;
+  }
+}
+
+/**
+ * Checks whether one commit is reachable from another
+ * A commit counts as its own ancestor.
+ * @param {string} repoPath - Repository path
+ * @param {string} ancestor - Commit SHA or revision that may be contained
+ * @param {string} descendant - Commit SHA or revision whose history is searched
+ * @returns {Promise<boolean>} True if ancestor is in the history of descendant
+ * @throws {GitCommandError} If either commit does not exist
+ */
+export async function isAncestor(repoPath, ancestor, descendant) {
+  try {
+    await executeGitCommand(['git', 'merge-base', '--is-ancestor', ancestor, descendant], repoPath);
+    return true;
+  } catch (error) {
+    // Exit code 1: not an ancestor
+    if (error instanceof GitCommandError && error.exitCode === 1) {
+      return false;
+    }
+    throw error;
+  }
+}
+
+/**
+ * Applies url.<base>.insteadOf rewrites from git config to a URL
+ * As in git, the longest matching prefix wins. With push, pushInsteadOf rewrites take precedence over insteadOf.
+ * @param {string} url - Remote URL as configured
+ * @param {string} repoPath - Repository path (for repository-level config)
+ * @param {object} [options={}] - Options
+ * @param {boolean} [options.push=false] - Also consider pushInsteadOf rewrites
+ * @returns {Promise<string>} Rewritten URL, or the URL unchanged if no rewrite applies
+ */
+export async function applyUrlRewrites(url, repoPath, options = {}) {
+  const { push = false } = options;
+
+  let output;
+  try {
+    output = await executeGitCommand(['git', 'config', '--get-regexp', '^url\\..*\\.(push)?insteadof$'], repoPath);
+  } catch {
+    return url;
+  }
+
+  const rewrites = { insteadof: [], pushinsteadof: [] };
+  for (const line of output.split('\n').filter(Boolean)) {
+    const separator = line.indexOf(' ');
+    const key = line.slice(0, separator);
+    const prefix = line.slice(separator + 1);
+    const dot = key.lastIndexOf('.');
+    if (separator > 0 && prefix) {
+      rewrites[key.slice(dot + 1).toLowerCase()].push({ prefix, base: key.slice('url.'.length, dot) });
+    }
+  }
+
+  for (const kind of push ? ['pushinsteadof', 'insteadof'] : ['insteadof']) {
+    const best = rewrites[kind]
+      .filter(({ prefix }) => url.startsWith(prefix))
+      .reduce((longest, rewrite) => (!longest || rewrite.prefix.length > longest.prefix.length ? rewrite : longest), null);
+    if (best) {
+      return best.base + url.slice(best.prefix.length);
+    }
+  }
+
+  return url;
+}
+
+/**
+ * Gets the most recent commit that modified a file or directory
+ * Unlike HEAD, this only changes when the path itself changes, so links built from it stay stable across unrelated commits.
+ * In a shallow clone the search stops at the shallow boundary: if the path was last changed before it, the boundary
+ * commit is returned (check it against getShallowBoundary).
+ * @param {string} repoPath - Repository path
+ * @param {string} filePath - Path relative to the repository root ('' or '.' for everything)
+ * @param {string} [rev='HEAD'] - Revision whose history is searched
+ * @returns {Promise<string | null>} Commit SHA, or null if no commit in the history touches the path
+ */
+export async function getLastCommitForFile(repoPath, filePath, rev = 'HEAD') {
+  try {
+    const output = await executeGitCommand(['git', 'log', '-1', '--format=%H', rev, '--', filePath || '.'], repoPath);
+    return output || null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Gets the promisor remotes of a partial clone
+ * Partial clones (git clone --filter=...) leave objects out and fetch them from these remotes when first needed.
+ * @param {string} repoPath - Repository path
+ * @returns {Promise<string[]>} Promisor remote names (empty if the repository is not a partial clone)
+ */
+export async function getPromisorRemotes(repoPath) {
+  let output;
+  try {
+    output = await executeGitCommand(['git', 'config', '--get-regexp', '^remote\\..*\\.promisor$'], repoPath);
+  } catch {
+    return [];
+  }
+
+  const remotes = [];
+  for (const line of output.split('\n')) {
+    const [key, value = ''] = line.split(' ');
+    if (['true', 'yes', 'on', '1'].includes(value.toLowerCase())) {
+      remotes.push(key.slice('remote.'.length, -'.promisor'.length));
+    }
+  }
+  return remotes;
+}
+
+/**
+ * Checks whether an object is present in the local object database
+ * Unlike most Git commands this never fetches from a promisor remote, so it is safe to call offline in a partial clone.
+ * @param {string} repoPath - Repository path
+ * @param {string} objectId - Object SHA
+ * @returns {Promise<boolean>} True if the object is stored locally, false otherwise
+ */
+export async function isObjectLocal(repoPath, objectId) {
+  // --missing disables on-demand fetching; tree:0 stops the walk at the object itself
+  try {
+    await executeGitCommand(
+      ['git', 'rev-list', '--objects', '--no-walk', '--missing=print', '--filter=tree:0', objectId],
+      repoPath
+    );
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Checks whether the repository is a shallow clone
+ * @param {string} repoPath - Repository path
+ * @returns {Promise<boolean>} True if history was truncated (e.g. git clone --depth), false otherwise
+ */
+export async function isShallow(repoPath) {
+  return (await executeGitCommand('git rev-parse --is-shallow-repository', repoPath)) === 'true';
+}
+
+/**
+ * Gets the commits at which a shallow clone's history is cut off
+ * History walks treat these commits as parentless, so results that land on one of them may be truncated rather than exact.
+ * @param {string} repoPath - Repository path
+ * @returns {Promise<string[]>} Boundary commit SHAs (empty if the repository is not shallow)
+ */
+export async function getShallowBoundary(repoPath) {
+  const shallowFile = await executeGitCommand('git rev-parse --git-path shallow', repoPath);
+
+  try {
+    return fs.readFileSync(path.resolve(repoPath, shallowFile), 'utf8').split(/\s+/).filter(Boolean);
+  } catch {
+    return [];
+  }
+}
+
+/**
+ * Gets the Git blob SHA of a file as stored in HEAD's tree or the index
+ * This is the per-file content identifier: it changes only when the file content changes, unlike the commit hash.
+ * @param {string} repoPath - Path within the repository
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {object} [options={}] - Options
+ * @param {boolean} [options.index=false] - Read the staged version from the index instead of HEAD
+ * @returns {Promise<string | null>} Blob SHA, or null if the path is not a file in HEAD (or the index)
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ */
+export async function getBlobOid(repoPath, filePath, options = {}) {
+  const { index = false } = options;
+
+  const repoRoot = await getRepositoryRoot(repoPath);
+  const relativePath = resolveFilePath(repoRoot, filePath);
+
+  if (!index) {
+    try {
+      return await getFileHashAtCommit(repoRoot, relativePath, 'HEAD');
+    } catch {
+      // Unborn HEAD (no commits yet)
+      return null;
+    }
+  }
+
+  let output;
+  try {
+    output = await executeGitCommand(['git', 'ls-files', '--stage', '-z', '--', relativePath], repoRoot);
+  } catch {
+    return null;
+  }
+
+  return findBlobEntry(output, INDEX_ENTRY_PATTERN, relativePath);
+}
+
+/**
+ * Reads the raw content of a blob
+ * @param {string} repoPath - Repository path
+ * @param {string} blobHash - Blob SHA, or any revision naming a blob such as HEAD:src/file.js
+ * @returns {Promise<Buffer>} Blob content (a Uint8Array)
+ * @throws {GitCommandError} If the object does not exist or is not a blob
+ * @throws {ObjectNotLocalError} If the blob is missing from a partial clone and cannot be fetched
+ */
+export async function getBlobBytes(repoPath, blobHash) {
+  const size = Number(await executeGitCommand(['git', 'cat-file', '-s', blobHash], repoPath));
+  return executeGitCommand(['git', 'cat-file', 'blob', blobHash], repoPath, {
+    encoding: 'buffer',
+    maxBuffer: size + 1
+  });
+}
+
+/**
+ * Reads the content of a blob as text
+ * Decoded as UTF-8; invalid byte sequences (binary files, other encodings) are replaced with U+FFFD rather than
+ * throwing.
+ * @param {string} repoPath - Repository path
+ * @param {string} blobHash - Blob SHA, or any revision naming a blob such as HEAD:src/file.js
+ * @returns {Promise<string>} Blob content as text
+ * @throws {GitCommandError} If the object does not exist or is not a blob
+ * @throws {ObjectNotLocalError} If the blob is missing from a partial clone and cannot be fetched
+ */
+export async function getBlobContent(repoPath, blobHash) {
+  return (await getBlobBytes(repoPath, blobHash)).toString('utf8');
+}
+
+/**
+ * Gets the size of a blob in bytes without reading its content
+ * @param {string} repoPath - Repository path
+ * @param {string} blobHash - Blob SHA, or any revision naming a blob
+ * @returns {Promise<number>} Size in bytes
+ * @throws {GitCommandError} If the object does not exist
+ */
+export async function getBlobSize(repoPath, blobHash) {
+  return Number(await executeGitCommand(['git', 'cat-file', '-s', blobHash], repoPath));
+}
+
+/**
+ * Reads a blob in chunks instead of loading it whole
+ * Chunks are yielded as git cat-file writes them, so memory use stays at one pipe buffer; combine with
+ * getBlobSize to report progress. The object is checked before the first chunk, so missing objects throw the
+ * same errors as getBlobBytes.
+ * @param {string} repoPath - Repository path
+ * @param {string} blobHash - Blob SHA, or any revision naming a blob such as HEAD:src/file.js
+ * @yields {Buffer} Consecutive chunks of the blob content
+ * @throws {GitCommandError} If the object does not exist or is not a blob
+ * @throws {ObjectNotLocalError} If the blob is missing from a partial clone and cannot be fetched
+ */
+export async function* streamBlob(repoPath, blobHash) {
+  // Resolve through executeGitCommand for its error handling
+  const objectType = await executeGitCommand(['git', 'cat-file', '-t', blobHash], repoPath);
+  if (objectType !== 'blob') {
+    throw new GitCommandError(
+      `Not a blob: ${blobHash} is a ${objectType}`,
+      { command: `git cat-file blob "${blobHash}"`, context: { cwd: repoPath } }
+    );
+  }
+
+  const child = spawn('git', ['cat-file', 'blob', blobHash], { cwd: repoPath, env: gitEnvironment() });
+  const exitCode = new Promise((resolve, reject) => {
+    child.on('error', reject);
+    child.on('close', resolve);
+  });
+
+  let stderr = '';
+  child.stderr.on('data', (data) => {
+    stderr += data;
+  });
+
+  for await (const chunk of child.stdout) {
+    yield chunk;
+  }
+
+  const code = await exitCode;
+  if (code !== 0) {
+    throw new GitCommandError(
+      `Git command failed: git cat-file blob "${blobHash}"`,
+      {
+        command: `git cat-file blob "${blobHash}"`,
+        exitCode: code,
+        stderr: stderr.trim(),
+        context: { cwd: repoPath }
+      }
+    );
+  }
+}
+
+/**
+ * Gets the Git blob SHA of a file as stored at a given commit
+ * @param {string} repoPath - Path within the repository
+ * @param {string} filePath - File path (absolute or relative to repo root)
+ * @param {string} commit - Commit SHA, branch, tag or other revision
+ * @returns {Promise<string | null>} Blob SHA, or null if the path is not a file at that commit
+ * @throws {RepositoryNotFoundError} If repoPath is not in a Git repository
+ * @throws {GitError} If the commit cannot be resolved
+ */
+export async function getFileHashAtCommit(repoPath, filePath, commit) {
+  const repoRoot = await getRepositoryRoot(repoPath);
+  const relativePath = resolveFilePath(repoRoot, filePath);
+
+  let output;
+  try {
+    output = await executeGitCommand(['git', 'ls-tree', '-z', commit, '--', relativePath], repoRoot);
+  } catch (error) {
+    throw new GitError(
+      `Cannot resolve commit: ${commit}`,
+      {
+        code: 'REF_NOT_FOUND',
+        cause: error,
+        context: { path: repoRoot, ref: commit }
+      }
+    );
+  }
+
+  return findBlobEntry(output, TREE_ENTRY_PATTERN, relativePath);
+}
+
+/**
+ * Finds the blob SHA for an exact path in NUL-separated ls-tree/ls-files output
+ * @param {string} output - Command output produced with -z
+ * @param {RegExp} pattern - Entry pattern capturing (blob SHA, path)
+ * @param {string} relativePath - Path relative to the repository root
+ * @returns {string | null} Blob SHA, or null if no entry matches the path
+ */
+function findBlobEntry(output, pattern, relativePath) {
+  for (const entry of output.split('\0')) {
+    const match = entry.match(pattern);
+    if (match && match[2] === relativePath) {
+      return match[1];
+    }
+  }
+  return null;
+}
+
+/**
+ * Gets the tag pointing exactly at HEAD
+ * When several tags point at HEAD, the highest version-sorted tag wins (e.g. v1.10.0 over v1.9.0)
+ * @param {string} repoPath - Repository path
+ * @returns {Promise<string | null>} Tag name, or null if HEAD is not tagged
+ */
+export async function getHeadTag(repoPath) {
+  try {
+    const output = await executeGitCommand('git tag --points-at HEAD --sort=-v:refname', repoPath);
+    const tags = output.split('\n').filter(Boolean);
+    return tags.length > 0 ? tags[0] : null;
+  } catch {
+    return null;
+  }
+}
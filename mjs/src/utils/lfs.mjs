@@ -0,0 +1,75 @@
+/**
+ * Pointer files are at most this many bytes
+ */
+export const LFS_POINTER_MAX_SIZE = 1024;
+
+/**
+ * Spec versions accepted on a pointer's first line; the second is the pre-release name of Git LFS
+ */
+const LFS_SPEC_VERSIONS = ['https://git-lfs.github.com/spec/v1', 'https://hawser.github.com/spec/v1'];
+
+/**
+ * "oid sha256:<64 hex digits>" pointer line
+ */
+const LFS_OID_PATTERN = /^sha256:([0-9a-f]{64})$/;
+
+/**
+ * Parses the content of a Git LFS pointer file (https://github.com/git-lfs/git-lfs/blob/main/docs/spec.md)
+ * Returns { oid, size, pointerOid, path }: oid is the SHA-256 of the real content as hex (the LFS object ID) and size
+ * its size in bytes; pointerOid and path, the pointer blob's SHA and path, are null here and filled in by getLfsInfo.
+ * @param {Buffer|Uint8Array} content - Blob content
+ * @returns {object | null} Pointer, or null if the content is not a valid pointer
+ */
+export function parseLfsPointer(content) {
+  if (content.length > LFS_POINTER_MAX_SIZE) {
+    return null;
+  }
+
+  let text;
+  try {
+    text = new TextDecoder('utf-8', { fatal: true }).decode(content);
+  } catch {
+    return null;
+  }
+
+  // "<key> <value>" lines, version first
+  const lines = text.split('\n');
+  if (lines[lines.length - 1] === '') {
+    lines.pop();
+  }
+  if (!lines.length) {
+    return null;
+  }
+
+  const [key, version] = splitPointerLine(lines[0]);
+  if (key !== 'version' || !LFS_SPEC_VERSIONS.includes(version)) {
+    return null;
+  }
+
+  const fields = {};
+  for (const line of lines.slice(1)) {
+    const [name, value] = splitPointerLine(line);
+    if (value === null) {
+      return null;
+    }
+    fields[name] = value;
+  }
+
+  const match = (fields.oid || '').match(LFS_OID_PATTERN);
+  const size = fields.size || '';
+  if (!match || !/^\d+$/.test(size)) {
+    return null;
+  }
+
+  return { oid: match[1], size: parseInt(size, 10), pointerOid: null, path: null };
+}
+
+/**
+ * Splits a pointer line at its first space
+ * @param {string} line - Pointer line
+ * @returns {[string, string|null]} Key and value (null if the line has no space)
+ */
+function splitPointerLine(line) {
+  const separator = line.indexOf(' ');
+  return separator === -1 ? [line, null] : [line.slice(0, separator), line.slice(separator + 1)];
+}
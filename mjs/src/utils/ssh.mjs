@@ -0,0 +1,83 @@
+import fs from 'fs';
+import os from 'os';
+import path from 'path';
+
+/**
+ * "Key value" or "Key=value" configuration lines
+ */
+const CONFIG_LINE_PATTERN = /^(\S+?)(?:\s*=\s*|\s+)(.+)$/;
+
+/**
+ * Resolves an SSH host alias to its real hostname
+ * Reads Host blocks from the SSH client configuration and returns the first matching HostName
+ * (as ssh does, the first obtained value wins). Wildcards (*, ?) and negated patterns (!) are
+ * supported; Match blocks and Include directives are not.
+ * @param {string} alias - Host alias as written in the remote URL
+ * @param {string} [configPath='~/.ssh/config'] - SSH config file
+ * @returns {string} Resolved hostname, or the alias itself if no HostName applies
+ */
+export function resolveSshHost(alias, configPath = path.join(os.homedir(), '.ssh', 'config')) {
+  let lines;
+  try {
+    lines = fs.readFileSync(configPath, 'utf8').split(/\r?\n/);
+  } catch {
+    return alias;
+  }
+
+  // Options before the first Host block apply to every host
+  let matched = true;
+
+  for (const rawLine of lines) {
+    const line = rawLine.trim();
+    if (!line || line.startsWith('#')) {
+      continue;
+    }
+
+    const match = line.match(CONFIG_LINE_PATTERN);
+    if (!match) {
+      continue;
+    }
+
+    const key = match[1].toLowerCase();
+    const value = match[2].trim().replace(/^"|"$/g, '');
+
+    if (key === 'host') {
+      matched = hostMatches(alias, value.split(/\s+/));
+    } else if (key === 'match') {
+      // Match criteria are not evaluated
+      matched = false;
+    } else if (key === 'hostname' && matched) {
+      return value.replace(/%h/g, alias);
+    }
+  }
+
+  return alias;
+}
+
+/**
+ * Checks whether a host alias matches a Host line's pattern list
+ * @param {string} alias - Host alias
+ * @param {string[]} patterns - Patterns from a Host line
+ * @returns {boolean} True if a pattern matches and no negated pattern matches
+ */
+function hostMatches(alias, patterns) {
+  const host = alias.toLowerCase();
+  let found = false;
+
+  for (const pattern of patterns) {
+    const negated = pattern.startsWith('!');
+    const glob = pattern.replace(/^!/, '').toLowerCase();
+    const regex = new RegExp(
+      `^${glob.replace(/[.+^${}()|[\]\\]/g, '\\$&').replace(/\*/g, '.*').replace(/\?/g, '.')}$`
+    );
+
+    if (regex.test(host)) {
+      if (negated) {
+        return false;
+      }
+      found = true;
+    }
+  }
+
+  return found;
+}
@@ -1,104 +1,415 @@
+import { resolveSshHost } from './ssh.mjs';
+
+/**
+ * Detects the hosting provider from a remote hostname
+ * Self-hosted instances are recognized when the provider name appears in the hostname (e.g. gitlab.example.com)
+ * @param {string} hostname - Remote hostname
+ * @returns {'github'|'gitlab'|'bitbucket'|null} Provider, or null if unrecognized
+ */
+export function detectProvider(hostname) {
+  const host = hostname.toLowerCase();
+  if (['github.com', 'ssh.github.com', 'www.github.com'].includes(host)) {
+    return 'github';
+  }
+  if (host.includes('gitlab')) {
+    return 'gitlab';
+  }
+  if (host.includes('bitbucket')) {
+    return 'bitbucket';
+  }
+  return null;
+}
+
 /**
  * Parses a GitHub remote URL to extract owner and repository name
- * Supports both SSH and HTTPS formats
+ * Supports SSH (git@github.com:owner/repo.git), SSH URL (ssh://git@github.com:2222/owner/repo.git,
+ * user and port optional) and HTTP(S) formats (with or without credentials). GitLab and Bitbucket are supported
+ * with similar formats; SSH and HTTP(S) remotes are accepted for any host, including self-hosted ones and
+ * ~/.ssh/config aliases such as git@github-work:owner/repo.git. A trailing slash is ignored, also after .git.
  * @param {string} remoteUrl - Git remote URL
- * @returns {{owner: string, repo: string} | null} Parsed owner and repo, or null if not a GitHub URL
+ * @param {object} [options={}] - Parse options
+ * @param {boolean} [options.resolveSshAliases=false] - Resolve unrecognized SSH hosts through ~/.ssh/config HostName entries
+ * @returns {{owner: string, repo: string, hostname: string, port?: string, host?: string} | null} Parsed remote
+ *   (host is the provider for non-GitHub remotes: 'gitlab', 'bitbucket' or 'unknown'), or null if not parseable
  */
-export function parseGitHubUrl(remoteUrl) {
+export function parseGitHubUrl(remoteUrl, options = {}) {
+  const { resolveSshAliases = false } = options;
+
   if (typeof remoteUrl !== 'string' || !remoteUrl) {
     return null;
   }
 
   // SSH format: git@github.com:owner/repo.git
-  const sshPattern = /^git@github\.com:([^/]+)\/(.+?)(\.git)?$/;
+  const sshPattern = /^git@(github\.com|gitlab\.com|bitbucket\.org):([^/]+)\/(.+?)(\.git)?\/?$/;
   const sshMatch = remoteUrl.match(sshPattern);
 
   if (sshMatch) {
-    return {
-      owner: sshMatch[1],
-      repo: sshMatch[2]
-    };
+    return remoteInfo(sshMatch[1], sshMatch[2], sshMatch[3]);
   }
 
-  // HTTPS format: https://github.com/owner/repo.git
-  const httpsPattern = /^https:\/\/github\.com\/([^/]+)\/(.+?)(\.git)?$/;
+  // HTTP(S) format for any host: http[s]://[user@]host[:port]/owner/repo.git
+  const httpsPattern = /^https?:\/\/(?:[^@/]+@)?([^:/]+)(?::(\d+))?\/([^/]+)\/(.+?)(\.git)?\/?$/;
   const httpsMatch = remoteUrl.match(httpsPattern);
 
   if (httpsMatch) {
-    return {
-      owner: httpsMatch[1],
-      repo: httpsMatch[2]
-    };
+    return remoteInfo(httpsMatch[1], httpsMatch[3], httpsMatch[4], { port: httpsMatch[2] });
   }
 
-  // Also support GitLab and Bitbucket patterns
-  // GitLab SSH: git@gitlab.com:owner/repo.git
-  const gitlabSshPattern = /^git@gitlab\.com:([^/]+)\/(.+?)(\.git)?$/;
-  const gitlabSshMatch = remoteUrl.match(gitlabSshPattern);
+  // SSH URL format: ssh://[user@]host[:port]/owner/repo.git
+  const sshUrlPattern = /^ssh:\/\/(?:[^@/]+@)?([^:/]+)(?::(\d+))?\/([^/]+)\/(.+?)(\.git)?\/?$/;
+  const sshUrlMatch = remoteUrl.match(sshUrlPattern);
 
-  if (gitlabSshMatch) {
-    return {
-      owner: gitlabSshMatch[1],
-      repo: gitlabSshMatch[2],
-      host: 'gitlab'
-    };
+  if (sshUrlMatch) {
+    return remoteInfo(sshUrlMatch[1], sshUrlMatch[3], sshUrlMatch[4], {
+      port: sshUrlMatch[2],
+      resolveSshAliases
+    });
   }
 
-  // GitLab HTTPS: https://gitlab.com/owner/repo.git
-  const gitlabHttpsPattern = /^https:\/\/gitlab\.com\/([^/]+)\/(.+?)(\.git)?$/;
-  const gitlabHttpsMatch = remoteUrl.match(gitlabHttpsPattern);
+  // SSH format for any host or alias: [user@]host:owner/repo.git
+  const scpPattern = /^(?:[^@/]+@)?([^:/]+):([^/]+)\/(.+?)(\.git)?\/?$/;
+  const scpMatch = remoteUrl.match(scpPattern);
 
-  if (gitlabHttpsMatch) {
-    return {
-      owner: gitlabHttpsMatch[1],
-      repo: gitlabHttpsMatch[2],
-      host: 'gitlab'
-    };
+  if (scpMatch) {
+    return remoteInfo(scpMatch[1], scpMatch[2], scpMatch[3], { resolveSshAliases });
   }
 
-  // Bitbucket SSH: git@bitbucket.org:owner/repo.git
-  const bitbucketSshPattern = /^git@bitbucket\.org:([^/]+)\/(.+?)(\.git)?$/;
-  const bitbucketSshMatch = remoteUrl.match(bitbucketSshPattern);
+  return null;
+}
+
+/**
+ * Builds the parseGitHubUrl result for a matched remote
+ * @param {string} hostname - Remote hostname or SSH alias
+ * @param {string} owner - Repository owner
+ * @param {string} repo - Repository name
+ * @param {object} [options={}] - Options
+ * @param {string} [options.port] - Port from an ssh:// or http(s):// URL
+ * @param {boolean} [options.resolveSshAliases=false] - Resolve an unrecognized hostname through ~/.ssh/config
+ * @returns {object} Remote info
+ */
+function remoteInfo(hostname, owner, repo, options = {}) {
+  const { port, resolveSshAliases = false } = options;
 
-  if (bitbucketSshMatch) {
-    return {
-      owner: bitbucketSshMatch[1],
-      repo: bitbucketSshMatch[2],
-      host: 'bitbucket'
-    };
+  if (resolveSshAliases && detectProvider(hostname) === null) {
+    hostname = resolveSshHost(hostname);
   }
 
-  // Bitbucket HTTPS: https://bitbucket.org/owner/repo.git
-  const bitbucketHttpsPattern = /^https:\/\/bitbucket\.org\/([^/]+)\/(.+?)(\.git)?$/;
-  const bitbucketHttpsMatch = remoteUrl.match(bitbucketHttpsPattern);
+  const info = { owner, repo, hostname };
 
-  if (bitbucketHttpsMatch) {
-    return {
-      owner: bitbucketHttpsMatch[1],
-      repo: bitbucketHttpsMatch[2],
-      host: 'bitbucket'
-    };
+  if (port) {
+    info.port = port;
   }
 
-  return null;
+  // GitHub remotes carry no host key, matching the original format
+  const provider = detectProvider(hostname);
+  if (provider !== 'github') {
+    info.host = provider || 'unknown';
+  }
+
+  return info;
+}
+
+/**
+ * Normalizes a remote URL into a canonical https://host/owner/repo form
+ * Any form accepted by parseGitHubUrl (scp-like SSH, ssh://, HTTP(S) with or without credentials, with or without
+ * .git and a trailing slash) maps to the same string, which makes the result usable as a cache key. The hostname
+ * is lowercased, GitHub host variants (ssh.github.com, www.github.com) collapse to github.com, and ports and
+ * credentials are dropped.
+ * @param {string} remoteUrl - Git remote URL
+ * @returns {string} Canonical HTTPS repository URL
+ * @throws {TypeError} If the remote URL cannot be parsed
+ */
+export function normalizeRemoteUrl(remoteUrl) {
+  const parsed = parseGitHubUrl(remoteUrl);
+  if (!parsed) {
+    throw new TypeError(`Unsupported remote URL: ${remoteUrl}`);
+  }
+
+  const hostname = parsed.host ? parsed.hostname.toLowerCase() : 'github.com';
+  return `https://${hostname}/${parsed.owner}/${parsed.repo}`;
+}
+
+/**
+ * https://github.com/<owner>/<repo>/<blob|tree>/<ref and path>[?query][#fragment]
+ */
+export const GITHUB_LINK_PATTERN = /^https?:\/\/(?:www\.)?github\.com\/([^/]+)\/([^/]+)\/(blob|tree)\/([^?#]+)(\?[^#]*)?(#.*)?$/;
+
+/**
+ * GitHub line anchors: #L10, #L10-L25, with optional column suffixes (#L10C5-L25C3)
+ */
+const LINE_ANCHOR_PATTERN = /^#L(\d+)(?:C\d+)?(?:-L(\d+)(?:C\d+)?)?$/;
+
+/**
+ * Decomposes a GitHub blob or tree URL into its components
+ * The first segment after blob/ or tree/ is taken as the ref. Branch names containing slashes
+ * cannot be told apart from the path without a repository; use canonicalizeGitHubUrl to resolve them.
+ * @param {string} url - GitHub blob or tree URL
+ * @returns {{owner: string, repo: string, kind: 'blob'|'tree', ref: string, path: string, lineRange: {start: number, end: number} | null} | null} Parsed link, or null if not a GitHub blob or tree URL
+ */
+export function parseGitHubLink(url) {
+  if (typeof url !== 'string') {
+    return null;
+  }
+
+  const match = url.match(GITHUB_LINK_PATTERN);
+  if (!match) {
+    return null;
+  }
+
+  const [, owner, repo, kind, refAndPath, , fragment] = match;
+  const [ref, ...pathSegments] = refAndPath.replace(/^\/+|\/+$/g, '').split('/');
+
+  let lineRange = null;
+  const anchor = fragment ? fragment.match(LINE_ANCHOR_PATTERN) : null;
+  if (anchor) {
+    const start = parseInt(anchor[1], 10);
+    const end = anchor[2] ? parseInt(anchor[2], 10) : start;
+    lineRange = { start, end };
+  }
+
+  return {
+    owner,
+    repo,
+    kind,
+    ref: decodeURIComponent(ref),
+    path: decodeURIComponent(pathSegments.join('/')),
+    lineRange
+  };
+}
+
+/**
+ * Percent-encodes each segment of a repository path for use in a URL
+ * Spaces, '#', '?', '%' and non-ASCII characters are encoded while '/' separators are preserved
+ * @param {string} filePath - POSIX path relative to the repository root
+ * @returns {string} URL-safe path
+ */
+export function encodeUrlPath(filePath) {
+  return filePath
+    .split('/')
+    .map((segment) => encodeURIComponent(segment)
+      // Keep RFC 3986 sub-delims, ':' and '@' readable - they are valid in path segments
+      .replace(/%(24|26|2B|2C|3B|3D|3A|40)/gi, (match) => decodeURIComponent(match)))
+    .join('/');
+}
+
+/**
+ * Markup formats GitHub renders instead of showing source lines
+ */
+export const RENDERED_EXTENSIONS = new Set([
+  '.md', '.markdown', '.mdown', '.mkdn', '.mkd',
+  '.adoc', '.asciidoc', '.asc',
+  '.rst', '.org', '.textile', '.rdoc',
+  '.creole', '.mediawiki', '.wiki', '.pod'
+]);
+
+/**
+ * Checks if GitHub renders a file as markup rather than showing its source
+ * Line anchors on rendered files are lost unless the URL requests ?plain=1
+ * @param {string} filePath - File path
+ * @returns {boolean} True if the file extension is a rendered markup format
+ */
+export function isRenderedFile(filePath) {
+  const dotIndex = filePath.lastIndexOf('.');
+  if (dotIndex === -1) {
+    return false;
+  }
+  return RENDERED_EXTENSIONS.has(filePath.slice(dotIndex).toLowerCase());
+}
+
+/**
+ * Builds a line or line-range anchor in the syntax of a hosting provider
+ * @param {number} lineStart - First line number (1-based)
+ * @param {number} [lineEnd] - Last line number (inclusive), omit for a single line
+ * @param {'github'|'gitlab'|'bitbucket'} [provider='github'] - Hosting provider
+ * @returns {string} URL fragment including the leading '#'
+ * @throws {TypeError} If the line range or provider is invalid
+ */
+export function buildLineAnchor(lineStart, lineEnd, provider = 'github') {
+  if (!Number.isInteger(lineStart) || lineStart < 1) {
+    throw new TypeError(`Invalid line number: ${lineStart}. Must be an integer >= 1`);
+  }
+
+  if (lineEnd !== undefined && lineEnd !== null && (!Number.isInteger(lineEnd) || lineEnd < lineStart)) {
+    throw new TypeError(`Invalid line range: ${lineStart}-${lineEnd}. End must be >= start`);
+  }
+
+  // A range ending on its first line is a single-line anchor
+  const isRange = lineEnd !== undefined && lineEnd !== null && lineEnd !== lineStart;
+
+  switch (provider) {
+    case 'github':
+      return isRange ? `#L${lineStart}-L${lineEnd}` : `#L${lineStart}`;
+    case 'gitlab':
+      return isRange ? `#L${lineStart}-${lineEnd}` : `#L${lineStart}`;
+    case 'bitbucket':
+      return isRange ? `#lines-${lineStart}:${lineEnd}` : `#lines-${lineStart}`;
+    default:
+      throw new TypeError(`Invalid provider: ${provider}. Must be 'github', 'gitlab' or 'bitbucket'`);
+  }
 }
 
 /**
- * Builds a GitHub permalink URL for a file at a specific commit
+ * Builds a GitHub permalink URL for a file or directory at a specific commit
+ * Files are linked with /blob/ and directories with /tree/
+ * Rendered markup files get ?plain=1 when a line anchor is requested
  * @param {string} owner - Repository owner
  * @param {string} repo - Repository name
- * @param {string} commitHash - Commit hash
- * @param {string} filePath - File path (POSIX format)
+ * @param {string} commitHash - Commit hash (or branch/tag name for non-pinned links)
+ * @param {string} filePath - File or directory path (POSIX format, empty for repository root)
+ * @param {object} [options={}] - URL options
+ * @param {boolean} [options.isDirectory=false] - Whether the path is a directory
+ * @param {number} [options.lineStart] - First line to anchor (files only)
+ * @param {number} [options.lineEnd] - Last line of the anchored range
+ * @param {boolean} [options.plain] - Force (true) or suppress (false) ?plain=1; added for rendered files with a line anchor when omitted
  * @returns {string} GitHub permalink URL
  */
-export function buildGitHubUrl(owner, repo, commitHash, filePath) {
-  if (!owner || !repo || !commitHash || !filePath) {
+export function buildGitHubUrl(owner, repo, commitHash, filePath, options = {}) {
+  const { isDirectory = false, lineStart, lineEnd } = options;
+  let { plain } = options;
+
+  if (!owner || !repo || !commitHash || (!filePath && !isDirectory)) {
     throw new TypeError('All parameters (owner, repo, commitHash, filePath) are required');
   }
 
   // Ensure file path starts without leading slash
-  const normalizedPath = filePath.replace(/^\/+/, '');
+  const normalizedPath = (filePath || '').replace(/^\/+/, '');
+
+  // Build permalink: https://github.com/owner/repo/{blob|tree}/commitHash/path
+  const kind = isDirectory ? 'tree' : 'blob';
+  let url = `https://github.com/${owner}/${repo}/${kind}/${encodeUrlPath(commitHash)}`;
+
+  // Repository root directory has no path component
+  if (normalizedPath) {
+    url += `/${encodeUrlPath(normalizedPath)}`;
+  }
+
+  const hasAnchor = lineStart !== undefined && lineStart !== null && !isDirectory;
+
+  if (plain === undefined || plain === null) {
+    plain = hasAnchor && isRenderedFile(normalizedPath);
+  }
+
+  // Query string must precede the fragment
+  if (plain && !isDirectory) {
+    url += '?plain=1';
+  }
+
+  // Line anchors only apply to files
+  if (hasAnchor) {
+    url += buildLineAnchor(lineStart, lineEnd, 'github');
+  }
+
+  return url;
+}
+
+/**
+ * Builds a GitHub REST API contents URL for a file or directory
+ * @param {string} owner - Repository owner
+ * @param {string} repo - Repository name
+ * @param {string} filePath - File or directory path relative to repository root
+ * @param {string} [ref] - Commit SHA, branch or tag name (default: repository default branch)
+ * @returns {string} GitHub REST API contents URL
+ */
+export function buildGitHubApiContentsUrl(owner, repo, filePath, ref) {
+  if (!owner || !repo) {
+    throw new TypeError('owner and repo are required');
+  }
+
+  const normalizedPath = (filePath || '').replace(/^\/+|\/+$/g, '').replace(/^\.$/, '');
+
+  let url = `https://api.github.com/repos/${owner}/${repo}/contents`;
+  if (normalizedPath) {
+    url += `/${encodeUrlPath(normalizedPath)}`;
+  }
+  if (ref) {
+    url += `?ref=${encodeURIComponent(ref).replace(/%2F/g, '/')}`;
+  }
+  return url;
+}
+
+/**
+ * Builds a GitHub pull request URL
+ * @param {string} owner - Repository owner
+ * @param {string} repo - Repository name
+ * @param {number} number - Pull request number
+ * @returns {string} GitHub pull request URL
+ */
+export function buildGitHubPullRequestUrl(owner, repo, number) {
+  if (!owner || !repo || !number) {
+    throw new TypeError('All parameters (owner, repo, number) are required');
+  }
+
+  return `https://github.com/${owner}/${repo}/pull/${number}`;
+}
+
+/**
+ * Builds a GitHub compare URL between two revisions
+ * @param {string} owner - Repository owner
+ * @param {string} repo - Repository name
+ * @param {string} base - Base commit SHA (or branch/tag name)
+ * @param {string} head - Head commit SHA (or branch/tag name)
+ * @returns {string} GitHub compare URL
+ */
+export function buildGitHubCompareUrl(owner, repo, base, head) {
+  if (!owner || !repo || !base || !head) {
+    throw new TypeError('All parameters (owner, repo, base, head) are required');
+  }
+
+  return `https://github.com/${owner}/${repo}/compare/${encodeUrlPath(base)}...${encodeUrlPath(head)}`;
+}
+
+/**
+ * GitHub URL serving the content behind a Git LFS pointer
+ */
+export const LFS_MEDIA_URL_TEMPLATE = 'https://media.githubusercontent.com/media/{owner}/{repo}/{ref}/{path}';
+
+/**
+ * Builds the GitHub URL serving the real content of a Git LFS file
+ * GitHub's raw URLs return the pointer file for LFS-tracked paths; the media host resolves the pointer and serves
+ * the content.
+ * @param {string} owner - Repository owner
+ * @param {string} repo - Repository name
+ * @param {string} ref - Commit SHA (or branch/tag name)
+ * @param {string} filePath - File path relative to repository root
+ * @returns {string} GitHub LFS media URL
+ * @throws {TypeError} If a parameter is missing
+ */
+export function buildGitHubLfsMediaUrl(owner, repo, ref, filePath) {
+  if (!owner || !repo || !ref || !filePath) {
+    throw new TypeError('All parameters (owner, repo, ref, filePath) are required');
+  }
+
+  const fields = {
+    owner,
+    repo,
+    ref: encodeUrlPath(ref),
+    path: encodeUrlPath(filePath.replace(/^\/+/, ''))
+  };
+  return LFS_MEDIA_URL_TEMPLATE.replace(/\{(\w+)\}/g, (_, name) => fields[name]);
+}
+
+/**
+ * Source archive formats served by GitHub
+ */
+export const ARCHIVE_FORMATS = ['tar.gz', 'zip'];
+
+/**
+ * Builds a GitHub source archive (tarball or zip) URL for a revision
+ * @param {string} owner - Repository owner
+ * @param {string} repo - Repository name
+ * @param {string} ref - Commit SHA (or branch/tag name)
+ * @param {'tar.gz'|'zip'} [archiveFormat='tar.gz'] - Archive format
+ * @returns {string} GitHub archive URL
+ * @throws {TypeError} If a parameter is missing or the archive format is invalid
+ */
+export function buildGitHubArchiveUrl(owner, repo, ref, archiveFormat = 'tar.gz') {
+  if (!owner || !repo || !ref) {
+    throw new TypeError('All parameters (owner, repo, ref) are required');
+  }
+
+  if (!ARCHIVE_FORMATS.includes(archiveFormat)) {
+    throw new TypeError(`Invalid archive format: ${archiveFormat}. Must be 'tar.gz' or 'zip'`);
+  }
 
-  // Build permalink: https://github.com/owner/repo/blob/commitHash/path
-  return `https://github.com/${owner}/${repo}/blob/${commitHash}/${normalizedPath}`;
+  return `https://github.com/${owner}/${repo}/archive/${encodeUrlPath(ref)}.${archiveFormat}`;
 }
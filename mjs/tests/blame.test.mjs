@@ -0,0 +1,82 @@
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import { blameFile, mapLine } from '../src/blame.mjs';
+import { commitFiles, createRepo, git, shallowClone } from './helpers.mjs';
+
+describe('mapLine', () => {
+  it('shifts past inserted lines', async () => {
+    const repo = createRepo();
+    const first = commitFiles(repo, { 'app.js': 'a\nb\nc\n' });
+    const second = commitFiles(repo, { 'app.js': 'new\na\nb\nc\n' });
+
+    assert.deepEqual(
+      await mapLine('app.js', 3, first, { repoPath: repo }),
+      { path: 'app.js', line: 4, commit: second }
+    );
+  });
+
+  it('maps a changed line to null', async () => {
+    const repo = createRepo();
+    const first = commitFiles(repo, { 'app.js': 'a\nb\nc\n' });
+    commitFiles(repo, { 'app.js': 'a\nB\nc\n' });
+
+    assert.equal(await mapLine('app.js', 2, first, { repoPath: repo }), null);
+  });
+
+  it('follows a rename', async () => {
+    const repo = createRepo();
+    const first = commitFiles(repo, { 'old.js': 'a\nb\nc\nd\ne\n' });
+    git(repo, 'mv', 'old.js', 'new.js');
+    commitFiles(repo, {});
+
+    const mapping = await mapLine('old.js', 2, first, { repoPath: repo });
+    assert.deepEqual([mapping.path, mapping.line], ['new.js', 2]);
+  });
+
+  for (const content of ['a\nb\nc\n', 'a\nb\nc']) {
+    for (const changed of [false, true]) {
+      it(`rejects a line past the end (${JSON.stringify(content)}, changed: ${changed})`, async () => {
+        const repo = createRepo();
+        const first = commitFiles(repo, { 'app.js': content });
+        if (changed) {
+          commitFiles(repo, { 'app.js': `new\n${content}` });
+        }
+
+        assert.notEqual(await mapLine('app.js', 3, first, { repoPath: repo }), null);
+        await assert.rejects(
+          mapLine('app.js', 4, first, { repoPath: repo }),
+          { name: 'TypeError', message: /has 3 lines/ }
+        );
+      });
+    }
+  }
+
+  it('rejects any line of an empty file', async () => {
+    const repo = createRepo();
+    const first = commitFiles(repo, { 'empty.txt': '' });
+
+    await assert.rejects(mapLine('empty.txt', 1, first, { repoPath: repo }), { message: /has 0 lines/ });
+  });
+
+  it('throws for a file missing at fromCommit', async () => {
+    const repo = createRepo();
+    const first = commitFiles(repo, { 'app.js': 'a\n' });
+
+    await assert.rejects(mapLine('other.js', 1, first, { repoPath: repo }), { name: 'FileNotFoundError' });
+  });
+});
+
+describe('blameFile', () => {
+  it('tells the shallow boundary from a root commit', async () => {
+    const repo = createRepo();
+    const root = commitFiles(repo, { 'app.js': 'a\n' });
+    const head = commitFiles(repo, { 'app.js': 'a\nb\n' });
+    const clone = shallowClone(repo);
+
+    const flags = async (repoPath) => (await blameFile('app.js', { repoPath }))
+      .map((hunk) => [hunk.oid, hunk.boundary, hunk.shallowBoundary]);
+
+    assert.deepEqual(await flags(clone), [[head, true, true]]);
+    assert.deepEqual(await flags(repo), [[root, true, false], [head, false, false]]);
+  });
+});
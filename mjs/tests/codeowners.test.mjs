@@ -0,0 +1,78 @@
+import assert from 'node:assert/strict';
+import fs from 'fs';
+import path from 'path';
+import { describe, it } from 'node:test';
+import { getCodeOwners, parseCodeowners } from '../src/codeowners.mjs';
+import { commitFiles, createRepo } from './helpers.mjs';
+
+/**
+ * Pattern, path and whether the pattern matches it
+ */
+const MATCHES = [
+  ['apps/', 'apps', true],
+  ['apps/', 'apps/web/index.js', true],
+  ['apps/', 'lib/apps/index.js', true],
+  ['apps/', 'apps.js', false],
+  ['/apps/', 'lib/apps', false],
+  ['docs/*', 'docs/readme.md', true],
+  ['docs/*', 'docs/guide/intro.md', false],
+  ['*.js', 'src/app.js', true],
+  ['*.js', 'src/app.jsx', false],
+  ['/build/logs', 'build/logs/out.txt', true],
+  ['/build/logs', 'src/build/logs', false],
+  ['docs/**/*.md', 'docs/a/b/c.md', true],
+  ['docs/**/*.md', 'docs/c.md', true],
+  ['a?c', 'abc', true],
+  ['a?c', 'a/c', false]
+];
+
+describe('CODEOWNERS patterns', () => {
+  const repo = createRepo();
+
+  for (const [pattern, filePath, expected] of MATCHES) {
+    it(`${pattern} ${expected ? 'matches' : 'does not match'} ${filePath}`, async () => {
+      fs.writeFileSync(path.join(repo, 'CODEOWNERS'), `${pattern} @owner\n`);
+
+      const { rule } = await getCodeOwners(filePath, { repoPath: repo, commit: null });
+
+      assert.equal(rule !== null, expected);
+    });
+  }
+});
+
+describe('parseCodeowners', () => {
+  it('skips comments', () => {
+    assert.deepEqual(parseCodeowners('# Owners\n\n*.js @org/web # web team\n\\#notes @a\n/docs/\n'), [
+      { pattern: '*.js', owners: ['@org/web'], line: 3 },
+      { pattern: '#notes', owners: ['@a'], line: 4 },
+      { pattern: '/docs/', owners: [], line: 5 }
+    ]);
+  });
+});
+
+describe('getCodeOwners', () => {
+  it('lets the last matching rule decide', async () => {
+    const repo = createRepo();
+    commitFiles(repo, {
+      '.github/CODEOWNERS': '* @org/all\napps/ @org/apps\n/apps/legacy/\n',
+      CODEOWNERS: '* @ignored\n',
+      'apps/web/index.js': ''
+    });
+    const options = { repoPath: repo };
+
+    const owners = await getCodeOwners('apps/web/index.js', options);
+    assert.deepEqual([owners.owners, owners.rule.line, owners.source], [['@org/apps'], 2, '.github/CODEOWNERS']);
+    assert.deepEqual((await getCodeOwners('apps', options)).owners, ['@org/apps']);
+    assert.deepEqual((await getCodeOwners('apps/legacy/old.js', options)).owners, []);
+  });
+
+  it('reads the working tree and returns null without a file', async () => {
+    const repo = createRepo();
+    commitFiles(repo, { 'README.md': 'readme\n' });
+
+    assert.equal(await getCodeOwners('README.md', { repoPath: repo }), null);
+
+    fs.writeFileSync(path.join(repo, 'CODEOWNERS'), '*.md @docs\n');
+    assert.deepEqual((await getCodeOwners('README.md', { repoPath: repo, commit: null })).owners, ['@docs']);
+  });
+});
@@ -0,0 +1,21 @@
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import { formatOrgLink } from '../src/utils/format.mjs';
+
+describe('formatOrgLink', () => {
+  it('protects brackets in the target and description', () => {
+    const url = 'https://github.com/owner/repo/blob/abc1234/routes/[[id]].tsx';
+
+    assert.equal(
+      formatOrgLink(url, 'routes/[[id]].tsx'),
+      '[[https://github.com/owner/repo/blob/abc1234/routes/%5B%5Bid%5D%5D.tsx][routes/{{id}}.tsx]]'
+    );
+  });
+
+  it('protects brackets in the default description', () => {
+    const link = formatOrgLink('https://github.com/owner/repo/blob/abc1234/docs/a]]b.md');
+
+    assert.ok(link.endsWith('[docs/a}}b.md@abc1234]]'));
+    assert.equal(link.split(']]').length, 2);
+  });
+});
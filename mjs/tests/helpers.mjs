@@ -0,0 +1,114 @@
+import { execFileSync } from 'child_process';
+import fs from 'fs';
+import os from 'os';
+import path from 'path';
+
+/**
+ * Fixed identity and dates so commit SHAs do not depend on the machine
+ */
+const GIT_ENV = {
+  GIT_AUTHOR_NAME: 'Test Author',
+  GIT_AUTHOR_EMAIL: 'author@example.com',
+  GIT_AUTHOR_DATE: '2024-01-15T10:30:00Z',
+  GIT_COMMITTER_NAME: 'Test Author',
+  GIT_COMMITTER_EMAIL: 'author@example.com',
+  GIT_COMMITTER_DATE: '2024-01-15T10:30:00Z',
+  GIT_CONFIG_NOSYSTEM: '1'
+};
+
+Object.assign(process.env, GIT_ENV);
+
+/**
+ * Runs a Git command in a repository
+ * @param {string} repo - Repository path
+ * @param {...string} args - Git arguments
+ * @returns {string} Trimmed stdout
+ */
+export function git(repo, ...args) {
+  return execFileSync('git', args, { cwd: repo, encoding: 'utf8', env: { ...process.env, HOME: repo } }).trim();
+}
+
+/**
+ * Temporary directories to remove when the process exits
+ */
+const tempDirs = [];
+
+process.on('exit', () => {
+  for (const dir of tempDirs) {
+    fs.rmSync(dir, { recursive: true, force: true });
+  }
+});
+
+/**
+ * Creates a temporary directory removed when the process exits
+ * @returns {string} Directory path
+ */
+export function makeTempDir() {
+  const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'git-identify-'));
+  tempDirs.push(dir);
+  return dir;
+}
+
+/**
+ * Creates an empty repository on branch main with a GitHub origin
+ * @returns {string} Repository path
+ */
+export function createRepo() {
+  const repo = path.join(makeTempDir(), 'repo');
+  fs.mkdirSync(repo);
+  git(repo, 'init', '-q', '-b', 'main');
+  git(repo, 'remote', 'add', 'origin', 'https://github.com/owner/repo.git');
+  return repo;
+}
+
+/**
+ * Writes a file in a repository, creating parent directories
+ * @param {string} repo - Repository path
+ * @param {string} filePath - Path relative to the repository
+ * @param {string} content - File content
+ */
+export function write(repo, filePath, content) {
+  const target = path.join(repo, filePath);
+  fs.mkdirSync(path.dirname(target), { recursive: true });
+  fs.writeFileSync(target, content);
+}
+
+/**
+ * Writes files, commits them all and returns the new commit SHA
+ * @param {string} repo - Repository path
+ * @param {object} files - Map of path to content
+ * @param {string} [message='Update'] - Commit message
+ * @returns {string} Commit SHA
+ */
+export function commitFiles(repo, files, message = 'Update') {
+  for (const [filePath, content] of Object.entries(files)) {
+    write(repo, filePath, content);
+  }
+  git(repo, 'add', '-A');
+  git(repo, 'commit', '-q', '-m', message);
+  return git(repo, 'rev-parse', 'HEAD');
+}
+
+/**
+ * Clones a repository with only its newest commit
+ * @param {string} source - Repository path
+ * @returns {string} Clone path
+ */
+export function shallowClone(source) {
+  const target = path.join(makeTempDir(), 'clone');
+  git(path.dirname(source), 'clone', '-q', '--depth=1', `file://${source}`, target);
+  return target;
+}
+
+/**
+ * Collects an async iterable into an array
+ * @param {AsyncIterable} iterable - Iterable
+ * @returns {Promise<Array>} Items
+ */
+export async function collect(iterable) {
+  const items = [];
+  for await (const item of iterable) {
+    items.push(item);
+  }
+  return items;
+}
@@ -0,0 +1,55 @@
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import { countCommits, getCommitInfo, getFileHistory } from '../src/history.mjs';
+import { collect, commitFiles, createRepo, shallowClone, write } from './helpers.mjs';
+
+describe('getFileHistory', () => {
+  it('ends at the root commit', async () => {
+    const repo = createRepo();
+    const first = commitFiles(repo, { 'app.js': 'one\n' });
+    const second = commitFiles(repo, { 'app.js': 'two\n' });
+
+    const history = await collect(getFileHistory('app.js', { repoPath: repo }));
+
+    assert.deepEqual(history.map((info) => [info.oid, info.parents, info.shallowBoundary]), [
+      [second, [first], false],
+      [first, [], false]
+    ]);
+  });
+
+  it('flags the shallow boundary', async () => {
+    const repo = createRepo();
+    commitFiles(repo, { 'app.js': 'one\n' });
+    const head = commitFiles(repo, { 'app.js': 'two\n' });
+    const clone = shallowClone(repo);
+
+    const history = await collect(getFileHistory('app.js', { repoPath: clone }));
+
+    assert.deepEqual(history.map((info) => [info.oid, info.parents, info.shallowBoundary]), [[head, [], true]]);
+    assert.equal((await getCommitInfo(head, { repoPath: clone })).shallowBoundary, true);
+  });
+});
+
+describe('unborn HEAD', () => {
+  it('has no history', async () => {
+    const repo = createRepo();
+    write(repo, 'app.js', 'one\n');
+
+    assert.deepEqual(await collect(getFileHistory('app.js', { repoPath: repo })), []);
+    assert.equal(await countCommits('HEAD', { repoPath: repo }), 0);
+    assert.equal(await countCommits('HEAD', { repoPath: repo, pathFilter: 'app.js' }), 0);
+  });
+});
+
+describe('countCommits', () => {
+  it('counts a range and a path', async () => {
+    const repo = createRepo();
+    const first = commitFiles(repo, { 'app.js': 'one\n' });
+    commitFiles(repo, { 'other.js': 'one\n' });
+    commitFiles(repo, { 'app.js': 'two\n' });
+
+    assert.equal(await countCommits('HEAD', { repoPath: repo }), 3);
+    assert.equal(await countCommits('HEAD', { repoPath: repo, pathFilter: 'app.js' }), 2);
+    assert.equal(await countCommits(`${first}..HEAD`, { repoPath: repo }), 2);
+  });
+});
@@ -0,0 +1,215 @@
+import assert from 'node:assert/strict';
+import { createHash } from 'crypto';
+import fs from 'fs';
+import path from 'path';
+import { describe, it } from 'node:test';
+import { getFileHistory } from '../src/history.mjs';
+import { getLocalMetadata } from '../src/metadata/local.mjs';
+import {
+  canonicalizeGitHubUrl,
+  checkDrift,
+  findPullRequestForCommit,
+  generateGitHubUrl,
+  locateGitHubLink
+} from '../src/permalink.mjs';
+import { getFileStatus } from '../src/status.mjs';
+import { getBlobOid } from '../src/utils/git.mjs';
+import { collect, commitFiles, createRepo, git, makeTempDir } from './helpers.mjs';
+
+/**
+ * File names that shell quoting would mangle
+ */
+const SPECIAL_NAMES = ['routes/$id.tsx', 'routes/`id`.tsx', 'routes/q"uote.tsx', "routes/it's.tsx"];
+
+/**
+ * Encodes a path for a GitHub URL
+ * @param {string} filePath - Path relative to the repository
+ * @returns {string} URL path
+ */
+function encodePath(filePath) {
+  return filePath.split('/').map(encodeURIComponent).join('/');
+}
+
+describe('special characters in paths', () => {
+  for (const name of SPECIAL_NAMES) {
+    it(`handles ${name}`, async () => {
+      const repo = createRepo();
+      const commit = commitFiles(repo, { [name]: 'content\n' });
+      const options = { repoPath: repo };
+
+      assert.equal((await getFileStatus(name, options)).worktree, 'unmodified');
+      assert.notEqual(await getBlobOid(repo, name), null);
+      assert.equal(await checkDrift(name, options), 'identical');
+      assert.deepEqual((await collect(getFileHistory(name, options))).map((info) => info.oid), [commit]);
+      assert.equal((await getLocalMetadata(repo, name)).commitHash, commit);
+
+      const location = await locateGitHubLink(`https://github.com/owner/repo/blob/main/${encodePath(name)}`, options);
+      assert.equal(location.path, name);
+      assert.equal(location.status, 'identical');
+    });
+  }
+
+  it('does not run a link path through a shell', async () => {
+    const repo = createRepo();
+    commitFiles(repo, { 'README.md': 'readme\n' });
+    const marker = path.join(makeTempDir(), 'pwned');
+
+    const url = `https://github.com/owner/repo/blob/main/${encodeURIComponent(`$(touch ${marker})`)}`;
+    await assert.rejects(locateGitHubLink(url, { repoPath: repo }), { name: 'FileNotFoundError' });
+
+    assert.equal(fs.existsSync(marker), false);
+  });
+});
+
+describe('generateGitHubUrl', () => {
+  it('pins the URL to the HEAD commit', async () => {
+    const repo = createRepo();
+    const commit = commitFiles(repo, { 'src/app.js': 'console.log();\n' });
+
+    const url = await generateGitHubUrl('src/app.js', { repoPath: repo, lineStart: 1 });
+
+    assert.equal(url, `https://github.com/owner/repo/blob/${commit}/src/app.js#L1`);
+  });
+});
+
+describe('canonicalizeGitHubUrl', () => {
+  it('pins a branch containing a slash', async () => {
+    const repo = createRepo();
+    const commit = commitFiles(repo, { 'routes/$id.tsx': 'content\n' });
+    git(repo, 'branch', 'feature/x');
+
+    const url = 'https://github.com/owner/repo/blob/feature/x/routes/%24id.tsx?plain=1#L2';
+
+    assert.equal(
+      await canonicalizeGitHubUrl(url, { repoPath: repo }),
+      `https://github.com/owner/repo/blob/${commit}/routes/%24id.tsx?plain=1#L2`
+    );
+  });
+
+  for (const ref of ['main~1', 'main^', '@{-1}', '--all', 'HEAD:README.md']) {
+    it(`rejects revision syntax ${ref}`, async () => {
+      const repo = createRepo();
+      commitFiles(repo, { 'README.md': 'one\n' });
+      commitFiles(repo, { 'README.md': 'two\n' });
+
+      const url = `https://github.com/owner/repo/blob/${encodeURIComponent(ref)}/README.md`;
+      await assert.rejects(canonicalizeGitHubUrl(url, { repoPath: repo }), { code: 'REF_NOT_FOUND' });
+    });
+  }
+
+  it('does not run a ref through a shell', async () => {
+    const repo = createRepo();
+    commitFiles(repo, { 'README.md': 'readme\n' });
+    const marker = path.join(makeTempDir(), 'pwned');
+
+    const url = `https://github.com/owner/repo/blob/${encodeURIComponent(`$(touch ${marker})`)}/README.md`;
+    await assert.rejects(canonicalizeGitHubUrl(url, { repoPath: repo }), { code: 'REF_NOT_FOUND' });
+
+    assert.equal(fs.existsSync(marker), false);
+  });
+});
+
+describe('symbolic links', () => {
+  it('agrees with checkDrift', async () => {
+    const repo = createRepo();
+    fs.symlinkSync('target.txt', path.join(repo, 'link'));
+    commitFiles(repo, { 'target.txt': 'target\n', 'other.txt': 'other\n' });
+    const options = { repoPath: repo };
+    const url = 'https://github.com/owner/repo/blob/main/link';
+
+    let location = await locateGitHubLink(url, options);
+    assert.deepEqual([location.status, await checkDrift('link', options)], ['identical', 'identical']);
+    assert.equal(location.workingHash, location.blobHash);
+
+    fs.unlinkSync(path.join(repo, 'link'));
+    fs.symlinkSync('other.txt', path.join(repo, 'link'));
+
+    location = await locateGitHubLink(url, options);
+    assert.deepEqual([location.status, await checkDrift('link', options)], ['modified', 'modified']);
+    assert.equal(location.workingHash, createHash('sha1').update('blob 9\0other.txt').digest('hex'));
+  });
+});
+
+describe('unpushed policies', () => {
+  /**
+   * Creates a repository with a commit on origin/main followed by one that is not pushed
+   * @returns {{repo: string, pushed: string, local: string}} Repository and commit SHAs
+   */
+  function createUnpushedRepo() {
+    const repo = createRepo();
+    const pushed = commitFiles(repo, { 'app.js': 'one\n' });
+    git(repo, 'update-ref', 'refs/remotes/origin/main', pushed);
+    return { repo, pushed, local: commitFiles(repo, { 'app.js': 'two\n' }) };
+  }
+
+  it('warns and links the commit', async () => {
+    const { repo, local } = createUnpushedRepo();
+    const warnings = [];
+    const onWarning = (warning) => warnings.push(warning);
+    process.on('warning', onWarning);
+
+    try {
+      const url = await generateGitHubUrl('app.js', { repoPath: repo, unpushed: 'warn' });
+      await new Promise((resolve) => setImmediate(resolve));
+
+      assert.equal(url, `https://github.com/owner/repo/blob/${local}/app.js`);
+      assert.deepEqual(
+        warnings.map((warning) => [warning.code, warning.message]),
+        [['COMMIT_NOT_PUSHED', `Commit ${local} is not on remote 'origin'; the link 404s until it is pushed`]]
+      );
+    } finally {
+      process.off('warning', onWarning);
+    }
+  });
+
+  it('falls back to the nearest pushed ancestor', async () => {
+    const { repo, pushed } = createUnpushedRepo();
+
+    const url = await generateGitHubUrl('app.js', { repoPath: repo, unpushed: 'fallback' });
+
+    assert.equal(url, `https://github.com/owner/repo/blob/${pushed}/app.js`);
+  });
+
+  it('throws for error', async () => {
+    const { repo } = createUnpushedRepo();
+
+    await assert.rejects(
+      generateGitHubUrl('app.js', { repoPath: repo, unpushed: 'error' }),
+      { code: 'COMMIT_NOT_PUSHED' }
+    );
+  });
+});
+
+describe('findPullRequestForCommit', () => {
+  /**
+   * Creates a repository with a direct commit on main, then a branch commit merged as pull request #5
+   * @returns {{repo: string, direct: string, branch: string}} Repository and commit SHAs
+   */
+  function createMergedRepo() {
+    const repo = createRepo();
+    commitFiles(repo, { 'README.md': 'readme\n' });
+    const direct = commitFiles(repo, { 'main.js': 'direct\n' }, 'Direct commit');
+    git(repo, 'checkout', '-q', '-b', 'feature');
+    const branch = commitFiles(repo, { 'feature.js': 'feature\n' }, 'Add feature');
+    git(repo, 'checkout', '-q', 'main');
+    git(repo, 'merge', '-q', '--no-ff', '-m', 'Merge pull request #5 from owner/feature', 'feature');
+    return { repo, direct, branch };
+  }
+
+  it('credits the merge that brought a branch commit in', async () => {
+    const { repo, branch } = createMergedRepo();
+
+    const pr = await findPullRequestForCommit(branch, { repoPath: repo });
+
+    assert.deepEqual(
+      [pr.number, pr.source, pr.url],
+      [5, 'merge', 'https://github.com/owner/repo/pull/5']
+    );
+  });
+
+  it('does not credit a later merge for a direct commit', async () => {
+    const { repo, direct } = createMergedRepo();
+
+    assert.equal(await findPullRequestForCommit(direct, { repoPath: repo }), null);
+  });
+});
@@ -0,0 +1,19 @@
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import { createProvenanceRecord } from '../src/provenance.mjs';
+import { commitFiles, createRepo, git } from './helpers.mjs';
+
+describe('createProvenanceRecord', () => {
+  it('fingerprints the history of the recorded commit', async () => {
+    const repo = createRepo();
+    const root = commitFiles(repo, { 'app.js': 'one\n' });
+    git(repo, 'checkout', '-q', '--orphan', 'other');
+    const other = commitFiles(repo, { 'app.js': 'other\n' });
+    git(repo, 'checkout', '-q', 'main');
+
+    assert.equal((await createProvenanceRecord('app.js', { repoPath: repo })).repoFingerprint, root);
+
+    const record = await createProvenanceRecord('app.js', { commit: other, repoPath: repo });
+    assert.deepEqual([record.commit, record.repoFingerprint, record.dirty], [other, other, true]);
+  });
+});
@@ -0,0 +1,58 @@
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import { getHeadInfo, getRepoFingerprint } from '../src/refs.mjs';
+import { commitFiles, createRepo, git } from './helpers.mjs';
+
+describe('getHeadInfo', () => {
+  it('reports the branch, commit and upstream', async () => {
+    const repo = createRepo();
+    const commit = commitFiles(repo, { 'app.js': 'one\n' });
+    git(repo, 'update-ref', 'refs/remotes/origin/main', commit);
+    git(repo, 'branch', '--set-upstream-to=origin/main');
+
+    assert.deepEqual(
+      await getHeadInfo({ repoPath: repo }),
+      { branch: 'main', oid: commit, detached: false, upstream: 'origin/main' }
+    );
+  });
+
+  it('reports a detached HEAD', async () => {
+    const repo = createRepo();
+    const commit = commitFiles(repo, { 'app.js': 'one\n' });
+    git(repo, 'checkout', '-q', '--detach');
+
+    assert.deepEqual(
+      await getHeadInfo({ repoPath: repo }),
+      { branch: null, oid: commit, detached: true, upstream: null }
+    );
+  });
+
+  it('reports the branch of an unborn HEAD', async () => {
+    const repo = createRepo();
+
+    assert.deepEqual(
+      await getHeadInfo({ repoPath: repo }),
+      { branch: 'main', oid: null, detached: false, upstream: null }
+    );
+  });
+});
+
+describe('getRepoFingerprint', () => {
+  it('picks the lowest of several roots', async () => {
+    const repo = createRepo();
+    const first = commitFiles(repo, { 'app.js': 'one\n' });
+    git(repo, 'checkout', '-q', '--orphan', 'other');
+    git(repo, 'rm', '-q', '-r', '--cached', '.');
+    const second = commitFiles(repo, { 'lib.js': 'two\n' });
+    git(repo, 'checkout', '-q', 'main');
+    git(repo, 'merge', '-q', '--allow-unrelated-histories', '-m', 'Merge', 'other');
+
+    // Both roots share the fixture's commit date, so only the SHA can order them
+    assert.equal(await getRepoFingerprint({ repoPath: repo }), [first, second].sort()[0]);
+    assert.equal(await getRepoFingerprint({ repoPath: repo, rev: second }), second);
+  });
+
+  it('is null for an unborn HEAD', async () => {
+    assert.equal(await getRepoFingerprint({ repoPath: createRepo() }), null);
+  });
+});
@@ -0,0 +1,120 @@
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import {
+  explainIgnore,
+  getDirectoryStatus,
+  getFileStatus,
+  getRepoStatus,
+  isPathIgnored,
+  listUntracked
+} from '../src/status.mjs';
+import { commitFiles, createRepo, git, write } from './helpers.mjs';
+
+describe('getFileStatus', () => {
+  it('reports an unmodified file', async () => {
+    const repo = createRepo();
+    commitFiles(repo, { 'app.js': 'one\n' });
+
+    const status = await getFileStatus('app.js', { repoPath: repo });
+
+    assert.deepEqual(
+      [status.index, status.worktree, status.isStaged, status.isUnstaged],
+      ['unmodified', 'unmodified', false, false]
+    );
+  });
+
+  it('separates staged and unstaged changes', async () => {
+    const repo = createRepo();
+    commitFiles(repo, { 'app.js': 'one\n' });
+    write(repo, 'app.js', 'two\n');
+    git(repo, 'add', 'app.js');
+    write(repo, 'app.js', 'three\n');
+
+    const status = await getFileStatus('app.js', { repoPath: repo });
+
+    assert.deepEqual([status.index, status.worktree], ['modified', 'modified']);
+    assert.ok(status.isStaged && status.isUnstaged);
+  });
+
+  it('reports the rename source', async () => {
+    const repo = createRepo();
+    commitFiles(repo, { 'old.js': 'content\n' });
+    git(repo, 'mv', 'old.js', 'new.js');
+
+    const status = await getFileStatus('new.js', { repoPath: repo });
+
+    assert.deepEqual([status.index, status.renameSource], ['renamed', 'old.js']);
+  });
+
+  it('reports an untracked file', async () => {
+    const repo = createRepo();
+    commitFiles(repo, { 'app.js': 'one\n' });
+    write(repo, 'notes.txt', 'draft\n');
+
+    assert.equal((await getFileStatus('notes.txt', { repoPath: repo })).worktree, 'untracked');
+  });
+
+  it('flags skip-worktree files as possibly stale', async () => {
+    const repo = createRepo();
+    commitFiles(repo, { 'app.js': 'one\n' });
+    git(repo, 'update-index', '--skip-worktree', 'app.js');
+    write(repo, 'app.js', 'edited\n');
+
+    const status = await getFileStatus('app.js', { repoPath: repo });
+
+    assert.deepEqual([status.worktree, status.mayBeStale], ['unmodified', true]);
+  });
+});
+
+describe('getRepoStatus', () => {
+  it('lists changed and untracked files', async () => {
+    const repo = createRepo();
+    commitFiles(repo, { 'a.js': 'a\n', 'b.js': 'b\n' });
+    write(repo, 'a.js', 'changed\n');
+    write(repo, 'c.js', 'new\n');
+
+    const statuses = await getRepoStatus({ repoPath: repo });
+
+    assert.deepEqual(
+      statuses.map((status) => [status.path, status.index, status.worktree]),
+      [['a.js', 'unmodified', 'modified'], ['c.js', 'untracked', 'untracked']]
+    );
+  });
+});
+
+describe('getDirectoryStatus', () => {
+  it('counts the files below a directory', async () => {
+    const repo = createRepo();
+    commitFiles(repo, { 'src/a.js': 'a\n', 'src/b.js': 'b\n', 'docs/c.md': 'c\n' });
+    write(repo, 'src/a.js', 'changed\n');
+    write(repo, 'src/new.js', 'new\n');
+
+    const status = await getDirectoryStatus('src', { repoPath: repo });
+
+    assert.deepEqual([status.clean, status.modified, status.untracked], [1, 1, 1]);
+  });
+});
+
+describe('ignored files', () => {
+  it('leaves ignored files out of listUntracked', async () => {
+    const repo = createRepo();
+    commitFiles(repo, { '.gitignore': '*.log\n' });
+    write(repo, 'notes.txt', 'draft\n');
+    write(repo, 'debug.log', 'log\n');
+
+    assert.deepEqual(await listUntracked({ repoPath: repo }), ['notes.txt']);
+  });
+
+  it('names the rule that ignores a path', async () => {
+    const repo = createRepo();
+    commitFiles(repo, { '.gitignore': '# build output\ndist/\n*.log\n' });
+    const options = { repoPath: repo };
+
+    const rule = await explainIgnore('debug.log', options);
+
+    assert.equal(await isPathIgnored('debug.log', options), true);
+    assert.equal(await isPathIgnored('app.js', options), false);
+    assert.deepEqual([rule.source, rule.line, rule.pattern], ['.gitignore', 3, '*.log']);
+    assert.equal(await explainIgnore('app.js', options), null);
+  });
+});
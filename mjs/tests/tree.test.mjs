@@ -0,0 +1,40 @@
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import { getFileText, isBinary } from '../src/tree.mjs';
+import { commitFiles, createRepo, git, write } from './helpers.mjs';
+
+describe('getFileText', () => {
+  it('reads the encoding attribute at the commit', async () => {
+    const repo = createRepo();
+    commitFiles(repo, { 'data.csv': 'name,value\n' });
+    write(repo, '.gitattributes', '*.csv working-tree-encoding=UTF-16\n');
+    git(repo, 'add', '.gitattributes');
+    git(repo, 'commit', '-q', '-m', 'Add attributes');
+    const old = git(repo, 'rev-parse', 'HEAD');
+    git(repo, 'rm', '-q', '.gitattributes');
+    git(repo, 'commit', '-q', '-m', 'Drop attributes');
+
+    assert.equal((await getFileText('data.csv', { commit: old, repoPath: repo })).workingTreeEncoding, 'UTF-16');
+    assert.equal((await getFileText('data.csv', { repoPath: repo })).workingTreeEncoding, null);
+  });
+});
+
+describe('isBinary', () => {
+  it('reads attributes at the commit', async () => {
+    const repo = createRepo();
+    const old = commitFiles(repo, { '.gitattributes': '*.dat binary\n', 'data.dat': 'plain text\n' });
+    git(repo, 'rm', '-q', '.gitattributes');
+    git(repo, 'commit', '-q', '-m', 'Drop attributes');
+
+    assert.equal(await isBinary('data.dat', { commit: old, repoPath: repo }), true);
+    assert.equal(await isBinary('data.dat', { repoPath: repo }), false);
+  });
+
+  it('ignores uncommitted attributes', async () => {
+    const repo = createRepo();
+    commitFiles(repo, { 'data.dat': 'plain text\n' });
+    write(repo, '.gitattributes', '*.dat binary\n');
+
+    assert.equal(await isBinary('data.dat', { repoPath: repo }), false);
+  });
+});
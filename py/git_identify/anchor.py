@@ -0,0 +1,290 @@
+"""
+Content-addressed line anchors.
+
+Identifies a range of lines by a hash of their content rather than by line
+numbers alone, so an anchor (a review comment, a bookmark) can be found
+again after edits elsewhere in the file shift the lines.
+"""
+
+import hashlib
+import os
+import re
+from typing import Any, Optional
+
+from .errors import FileNotFoundError
+from .utils.encoding import decode_text
+from .utils.git import (
+    execute_git_command,
+    get_blob_bytes,
+    get_file_hash_at_commit,
+    resolve_repository_path,
+)
+
+# Hex digits of the SHA-256 digest kept in an anchor ID
+ANCHOR_ID_LENGTH = 16
+
+# Runs of whitespace collapsed to one space when normalizing
+WHITESPACE_PATTERN = re.compile(r"\s+")
+
+
+class ContentAnchor:
+    """
+    A range of lines identified by a hash of their content.
+
+    Attributes:
+        anchor_id: 'sha256:' followed by ANCHOR_ID_LENGTH hex digits of the
+            hash of the lines
+        path: File path relative to repo root
+        start_line: First line of the range (1-based)
+        end_line: Last line of the range
+        commit: Commit SHA the lines were read at (None for the working tree)
+        normalize_whitespace: Whether leading and trailing whitespace was
+            removed and inner runs collapsed before hashing, so reindented
+            lines keep their ID
+    """
+
+    def __init__(
+        self,
+        anchor_id: str,
+        path: str,
+        start_line: int,
+        end_line: int,
+        commit: Optional[str] = None,
+        normalize_whitespace: bool = False
+    ) -> None:
+        self.anchor_id = anchor_id
+        self.path = path
+        self.start_line = start_line
+        self.end_line = end_line
+        self.commit = commit
+        self.normalize_whitespace = normalize_whitespace
+
+    @property
+    def line_count(self) -> int:
+        """Number of lines in the range."""
+        return self.end_line - self.start_line + 1
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "anchorId": self.anchor_id,
+            "path": self.path,
+            "startLine": self.start_line,
+            "endLine": self.end_line,
+            "commit": self.commit,
+            "normalizeWhitespace": self.normalize_whitespace
+        }
+
+
+def hash_lines(lines: list[str], normalize_whitespace: bool = False) -> str:
+    """
+    Compute the anchor ID of a sequence of lines.
+
+    Args:
+        lines: Line contents without line terminators
+        normalize_whitespace: Strip each line and collapse inner runs of
+            whitespace to one space before hashing (default: False)
+
+    Returns:
+        'sha256:' followed by ANCHOR_ID_LENGTH hex digits
+
+    Examples:
+        >>> hash_lines(["def main():", "    run()"])
+        'sha256:1766ef0f07222724'
+        >>> hash_lines(["x  =  1"], True) == hash_lines(["  x = 1"], True)
+        True
+    """
+    if normalize_whitespace:
+        lines = [WHITESPACE_PATTERN.sub(" ", line).strip() for line in lines]
+    digest = hashlib.sha256("\n".join(lines).encode("utf-8")).hexdigest()
+    return f"sha256:{digest[:ANCHOR_ID_LENGTH]}"
+
+
+def create_content_anchor(
+    file_path: str,
+    start_line: int,
+    end_line: Optional[int] = None,
+    commit: Optional[str] = "HEAD",
+    normalize_whitespace: bool = False,
+    repo_path: str = "."
+) -> ContentAnchor:
+    """
+    Create an anchor for a range of lines of a file.
+
+    Args:
+        file_path: File path (absolute or relative to repo root)
+        start_line: First line of the range (1-based)
+        end_line: Last line of the range (default: start_line)
+        commit: Revision to read the file at; None reads the working tree
+            (default: 'HEAD')
+        normalize_whitespace: Hash the lines with whitespace normalized, so
+            reindenting them keeps the ID (default: False)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        ContentAnchor
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit
+        FileNotFoundError: If the file does not exist at commit
+        ValueError: If the range is empty or past the end of the file
+
+    Examples:
+        >>> anchor = create_content_anchor("src/file.py", 10, 12, repo_path="/path/to/repo")
+        >>> anchor.to_dict()
+        {'anchorId': 'sha256:3f9a...', 'path': 'src/file.py', 'startLine': 10, 'endLine': 12, ...}
+    """
+    if end_line is None:
+        end_line = start_line
+    if start_line < 1 or end_line < start_line:
+        raise ValueError(f"Invalid line range: {start_line}-{end_line}")
+
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+    oid = _resolve_commit(repo_root, commit)
+    lines = _read_lines(repo_root, relative_path, oid)
+
+    if end_line > len(lines):
+        raise ValueError(
+            f"Invalid line range: {start_line}-{end_line}. {relative_path} has {len(lines)} lines"
+        )
+
+    return ContentAnchor(
+        hash_lines(lines[start_line - 1:end_line], normalize_whitespace),
+        relative_path,
+        start_line,
+        end_line,
+        oid,
+        normalize_whitespace
+    )
+
+
+def locate_content_anchor(
+    anchor: ContentAnchor,
+    commit: Optional[str] = "HEAD",
+    file_path: Optional[str] = None,
+    repo_path: str = "."
+) -> list[ContentAnchor]:
+    """
+    Find the lines an anchor identifies in another version of its file.
+
+    Every run of anchor.line_count lines whose hash matches is returned,
+    closest to the anchor's original position first, so the first result is
+    the best guess when the content occurs more than once.
+
+    Args:
+        anchor: Anchor to look for
+        commit: Revision to search; None searches the working tree
+            (default: 'HEAD')
+        file_path: File to search, if it was renamed since the anchor was
+            created (absolute or relative to repo root; default: anchor.path)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        ContentAnchor list with the new line numbers, empty if the lines
+        were changed or the file does not exist at commit
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit
+
+    Examples:
+        >>> [(a.start_line, a.end_line) for a in locate_content_anchor(anchor, repo_path="/path/to/repo")]
+        [(14, 16)]
+    """
+    repo_root, relative_path = resolve_repository_path(
+        repo_path,
+        anchor.path if file_path is None else file_path
+    )
+    oid = _resolve_commit(repo_root, commit)
+
+    try:
+        lines = _read_lines(repo_root, relative_path, oid)
+    except FileNotFoundError:
+        return []
+
+    count = anchor.line_count
+    matches = [
+        ContentAnchor(
+            anchor.anchor_id,
+            relative_path,
+            start,
+            start + count - 1,
+            oid,
+            anchor.normalize_whitespace
+        )
+        for start in range(1, len(lines) - count + 2)
+        if hash_lines(lines[start - 1:start - 1 + count], anchor.normalize_whitespace)
+        == anchor.anchor_id
+    ]
+    matches.sort(key=lambda match: abs(match.start_line - anchor.start_line))
+    return matches
+
+
+def _resolve_commit(repo_root: str, commit: Optional[str]) -> Optional[str]:
+    """
+    Resolve a revision to a commit SHA, passing None (the working tree) through.
+
+    Args:
+        repo_root: Repository root path
+        commit: Commit SHA, branch, tag or other revision, or None
+
+    Returns:
+        Commit SHA, or None
+
+    Raises:
+        GitCommandError: If commit does not name a commit
+    """
+    if commit is None:
+        return None
+    return execute_git_command(["git", "rev-parse", f"{commit}^{{commit}}"], cwd=repo_root)
+
+
+def _read_lines(repo_root: str, relative_path: str, commit: Optional[str]) -> list[str]:
+    """
+    Read a file as lines, at a commit or from the working tree.
+
+    CRLF line endings are treated as LF, so a checkout with autocrlf hashes
+    the same as the committed file.
+
+    Args:
+        repo_root: Repository root path
+        relative_path: Path relative to repo root
+        commit: Commit SHA, or None for the working tree
+
+    Returns:
+        Lines without terminators
+
+    Raises:
+        FileNotFoundError: If the file does not exist
+    """
+    if commit is None:
+        try:
+            with open(os.path.join(repo_root, relative_path), "rb") as file:
+                data = file.read()
+        except OSError:
+            data = None
+    else:
+        blob_hash = get_file_hash_at_commit(repo_root, relative_path, commit)
+        data = None if blob_hash is None else get_blob_bytes(repo_root, blob_hash)
+
+    if data is None:
+        raise FileNotFoundError(
+            f"File not found in repository: {relative_path}",
+            file_path=relative_path
+        )
+
+    text, _ = decode_text(data)
+    lines = text.replace("\r\n", "\n").split("\n")
+    if lines[-1] == "":
+        lines.pop()
+    return lines
+
+
+__all__ = [
+    "ANCHOR_ID_LENGTH",
+    "ContentAnchor",
+    "hash_lines",
+    "create_content_anchor",
+    "locate_content_anchor",
+]
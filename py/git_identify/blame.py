@@ -0,0 +1,422 @@
+"""
+Line attribution.
+
+Blames the lines of a file to the commits that last changed them, maps
+a line from one commit to another and sums blame into per-author
+ownership.
+"""
+
+from typing import Any, Optional
+
+from .diff import DIFF_HUNK_PATTERN, _renamed_path
+from .errors import FileNotFoundError
+from .history import _read_authors
+from .refs import _format_timestamp
+from .tree import _tree_entry_at
+from .utils.git import (
+    execute_git_command,
+    execute_git_command_bytes,
+    get_blob_bytes,
+    get_shallow_boundary,
+    resolve_repository_path,
+)
+
+
+class BlameHunk:
+    """
+    A run of consecutive lines that ``git blame`` attributes to one commit.
+
+    Attributes:
+        oid: Commit that last changed the lines (all zeros for uncommitted
+            changes in the working tree)
+        start_line: First line of the run in the blamed file (1-based)
+        line_count: Number of lines in the run
+        orig_start_line: Line number of the first line in the commit's
+            version of the file
+        orig_path: Path of the file in that commit, which differs from the
+            blamed path when the file was renamed since
+        author_name: Author name (after .mailmap, unless disabled)
+        author_email: Author email (after .mailmap, unless disabled)
+        author_date: Author date in ISO 8601 format
+        summary: First line of the commit message
+        boundary: Whether the commit is a root commit or the boundary of
+            the blamed range, so the lines may be older than it
+        shallow_boundary: Whether the commit is where a shallow clone's
+            history is cut off, so boundary does not mean a root commit
+    """
+
+    def __init__(
+        self,
+        oid: str,
+        start_line: int,
+        line_count: int,
+        orig_start_line: int,
+        orig_path: str,
+        author_name: str,
+        author_email: str,
+        author_date: str,
+        summary: str,
+        boundary: bool = False,
+        shallow_boundary: bool = False
+    ) -> None:
+        self.oid = oid
+        self.start_line = start_line
+        self.line_count = line_count
+        self.orig_start_line = orig_start_line
+        self.orig_path = orig_path
+        self.author_name = author_name
+        self.author_email = author_email
+        self.author_date = author_date
+        self.summary = summary
+        self.boundary = boundary
+        self.shallow_boundary = shallow_boundary
+
+    @property
+    def end_line(self) -> int:
+        """Last line of the run in the blamed file."""
+        return self.start_line + self.line_count - 1
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "oid": self.oid,
+            "startLine": self.start_line,
+            "endLine": self.end_line,
+            "lineCount": self.line_count,
+            "origStartLine": self.orig_start_line,
+            "origPath": self.orig_path,
+            "authorName": self.author_name,
+            "authorEmail": self.author_email,
+            "authorDate": self.author_date,
+            "summary": self.summary,
+            "boundary": self.boundary,
+            "shallowBoundary": self.shallow_boundary
+        }
+
+
+class LineMapping:
+    """
+    Where a line of a file at one commit is found at another.
+
+    Attributes:
+        path: Path of the file at the target commit, which differs from the
+            source path when the file was renamed in between
+        line: Line number at the target commit (1-based)
+        commit: Target commit SHA
+    """
+
+    def __init__(self, path: str, line: int, commit: str) -> None:
+        self.path = path
+        self.line = line
+        self.commit = commit
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "line": self.line,
+            "commit": self.commit
+        }
+
+
+class AuthorShare:
+    """
+    An author's share of the current lines of a file.
+
+    Attributes:
+        name: Author name (as on the first of the author's lines)
+        email: Author email
+        lines: Lines last changed by the author
+        fraction: lines as a fraction of the file's lines (0 to 1)
+    """
+
+    def __init__(self, name: str, email: str, lines: int, fraction: float) -> None:
+        self.name = name
+        self.email = email
+        self.lines = lines
+        self.fraction = fraction
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "name": self.name,
+            "email": self.email,
+            "lines": self.lines,
+            "fraction": self.fraction
+        }
+
+
+def blame_file(
+    file_path: str,
+    rev: Optional[str] = "HEAD",
+    line_start: Optional[int] = None,
+    line_end: Optional[int] = None,
+    ignore_whitespace: bool = False,
+    detect_moves: bool = False,
+    mailmap: bool = True,
+    repo_path: str = "."
+) -> list[BlameHunk]:
+    """
+    Attribute each line of a file to the commit that last changed it.
+
+    Lines are grouped into hunks of consecutive lines from the same commit,
+    with the line numbers and path they had in that commit, so editor
+    plugins can link a line to the version of the file it came from.
+
+    Args:
+        file_path: File path (absolute or relative to repo root)
+        rev: Revision to blame; None blames the working tree file, with
+            uncommitted lines attributed to an all-zero OID (default: 'HEAD')
+        line_start: First line to blame (1-based; default: 1)
+        line_end: Last line to blame (default: end of file)
+        ignore_whitespace: Ignore whitespace-only changes (default: False)
+        detect_moves: Attribute lines moved or copied within the file, and
+            from other files changed in the same commit, to where they came
+            from (``git blame -M -C``; default: False)
+        mailmap: Map names and emails through .mailmap (default: True)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        BlameHunk list ordered by start_line
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If rev does not name a commit, the file does not
+            exist there, or the line range is past the end of the file
+        ValueError: If line_start or line_end is out of range
+
+    Examples:
+        >>> [(h.start_line, h.end_line, h.oid[:7]) for h in blame_file("src/file.py", repo_path="/path/to/repo")]
+        [(1, 12, 'abc1234'), (13, 13, 'def5678'), (14, 40, 'abc1234')]
+    """
+    if line_start is not None and line_start < 1:
+        raise ValueError(f"Invalid start line: {line_start}. Must be positive")
+    if line_end is not None and line_end < (line_start or 1):
+        raise ValueError(f"Invalid end line: {line_end}. Must not be before the start line")
+
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    command = ["git", "blame", "--incremental"]
+    if ignore_whitespace:
+        command.append("-w")
+    if detect_moves:
+        command += ["-M", "-C"]
+    if line_start is not None or line_end is not None:
+        command.append(f"-L{line_start or 1},{line_end or ''}")
+    if rev is not None:
+        command.append(rev)
+    command += ["--", relative_path]
+
+    output = execute_git_command(command, cwd=repo_root)
+    shallow_boundary = set(get_shallow_boundary(repo_root))
+
+    # Each hunk is "<oid> <orig line> <final line> <count>", then header
+    # lines for the commit (only the first time it appears), ending with
+    # "filename <path>"
+    commits: dict[str, dict[str, str]] = {}
+    hunks: list[BlameHunk] = []
+    header: Optional[list[str]] = None
+    for line in output.split("\n"):
+        if header is None:
+            header = line.split(" ")
+            continue
+        key, _, value = line.partition(" ")
+        if key != "filename":
+            commits.setdefault(header[0], {})[key] = value
+            continue
+
+        oid, orig_line, final_line, count = header
+        commit = commits.setdefault(oid, {})
+        hunks.append(BlameHunk(
+            oid,
+            int(final_line),
+            int(count),
+            int(orig_line),
+            value,
+            commit.get("author", ""),
+            commit.get("author-mail", "").strip("<>"),
+            _format_timestamp(commit.get("author-time", "0"), commit.get("author-tz", "+0000")),
+            commit.get("summary", ""),
+            "boundary" in commit,
+            oid in shallow_boundary
+        ))
+        header = None
+
+    if not mailmap:
+        # git blame always applies .mailmap, so read the recorded identities
+        authors = _read_authors(repo_root, [oid for oid in commits if oid.strip("0")])
+        for hunk in hunks:
+            hunk.author_name, hunk.author_email = authors.get(
+                hunk.oid,
+                (hunk.author_name, hunk.author_email)
+            )
+
+    hunks.sort(key=lambda hunk: hunk.start_line)
+    return hunks
+
+
+def map_line(
+    file_path: str,
+    line: int,
+    from_commit: str,
+    to_commit: str = "HEAD",
+    repo_path: str = "."
+) -> Optional[LineMapping]:
+    """
+    Find where a line of a file at one commit is at another commit.
+
+    The line is carried through the diff between the two versions of the
+    file, following a rename, so a stored line-anchored link can be moved
+    onto a newer (or older) revision. Lines the diff changes or deletes
+    have no counterpart and map to None rather than to nearby code.
+
+    Args:
+        file_path: File path at from_commit (absolute or relative to repo root)
+        line: Line number at from_commit (1-based)
+        from_commit: Commit SHA, branch, tag or other revision the line
+            number refers to
+        to_commit: Revision to map the line onto (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        LineMapping, or None if the line was changed or deleted, or the
+        file no longer exists at to_commit
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If from_commit or to_commit does not name a commit
+        FileNotFoundError: If the file does not exist at from_commit
+        ValueError: If line is not positive or is past the end of the file
+            at from_commit
+
+    Examples:
+        >>> map_line("src/file.py", 42, "abc1234", repo_path="/path/to/repo").to_dict()
+        {'path': 'src/file.py', 'line': 47, 'commit': 'def5678...'}
+        >>> map_line("src/old_name.py", 10, "abc1234", repo_path="/path/to/repo").path
+        'src/new_name.py'
+    """
+    if line < 1:
+        raise ValueError(f"Invalid line: {line}. Must be positive")
+
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+    from_oid = execute_git_command(["git", "rev-parse", f"{from_commit}^{{commit}}"], cwd=repo_root)
+    to_oid = execute_git_command(["git", "rev-parse", f"{to_commit}^{{commit}}"], cwd=repo_root)
+
+    source = _tree_entry_at(repo_root, from_oid, relative_path)
+    if source is None or source.type != "blob":
+        raise FileNotFoundError(
+            f"File not found at {from_commit}: {relative_path}",
+            file_path=relative_path
+        )
+
+    content = get_blob_bytes(repo_root, source.oid)
+    line_count = content.count(b"\n")
+    if content and not content.endswith(b"\n"):
+        # Last line without a newline
+        line_count += 1
+    if line > line_count:
+        raise ValueError(
+            f"Invalid line: {line}. {relative_path} has {line_count} lines at {from_commit}"
+        )
+
+    target_path = _renamed_path(repo_root, from_oid, to_oid, relative_path)
+    target = None if target_path is None else _tree_entry_at(repo_root, to_oid, target_path)
+    if target_path is None or target is None or target.type != "blob":
+        return None
+    if target.oid == source.oid:
+        return LineMapping(target_path, line, to_oid)
+
+    patch = execute_git_command_bytes(
+        ["git", "diff", "--no-ext-diff", "--no-textconv", "--text", "-U0", source.oid, target.oid],
+        cwd=repo_root
+    ).decode("utf-8", errors="replace")
+
+    shift = 0
+    for hunk in DIFF_HUNK_PATTERN.finditer(patch):
+        old_start = int(hunk.group("old_start"))
+        old_count = int(hunk.group("old_count") or 1)
+        new_count = int(hunk.group("new_count") or 1)
+        if old_count == 0:
+            # Pure insertion after old_start
+            if line <= old_start:
+                break
+        elif line < old_start:
+            break
+        elif line < old_start + old_count:
+            return None
+        shift += new_count - old_count
+
+    return LineMapping(target_path, line + shift, to_oid)
+
+
+def get_file_ownership(
+    file_path: str,
+    rev: Optional[str] = "HEAD",
+    ignore_whitespace: bool = False,
+    detect_moves: bool = False,
+    mailmap: bool = True,
+    repo_path: str = "."
+) -> list[AuthorShare]:
+    """
+    Break down who wrote the current lines of a file.
+
+    Each line is credited to the author of the commit that last changed it,
+    as ``blame_file`` reports; authors are told apart by email, ignoring
+    case.
+
+    Args:
+        file_path: File path (absolute or relative to repo root)
+        rev: Revision to blame; None uses the working tree file, crediting
+            uncommitted lines to 'Not Committed Yet' (default: 'HEAD')
+        ignore_whitespace: Do not credit whitespace-only changes (default:
+            False)
+        detect_moves: Credit moved or copied lines to their original author
+            (default: False)
+        mailmap: Map authors through .mailmap, so lines written under
+            several aliases are credited to one person (default: True)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        AuthorShare list, most lines first (empty for an empty file)
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If rev does not name a commit or the file does not
+            exist there
+
+    Examples:
+        >>> [(a.name, round(a.fraction, 2)) for a in get_file_ownership("src/app.py", repo_path="/path/to/repo")]
+        [('Alice', 0.71), ('Bob', 0.29)]
+    """
+    hunks = blame_file(
+        file_path,
+        rev=rev,
+        ignore_whitespace=ignore_whitespace,
+        detect_moves=detect_moves,
+        repo_path=repo_path,
+        mailmap=mailmap
+    )
+
+    shares: dict[str, AuthorShare] = {}
+    for hunk in hunks:
+        share = shares.setdefault(
+            hunk.author_email.lower(),
+            AuthorShare(hunk.author_name, hunk.author_email, 0, 0.0)
+        )
+        share.lines += hunk.line_count
+
+    total = sum(share.lines for share in shares.values())
+    for share in shares.values():
+        share.fraction = share.lines / total
+
+    return sorted(shares.values(), key=lambda share: (-share.lines, share.name))
+
+
+__all__ = [
+    "BlameHunk",
+    "blame_file",
+    "LineMapping",
+    "map_line",
+    "AuthorShare",
+    "get_file_ownership",
+]
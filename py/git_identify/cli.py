@@ -8,6 +8,7 @@ import argparse
 import asyncio
 import json
 import sys
+import warnings
 from pathlib import Path
 from typing import Optional
 
@@ -24,6 +25,18 @@ from . import (
 )
 from .batch import BatchInput
 from .errors import GitError
+from .permalink import REF_STRATEGIES, generate_github_url
+from .repository import get_repo_state
+from .status import explain_ignore
+from .utils.format import LINK_FORMATS, SHORT_SHA_LENGTH, format_link
+from .utils.git import (
+    abbreviate_oid,
+    get_shallow_boundary,
+    resolve_repository_path,
+    set_honor_git_env,
+)
+from .utils.hash import is_valid_git_hash
+from .utils.url import parse_github_link
 
 
 def main() -> int:
@@ -38,6 +51,11 @@ def main() -> int:
         action="version",
         version=f"%(prog)s {__version__}"
     )
+    parser.add_argument(
+        "--ignore-git-env",
+        action="store_true",
+        help="Ignore GIT_DIR / GIT_WORK_TREE and discover repositories from paths"
+    )
 
     subparsers = parser.add_subparsers(dest="command", help="Command to execute")
 
@@ -182,12 +200,71 @@ def main() -> int:
         help="Repository path (default: current directory)"
     )
 
+    # URL command
+    url_parser = subparsers.add_parser(
+        "url",
+        help="Generate GitHub permalink for a file or directory"
+    )
+    url_parser.add_argument("file", help="File or directory path")
+    url_parser.add_argument(
+        "--repo",
+        default=".",
+        help="Repository path (default: current directory)"
+    )
+    url_parser.add_argument(
+        "--tree",
+        action="store_true",
+        default=None,
+        help="Link as a directory (default: detect from working tree)"
+    )
+    url_parser.add_argument(
+        "--lines",
+        help="Line anchor, e.g. 10 or 10-25"
+    )
+    url_parser.add_argument(
+        "--plain",
+        action=argparse.BooleanOptionalAction,
+        default=None,
+        help="Append ?plain=1 (default: automatic for rendered files with --lines)"
+    )
+    url_parser.add_argument(
+        "--ref",
+        choices=REF_STRATEGIES,
+        default="commit",
+        help="Ref used in the URL (default: commit)"
+    )
+    url_parser.add_argument(
+        "--remote",
+        help="Remote to link against (default: the branch's remote, then origin, then the first remote)"
+    )
+    url_parser.add_argument(
+        "--prefer-upstream",
+        action="store_true",
+        help="Link against the upstream remote when origin is a fork"
+    )
+    url_parser.add_argument(
+        "--unpushed",
+        choices=("warn", "fallback", "error"),
+        default="warn",
+        help="When the linked commit is not on the remote: warn (default), "
+             "fall back to the nearest pushed ancestor, or fail"
+    )
+    url_parser.add_argument(
+        "--format",
+        choices=LINK_FORMATS,
+        default="url",
+        help="Output format (default: url)"
+    )
+
     args = parser.parse_args()
 
     if not args.command:
         parser.print_help()
         return 1
 
+    if args.ignore_git_env:
+        set_honor_git_env(False)
+
     try:
         if args.command == "local":
             return cmd_local(args)
@@ -199,6 +276,8 @@ def main() -> int:
             return asyncio.run(cmd_diff(args))
         elif args.command == "info":
             return cmd_info(args)
+        elif args.command == "url":
+            return cmd_url(args)
         else:
             parser.print_help()
             return 1
@@ -352,5 +431,69 @@ def cmd_info(args: argparse.Namespace) -> int:
     return 0
 
 
+def cmd_url(args: argparse.Namespace) -> int:
+    """Handle url command."""
+    line_start: Optional[int] = None
+    line_end: Optional[int] = None
+    if args.lines:
+        start, _, end = args.lines.partition("-")
+        line_start = int(start)
+        line_end = int(end) if end else None
+
+    with warnings.catch_warnings(record=True) as unpushed_warnings:
+        warnings.simplefilter("always")
+        url = generate_github_url(
+            args.file,
+            is_directory=args.tree,
+            line_start=line_start,
+            line_end=line_end,
+            plain=args.plain,
+            ref_strategy=args.ref,
+            remote=args.remote,
+            prefer_upstream=args.prefer_upstream,
+            unpushed=args.unpushed,
+            repo_path=args.repo
+        )
+
+    link = parse_github_link(url)
+    repo_root, _ = resolve_repository_path(args.repo, args.file)
+
+    rule = explain_ignore(args.file, args.repo)
+    if rule:
+        print(
+            f"Warning: {rule.path} is ignored by {rule.source}:{rule.line} ({rule.pattern}); "
+            "it is not committed, so the link 404s",
+            file=sys.stderr
+        )
+
+    for warning in unpushed_warnings:
+        print(f"Warning: {warning.message}", file=sys.stderr)
+
+    if args.ref == "last-commit":
+        if link and link.ref in get_shallow_boundary(repo_root):
+            print(
+                "Warning: history stops at the shallow clone boundary; the path may have changed earlier",
+                file=sys.stderr
+            )
+
+    state = get_repo_state(args.repo)
+    if state != "clean":
+        print(
+            f"Warning: {state} in progress; HEAD is transient and the link may not survive it",
+            file=sys.stderr
+        )
+
+    # Link text uses the shortest SHA prefix that is unambiguous locally
+    sha_length = SHORT_SHA_LENGTH
+    if link and is_valid_git_hash(link.ref):
+        try:
+            sha_length = len(abbreviate_oid(repo_root, link.ref, SHORT_SHA_LENGTH))
+        except GitError:
+            pass
+
+    print(format_link(url, args.format, sha_length=sha_length))
+    return 0
+
+
 if __name__ == "__main__":
     sys.exit(main())
@@ -0,0 +1,250 @@
+"""
+CODEOWNERS resolution.
+
+Reads a repository's CODEOWNERS file and finds the users and teams that
+own a path, using GitHub's pattern semantics, so review requests can be
+routed from a file's identity.
+"""
+
+import os
+import re
+from typing import Any, Optional
+
+from .utils.git import (
+    execute_git_command,
+    get_blob_bytes,
+    get_file_hash_at_commit,
+    resolve_repository_path,
+)
+
+# Where GitHub looks for the file, in order; the first one found is used
+CODEOWNERS_LOCATIONS = (".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS")
+
+
+class CodeOwnersRule:
+    """
+    A line of a CODEOWNERS file.
+
+    Attributes:
+        pattern: Path pattern as written
+        owners: '@user', '@org/team' or email owners; empty when the
+            pattern marks paths as having no owner
+        line: Line number in the file (1-based)
+    """
+
+    def __init__(self, pattern: str, owners: list[str], line: int) -> None:
+        self.pattern = pattern
+        self.owners = owners
+        self.line = line
+        self._regex = _pattern_to_regex(pattern)
+
+    def matches(self, path: str) -> bool:
+        """Whether the rule applies to a path relative to repo root."""
+        return self._regex.match(path) is not None
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "pattern": self.pattern,
+            "owners": self.owners,
+            "line": self.line
+        }
+
+
+class CodeOwners:
+    """
+    The owners of a path.
+
+    Attributes:
+        path: Path relative to repo root
+        owners: Owners from the matching rule (empty if no rule matches or
+            the rule lists none)
+        rule: The last rule matching the path, which is the one that
+            applies (None if no rule matches)
+        source: Path of the CODEOWNERS file used, relative to repo root
+    """
+
+    def __init__(
+        self,
+        path: str,
+        owners: list[str],
+        source: str,
+        rule: Optional[CodeOwnersRule] = None
+    ) -> None:
+        self.path = path
+        self.owners = owners
+        self.source = source
+        self.rule = rule
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "owners": self.owners,
+            "rule": self.rule.to_dict() if self.rule else None,
+            "source": self.source
+        }
+
+
+def parse_codeowners(content: str) -> list[CodeOwnersRule]:
+    """
+    Parse the rules of a CODEOWNERS file.
+
+    Blank lines and comments ('#' at the start of a line or of a token) are
+    skipped; a pattern starting with '#' is written '\\#'.
+
+    Args:
+        content: File content
+
+    Returns:
+        CodeOwnersRule list in file order
+
+    Examples:
+        >>> rules = parse_codeowners("# Owners\\n*.js @org/web\\n/docs/ docs@example.com\\n")
+        >>> [(rule.pattern, rule.owners, rule.line) for rule in rules]
+        [('*.js', ['@org/web'], 2), ('/docs/', ['docs@example.com'], 3)]
+    """
+    rules: list[CodeOwnersRule] = []
+    for number, line in enumerate(content.splitlines(), start=1):
+        tokens = line.split()
+        if not tokens or tokens[0].startswith("#"):
+            continue
+
+        pattern = tokens[0]
+        if pattern.startswith("\\#"):
+            pattern = pattern[1:]
+        owners: list[str] = []
+        for token in tokens[1:]:
+            if token.startswith("#"):
+                break
+            owners.append(token)
+        rules.append(CodeOwnersRule(pattern, owners, number))
+    return rules
+
+
+def get_code_owners(
+    file_path: str,
+    commit: Optional[str] = "HEAD",
+    repo_path: str = "."
+) -> Optional[CodeOwners]:
+    """
+    Find the owners of a file or directory from the repository's CODEOWNERS.
+
+    The file is looked for in CODEOWNERS_LOCATIONS order, and as on GitHub
+    the last matching rule decides.
+
+    Args:
+        file_path: File or directory path (absolute or relative to repo root)
+        commit: Revision to read CODEOWNERS at; None reads the working tree
+            (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        CodeOwners, or None if the repository has no CODEOWNERS file
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit
+
+    Examples:
+        >>> get_code_owners("src/app.js", repo_path="/path/to/repo").owners
+        ['@org/web']
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+    if commit is not None:
+        commit = execute_git_command(["git", "rev-parse", f"{commit}^{{commit}}"], cwd=repo_root)
+
+    for source in CODEOWNERS_LOCATIONS:
+        content = _read_file(repo_root, source, commit)
+        if content is None:
+            continue
+
+        rule = None
+        for candidate in parse_codeowners(content):
+            if candidate.matches(relative_path):
+                rule = candidate
+        return CodeOwners(relative_path, rule.owners if rule else [], source, rule)
+
+    return None
+
+
+def _pattern_to_regex(pattern: str) -> re.Pattern[str]:
+    """
+    Compile a CODEOWNERS pattern to a regular expression over paths.
+
+    A pattern with a slash other than a trailing one is anchored at the
+    repository root, otherwise it matches at any depth. A match also covers
+    everything under a matched directory, except that a trailing '/*' only
+    covers the directory's direct children. '**' spans directories. Paths
+    are not checked against the file system, so 'apps/' matches the path
+    'apps' as the directory itself.
+
+    Args:
+        pattern: Path pattern
+
+    Returns:
+        Compiled regular expression
+    """
+    anchored = "/" in pattern.rstrip("/")
+    directory = pattern.endswith("/")
+    glob = pattern.strip("/")
+
+    regex = ""
+    index = 0
+    while index < len(glob):
+        if glob.startswith("**/", index):
+            regex += "(?:.*/)?"
+            index += 3
+        elif glob.startswith("**", index):
+            regex += ".*"
+            index += 2
+        elif glob[index] == "*":
+            regex += "[^/]*"
+            index += 1
+        elif glob[index] == "?":
+            regex += "[^/]"
+            index += 1
+        else:
+            regex += re.escape(glob[index])
+            index += 1
+
+    prefix = "^" if anchored else "^(?:.*/)?"
+    if glob.endswith("*") and not glob.endswith("**") and not directory:
+        suffix = "$"
+    else:
+        suffix = "(?:/.*)?$"
+    return re.compile(prefix + regex + suffix)
+
+
+def _read_file(repo_root: str, path: str, commit: Optional[str]) -> Optional[str]:
+    """
+    Read a text file at a commit or from the working tree.
+
+    Args:
+        repo_root: Repository root path
+        path: Path relative to repo root
+        commit: Commit SHA, or None for the working tree
+
+    Returns:
+        Content, or None if the file does not exist
+    """
+    if commit is None:
+        try:
+            with open(os.path.join(repo_root, path), encoding="utf-8", errors="replace") as file:
+                return file.read()
+        except OSError:
+            return None
+
+    blob_hash = get_file_hash_at_commit(repo_root, path, commit)
+    if blob_hash is None:
+        return None
+    return get_blob_bytes(repo_root, blob_hash).decode("utf-8", errors="replace")
+
+
+__all__ = [
+    "CODEOWNERS_LOCATIONS",
+    "CodeOwnersRule",
+    "CodeOwners",
+    "parse_codeowners",
+    "get_code_owners",
+]
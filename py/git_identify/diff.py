@@ -0,0 +1,680 @@
+"""
+Commit and working-tree diffs.
+
+Describes what a commit changed against its first parent and how a file
+differs between two commits or between HEAD, the index and the working
+tree.
+"""
+
+import os
+import re
+from typing import Any, Optional
+
+from .errors import FileNotFoundError
+from .status import STATUS_CODES, FileChange
+from .tree import _tree_entry_at
+from .utils.git import (
+    execute_git_command,
+    execute_git_command_bytes,
+    get_repository_root,
+    is_head_unborn,
+    resolve_repository_path,
+)
+from .utils.path import resolve_file_path
+
+# "@@ -<start>[,<count>] +<start>[,<count>] @@" unified diff hunk header
+DIFF_HUNK_PATTERN = re.compile(
+    r"^@@ -(?P<old_start>\d+)(?:,(?P<old_count>\d+))? "
+    r"\+(?P<new_start>\d+)(?:,(?P<new_count>\d+))? @@",
+    re.MULTILINE
+)
+
+# Empty tree object per object format, the base of an unborn HEAD
+EMPTY_TREE_OIDS = {
+    "sha1": "4b825dc642cb6eb9a060e54bf8d69288fbee4904",
+    "sha256": "6ef19b41225c5369f1c104d45d8d85efa9b057b53b14b4b9b939dd74decc5321",
+}
+
+
+class ChangedFile:
+    """
+    A file touched by a commit.
+
+    Attributes:
+        path: Path relative to the repository root (the new path if renamed
+            or copied)
+        change: 'added', 'modified', 'deleted', 'renamed', 'copied' or
+            'type-changed'
+        old_path: Source path of a rename or copy (None otherwise)
+    """
+
+    def __init__(self, path: str, change: FileChange, old_path: Optional[str] = None) -> None:
+        self.path = path
+        self.change = change
+        self.old_path = old_path
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "change": self.change,
+            "oldPath": self.old_path
+        }
+
+
+class DiffHunk:
+    """
+    A hunk of a unified diff.
+
+    Attributes:
+        old_start: First line of the hunk in the old file (1-based; the line
+            before an insertion when old_lines is 0)
+        old_lines: Number of old file lines the hunk covers
+        new_start: First line of the hunk in the new file
+        new_lines: Number of new file lines the hunk covers
+        section: Text after the hunk header's closing '@@', usually the
+            enclosing function ('' if none)
+        lines: Hunk body, each line prefixed with ' ', '-' or '+'
+    """
+
+    def __init__(
+        self,
+        old_start: int,
+        old_lines: int,
+        new_start: int,
+        new_lines: int,
+        section: str = "",
+        lines: Optional[list[str]] = None
+    ) -> None:
+        self.old_start = old_start
+        self.old_lines = old_lines
+        self.new_start = new_start
+        self.new_lines = new_lines
+        self.section = section
+        self.lines = lines or []
+
+    @property
+    def added(self) -> list[str]:
+        """Lines the hunk adds, without the '+' prefix."""
+        return [line[1:] for line in self.lines if line.startswith("+")]
+
+    @property
+    def removed(self) -> list[str]:
+        """Lines the hunk removes, without the '-' prefix."""
+        return [line[1:] for line in self.lines if line.startswith("-")]
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "oldStart": self.old_start,
+            "oldLines": self.old_lines,
+            "newStart": self.new_start,
+            "newLines": self.new_lines,
+            "section": self.section,
+            "lines": self.lines,
+            "added": self.added,
+            "removed": self.removed
+        }
+
+
+class FileDiff:
+    """
+    Changes to one file between two commits.
+
+    Attributes:
+        old_path: Path at the first commit (None if the file was added)
+        new_path: Path at the second commit (None if the file was deleted)
+        change: 'added', 'deleted', 'modified', 'renamed' or 'unmodified'
+        binary: Whether Git reports the file as binary, in which case there
+            are no hunks
+        hunks: DiffHunk list in file order
+        patch: Unified diff text, as ``git diff`` prints it ('' if unmodified)
+    """
+
+    def __init__(
+        self,
+        old_path: Optional[str],
+        new_path: Optional[str],
+        change: FileChange,
+        binary: bool = False,
+        hunks: Optional[list[DiffHunk]] = None,
+        patch: str = ""
+    ) -> None:
+        self.old_path = old_path
+        self.new_path = new_path
+        self.change = change
+        self.binary = binary
+        self.hunks = hunks or []
+        self.patch = patch
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "oldPath": self.old_path,
+            "newPath": self.new_path,
+            "change": self.change,
+            "binary": self.binary,
+            "hunks": [hunk.to_dict() for hunk in self.hunks],
+            "patch": self.patch
+        }
+
+
+class WorkdirDiff:
+    """
+    Local changes to a file, as ``git diff HEAD`` and ``git diff`` show them.
+
+    Attributes:
+        path: Path relative to repo root
+        head: Working tree file compared with HEAD (staged and unstaged
+            changes together)
+        index: Working tree file compared with the index (unstaged changes
+            only)
+    """
+
+    def __init__(self, path: str, head: FileDiff, index: FileDiff) -> None:
+        self.path = path
+        self.head = head
+        self.index = index
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "head": self.head.to_dict(),
+            "index": self.index.to_dict()
+        }
+
+
+class FileStats:
+    """
+    Lines added and deleted in one file by a commit.
+
+    Attributes:
+        path: Path relative to the repository root (the new path if renamed)
+        additions: Lines added (0 for binary files)
+        deletions: Lines deleted (0 for binary files)
+        old_path: Path before a rename or copy (None otherwise)
+        binary: Whether Git treated the file as binary, with no line counts
+    """
+
+    def __init__(
+        self,
+        path: str,
+        additions: int,
+        deletions: int,
+        old_path: Optional[str] = None,
+        binary: bool = False
+    ) -> None:
+        self.path = path
+        self.additions = additions
+        self.deletions = deletions
+        self.old_path = old_path
+        self.binary = binary
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "additions": self.additions,
+            "deletions": self.deletions,
+            "oldPath": self.old_path,
+            "binary": self.binary
+        }
+
+
+class CommitStats:
+    """
+    Per-file and total line changes of a commit, as in ``git show --stat``.
+
+    Attributes:
+        commit: Commit SHA
+        files: FileStats for each changed file
+    """
+
+    def __init__(self, commit: str, files: list[FileStats]) -> None:
+        self.commit = commit
+        self.files = files
+
+    @property
+    def files_changed(self) -> int:
+        """Number of files changed."""
+        return len(self.files)
+
+    @property
+    def additions(self) -> int:
+        """Total lines added."""
+        return sum(stats.additions for stats in self.files)
+
+    @property
+    def deletions(self) -> int:
+        """Total lines deleted."""
+        return sum(stats.deletions for stats in self.files)
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "commit": self.commit,
+            "filesChanged": self.files_changed,
+            "additions": self.additions,
+            "deletions": self.deletions,
+            "files": [stats.to_dict() for stats in self.files]
+        }
+
+
+def get_commit_stats(commit: str = "HEAD", repo_path: str = ".") -> CommitStats:
+    """
+    Count the lines a commit added and deleted, per file and in total.
+
+    The diff is taken against the first parent (against the empty tree for
+    a root commit), so a merge reports what it brought into its branch.
+    Renames are detected and reported once under the new path.
+
+    Args:
+        commit: Commit SHA, branch, tag or other revision (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        CommitStats
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit
+
+    Examples:
+        >>> stats = get_commit_stats("HEAD", "/path/to/repo")
+        >>> (stats.files_changed, stats.additions, stats.deletions)
+        (3, 42, 7)
+        >>> stats.files[0].to_dict()
+        {'path': 'src/file.py', 'additions': 30, 'deletions': 5, 'oldPath': None, 'binary': False}
+    """
+    repo_root = get_repository_root(repo_path)
+
+    oid, diff_command = _first_parent_diff(commit, repo_root)
+    tokens = iter(
+        execute_git_command([*diff_command, "--numstat", "-z"], cwd=repo_root).split("\0")
+    )
+
+    files: list[FileStats] = []
+    for token in tokens:
+        if not token:
+            continue
+        # "<added>\t<deleted>\t<path>", or "<added>\t<deleted>\t" followed by
+        # the old and new paths of a rename
+        added, deleted, path = token.split("\t", 2)
+        old_path = None
+        if not path:
+            old_path = next(tokens)
+            path = next(tokens)
+        binary = added == "-"
+        files.append(FileStats(
+            path,
+            0 if binary else int(added),
+            0 if binary else int(deleted),
+            old_path,
+            binary
+        ))
+
+    return CommitStats(oid, files)
+
+
+def get_commit_changed_files(commit: str = "HEAD", repo_path: str = ".") -> list[ChangedFile]:
+    """
+    List the files a commit touched and how each changed.
+
+    The diff is taken against the first parent (against the empty tree for
+    a root commit), with renames detected. Only paths are compared, so this
+    is cheaper than ``get_commit_stats`` for large commits.
+
+    Args:
+        commit: Commit SHA, branch, tag or other revision (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        ChangedFile list in path order
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit
+
+    Examples:
+        >>> [(f.change, f.path) for f in get_commit_changed_files("HEAD", "/path/to/repo")]
+        [('modified', 'README.md'), ('renamed', 'src/new.py'), ('deleted', 'src/old_util.py')]
+    """
+    repo_root = get_repository_root(repo_path)
+
+    _, diff_command = _first_parent_diff(commit, repo_root)
+    tokens = iter(
+        execute_git_command([*diff_command, "--name-status", "-z"], cwd=repo_root).split("\0")
+    )
+
+    files: list[ChangedFile] = []
+    for token in tokens:
+        if not token:
+            continue
+        # "<status>\0<path>", or "R<score>\0<old path>\0<new path>" for renames
+        # and copies
+        change = STATUS_CODES.get(token[0], "modified")
+        old_path = next(tokens) if change in ("renamed", "copied") else None
+        files.append(ChangedFile(next(tokens), change, old_path))
+
+    return files
+
+
+def get_commit_patch(
+    commit: str = "HEAD",
+    path: Optional[str] = None,
+    repo_path: str = "."
+) -> str:
+    """
+    Get the unified diff a commit introduced, as ``git show`` prints it.
+
+    The diff is taken against the first parent (against the empty tree for
+    a root commit), with renames detected. Binary files are reported as
+    differing without content.
+
+    Args:
+        commit: Commit SHA, branch, tag or other revision (default: 'HEAD')
+        path: Only include changes to this file or directory (absolute or
+            relative to repo root; default: the whole commit)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        Patch text, empty if the commit (or the path) has no changes
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit
+
+    Examples:
+        >>> print(get_commit_patch("HEAD", "src/file.py", "/path/to/repo"))
+        diff --git a/src/file.py b/src/file.py
+        index 3b18e51..a1b2c3d 100644
+        --- a/src/file.py
+        +++ b/src/file.py
+        @@ -1,3 +1,4 @@
+        ...
+    """
+    repo_root = get_repository_root(repo_path)
+
+    _, diff_command = _first_parent_diff(commit, repo_root)
+    command = [*diff_command, "-p"]
+    if path is not None:
+        command += ["--", resolve_file_path(repo_root, path)]
+
+    return execute_git_command_bytes(command, cwd=repo_root).decode("utf-8", errors="replace")
+
+
+def diff_file(
+    file_path: str,
+    commit_a: str,
+    commit_b: str = "HEAD",
+    context_lines: int = 3,
+    ignore_whitespace: bool = False,
+    repo_path: str = "."
+) -> FileDiff:
+    """
+    Diff one file between two commits.
+
+    A rename between the commits is followed, so file_path is the path at
+    commit_a. Text conversion and external diff drivers are not applied.
+
+    Args:
+        file_path: File path at commit_a (absolute or relative to repo root)
+        commit_a: Old side: commit SHA, branch, tag or other revision
+        commit_b: New side (default: 'HEAD')
+        context_lines: Unchanged lines shown around each change (default: 3)
+        ignore_whitespace: Ignore whitespace-only changes (default: False)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        FileDiff
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit_a or commit_b does not name a commit
+        FileNotFoundError: If the file exists at neither commit
+        ValueError: If context_lines is negative
+
+    Examples:
+        >>> diff = diff_file("src/file.py", "v1.0.0", repo_path="/path/to/repo")
+        >>> [(h.old_start, h.old_lines, h.new_start, h.new_lines) for h in diff.hunks]
+        [(10, 3, 10, 4)]
+        >>> diff.hunks[0].added
+        ['    validate(config)']
+    """
+    if context_lines < 0:
+        raise ValueError(f"Invalid context lines: {context_lines}. Must not be negative")
+
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+    oid_a = execute_git_command(["git", "rev-parse", f"{commit_a}^{{commit}}"], cwd=repo_root)
+    oid_b = execute_git_command(["git", "rev-parse", f"{commit_b}^{{commit}}"], cwd=repo_root)
+
+    new_path = _renamed_path(repo_root, oid_a, oid_b, relative_path)
+    pathspec = [relative_path]
+    if new_path is not None and new_path != relative_path:
+        pathspec.append(new_path)
+
+    command = _diff_command(context_lines, ignore_whitespace)
+    patch = execute_git_command_bytes(
+        [*command, "-M", oid_a, oid_b, "--", *pathspec],
+        cwd=repo_root
+    ).decode("utf-8", errors="replace")
+
+    if not patch and _tree_entry_at(repo_root, oid_a, relative_path) is None:
+        raise FileNotFoundError(
+            f"File not found at {commit_a} or {commit_b}: {relative_path}",
+            file_path=relative_path
+        )
+
+    return _parse_file_diff(patch, relative_path, new_path)
+
+
+def diff_workdir(
+    file_path: str,
+    context_lines: int = 3,
+    ignore_whitespace: bool = False,
+    repo_path: str = "."
+) -> Optional[WorkdirDiff]:
+    """
+    Show what changed locally in a file, against HEAD and against the index.
+
+    Complements ``get_file_status``, which tells whether a file changed,
+    with the changes themselves. A file deleted from the working tree is
+    reported as deleted; with an unborn HEAD, the HEAD side is compared
+    with an empty tree.
+
+    Args:
+        file_path: File path (absolute or relative to repo root)
+        context_lines: Unchanged lines shown around each change (default: 3)
+        ignore_whitespace: Ignore whitespace-only changes (default: False)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        WorkdirDiff, or None if the file is untracked (neither in HEAD nor
+        in the index)
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        FileNotFoundError: If the file is neither tracked nor on disk
+        ValueError: If context_lines is negative
+
+    Examples:
+        >>> diff = diff_workdir("src/file.py", repo_path="/path/to/repo")
+        >>> diff.head.change, diff.index.change
+        ('modified', 'unmodified')
+        >>> print(diff.head.patch)
+        diff --git a/src/file.py b/src/file.py
+        ...
+    """
+    if context_lines < 0:
+        raise ValueError(f"Invalid context lines: {context_lines}. Must not be negative")
+
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    if is_head_unborn(repo_root):
+        object_format = execute_git_command("git rev-parse --show-object-format", cwd=repo_root)
+        base = EMPTY_TREE_OIDS[object_format]
+        in_head = False
+    else:
+        base = "HEAD"
+        in_head = _tree_entry_at(repo_root, base, relative_path) is not None
+    in_index = bool(execute_git_command(["git", "ls-files", "--", relative_path], cwd=repo_root))
+
+    if not in_head and not in_index:
+        if os.path.lexists(os.path.join(repo_root, relative_path)):
+            return None
+        raise FileNotFoundError(
+            f"File not found in repository: {relative_path}",
+            file_path=relative_path
+        )
+
+    command = _diff_command(context_lines, ignore_whitespace)
+    patches = [
+        execute_git_command_bytes(
+            [*command, *revision, "--", relative_path],
+            cwd=repo_root
+        ).decode("utf-8", errors="replace")
+        for revision in ([base], [])
+    ]
+
+    return WorkdirDiff(
+        relative_path,
+        _parse_file_diff(patches[0], relative_path, relative_path),
+        _parse_file_diff(patches[1], relative_path, relative_path)
+    )
+
+
+def _diff_command(context_lines: int, ignore_whitespace: bool) -> list[str]:
+    """
+    Build a ``git diff`` command whose output does not depend on user config.
+
+    Args:
+        context_lines: Unchanged lines shown around each change
+        ignore_whitespace: Ignore whitespace-only changes
+
+    Returns:
+        Command without revisions or paths
+    """
+    command = ["git", "diff", "--no-ext-diff", "--no-textconv", "--no-color", f"-U{context_lines}"]
+    if ignore_whitespace:
+        command.append("-w")
+    return command
+
+
+def _parse_file_diff(patch: str, old_path: Optional[str], new_path: Optional[str]) -> FileDiff:
+    """
+    Build a FileDiff from the ``git diff`` output for a single file.
+
+    Args:
+        patch: Unified diff text, empty if the file is unchanged
+        old_path: Path on the old side
+        new_path: Path on the new side
+
+    Returns:
+        FileDiff, with old_path or new_path cleared if the patch adds or
+        deletes the file
+    """
+    change: FileChange = "modified" if patch else "unmodified"
+    binary = False
+    hunks: list[DiffHunk] = []
+    for line in patch.split("\n"):
+        if hunks:
+            if line[:1] in (" ", "-", "+"):
+                hunks[-1].lines.append(line)
+                continue
+            if line.startswith("\\"):
+                # "\ No newline at end of file"
+                continue
+
+        match = DIFF_HUNK_PATTERN.match(line)
+        if match:
+            hunks.append(DiffHunk(
+                int(match.group("old_start")),
+                int(match.group("old_count") or 1),
+                int(match.group("new_start")),
+                int(match.group("new_count") or 1),
+                line[match.end():].strip()
+            ))
+        elif line.startswith("new file mode"):
+            old_path, change = None, "added"
+        elif line.startswith("deleted file mode"):
+            new_path, change = None, "deleted"
+        elif line.startswith("rename from"):
+            change = "renamed"
+        elif line.startswith("Binary files"):
+            binary = True
+
+    return FileDiff(old_path, new_path, change, binary, hunks, patch)
+
+
+def _first_parent_diff(commit: str, repo_root: str) -> tuple[str, list[str]]:
+    """
+    Build the diff-tree command comparing a commit with its first parent.
+
+    Args:
+        commit: Commit SHA, branch, tag or other revision
+        repo_root: Repository root path
+
+    Returns:
+        Tuple of (commit SHA, command without output options)
+
+    Raises:
+        GitCommandError: If commit does not name a commit
+    """
+    oid, *parents = execute_git_command(
+        ["git", "rev-list", "--parents", "-n", "1", f"{commit}^{{commit}}"],
+        cwd=repo_root
+    ).split()
+
+    if parents:
+        return oid, ["git", "diff-tree", "-r", "-M", parents[0], oid]
+    # Root commit: diff against the empty tree
+    return oid, ["git", "diff-tree", "-r", "-M", "--root", "--no-commit-id", oid]
+
+
+def _renamed_path(repo_root: str, from_commit: str, to_commit: str, path: str) -> Optional[str]:
+    """
+    Follow a path from one commit to another through a rename.
+
+    Args:
+        repo_root: Repository root path
+        from_commit: Commit SHA the path refers to
+        to_commit: Commit SHA to follow it to
+        path: Path relative to repo root at from_commit
+
+    Returns:
+        Path at to_commit (path itself if not renamed), or None if deleted
+
+    Raises:
+        GitCommandError: If either commit does not name a commit
+    """
+    # Renames are only paired when the diff is not limited to one path
+    tokens = iter(execute_git_command(
+        ["git", "diff-tree", "-r", "-M", "--name-status", "-z", from_commit, to_commit],
+        cwd=repo_root
+    ).split("\0"))
+    for token in tokens:
+        if not token:
+            continue
+        # "<status>\0<path>", or "R<score>\0<old path>\0<new path>"
+        paths = [next(tokens)]
+        if token[0] in ("R", "C"):
+            paths.append(next(tokens))
+        if paths[0] == path and token[0] != "C":
+            return None if token[0] == "D" else paths[-1]
+    return path
+
+
+__all__ = [
+    "ChangedFile",
+    "get_commit_changed_files",
+    "FileStats",
+    "CommitStats",
+    "get_commit_stats",
+    "get_commit_patch",
+    "DiffHunk",
+    "FileDiff",
+    "diff_file",
+    "WorkdirDiff",
+    "diff_workdir",
+]
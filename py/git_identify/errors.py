@@ -174,6 +174,39 @@ class AuthenticationError(GitError):
         )
 
 
+class UnbornHeadError(GitError):
+    """
+    Raised when HEAD points at a branch that has no commits yet.
+
+    This typically occurs when:
+    - The repository was just created with ``git init``
+    - An orphan branch was checked out and nothing has been committed
+
+    Attributes:
+        branch: Name of the unborn branch HEAD points at
+    """
+
+    def __init__(
+        self,
+        message: str,
+        branch: Optional[str] = None,
+        path: Optional[str] = None,
+        cause: Optional[Exception] = None
+    ) -> None:
+        context = {}
+        if branch:
+            context["branch"] = branch
+        if path:
+            context["path"] = path
+        super().__init__(
+            message,
+            code="UNBORN_HEAD",
+            context=context,
+            cause=cause
+        )
+        self.branch = branch
+
+
 class GitCommandError(GitError):
     """
     Raised when a Git command execution fails.
@@ -212,6 +245,45 @@ class GitCommandError(GitError):
         )
 
 
+class ObjectNotLocalError(GitCommandError):
+    """
+    Raised when a Git object is missing from a partial clone and cannot be fetched.
+
+    Blobless (``--filter=blob:none``) and treeless clones fetch objects
+    from their promisor remote on demand; this is raised when that fetch
+    fails, e.g. while offline.
+
+    Attributes:
+        object_id: SHA of the missing object
+        remote_url: URL of the promisor remote the object can be fetched from
+    """
+
+    def __init__(
+        self,
+        message: str,
+        object_id: Optional[str] = None,
+        remote_url: Optional[str] = None,
+        command: Optional[str] = None,
+        exit_code: Optional[int] = None,
+        stderr: Optional[str] = None,
+        cause: Optional[Exception] = None
+    ) -> None:
+        super().__init__(
+            message,
+            command=command,
+            exit_code=exit_code,
+            stderr=stderr,
+            cause=cause
+        )
+        self.code = "OBJECT_NOT_LOCAL"
+        self.object_id = object_id
+        self.remote_url = remote_url
+        if object_id:
+            self.context["object_id"] = object_id
+        if remote_url:
+            self.context["remote_url"] = remote_url
+
+
 # Export all error classes
 __all__ = [
     "GitError",
@@ -220,5 +292,7 @@ __all__ = [
     "InvalidHashError",
     "RateLimitError",
     "AuthenticationError",
+    "UnbornHeadError",
     "GitCommandError",
+    "ObjectNotLocalError",
 ]
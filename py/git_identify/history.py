@@ -0,0 +1,951 @@
+"""
+Commit history.
+
+Reads commit metadata and walks the history of a revision or a path:
+ranges, counts, content and message searches, churn, contributors and
+the commit that created a file.
+"""
+
+from datetime import datetime
+from typing import Any, Iterator, Optional, Union
+
+from .errors import GitCommandError
+from .utils.conventional import ConventionalCommit, parse_conventional_commit
+from .utils.git import (
+    execute_git_command,
+    get_repository_root,
+    get_shallow_boundary,
+    is_head_unborn,
+    resolve_repository_path,
+)
+from .utils.hash import validate_git_hash
+from .utils.path import resolve_file_path
+
+# Commit fields read by get_commit_info and log_range, NUL-separated, with
+# identities mapped through .mailmap
+COMMIT_INFO_FORMAT = (
+    "%H%x00%P%x00%aN%x00%aE%x00%aI%x00%cN%x00%cE%x00%cI%x00"
+    "%(trailers:only,unfold)%x00%B"
+)
+
+# COMMIT_INFO_FORMAT with identities as recorded in the commit
+RAW_COMMIT_INFO_FORMAT = (
+    "%H%x00%P%x00%an%x00%ae%x00%aI%x00%cn%x00%ce%x00%cI%x00"
+    "%(trailers:only,unfold)%x00%B"
+)
+COMMIT_INFO_FIELDS = 10
+
+# Commits read per git invocation by log_range and get_file_history
+LOG_PAGE_SIZE = 100
+
+
+class CommitInfo:
+    """
+    A commit's identities, dates and message.
+
+    The author wrote the change; the committer last applied it (for
+    example by rebasing or cherry-picking), so the two can differ.
+
+    Attributes:
+        oid: Commit SHA
+        parents: Parent commit SHAs (empty for a root commit)
+        author_name: Author name (after .mailmap, unless disabled)
+        author_email: Author email (after .mailmap, unless disabled)
+        author_date: Author date as an ISO 8601 string
+        committer_name: Committer name (after .mailmap, unless disabled)
+        committer_email: Committer email (after .mailmap, unless disabled)
+        committer_date: Commit date as an ISO 8601 string
+        message: Full commit message
+        trailers: (key, value) pairs from the message's trailer block, such
+            as ('Signed-off-by', 'Jane Doe <jane@example.com>'), in order
+        shallow_boundary: Whether a shallow clone's history is cut off at
+            the commit, so parents is empty without it being a root commit
+    """
+
+    def __init__(
+        self,
+        oid: str,
+        parents: list[str],
+        author_name: str,
+        author_email: str,
+        author_date: str,
+        committer_name: str,
+        committer_email: str,
+        committer_date: str,
+        message: str,
+        trailers: Optional[list[tuple[str, str]]] = None,
+        shallow_boundary: bool = False
+    ) -> None:
+        self.oid = oid
+        self.parents = parents
+        self.author_name = author_name
+        self.author_email = author_email
+        self.author_date = author_date
+        self.committer_name = committer_name
+        self.committer_email = committer_email
+        self.committer_date = committer_date
+        self.message = message
+        self.trailers = trailers or []
+        self.shallow_boundary = shallow_boundary
+
+    @property
+    def summary(self) -> str:
+        """One-line subject: the first paragraph of the message, joined as Git does."""
+        paragraph = self.message.strip().partition("\n\n")[0]
+        return " ".join(line.strip() for line in paragraph.splitlines())
+
+    @property
+    def body(self) -> str:
+        """Message after the subject paragraph ('' if there is none)."""
+        return self.message.strip().partition("\n\n")[2].strip()
+
+    @property
+    def conventional(self) -> Optional[ConventionalCommit]:
+        """Conventional Commits breakdown of the message (None if it does not follow the spec)."""
+        return parse_conventional_commit(self.message)
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "oid": self.oid,
+            "parents": self.parents,
+            "authorName": self.author_name,
+            "authorEmail": self.author_email,
+            "authorDate": self.author_date,
+            "committerName": self.committer_name,
+            "committerEmail": self.committer_email,
+            "committerDate": self.committer_date,
+            "summary": self.summary,
+            "body": self.body,
+            "message": self.message,
+            "trailers": [{"key": key, "value": value} for key, value in self.trailers],
+            "conventional": self.conventional.to_dict() if self.conventional else None,
+            "shallowBoundary": self.shallow_boundary
+        }
+
+
+class FileOrigin:
+    """
+    The commit that first added a file.
+
+    Attributes:
+        commit: CommitInfo of the commit
+        path: Path the file was added under, before any later renames
+    """
+
+    def __init__(self, commit: "CommitInfo", path: str) -> None:
+        self.commit = commit
+        self.path = path
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "commit": self.commit.to_dict(),
+            "path": self.path
+        }
+
+
+class FileChurn:
+    """
+    How much a file or directory changed over a period.
+
+    Attributes:
+        path: Path relative to repo root
+        commits: Number of non-merge commits that changed the path
+        additions: Lines added across those commits
+        deletions: Lines deleted across those commits
+        authors: Number of distinct authors (by email, after .mailmap)
+        since: Start of the period, as given (None for all history)
+    """
+
+    def __init__(
+        self,
+        path: str,
+        commits: int,
+        additions: int,
+        deletions: int,
+        authors: int,
+        since: Optional[str] = None
+    ) -> None:
+        self.path = path
+        self.commits = commits
+        self.additions = additions
+        self.deletions = deletions
+        self.authors = authors
+        self.since = since
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "commits": self.commits,
+            "additions": self.additions,
+            "deletions": self.deletions,
+            "authors": self.authors,
+            "since": self.since
+        }
+
+
+class Contributor:
+    """
+    An author of commits to a file or directory.
+
+    Attributes:
+        name: Author name (after .mailmap; as on the author's latest commit)
+        email: Author email (after .mailmap)
+        commits: Number of commits by the author that touched the path
+        first_commit_date: Author date of their earliest such commit (ISO 8601)
+        last_commit_date: Author date of their latest such commit (ISO 8601)
+    """
+
+    def __init__(
+        self,
+        name: str,
+        email: str,
+        commits: int,
+        first_commit_date: str,
+        last_commit_date: str
+    ) -> None:
+        self.name = name
+        self.email = email
+        self.commits = commits
+        self.first_commit_date = first_commit_date
+        self.last_commit_date = last_commit_date
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "name": self.name,
+            "email": self.email,
+            "commits": self.commits,
+            "firstCommitDate": self.first_commit_date,
+            "lastCommitDate": self.last_commit_date
+        }
+
+
+def get_commit_info(
+    commit: str = "HEAD",
+    mailmap: bool = True,
+    repo_path: str = "."
+) -> CommitInfo:
+    """
+    Read the author, committer, message and trailers of a commit.
+
+    Trailers are parsed by Git, so continuation lines are unfolded and
+    only the message's final trailer block counts. Identities are mapped
+    through .mailmap, so one person's aliases read the same.
+
+    Args:
+        commit: Commit SHA, branch, tag or other revision (default: 'HEAD')
+        mailmap: Map names and emails through .mailmap (default: True)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        CommitInfo
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit
+
+    Examples:
+        >>> info = get_commit_info("HEAD", repo_path="/path/to/repo")
+        >>> info.summary
+        'Fix race in file watcher'
+        >>> info.committer_date
+        '2024-01-15T10:30:00+00:00'
+        >>> info.trailers
+        [('Signed-off-by', 'Jane Doe <jane@example.com>'), ('Co-authored-by', 'Sam Roe <sam@example.com>')]
+    """
+    repo_root = get_repository_root(repo_path)
+
+    commit_format = COMMIT_INFO_FORMAT if mailmap else RAW_COMMIT_INFO_FORMAT
+    output = execute_git_command(
+        ["git", "show", "-s", f"--format={commit_format}", f"{commit}^{{commit}}"],
+        cwd=repo_root
+    )
+
+    return _parse_commit_info(
+        output.split("\0", COMMIT_INFO_FIELDS - 1),
+        set(get_shallow_boundary(repo_root))
+    )
+
+
+def log_range(
+    from_rev: Optional[str],
+    to_rev: str = "HEAD",
+    path_filter: Optional[str] = None,
+    mailmap: bool = True,
+    repo_path: str = "."
+) -> Iterator[CommitInfo]:
+    """
+    Walk the commits in from_rev..to_rev, newest first.
+
+    Commits are read as they are consumed, a page of LOG_PAGE_SIZE at a
+    time, so long ranges can be processed without loading every message.
+    The range is the one ``git log from_rev..to_rev`` shows: commits
+    reachable from to_rev but not from from_rev.
+
+    Args:
+        from_rev: Exclusive start of the range, e.g. the previous release
+            tag (None walks all history reachable from to_rev)
+        to_rev: Inclusive end of the range (default: 'HEAD')
+        path_filter: Only include commits that changed this file or
+            directory (absolute or relative to repo root; default: all)
+        mailmap: Map names and emails through .mailmap (default: True)
+        repo_path: Path within the repository (default: current directory)
+
+    Yields:
+        CommitInfo for each commit in the range
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If from_rev or to_rev does not name a commit
+
+    Examples:
+        >>> for info in log_range("v1.2.0", "HEAD", repo_path="/path/to/repo"):
+        ...     print(info.oid[:7], info.summary)
+        9fceb02 Fix race in file watcher
+        1a410ef Add describe naming a commit by its nearest tag
+    """
+    repo_root = get_repository_root(repo_path)
+
+    command = ["git", "rev-list"]
+    if from_rev is not None:
+        command.append(f"^{from_rev}^{{commit}}")
+    command.append(f"{to_rev}^{{commit}}")
+    if path_filter is not None:
+        command += ["--", resolve_file_path(repo_root, path_filter)]
+
+    oids = execute_git_command(command, cwd=repo_root).split()
+
+    yield from _read_commits(repo_root, oids, mailmap)
+
+
+def count_commits(
+    revspec_range: str = "HEAD",
+    path_filter: Optional[str] = None,
+    repo_path: str = "."
+) -> int:
+    """
+    Count the commits in a revision range, like ``git rev-list --count``.
+
+    Cheaper than walking the commits, for figures such as "this file has
+    213 commits" or repository size metrics. A branch with no commits yet
+    counts 0 for 'HEAD'.
+
+    Args:
+        revspec_range: Revisions as git rev-list takes them, e.g. 'HEAD',
+            'v1.2.0..HEAD' or 'main...feature' (default: 'HEAD')
+        path_filter: Only count commits that changed this file or directory
+            (absolute or relative to repo root; default: all)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        Number of commits
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If revspec_range does not name commits
+        ValueError: If revspec_range is empty
+
+    Examples:
+        >>> count_commits(path_filter="src/parser.py", repo_path="/path/to/repo")
+        213
+        >>> count_commits("v1.2.0..HEAD", repo_path="/path/to/repo")
+        37
+    """
+    revisions = revspec_range.split()
+    if not revisions:
+        raise ValueError("Invalid revspec range: must not be empty")
+
+    repo_root = get_repository_root(repo_path)
+
+    command = ["git", "rev-list", "--count", *revisions]
+    if path_filter is not None:
+        command += ["--", resolve_file_path(repo_root, path_filter)]
+
+    try:
+        return int(execute_git_command(command, cwd=repo_root))
+    except GitCommandError:
+        if revisions == ["HEAD"] and is_head_unborn(repo_root):
+            return 0
+        raise
+
+
+def get_file_history(
+    file_path: str,
+    limit: Optional[int] = None,
+    offset: int = 0,
+    rev: str = "HEAD",
+    follow: bool = False,
+    mailmap: bool = True,
+    repo_path: str = "."
+) -> Iterator[CommitInfo]:
+    """
+    Walk the commits that changed a file or directory, newest first.
+
+    Lists the commits ``git log -- <path>`` shows, with Git's default
+    history simplification. limit and offset page through long histories;
+    commits are read as they are consumed, a page of LOG_PAGE_SIZE at a
+    time. In a shallow clone the history stops at the shallow boundary,
+    where a commit has shallow_boundary set rather than looking like a
+    root commit that added the path. A branch with no commits yet has no
+    history for 'HEAD'.
+
+    Args:
+        file_path: File or directory path (absolute or relative to repo
+            root)
+        limit: Most commits to yield (default: all)
+        offset: Commits to skip from the newest (default: 0)
+        rev: Revision whose history is searched (default: 'HEAD')
+        follow: Continue past renames of a single file, like
+            ``git log --follow`` (default: False)
+        mailmap: Map names and emails through .mailmap (default: True)
+        repo_path: Path within the repository (default: current directory)
+
+    Yields:
+        CommitInfo for each commit that changed the path
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If rev does not name a commit
+        ValueError: If limit or offset is negative
+
+    Examples:
+        >>> [c.summary for c in get_file_history("src/file.py", limit=2, repo_path="/path/to/repo")]
+        ['Fix race in file watcher', 'Add file watcher']
+    """
+    if limit is not None and limit < 0:
+        raise ValueError(f"Invalid limit: {limit}. Must be non-negative")
+    if offset < 0:
+        raise ValueError(f"Invalid offset: {offset}. Must be non-negative")
+
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    command = ["git", "log", "--format=%H"]
+    if offset:
+        command.append(f"--skip={offset}")
+    if limit is not None:
+        command.append(f"--max-count={limit}")
+    if follow:
+        command.append("--follow")
+    command += [f"{rev}^{{commit}}", "--", relative_path or "."]
+
+    try:
+        oids = execute_git_command(command, cwd=repo_root).split()
+    except GitCommandError:
+        if rev == "HEAD" and is_head_unborn(repo_root):
+            return
+        raise
+
+    yield from _read_commits(repo_root, oids, mailmap)
+
+
+def pickaxe(
+    path_filter: Optional[str],
+    needle: str,
+    rev: str = "HEAD",
+    regex: bool = False,
+    limit: Optional[int] = None,
+    repo_path: str = "."
+) -> Iterator[CommitInfo]:
+    """
+    Walk the commits that changed how many times a string occurs, newest first.
+
+    Mirrors ``git log -S``: a commit matches when it adds or removes an
+    occurrence, so the results show when a token or call was introduced and
+    when it went away, but not commits that only move it within a file.
+    Merge commits are not searched.
+
+    Args:
+        path_filter: Only search changes to this file or directory (absolute
+            or relative to repo root; None searches the whole repository)
+        needle: String to count
+        rev: Revision whose history is searched (default: 'HEAD')
+        regex: Treat needle as an extended regular expression
+            (``--pickaxe-regex``; default: False)
+        limit: Most commits to yield (default: all)
+        repo_path: Path within the repository (default: current directory)
+
+    Yields:
+        CommitInfo for each matching commit
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If rev does not name a commit or needle is not a
+            valid regular expression
+        ValueError: If needle is empty or limit is negative
+
+    Examples:
+        >>> [c.summary for c in pickaxe(None, "AKIA", repo_path="/path/to/repo")]
+        ['Remove leaked key', 'Add deploy script']
+    """
+    if not needle:
+        raise ValueError("Invalid needle: must not be empty")
+    if limit is not None and limit < 0:
+        raise ValueError(f"Invalid limit: {limit}. Must not be negative")
+
+    if path_filter is None:
+        repo_root, relative_path = get_repository_root(repo_path), ""
+    else:
+        repo_root, relative_path = resolve_repository_path(repo_path, path_filter)
+
+    command = ["git", "log", "--format=%H", f"-S{needle}"]
+    if regex:
+        command.append("--pickaxe-regex")
+    if limit is not None:
+        command.append(f"--max-count={limit}")
+    command += [f"{rev}^{{commit}}", "--", relative_path or "."]
+
+    oids = execute_git_command(command, cwd=repo_root).split()
+
+    yield from _read_commits(repo_root, oids)
+
+
+def search_commits(
+    pattern: str,
+    limit: Optional[int] = None,
+    regex: bool = False,
+    ignore_case: bool = False,
+    rev: str = "HEAD",
+    repo_path: str = "."
+) -> Iterator[CommitInfo]:
+    """
+    Walk the commits whose message matches a pattern, newest first.
+
+    Useful for resolving a ticket ID mentioned in commit messages (e.g.
+    'PROJ-123') to the commits that implement it.
+
+    Args:
+        pattern: Substring to look for, or an extended regular expression
+            with regex
+        limit: Most commits to yield (default: all)
+        regex: Treat pattern as an extended regular expression (default:
+            False, a literal substring)
+        ignore_case: Match case-insensitively (default: False)
+        rev: Revision whose history is searched (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Yields:
+        CommitInfo for each matching commit
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If rev does not name a commit or pattern is not a
+            valid regular expression
+        ValueError: If pattern is empty or limit is negative
+
+    Examples:
+        >>> [c.oid[:7] for c in search_commits("PROJ-123", repo_path="/path/to/repo")]
+        ['abc1234', 'def5678']
+    """
+    if not pattern:
+        raise ValueError("Invalid pattern: must not be empty")
+    if limit is not None and limit < 0:
+        raise ValueError(f"Invalid limit: {limit}. Must not be negative")
+
+    repo_root = get_repository_root(repo_path)
+
+    command = ["git", "log", "--format=%H", f"--grep={pattern}", "-E" if regex else "-F"]
+    if ignore_case:
+        command.append("-i")
+    if limit is not None:
+        command.append(f"--max-count={limit}")
+    command.append(f"{rev}^{{commit}}")
+
+    oids = execute_git_command(command, cwd=repo_root).split()
+
+    yield from _read_commits(repo_root, oids)
+
+
+def get_file_last_modified(
+    file_path: str,
+    rev: str = "HEAD",
+    repo_path: str = "."
+) -> Optional[str]:
+    """
+    Get when a file or directory was last changed, according to history.
+
+    Returns the committer date of the most recent commit touching the path,
+    the right "last modified" for generated documentation: unlike the
+    filesystem mtime it does not change on checkout or clone.
+
+    Args:
+        file_path: File or directory path (absolute or relative to repo root)
+        rev: Revision whose history is searched (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        ISO 8601 timestamp in the committer's time zone, or None if no
+        commit touches the path
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If rev does not name a commit
+
+    Examples:
+        >>> get_file_last_modified("docs/guide.md", repo_path="/path/to/repo")
+        '2024-01-15T10:30:00+02:00'
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    output = execute_git_command(
+        ["git", "log", "-1", "--format=%cI", f"{rev}^{{commit}}", "--", relative_path or "."],
+        cwd=repo_root
+    )
+    return output or None
+
+
+def get_file_churn(
+    file_path: str,
+    since: Optional[Union[str, datetime]] = None,
+    rev: str = "HEAD",
+    mailmap: bool = True,
+    repo_path: str = "."
+) -> FileChurn:
+    """
+    Measure how often and how much a file or directory changed.
+
+    Counts the non-merge commits in rev's history that touched the path and
+    the lines they added and deleted there. Binary changes count as commits
+    but add no lines.
+
+    Args:
+        file_path: File or directory path (absolute or relative to repo root)
+        since: Only count commits after this date: a datetime, or any date
+            Git understands, e.g. '2024-01-01' or '3 months ago' (default:
+            all history)
+        rev: Revision whose history is searched (default: 'HEAD')
+        mailmap: Map author emails through .mailmap before counting
+            distinct authors (default: True)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        FileChurn
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If rev does not name a commit
+
+    Examples:
+        >>> get_file_churn("src/parser.py", since="6 months ago", repo_path="/path/to/repo").to_dict()
+        {'path': 'src/parser.py', 'commits': 14, 'additions': 420, 'deletions': 311, 'authors': 3, ...}
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+    if isinstance(since, datetime):
+        since = since.isoformat()
+
+    identity = "%aE" if mailmap else "%ae"
+    command = ["git", "log", "--no-merges", "--numstat", f"--format=%x00{identity}"]
+    if since is not None:
+        command.append(f"--since={since}")
+    command += [f"{rev}^{{commit}}", "--", relative_path or "."]
+
+    output = execute_git_command(command, cwd=repo_root)
+
+    commits = additions = deletions = 0
+    authors: set[str] = set()
+    for line in output.split("\n"):
+        if line.startswith("\0"):
+            commits += 1
+            authors.add(line[1:].lower())
+            continue
+        # "<added>\t<deleted>\t<path>", "-" for binary files
+        fields = line.split("\t")
+        if len(fields) == 3 and fields[0] != "-":
+            additions += int(fields[0])
+            deletions += int(fields[1])
+
+    return FileChurn(relative_path, commits, additions, deletions, len(authors), since)
+
+
+def get_file_contributors(
+    file_path: str,
+    rev: str = "HEAD",
+    mailmap: bool = True,
+    repo_path: str = "."
+) -> list[Contributor]:
+    """
+    Summarize who committed to a file or directory, like ``git shortlog``.
+
+    Authors are mapped through .mailmap and told apart by email, ignoring
+    case, so one person committing under several names is counted once.
+
+    Args:
+        file_path: File or directory path (absolute or relative to repo root)
+        rev: Revision whose history is searched (default: 'HEAD')
+        mailmap: Map names and emails through .mailmap (default: True)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        Contributor list, most commits first, then by name
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If rev does not name a commit
+
+    Examples:
+        >>> [(c.name, c.commits) for c in get_file_contributors("src/app.py", repo_path="/path/to/repo")]
+        [('Alice', 12), ('Bob', 3)]
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    identity = "%aN%x00%aE" if mailmap else "%an%x00%ae"
+    output = execute_git_command(
+        [
+            "git", "log", "-z", f"--format={identity}%x00%aI", f"{rev}^{{commit}}",
+            "--", relative_path or "."
+        ],
+        cwd=repo_root
+    )
+
+    contributors: dict[str, Contributor] = {}
+    fields = output.split("\0") if output else []
+    for offset in range(0, len(fields) - 2, 3):
+        name, email, date = fields[offset:offset + 3]
+        contributor = contributors.get(email.lower())
+        if contributor is None:
+            contributors[email.lower()] = Contributor(name, email, 1, date, date)
+            continue
+        contributor.commits += 1
+        moment = datetime.fromisoformat(date)
+        if moment < datetime.fromisoformat(contributor.first_commit_date):
+            contributor.first_commit_date = date
+        if moment > datetime.fromisoformat(contributor.last_commit_date):
+            contributor.name = name
+            contributor.last_commit_date = date
+
+    return sorted(
+        contributors.values(),
+        key=lambda contributor: (-contributor.commits, contributor.name)
+    )
+
+
+def get_file_creation_commit(
+    file_path: str,
+    rev: str = "HEAD",
+    repo_path: str = "."
+) -> Optional[FileOrigin]:
+    """
+    Find the commit that first added a file, following renames back.
+
+    Uses ``git log --follow``, so renames are detected by content
+    similarity; a file deleted and later re-added under the same name is
+    traced to its first addition.
+
+    Args:
+        file_path: File path at rev (absolute or relative to repo root)
+        rev: Revision whose history is searched (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        FileOrigin, or None if no commit in the history adds the file
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If rev does not name a commit
+
+    Examples:
+        >>> origin = get_file_creation_commit("src/parser.py", repo_path="/path/to/repo")
+        >>> origin.commit.oid[:7], origin.path
+        ('abc1234', 'lib/parse.py')
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    output = execute_git_command(
+        [
+            "git", "log", "--follow", "--diff-filter=A", "--name-only", "-z", "--format=%H",
+            f"{rev}^{{commit}}", "--", relative_path
+        ],
+        cwd=repo_root
+    )
+
+    # "<commit>\0\n<path>\0" per addition, newest first
+    tokens = output.split("\0")
+    if tokens[-1] == "":
+        tokens.pop()
+    if len(tokens) < 2:
+        return None
+    oid, path = tokens[-2:]
+
+    commit = next(_read_commits(repo_root, [oid]))
+    return FileOrigin(commit, path.removeprefix("\n"))
+
+
+def _read_commits(
+    repo_root: str,
+    oids: list[str],
+    mailmap: bool = True
+) -> Iterator[CommitInfo]:
+    """
+    Read commits in order, a page of LOG_PAGE_SIZE per git invocation.
+
+    Args:
+        repo_root: Repository root path
+        oids: Commit SHAs
+        mailmap: Map names and emails through .mailmap (default: True)
+
+    Yields:
+        CommitInfo for each commit
+    """
+    commit_format = COMMIT_INFO_FORMAT if mailmap else RAW_COMMIT_INFO_FORMAT
+    shallow_boundary = set(get_shallow_boundary(repo_root)) if oids else set()
+    for start in range(0, len(oids), LOG_PAGE_SIZE):
+        page = oids[start:start + LOG_PAGE_SIZE]
+        output = execute_git_command(
+            ["git", "log", "--no-walk=unsorted", "-z", f"--format={commit_format}", *page],
+            cwd=repo_root
+        )
+        # -z separates commits with the same NUL that separates fields
+        fields = output.split("\0")
+        for offset in range(0, len(fields) - COMMIT_INFO_FIELDS + 1, COMMIT_INFO_FIELDS):
+            yield _parse_commit_info(
+                fields[offset:offset + COMMIT_INFO_FIELDS],
+                shallow_boundary
+            )
+
+
+def _read_authors(repo_root: str, oids: list[str]) -> dict[str, tuple[str, str]]:
+    """
+    Read the author name and email recorded in commits, ignoring .mailmap.
+
+    Args:
+        repo_root: Repository root path
+        oids: Commit SHAs
+
+    Returns:
+        Dictionary mapping commit SHA to (name, email)
+    """
+    authors: dict[str, tuple[str, str]] = {}
+    for start in range(0, len(oids), LOG_PAGE_SIZE):
+        page = oids[start:start + LOG_PAGE_SIZE]
+        output = execute_git_command(
+            ["git", "log", "--no-walk=unsorted", "-z", "--format=%H%x00%an%x00%ae", *page],
+            cwd=repo_root
+        )
+        fields = output.split("\0")
+        for offset in range(0, len(fields) - 2, 3):
+            oid, name, email = fields[offset:offset + 3]
+            authors[oid] = (name, email)
+    return authors
+
+
+def _parse_commit_info(fields: list[str], shallow_boundary: set[str]) -> CommitInfo:
+    """
+    Build a CommitInfo from the fields of COMMIT_INFO_FORMAT.
+
+    Args:
+        fields: The COMMIT_INFO_FIELDS values of one commit
+        shallow_boundary: Commits at which the clone's history is cut off
+
+    Returns:
+        CommitInfo
+    """
+    (
+        oid, parents, author_name, author_email, author_date,
+        committer_name, committer_email, committer_date, trailer_block, message
+    ) = fields
+    message = message.rstrip()
+
+    trailers: list[tuple[str, str]] = []
+    for line in trailer_block.splitlines():
+        key, separator, value = line.partition(":")
+        if separator:
+            trailers.append((key.strip(), value.strip()))
+
+    return CommitInfo(
+        oid,
+        parents.split(),
+        author_name,
+        author_email,
+        author_date,
+        committer_name,
+        committer_email,
+        committer_date,
+        message,
+        trailers,
+        oid in shallow_boundary
+    )
+
+
+def find_commit_for_blob(
+    file_path: str,
+    blob_oid: str,
+    rev: str = "HEAD",
+    repo_path: str = "."
+) -> list[CommitInfo]:
+    """
+    Find the commits that set a file to a given blob.
+
+    Answers which revision produced a copy of a file: hash it with
+    ``git hash-object`` and look the blob up at the file's path. Each
+    returned commit changed the path to exactly that content; the commits
+    after it, up to the next change of the path, hold it too. A blob that
+    only appears through a merge's conflict resolution is not found, as
+    merges are not diffed.
+
+    Args:
+        file_path: File path (absolute or relative to repo root)
+        blob_oid: Blob SHA (full 40 hex characters)
+        rev: Revision whose history is searched (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        CommitInfo list, newest first; empty if the path never held the blob
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If rev does not name a commit
+        InvalidHashError: If blob_oid is not a valid Git hash
+
+    Examples:
+        >>> [c.oid[:7] for c in find_commit_for_blob("config.yml", "def456abc789...", repo_path="/path/to/repo")]
+        ['abc1234']
+    """
+    validate_git_hash(blob_oid, "blobOid")
+    blob_oid = blob_oid.lower()
+
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    output = execute_git_command(
+        [
+            "git", "log", "--raw", "--no-abbrev", "--no-renames", "-z", "--format=%x01%H",
+            f"{rev}^{{commit}}", "--", relative_path
+        ],
+        cwd=repo_root
+    )
+
+    # "\x01<commit>" headers, then ":<old mode> <new mode> <old oid> <new oid>
+    # <status>\0<path>" per changed file
+    oids: list[str] = []
+    commit = ""
+    tokens = iter(output.split("\0"))
+    for token in tokens:
+        token = token.lstrip("\n")
+        if token.startswith("\x01"):
+            commit = token[1:]
+        elif token.startswith(":"):
+            path = next(tokens, "")
+            if path == relative_path and token.split(" ")[3] == blob_oid and commit not in oids:
+                oids.append(commit)
+
+    return list(_read_commits(repo_root, oids))
+
+
+__all__ = [
+    "CommitInfo",
+    "get_commit_info",
+    "log_range",
+    "count_commits",
+    "get_file_history",
+    "pickaxe",
+    "search_commits",
+    "get_file_last_modified",
+    "FileChurn",
+    "get_file_churn",
+    "Contributor",
+    "get_file_contributors",
+    "FileOrigin",
+    "get_file_creation_commit",
+    "find_commit_for_blob",
+]
@@ -18,7 +18,7 @@ from ..errors import (
     RepositoryNotFoundError,
 )
 from ..utils.path import normalize_file_path
-from ..utils.url import build_github_url
+from ..utils.url import build_github_api_contents_url, build_github_url
 
 
 def get_github_metadata(
@@ -70,10 +70,7 @@ def get_github_metadata(
 
     # Build API URLs
     # 1. Contents API - get file SHA and metadata
-    contents_url = (
-        f"https://api.github.com/repos/{owner}/{repo}/contents/{normalized_path}"
-        f"?ref={branch}"
-    )
+    contents_url = build_github_api_contents_url(owner, repo, normalized_path, branch)
 
     # 2. Commits API - get latest commit for file
     commits_url = (
@@ -12,10 +12,12 @@ from ..errors import FileNotFoundError, RepositoryNotFoundError
 from ..utils.git import (
     execute_git_command,
     get_current_branch,
+    get_head_commit,
     get_remote_url,
     get_repository_root,
+    resolve_repository_path,
 )
-from ..utils.path import normalize_file_path, resolve_file_path
+from ..utils.path import normalize_file_path
 from ..utils.url import parse_github_url
 
 
@@ -42,6 +44,7 @@ def get_local_metadata(
     Raises:
         RepositoryNotFoundError: If path is not in a Git repository
         FileNotFoundError: If file is not tracked by Git
+        UnbornHeadError: If the repository has no commits yet
 
     Examples:
         >>> meta = get_local_metadata("/path/to/repo", "src/file.py")
@@ -60,8 +63,8 @@ def get_local_metadata(
             cause=e
         ) from e
 
-    # Resolve file path relative to repo root
-    relative_path = resolve_file_path(repo_root, file_path)
+    # Resolve file path relative to the owning repository (submodules included)
+    repo_root, relative_path = resolve_repository_path(repo_root, file_path)
 
     # Check if file exists in repository
     if not is_file_in_git(repo_root, relative_path):
@@ -70,9 +73,12 @@ def get_local_metadata(
             file_path=relative_path
         )
 
+    # A file staged before the first commit has no history yet
+    get_head_commit(repo_root)
+
     # Get commit hash for latest commit affecting this file
     commit_hash = execute_git_command(
-        f'git log -1 --pretty=format:%H -- "{relative_path}"',
+        ["git", "log", "-1", "--pretty=format:%H", "--", relative_path],
         cwd=repo_root
     )
 
@@ -84,7 +90,7 @@ def get_local_metadata(
 
     # Get last modified timestamp (committer date in ISO 8601)
     last_modified = execute_git_command(
-        f'git log -1 --pretty=format:%cI -- "{relative_path}"',
+        ["git", "log", "-1", "--pretty=format:%cI", "--", relative_path],
         cwd=repo_root
     )
 
@@ -93,7 +99,7 @@ def get_local_metadata(
 
     # Get file hash (blob SHA) using git ls-tree
     ls_tree_output = execute_git_command(
-        f'git ls-tree HEAD "{relative_path}"',
+        ["git", "ls-tree", "HEAD", relative_path],
         cwd=repo_root
     )
 
@@ -144,7 +150,7 @@ def is_file_in_git(repo_path: str, file_path: str) -> bool:
     try:
         # Try to get file info from git ls-files
         output = execute_git_command(
-            f'git ls-files --error-unmatch "{file_path}"',
+            ["git", "ls-files", "--error-unmatch", file_path],
             cwd=repo_path
         )
         return bool(output)
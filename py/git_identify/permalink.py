@@ -0,0 +1,1149 @@
+"""
+GitHub permalink generation for local repository paths.
+
+Resolves a file or directory inside a local Git repository to a
+GitHub URL using the repository's remote and the HEAD commit, branch or tag.
+"""
+
+import hashlib
+import os
+import re
+import warnings
+from typing import Any, Literal, Optional
+from urllib.parse import unquote
+
+from .errors import FileNotFoundError, GitCommandError, GitError
+from .utils.git import (
+    execute_git_command,
+    get_blob_bytes,
+    get_current_branch,
+    get_default_branch,
+    get_default_remote,
+    get_head_commit,
+    get_head_tag,
+    get_last_commit_for_file,
+    get_nearest_pushed_ancestor,
+    get_tracking_branch,
+    get_remote_url,
+    get_repository_root,
+    is_ancestor,
+    is_commit_on_remote,
+    is_head_unborn,
+    is_shallow,
+    resolve_repository_path,
+)
+from .utils.hash import is_valid_git_hash
+from .utils.url import (
+    ARCHIVE_FORMATS,
+    GITHUB_LINK_PATTERN,
+    ArchiveFormat,
+    build_github_api_contents_url,
+    build_github_archive_url,
+    build_github_compare_url,
+    build_github_pull_request_url,
+    build_github_url,
+    parse_github_link,
+    parse_github_url,
+)
+
+# How the ref segment of a URL is chosen:
+# - commit: pinned HEAD commit SHA (permalink)
+# - branch: current branch name ("living" link that follows the branch)
+# - tag: tag pointing exactly at HEAD
+# - tag-or-commit: HEAD tag when there is one, otherwise the commit SHA
+# - last-commit: most recent commit that modified the path (stable across unrelated commits)
+# - default-branch: the remote's default branch ("latest on main" link)
+RefStrategy = Literal["commit", "branch", "tag", "tag-or-commit", "last-commit", "default-branch"]
+
+REF_STRATEGIES = ("commit", "branch", "tag", "tag-or-commit", "last-commit", "default-branch")
+
+# What to do when the linked commit is not on the remote:
+# - allow: link it anyway (the URL 404s until it is pushed)
+# - warn: link it anyway and emit a UserWarning through the warnings module
+# - fallback: link the nearest pushed ancestor instead
+# - error: raise GitError with code COMMIT_NOT_PUSHED
+UnpushedPolicy = Literal["allow", "warn", "fallback", "error"]
+
+UNPUSHED_POLICIES = ("allow", "warn", "fallback", "error")
+
+# Tree entry mode of a symbolic link
+SYMLINK_MODE = "120000"
+
+# Working tree state of a path compared to a revision; 'untracked' (not in
+# the revision) is only reported by check_drift
+DriftStatus = Literal["identical", "modified", "missing", "untracked"]
+
+# How a pull request was matched to a commit:
+# - ref: a fetched PR head ref (refs/pull/<n>/head or <remote>/pr/<n>) contains the commit
+# - message: the commit message references the PR ("Fix bug (#123)", "Merge pull request #123")
+# - merge: the mainline merge commit that brought the commit in references the PR
+PullRequestSource = Literal["ref", "message", "merge"]
+
+# Fetched PR head refs: refs/pull/123/head, refs/remotes/origin/pull/123/head, refs/remotes/origin/pr/123
+PULL_REQUEST_REF_PATTERN = re.compile(
+    r"^refs/(?:remotes/[^/]+/)?(?:pull/(\d+)/head|pr/(\d+))$"
+)
+
+# What a ref name cannot contain (see git check-ref-format); URL refs matching
+# it are rejected rather than read as revision syntax such as main~1 or @{-1}
+INVALID_REF_NAME_PATTERN = re.compile(r"[\x00-\x20\x7f~^:?*\[\\]|\.\.|@\{|^[-/]|/$|//")
+
+# PR references in commit subjects: squash merges "... (#123)" and merge commits "Merge pull request #123"
+PULL_REQUEST_MESSAGE_PATTERN = re.compile(r"(?:\(#(\d+)\)\s*$|^Merge pull request #(\d+))")
+
+
+class LinkLocation:
+    """
+    Local resolution of a GitHub link.
+
+    Attributes:
+        kind: 'blob' for files, 'tree' for directories
+        commit_hash: Commit SHA the link's ref resolved to
+        path: Path relative to the repository root
+        absolute_path: On-disk path in the local working tree
+        line_range: (start, end) line numbers from the link anchor, or None
+        blob_hash: Blob SHA of the file at the linked commit (None for directories)
+        working_hash: Blob SHA of the working tree file (None if missing or a directory)
+        status: 'identical', 'modified' or 'missing' compared to the linked revision
+    """
+
+    def __init__(
+        self,
+        kind: str,
+        commit_hash: str,
+        path: str,
+        absolute_path: str,
+        status: DriftStatus,
+        line_range: Optional[tuple[int, int]] = None,
+        blob_hash: Optional[str] = None,
+        working_hash: Optional[str] = None
+    ) -> None:
+        self.kind = kind
+        self.commit_hash = commit_hash
+        self.path = path
+        self.absolute_path = absolute_path
+        self.status = status
+        self.line_range = line_range
+        self.blob_hash = blob_hash
+        self.working_hash = working_hash
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        line_range = None
+        if self.line_range:
+            line_range = {"start": self.line_range[0], "end": self.line_range[1]}
+
+        return {
+            "kind": self.kind,
+            "commitHash": self.commit_hash,
+            "path": self.path,
+            "absolutePath": self.absolute_path,
+            "lineRange": line_range,
+            "blobHash": self.blob_hash,
+            "workingHash": self.working_hash,
+            "status": self.status
+        }
+
+
+class PullRequest:
+    """
+    Pull request that introduced a commit.
+
+    Attributes:
+        number: Pull request number
+        url: GitHub pull request URL
+        commit_hash: Commit SHA the pull request was looked up for
+        source: How the pull request was matched ('ref', 'message' or 'merge')
+    """
+
+    def __init__(
+        self,
+        number: int,
+        url: str,
+        commit_hash: str,
+        source: PullRequestSource
+    ) -> None:
+        self.number = number
+        self.url = url
+        self.commit_hash = commit_hash
+        self.source = source
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "number": self.number,
+            "url": self.url,
+            "commitHash": self.commit_hash,
+            "source": self.source
+        }
+
+
+def generate_github_url(
+    file_path: str,
+    is_directory: Optional[bool] = None,
+    line_start: Optional[int] = None,
+    line_end: Optional[int] = None,
+    plain: Optional[bool] = None,
+    ref_strategy: RefStrategy = "commit",
+    resolve_ssh_aliases: bool = True,
+    remote: Optional[str] = None,
+    prefer_upstream: bool = False,
+    unpushed: UnpushedPolicy = "allow",
+    repo_path: str = "."
+) -> str:
+    """
+    Generate a GitHub permalink for a file or directory in a local repository.
+
+    By default the URL is pinned to the current HEAD commit; ``ref_strategy``
+    selects the branch name or HEAD tag instead. Directories are linked
+    with ``/tree/`` and files with ``/blob/``. Paths inside a submodule are
+    linked against the submodule's remote and commit.
+
+    Args:
+        file_path: File or directory path (absolute or relative to repo root)
+        is_directory: Force directory (True) or file (False) links; detected
+            from the working tree when None
+        line_start: First line to anchor (optional, files only)
+        line_end: Last line of the anchored range (optional)
+        plain: Force (True) or suppress (False) ``?plain=1``; detected from
+            the file extension when None
+        ref_strategy: Ref used in the URL: 'commit' (default), 'branch' (the
+            upstream branch name when linking against the tracked remote),
+            'tag', 'tag-or-commit' (release-pinned link when HEAD is tagged)
+            'last-commit' (last commit that modified the path) or
+            'default-branch' (the remote's default branch, e.g. main)
+        resolve_ssh_aliases: Resolve ~/.ssh/config host aliases in the remote
+            URL (default: True)
+        remote: Remote whose URL is used (default: the current branch's
+            remote, so a branch tracking ``upstream/main`` links to
+            ``upstream``; then 'origin', then the first remote)
+        prefer_upstream: When no remote is named and both ``origin`` (a
+            fork) and ``upstream`` exist, use ``upstream`` (default: False)
+        unpushed: When the linked commit is not on the remote: 'allow'
+            (default), 'warn' and link it anyway, 'fallback' to the nearest
+            pushed ancestor, or 'error'
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        GitHub permalink URL
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitError: If the repository has no GitHub remote, or the requested
+            ref strategy cannot be satisfied (detached HEAD, untagged HEAD,
+            unknown default branch), or the commit is not pushed and
+            ``unpushed`` is 'error' (or 'fallback' with no pushed ancestor)
+        UnbornHeadError: If the current branch has no commits yet (every
+            strategy except 'branch' and 'default-branch', which only need
+            a branch name)
+        FileNotFoundError: If ref_strategy is 'last-commit' and the path has
+            never been committed
+        ValueError: If ref_strategy or unpushed is invalid
+
+    Examples:
+        >>> generate_github_url("src/file.py", repo_path="/path/to/repo")
+        'https://github.com/user/repo/blob/abc123.../src/file.py'
+
+        >>> generate_github_url("src", repo_path="/path/to/repo")
+        'https://github.com/user/repo/tree/abc123.../src'
+
+        >>> generate_github_url("src/file.py", repo_path="/path/to/repo", line_start=10, line_end=25)
+        'https://github.com/user/repo/blob/abc123.../src/file.py#L10-L25'
+
+        >>> generate_github_url("src/file.py", repo_path="/path/to/repo", ref_strategy="branch")
+        'https://github.com/user/repo/blob/main/src/file.py'
+    """
+    if ref_strategy not in REF_STRATEGIES:
+        raise ValueError(
+            f"Invalid ref strategy: {ref_strategy}. "
+            "Must be 'commit', 'branch', 'tag', 'tag-or-commit', 'last-commit' or 'default-branch'"
+        )
+
+    if unpushed not in UNPUSHED_POLICIES:
+        raise ValueError(
+            f"Invalid unpushed policy: {unpushed}. Must be 'allow', 'warn', 'fallback' or 'error'"
+        )
+
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    # Repository root resolves to "." - link it as the top-level tree
+    if relative_path == ".":
+        relative_path = ""
+
+    if is_directory is None:
+        is_directory = os.path.isdir(os.path.join(repo_root, relative_path))
+
+    parsed = _github_remote(repo_root, resolve_ssh_aliases, remote, prefer_upstream)
+    ref = _resolve_ref(repo_root, ref_strategy, relative_path, remote, prefer_upstream)
+
+    if unpushed != "allow" and is_valid_git_hash(ref):
+        ref = _pushed_commit(
+            repo_root,
+            ref,
+            remote or get_default_remote(repo_root, prefer_upstream),
+            unpushed
+        )
+
+    return build_github_url(
+        parsed["owner"],
+        parsed["repo"],
+        ref,
+        relative_path,
+        is_directory=is_directory,
+        line_start=line_start,
+        line_end=line_end,
+        plain=plain
+    )
+
+
+def generate_github_api_contents_url(
+    file_path: str,
+    resolve_ssh_aliases: bool = True,
+    remote: Optional[str] = None,
+    prefer_upstream: bool = False,
+    repo_path: str = "."
+) -> str:
+    """
+    Generate a GitHub REST API contents URL for a path at the HEAD commit.
+
+    Uses the same identification data as ``generate_github_url`` so
+    automation can fetch the file through the API instead of the web UI.
+
+    Args:
+        file_path: File or directory path (absolute or relative to repo root)
+        resolve_ssh_aliases: Resolve ~/.ssh/config host aliases in the remote
+            URL (default: True)
+        remote: Remote whose URL is used (default: the current branch's
+            remote, then 'origin', then the first remote)
+        prefer_upstream: When no remote is named and both ``origin`` (a
+            fork) and ``upstream`` exist, use ``upstream`` (default: False)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        GitHub REST API contents URL pinned to the HEAD commit
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitError: If the repository has no GitHub remote
+
+    Examples:
+        >>> generate_github_api_contents_url("src/file.py", repo_path="/path/to/repo")
+        'https://api.github.com/repos/user/repo/contents/src/file.py?ref=abc123...'
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+    parsed = _github_remote(repo_root, resolve_ssh_aliases, remote, prefer_upstream)
+    commit_hash = _resolve_rev(repo_root, "HEAD")
+
+    return build_github_api_contents_url(parsed["owner"], parsed["repo"], relative_path, commit_hash)
+
+
+def generate_github_compare_url(
+    base: str,
+    head: str = "HEAD",
+    resolve_ssh_aliases: bool = True,
+    remote: Optional[str] = None,
+    prefer_upstream: bool = False,
+    repo_path: str = "."
+) -> str:
+    """
+    Generate a GitHub compare URL between two revisions of a local repository.
+
+    Both revisions are resolved to commit SHAs in the local repository, so
+    the URL keeps showing the same diff after the branches move.
+
+    Args:
+        base: Base revision (commit SHA, branch, tag or other rev)
+        head: Head revision (default: 'HEAD')
+        resolve_ssh_aliases: Resolve ~/.ssh/config host aliases in the remote
+            URL (default: True)
+        remote: Remote whose URL is used (default: the current branch's
+            remote, then 'origin', then the first remote)
+        prefer_upstream: When no remote is named and both ``origin`` (a
+            fork) and ``upstream`` exist, use ``upstream`` (default: False)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        GitHub compare URL
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitError: If the repository has no GitHub remote, or a revision
+            cannot be resolved
+
+    Examples:
+        >>> generate_github_compare_url("v1.0.0", repo_path="/path/to/repo")
+        'https://github.com/user/repo/compare/abc123...def456'
+    """
+    repo_root = get_repository_root(repo_path)
+    parsed = _github_remote(repo_root, resolve_ssh_aliases, remote, prefer_upstream)
+
+    return build_github_compare_url(
+        parsed["owner"],
+        parsed["repo"],
+        _resolve_rev(repo_root, base),
+        _resolve_rev(repo_root, head)
+    )
+
+
+def generate_github_archive_url(
+    rev: str = "HEAD",
+    archive_format: ArchiveFormat = "tar.gz",
+    resolve_ssh_aliases: bool = True,
+    remote: Optional[str] = None,
+    prefer_upstream: bool = False,
+    repo_path: str = "."
+) -> str:
+    """
+    Generate a GitHub source archive URL for a revision of a local repository.
+
+    The revision is resolved to its commit SHA so build tooling fetches the
+    exact snapshot a file was identified at.
+
+    Args:
+        rev: Revision to archive (default: 'HEAD')
+        archive_format: 'tar.gz' (default) or 'zip'
+        resolve_ssh_aliases: Resolve ~/.ssh/config host aliases in the remote
+            URL (default: True)
+        remote: Remote whose URL is used (default: the current branch's
+            remote, then 'origin', then the first remote)
+        prefer_upstream: When no remote is named and both ``origin`` (a
+            fork) and ``upstream`` exist, use ``upstream`` (default: False)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        GitHub archive URL
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitError: If the repository has no GitHub remote, or the revision
+            cannot be resolved
+        ValueError: If archive_format is invalid
+
+    Examples:
+        >>> generate_github_archive_url(repo_path="/path/to/repo", archive_format="zip")
+        'https://github.com/user/repo/archive/abc123....zip'
+    """
+    if archive_format not in ARCHIVE_FORMATS:
+        raise ValueError(f"Invalid archive format: {archive_format}. Must be 'tar.gz' or 'zip'")
+
+    repo_root = get_repository_root(repo_path)
+    parsed = _github_remote(repo_root, resolve_ssh_aliases, remote, prefer_upstream)
+    commit_hash = _resolve_rev(repo_root, rev)
+
+    return build_github_archive_url(parsed["owner"], parsed["repo"], commit_hash, archive_format)
+
+
+def find_pull_request_for_commit(
+    commit: str = "HEAD",
+    resolve_ssh_aliases: bool = True,
+    remote: Optional[str] = None,
+    prefer_upstream: bool = False,
+    repo_path: str = "."
+) -> Optional[PullRequest]:
+    """
+    Find the pull request that introduced a commit.
+
+    Only local data is used, in this order:
+
+    1. The commit's own subject (``Fix bug (#123)`` from squash merges, or
+       ``Merge pull request #123``)
+    2. Fetched PR head refs (``refs/pull/<n>/head`` or ``<remote>/pr/<n>``)
+       containing the commit; a ref pointing exactly at the commit wins,
+       otherwise the lowest PR number
+    3. The oldest first-parent merge commit on HEAD's history that contains
+       the commit, if its subject references a PR and the commit came in
+       through the merged branch rather than being on the mainline already
+
+    PR head refs are only present if the remote is fetched with a refspec
+    such as ``+refs/pull/*/head:refs/remotes/origin/pr/*``.
+
+    Args:
+        commit: Commit SHA or other revision (default: 'HEAD')
+        resolve_ssh_aliases: Resolve ~/.ssh/config host aliases in the remote
+            URL (default: True)
+        remote: Remote whose URL is used (default: the current branch's
+            remote, then 'origin', then the first remote)
+        prefer_upstream: When no remote is named and both ``origin`` (a
+            fork) and ``upstream`` exist, use ``upstream`` (default: False)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        PullRequest, or None if no pull request could be found
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitError: If the repository has no GitHub remote, or the commit
+            cannot be resolved
+
+    Examples:
+        >>> pr = find_pull_request_for_commit("abc123", repo_path="/path/to/repo")
+        >>> pr.url
+        'https://github.com/user/repo/pull/123'
+    """
+    repo_root = get_repository_root(repo_path)
+    parsed = _github_remote(repo_root, resolve_ssh_aliases, remote, prefer_upstream)
+    commit_hash = _resolve_rev(repo_root, commit)
+
+    match = _pull_request_from_message(repo_root, commit_hash)
+    source: PullRequestSource = "message"
+
+    if match is None:
+        match = _pull_request_from_refs(repo_root, commit_hash)
+        source = "ref"
+
+    if match is None:
+        try:
+            merges = execute_git_command(
+                [
+                    "git", "rev-list", "--first-parent", "--merges", "--ancestry-path", "--reverse",
+                    f"{commit_hash}..HEAD"
+                ],
+                cwd=repo_root
+            ).splitlines()
+        except GitCommandError:
+            merges = []
+        # A commit made directly on the mainline is already in the merge's
+        # first parent; the merge did not bring it in
+        if merges and not is_ancestor(repo_root, commit_hash, f"{merges[0]}^1"):
+            match = _pull_request_from_message(repo_root, merges[0])
+            source = "merge"
+
+    if match is None:
+        return None
+
+    return PullRequest(
+        number=match,
+        url=build_github_pull_request_url(parsed["owner"], parsed["repo"], match),
+        commit_hash=commit_hash,
+        source=source
+    )
+
+
+def canonicalize_github_url(
+    url: str,
+    remote: Optional[str] = None,
+    repo_path: str = "."
+) -> str:
+    """
+    Rewrite a GitHub branch or tag URL into a commit permalink.
+
+    The programmatic equivalent of pressing "y" on GitHub: the ref in a
+    ``/blob/<ref>/<path>`` or ``/tree/<ref>/<path>`` URL is resolved in the
+    local repository and replaced with its commit SHA. Remote-tracking
+    branches (``<remote>/<ref>``) are preferred over local refs because they
+    reflect what the link showed on GitHub. Query string and line anchors
+    are preserved.
+
+    Args:
+        url: GitHub blob or tree URL
+        remote: Remote whose tracking branches are preferred (default: the
+            current branch's remote, then 'origin', then the first remote)
+        repo_path: Path within a local clone of the repository
+
+    Returns:
+        Commit-pinned GitHub URL (unchanged if already pinned to a SHA)
+
+    Raises:
+        ValueError: If url is not a GitHub blob or tree URL
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitError: If the ref cannot be resolved in the local repository
+
+    Examples:
+        >>> canonicalize_github_url(
+        ...     "https://github.com/user/repo/blob/main/src/file.py#L10", repo_path="/path/to/repo"
+        ... )
+        'https://github.com/user/repo/blob/abc123.../src/file.py#L10'
+    """
+    match = GITHUB_LINK_PATTERN.match(url)
+    if not match:
+        raise ValueError(f"Not a GitHub blob or tree URL: {url}")
+
+    owner, repo, kind, ref_and_path, query, fragment = match.groups()
+    segments = ref_and_path.strip("/").split("/")
+
+    # Already a permalink
+    if is_valid_git_hash(segments[0]):
+        return url
+
+    repo_root = get_repository_root(repo_path)
+    commit_hash, rest = _split_ref(repo_root, segments, url, remote)
+
+    canonical = f"https://github.com/{owner}/{repo}/{kind}/{commit_hash}"
+    if rest:
+        canonical = f"{canonical}/{'/'.join(rest)}"
+    return canonical + (query or "") + (fragment or "")
+
+
+def locate_github_link(
+    url: str,
+    remote: Optional[str] = None,
+    repo_path: str = "."
+) -> LinkLocation:
+    """
+    Resolve a GitHub link to a path in a local clone and check it for drift.
+
+    The link's ref is resolved like ``canonicalize_github_url``. For files,
+    the blob SHA at the linked commit is compared with the Git hash of the
+    working tree file; for directories, the working tree is diffed against
+    the linked commit. This backs "open link locally" features.
+
+    Args:
+        url: GitHub blob or tree URL
+        remote: Remote whose tracking branches are preferred (default: the
+            current branch's remote, then 'origin', then the first remote)
+        repo_path: Path within a local clone of the repository
+
+    Returns:
+        LinkLocation with the on-disk path and drift status
+
+    Raises:
+        ValueError: If url is not a GitHub blob or tree URL
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitError: If the ref cannot be resolved in the local repository
+        FileNotFoundError: If the path does not exist at the linked commit
+
+    Examples:
+        >>> location = locate_github_link(
+        ...     "https://github.com/user/repo/blob/main/src/file.py#L10", repo_path="/path/to/repo"
+        ... )
+        >>> location.absolute_path, location.status
+        ('/path/to/repo/src/file.py', 'identical')
+    """
+    match = GITHUB_LINK_PATTERN.match(url)
+    link = parse_github_link(url)
+    if not match or not link:
+        raise ValueError(f"Not a GitHub blob or tree URL: {url}")
+
+    segments = match.group(4).strip("/").split("/")
+    repo_root = get_repository_root(repo_path)
+
+    if is_valid_git_hash(segments[0]):
+        commit_hash, rest = segments[0], segments[1:]
+    else:
+        commit_hash, rest = _split_ref(repo_root, segments, url, remote)
+
+    relative_path = unquote("/".join(rest))
+    absolute_path = os.path.join(repo_root, relative_path) if relative_path else repo_root
+
+    try:
+        object_hash = execute_git_command(
+            ["git", "rev-parse", "--verify", "--quiet", f"{commit_hash}:{relative_path}"],
+            cwd=repo_root
+        )
+    except GitCommandError as e:
+        raise FileNotFoundError(
+            f"Path not found at commit {commit_hash}: {relative_path}",
+            file_path=relative_path,
+            cause=e
+        ) from e
+
+    status: DriftStatus
+    blob_hash = None
+    working_hash = None
+
+    if link.kind == "tree":
+        if not os.path.isdir(absolute_path):
+            status = "missing"
+        elif _is_clean_against(repo_root, commit_hash, relative_path):
+            status = "identical"
+        else:
+            status = "modified"
+    else:
+        # A blob link can point at a submodule, whose entry is not a blob
+        mode, blob_hash = _blob_entry(repo_root, commit_hash, relative_path) or ("", object_hash)
+        if not (os.path.isfile(absolute_path) or os.path.islink(absolute_path)):
+            status = "missing"
+        else:
+            status, working_hash = _working_drift(repo_root, relative_path, mode, blob_hash)
+
+    return LinkLocation(
+        kind=link.kind,
+        commit_hash=commit_hash,
+        path=relative_path,
+        absolute_path=absolute_path,
+        status=status,
+        line_range=link.line_range,
+        blob_hash=blob_hash,
+        working_hash=working_hash
+    )
+
+
+def check_drift(file_path: str, repo_path: str = ".") -> DriftStatus:
+    """
+    Compare a working tree file with its HEAD version.
+
+    The file is hashed as Git would store it, with the clean filters and
+    line-ending conversion configured for its path, and the OID compared
+    with the blob at HEAD. That is one ``git hash-object`` rather than a
+    scan of the working tree as ``git status`` does, cheap enough to run
+    before emitting a permalink so the link shows what is on disk. Only
+    content is compared, not the executable bit.
+
+    Args:
+        file_path: File path (absolute or relative to repo root)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        'identical', 'modified', 'missing' (at HEAD but deleted from the
+        working tree) or 'untracked' (not a file at HEAD)
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        FileNotFoundError: If the path is a file neither at HEAD nor in the
+            working tree
+
+    Examples:
+        >>> check_drift("src/file.py", "/path/to/repo")
+        'identical'
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+    absolute_path = os.path.join(repo_root, relative_path)
+    on_disk = os.path.isfile(absolute_path) or os.path.islink(absolute_path)
+
+    entry = None
+    if not is_head_unborn(repo_root):
+        entry = _blob_entry(repo_root, "HEAD", relative_path)
+
+    if entry is None:
+        if not on_disk:
+            raise FileNotFoundError(
+                f"File not found at HEAD or in the working tree: {relative_path}",
+                file_path=relative_path
+            )
+        return "untracked"
+    if not on_disk:
+        return "missing"
+
+    mode, blob_hash = entry
+    return _working_drift(repo_root, relative_path, mode, blob_hash)[0]
+
+
+def _blob_entry(repo_root: str, rev: str, relative_path: str) -> Optional[tuple[str, str]]:
+    """
+    Look up the mode and OID of a file at a revision.
+
+    Args:
+        repo_root: Repository root path
+        rev: Commit SHA or other revision
+        relative_path: Path relative to repo root
+
+    Returns:
+        (mode, blob SHA), or None if the path is not a file at rev
+    """
+    output = execute_git_command(["git", "ls-tree", "-z", rev, "--", relative_path], cwd=repo_root)
+
+    for line in output.split("\0"):
+        # "<mode> <type> <oid>\t<path>"
+        info, _, path = line.partition("\t")
+        fields = info.split(" ")
+        if path == relative_path and fields[1:2] == ["blob"]:
+            return fields[0], fields[2]
+    return None
+
+
+def _working_drift(
+    repo_root: str,
+    relative_path: str,
+    mode: str,
+    blob_hash: str
+) -> tuple[DriftStatus, Optional[str]]:
+    """
+    Compare a file on disk with a blob.
+
+    Symbolic links are compared by target, as Git stores them, rather than
+    hashed through ``git hash-object`` which would read the file they point
+    to.
+
+    Args:
+        repo_root: Repository root path
+        relative_path: Path of an existing file or link, relative to repo root
+        mode: Tree entry mode of the blob
+        blob_hash: Blob SHA to compare with
+
+    Returns:
+        ('identical' or 'modified', blob SHA of the working tree file)
+    """
+    absolute_path = os.path.join(repo_root, relative_path)
+
+    if os.path.islink(absolute_path):
+        # Git stores a link as a blob of its target path
+        target = os.fsencode(os.readlink(absolute_path))
+        if mode == SYMLINK_MODE and get_blob_bytes(repo_root, blob_hash) == target:
+            return "identical", blob_hash
+        object_format = execute_git_command(
+            ["git", "rev-parse", "--show-object-format"],
+            cwd=repo_root
+        )
+        working_hash = hashlib.new(object_format, b"blob %d\0%b" % (len(target), target))
+        return "modified", working_hash.hexdigest()
+
+    working_hash = execute_git_command(["git", "hash-object", "--", relative_path], cwd=repo_root)
+    if mode == SYMLINK_MODE or working_hash != blob_hash:
+        return "modified", working_hash
+    return "identical", working_hash
+
+
+def _github_remote(
+    repo_root: str,
+    resolve_ssh_aliases: bool,
+    remote: Optional[str] = None,
+    prefer_upstream: bool = False
+) -> dict[str, str]:
+    """
+    Get the parsed GitHub remote of a repository.
+
+    Args:
+        repo_root: Repository root path
+        resolve_ssh_aliases: Resolve ~/.ssh/config host aliases in the remote URL
+        remote: Remote name (default: ``get_default_remote``)
+        prefer_upstream: Passed to ``get_default_remote`` when no remote is named
+
+    Returns:
+        Remote info from ``parse_github_url``
+
+    Raises:
+        GitError: If the remote does not exist or is not a GitHub remote
+    """
+    if remote is None and prefer_upstream:
+        remote = get_default_remote(repo_root, prefer_upstream)
+
+    remote_url = get_remote_url(repo_root, remote)
+    parsed = parse_github_url(remote_url, resolve_ssh_aliases) if remote_url else None
+    if not parsed or "host" in parsed:
+        context: dict[str, Any] = {"path": repo_root, "remote_url": remote_url}
+        if remote:
+            context["remote"] = remote
+        raise GitError(
+            f"No GitHub remote configured for repository: {repo_root}",
+            code="REMOTE_NOT_FOUND",
+            context=context
+        )
+    return parsed
+
+
+def _pull_request_from_message(repo_root: str, commit_hash: str) -> Optional[int]:
+    """
+    Extract a PR number from a commit's subject line.
+
+    Args:
+        repo_root: Repository root path
+        commit_hash: Commit SHA
+
+    Returns:
+        PR number, or None if the subject does not reference a PR
+    """
+    subject = execute_git_command(["git", "log", "-1", "--format=%s", commit_hash], cwd=repo_root)
+    match = PULL_REQUEST_MESSAGE_PATTERN.search(subject)
+    if not match:
+        return None
+    return int(match.group(1) or match.group(2))
+
+
+def _pull_request_from_refs(repo_root: str, commit_hash: str) -> Optional[int]:
+    """
+    Find a PR number from fetched PR head refs containing a commit.
+
+    Args:
+        repo_root: Repository root path
+        commit_hash: Commit SHA
+
+    Returns:
+        Number of the PR whose head is the commit, otherwise the lowest PR
+        number containing it, or None
+    """
+    output = execute_git_command(
+        [
+            "git", "for-each-ref", "--contains", commit_hash,
+            "--format=%(objectname) %(refname)", "refs/pull", "refs/remotes"
+        ],
+        cwd=repo_root
+    )
+
+    exact = []
+    containing = []
+    for line in output.splitlines():
+        object_name, _, ref_name = line.partition(" ")
+        match = PULL_REQUEST_REF_PATTERN.match(ref_name)
+        if match:
+            number = int(match.group(1) or match.group(2))
+            (exact if object_name == commit_hash else containing).append(number)
+
+    if exact:
+        return min(exact)
+    return min(containing) if containing else None
+
+
+def _split_ref(
+    repo_root: str,
+    segments: list[str],
+    url: str,
+    remote: Optional[str] = None
+) -> tuple[str, list[str]]:
+    """
+    Split URL segments into a resolved commit SHA and the remaining path segments.
+
+    Branch names may contain slashes, so the longest resolvable prefix wins.
+
+    Args:
+        repo_root: Repository root path
+        segments: URL segments following ``blob/`` or ``tree/``
+        url: Original URL (for error reporting)
+        remote: Remote whose tracking branches are preferred (default: ``get_default_remote``)
+
+    Returns:
+        Tuple of (commit SHA, path segments)
+
+    Raises:
+        GitError: If no prefix resolves to a commit
+    """
+    if remote is None:
+        remote = get_default_remote(repo_root)
+
+    for split in range(len(segments), 0, -1):
+        ref = unquote("/".join(segments[:split]))
+        commit_hash = _resolve_commit(repo_root, ref, remote)
+        if commit_hash:
+            return commit_hash, segments[split:]
+
+    raise GitError(
+        f"Cannot resolve ref in URL: {url}",
+        code="REF_NOT_FOUND",
+        context={"path": repo_root, "url": url}
+    )
+
+
+def _is_clean_against(repo_root: str, commit_hash: str, relative_path: str) -> bool:
+    """
+    Check whether tracked files under a path match a commit in the working tree.
+
+    Args:
+        repo_root: Repository root path
+        commit_hash: Commit SHA to compare against
+        relative_path: Path relative to the repository root ('' for everything)
+
+    Returns:
+        True if there are no differences
+    """
+    pathspec = ["--", relative_path] if relative_path else []
+    try:
+        execute_git_command(["git", "diff", "--quiet", commit_hash, *pathspec], cwd=repo_root)
+        return True
+    except GitCommandError as e:
+        if e.context.get("exit_code") == 1:
+            return False
+        raise
+
+
+def _resolve_commit(repo_root: str, ref: str, remote: Optional[str]) -> Optional[str]:
+    """
+    Resolve a ref name to a commit SHA, preferring the remote-tracking branch.
+
+    Args:
+        repo_root: Repository root path
+        ref: Branch, tag or other ref name
+        remote: Remote whose tracking branch is tried first (None for local refs only)
+
+    Returns:
+        Commit SHA, or None if the ref does not exist locally or is not a
+        valid ref name
+    """
+    if INVALID_REF_NAME_PATTERN.search(ref):
+        return None
+
+    candidates = [f"refs/remotes/{remote}/{ref}", ref] if remote else [ref]
+    for candidate in candidates:
+        try:
+            return execute_git_command(
+                [
+                    "git", "rev-parse", "--verify", "--quiet", "--end-of-options",
+                    f"{candidate}^{{commit}}"
+                ],
+                cwd=repo_root
+            )
+        except GitCommandError:
+            continue
+    return None
+
+
+def _resolve_rev(repo_root: str, rev: str) -> str:
+    """
+    Resolve a revision in the local repository to a commit SHA.
+
+    Args:
+        repo_root: Repository root path
+        rev: Commit SHA, branch, tag or other revision expression
+
+    Returns:
+        Commit SHA
+
+    Raises:
+        UnbornHeadError: If rev is HEAD and the current branch has no commits yet
+        GitError: If the revision cannot be resolved (context ``shallow``
+            is set when the repository is a shallow clone)
+    """
+    try:
+        return execute_git_command(
+            ["git", "rev-parse", "--verify", "--quiet", f"{rev}^{{commit}}"],
+            cwd=repo_root
+        )
+    except GitCommandError as e:
+        if rev == "HEAD":
+            # Raises UnbornHeadError when the current branch has no commits yet
+            get_head_commit(repo_root)
+
+        context: dict[str, Any] = {"path": repo_root, "ref": rev}
+        message = f"Cannot resolve revision: {rev}"
+        if is_shallow(repo_root):
+            # The commit may exist upstream but predate the shallow boundary
+            context["shallow"] = True
+            message += " (repository is a shallow clone; fetch more history)"
+
+        raise GitError(
+            message,
+            code="REF_NOT_FOUND",
+            context=context,
+            cause=e
+        ) from e
+
+
+def _pushed_commit(
+    repo_root: str,
+    commit_hash: str,
+    remote: str,
+    unpushed: UnpushedPolicy
+) -> str:
+    """
+    Apply an unpushed policy to a commit about to be linked.
+
+    Args:
+        repo_root: Repository root path
+        commit_hash: Commit SHA
+        remote: Remote the URL is built against
+        unpushed: 'warn', 'fallback' or 'error'
+
+    Returns:
+        The commit itself if pushed or the policy is 'warn', otherwise its
+        nearest pushed ancestor (fallback)
+
+    Raises:
+        GitError: If the commit is not pushed and no fallback applies
+    """
+    if is_commit_on_remote(repo_root, commit_hash, remote):
+        return commit_hash
+
+    if unpushed == "warn":
+        # stacklevel points at the caller of generate_github_url
+        warnings.warn(
+            f"Commit {commit_hash} is not on remote '{remote}'; the link 404s until it is pushed",
+            stacklevel=3
+        )
+        return commit_hash
+
+    if unpushed == "fallback":
+        ancestor = get_nearest_pushed_ancestor(repo_root, commit_hash, remote)
+        if ancestor:
+            return ancestor
+
+    raise GitError(
+        f"Commit {commit_hash} is not on remote '{remote}'; push it before sharing the link",
+        code="COMMIT_NOT_PUSHED",
+        context={"path": repo_root, "commit": commit_hash, "remote": remote}
+    )
+
+
+def _resolve_ref(
+    repo_root: str,
+    ref_strategy: RefStrategy,
+    relative_path: str,
+    remote: Optional[str] = None,
+    prefer_upstream: bool = False
+) -> str:
+    """
+    Resolve the URL ref segment for a ref strategy.
+
+    Args:
+        repo_root: Repository root path
+        ref_strategy: Ref strategy
+        relative_path: Linked path relative to the repository root
+        remote: Remote the URL is built against (default: ``get_default_remote``)
+        prefer_upstream: Passed to ``get_default_remote`` when no remote is named
+
+    Returns:
+        Commit SHA, branch name or tag name
+
+    Raises:
+        GitError: If HEAD is detached (branch), not tagged (tag) or the
+            default branch is unknown (default-branch)
+        UnbornHeadError: If the current branch has no commits yet (commit,
+            tag, tag-or-commit and last-commit)
+        FileNotFoundError: If the path has never been committed (last-commit)
+    """
+    if ref_strategy == "branch":
+        # An unborn branch still has a name, so no commit is needed
+        branch = get_current_branch(repo_root)
+        if branch == "HEAD":
+            raise GitError(
+                "Cannot build a branch URL: HEAD is detached",
+                code="DETACHED_HEAD",
+                context={"path": repo_root}
+            )
+
+        # A branch tracking e.g. upstream/main is published there as main
+        tracking = get_tracking_branch(repo_root)
+        if tracking and tracking[0] == (remote or get_default_remote(repo_root, prefer_upstream)):
+            return tracking[1]
+        return branch
+
+    if ref_strategy == "default-branch":
+        default_branch = get_default_branch(
+            repo_root,
+            remote or get_default_remote(repo_root, prefer_upstream)
+        )
+        if not default_branch:
+            raise GitError(
+                "Cannot build a default branch URL: the remote has no HEAD and no "
+                "main, master, trunk or develop branch",
+                code="DEFAULT_BRANCH_NOT_FOUND",
+                context={"path": repo_root}
+            )
+        return default_branch
+
+    head_commit = get_head_commit(repo_root)
+
+    if ref_strategy == "last-commit":
+        commit_hash = get_last_commit_for_file(repo_root, relative_path)
+        if not commit_hash:
+            raise FileNotFoundError(
+                f"No commit modifies path: {relative_path}",
+                file_path=relative_path
+            )
+        return commit_hash
+
+    if ref_strategy in ("tag", "tag-or-commit"):
+        tag = get_head_tag(repo_root)
+        if tag:
+            return tag
+        if ref_strategy == "tag":
+            raise GitError(
+                "Cannot build a tag URL: no tag points at HEAD",
+                code="TAG_NOT_FOUND",
+                context={"path": repo_root}
+            )
+
+    return head_commit
+
+
+__all__ = [
+    "RefStrategy",
+    "UnpushedPolicy",
+    "generate_github_url",
+    "generate_github_api_contents_url",
+    "generate_github_compare_url",
+    "generate_github_archive_url",
+    "find_pull_request_for_commit",
+    "canonicalize_github_url",
+    "locate_github_link",
+    "check_drift",
+    "LinkLocation",
+    "PullRequest",
+]
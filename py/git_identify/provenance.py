@@ -0,0 +1,178 @@
+"""
+File provenance records.
+
+Builds a structured record of exactly which file content a repository
+held at a commit, in a stable JSON schema, for supply-chain attestations
+that reference file identities.
+"""
+
+from datetime import datetime, timezone
+from typing import Any, Optional
+
+from .errors import FileNotFoundError, GitCommandError
+from .refs import get_repo_fingerprint
+from .utils.git import (
+    execute_git_command,
+    get_file_hash_at_commit,
+    get_remote_url,
+    resolve_repository_path,
+)
+from .utils.url import normalize_remote_url
+
+# Identifies the layout of ProvenanceRecord.to_dict(); bumped on any change to it
+PROVENANCE_SCHEMA = "git-file-identifiers/provenance/v1"
+
+
+class ProvenanceRecord:
+    """
+    The identity of a file at a commit, with the repository it belongs to.
+
+    Attributes:
+        repo_fingerprint: Root commit SHA identifying the repository across
+            clones (None for a shallow clone, see ``get_repo_fingerprint``)
+        remote: Remote URL in canonical https://host/owner/repo form (None
+            without a remote or if the URL cannot be normalized)
+        commit: Commit SHA
+        tree: Root tree SHA of the commit
+        path: File path relative to repo root
+        blob_oid: Blob SHA of the file at the commit
+        dirty: Whether the working tree copy differs from that blob
+        timestamp: When the record was created, as UTC ISO 8601
+            ('2024-01-15T10:30:00Z')
+        schema: PROVENANCE_SCHEMA
+    """
+
+    def __init__(
+        self,
+        repo_fingerprint: Optional[str],
+        remote: Optional[str],
+        commit: str,
+        tree: str,
+        path: str,
+        blob_oid: str,
+        dirty: bool,
+        timestamp: str,
+        schema: str = PROVENANCE_SCHEMA
+    ) -> None:
+        self.repo_fingerprint = repo_fingerprint
+        self.remote = remote
+        self.commit = commit
+        self.tree = tree
+        self.path = path
+        self.blob_oid = blob_oid
+        self.dirty = dirty
+        self.timestamp = timestamp
+        self.schema = schema
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "schema": self.schema,
+            "repoFingerprint": self.repo_fingerprint,
+            "remote": self.remote,
+            "commit": self.commit,
+            "tree": self.tree,
+            "path": self.path,
+            "blobOid": self.blob_oid,
+            "dirty": self.dirty,
+            "timestamp": self.timestamp
+        }
+
+
+def create_provenance_record(
+    file_path: str,
+    commit: str = "HEAD",
+    remote: Optional[str] = None,
+    repo_path: str = "."
+) -> ProvenanceRecord:
+    """
+    Record the identity of a file at a commit for an attestation.
+
+    Every field is a plain string or boolean and to_dict() always has the
+    same keys, so the record can be serialized with ``json.dumps`` and
+    checked against PROVENANCE_SCHEMA by consumers.
+
+    Args:
+        file_path: File path (absolute or relative to repo root)
+        commit: Commit SHA, branch, tag or other revision (default: 'HEAD')
+        remote: Remote whose URL is recorded (default: the current branch's
+            remote, then 'origin', then the first remote)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        ProvenanceRecord
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit
+        FileNotFoundError: If the file does not exist at commit
+
+    Examples:
+        >>> create_provenance_record("dist/app.js", repo_path="/path/to/repo").to_dict()
+        {'schema': 'git-file-identifiers/provenance/v1', 'repoFingerprint': 'e83c516...',
+         'remote': 'https://github.com/owner/repo', 'commit': 'abc123...', 'tree': 'def456...',
+         'path': 'dist/app.js', 'blobOid': '789abc...', 'dirty': False,
+         'timestamp': '2024-01-15T10:30:00Z'}
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+    oid = execute_git_command(["git", "rev-parse", f"{commit}^{{commit}}"], cwd=repo_root)
+    tree = execute_git_command(["git", "rev-parse", f"{oid}^{{tree}}"], cwd=repo_root)
+
+    blob_oid = get_file_hash_at_commit(repo_root, relative_path, oid)
+    if blob_oid is None:
+        raise FileNotFoundError(
+            f"File not found at {commit}: {relative_path}",
+            file_path=relative_path
+        )
+
+    remote_url = get_remote_url(repo_root, remote)
+    try:
+        normalized_remote = normalize_remote_url(remote_url) if remote_url else None
+    except ValueError:
+        normalized_remote = None
+
+    return ProvenanceRecord(
+        get_repo_fingerprint(oid, repo_root),
+        normalized_remote,
+        oid,
+        tree,
+        relative_path,
+        blob_oid,
+        _differs_from_commit(repo_root, relative_path, oid),
+        datetime.now(timezone.utc).strftime("%Y-%m-%dT%H:%M:%SZ")
+    )
+
+
+def _differs_from_commit(repo_root: str, relative_path: str, commit: str) -> bool:
+    """
+    Check whether the working tree copy of a file differs from a commit.
+
+    Args:
+        repo_root: Repository root path
+        relative_path: Path relative to repo root
+        commit: Commit SHA
+
+    Returns:
+        True if the file was changed or deleted since commit
+    """
+    try:
+        execute_git_command(
+            [
+                "git", "diff", "--quiet", "--no-ext-diff", "--no-textconv", commit,
+                "--", relative_path
+            ],
+            cwd=repo_root
+        )
+        return False
+    except GitCommandError as e:
+        # Exit code 1: differences found
+        if e.context.get("exit_code") == 1:
+            return True
+        raise
+
+
+__all__ = [
+    "PROVENANCE_SCHEMA",
+    "ProvenanceRecord",
+    "create_provenance_record",
+]
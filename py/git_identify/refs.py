@@ -0,0 +1,868 @@
+"""
+Refs and revisions.
+
+Describes HEAD, refs, reflogs, stashes and tags, resolves revision
+expressions and fingerprints a repository by its root commit.
+"""
+
+import os
+import re
+from datetime import datetime, timedelta, timezone
+from typing import Any, Literal, Optional
+
+from .errors import GitCommandError
+from .utils.git import (
+    execute_git_command,
+    get_current_branch,
+    get_file_hash_at_commit,
+    get_head_commit,
+    get_repository_root,
+    is_head_unborn,
+    is_shallow,
+)
+
+RefKind = Literal["branch", "tag", "remote", "note", "other"]
+
+# Ref namespaces and the kind of ref they hold
+REF_KINDS: tuple[tuple[str, RefKind], ...] = (
+    ("refs/heads/", "branch"),
+    ("refs/tags/", "tag"),
+    ("refs/remotes/", "remote"),
+    ("refs/notes/", "note"),
+)
+
+# "<old> <new> <name> <<email>> <time> <offset>\t<message>" reflog line
+REFLOG_LINE_PATTERN = re.compile(
+    r"^(?P<old>[0-9a-f]+) (?P<new>[0-9a-f]+) (?P<name>.*?) ?<(?P<email>[^>]*)> "
+    r"(?P<time>\d+) (?P<offset>[+-]\d{4})(?:\t(?P<message>.*))?$"
+)
+
+# "WIP on <branch>: ..." or "On <branch>: ..." stash message
+STASH_MESSAGE_PATTERN = re.compile(r"^(?:WIP on|On) (?P<branch>[^:]+):")
+
+# Tag name globs that look like semantic versions, with or without a "v" prefix
+SEMVER_TAG_GLOBS = ("v[0-9]*.[0-9]*.[0-9]*", "[0-9]*.[0-9]*.[0-9]*")
+
+
+class ResolvedRevision:
+    """
+    A revision expression and the object it names.
+
+    Attributes:
+        spec: Revision expression as given, e.g. 'HEAD~3' or 'main:src/app.py'
+        oid: SHA of the named object
+        type: Object type: 'commit', 'tree', 'blob' or 'tag'
+        ref: Full name of the ref the expression names directly, e.g.
+            'refs/remotes/origin/main' for 'main@{upstream}' (None for
+            expressions that navigate from a ref, such as 'HEAD~3')
+    """
+
+    def __init__(self, spec: str, oid: str, type: str, ref: Optional[str] = None) -> None:
+        self.spec = spec
+        self.oid = oid
+        self.type = type
+        self.ref = ref
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "spec": self.spec,
+            "oid": self.oid,
+            "type": self.type,
+            "ref": self.ref
+        }
+
+
+class RefInfo:
+    """
+    A reference and the object it points at.
+
+    Attributes:
+        name: Full ref name, e.g. 'refs/heads/main'
+        shorthand: Unambiguous short name, e.g. 'main' or 'origin/main'
+        oid: SHA of the object the ref points at (the tag object for an
+            annotated tag)
+        kind: 'branch', 'tag', 'remote', 'note' or 'other'
+        peeled: Commit an annotated tag points at (None for other refs)
+        symref: Target ref name of a symbolic ref such as
+            'refs/remotes/origin/HEAD' (None otherwise)
+    """
+
+    def __init__(
+        self,
+        name: str,
+        shorthand: str,
+        oid: str,
+        kind: RefKind,
+        peeled: Optional[str] = None,
+        symref: Optional[str] = None
+    ) -> None:
+        self.name = name
+        self.shorthand = shorthand
+        self.oid = oid
+        self.kind = kind
+        self.peeled = peeled
+        self.symref = symref
+
+    @property
+    def commit(self) -> str:
+        """Commit SHA the ref resolves to (the peeled target for annotated tags)."""
+        return self.peeled or self.oid
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "name": self.name,
+            "shorthand": self.shorthand,
+            "oid": self.oid,
+            "kind": self.kind,
+            "peeled": self.peeled,
+            "symref": self.symref,
+            "commit": self.commit
+        }
+
+
+class ReflogEntry:
+    """
+    One update of a reference, as recorded in its reflog.
+
+    Attributes:
+        refname: Full ref name the entry belongs to, e.g. 'HEAD' or
+            'refs/heads/main'
+        index: Position in the reflog, 0 for the latest update
+        old_oid: Commit the ref pointed at before the update (all zeros
+            when the ref was created)
+        new_oid: Commit the ref pointed at after the update
+        committer_name: Name of whoever made the update
+        committer_email: Email of whoever made the update
+        timestamp: Time of the update in ISO 8601 format, in the
+            committer's time zone
+        message: Reason recorded for the update, e.g.
+            'checkout: moving from main to feature'
+    """
+
+    def __init__(
+        self,
+        refname: str,
+        index: int,
+        old_oid: str,
+        new_oid: str,
+        committer_name: str,
+        committer_email: str,
+        timestamp: str,
+        message: str
+    ) -> None:
+        self.refname = refname
+        self.index = index
+        self.old_oid = old_oid
+        self.new_oid = new_oid
+        self.committer_name = committer_name
+        self.committer_email = committer_email
+        self.timestamp = timestamp
+        self.message = message
+
+    @property
+    def selector(self) -> str:
+        """Revision naming this entry, e.g. 'HEAD@{2}'."""
+        return f"{self.refname}@{{{self.index}}}"
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "refname": self.refname,
+            "index": self.index,
+            "selector": self.selector,
+            "oldOid": self.old_oid,
+            "newOid": self.new_oid,
+            "committerName": self.committer_name,
+            "committerEmail": self.committer_email,
+            "timestamp": self.timestamp,
+            "message": self.message
+        }
+
+
+class StashEntry:
+    """
+    A stash entry, i.e. work saved with ``git stash``.
+
+    Attributes:
+        index: Position in the stash list, 0 for the latest
+        oid: SHA of the stash commit, whose tree holds the stashed working
+            tree
+        message: Stash message, e.g. 'WIP on main: abc1234 Fix parser'
+        branch: Branch the work was stashed from (None if detached or not
+            recorded in the message)
+    """
+
+    def __init__(self, index: int, oid: str, message: str, branch: Optional[str] = None) -> None:
+        self.index = index
+        self.oid = oid
+        self.message = message
+        self.branch = branch
+
+    @property
+    def selector(self) -> str:
+        """Revision naming this entry, e.g. 'stash@{1}'."""
+        return f"stash@{{{self.index}}}"
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "index": self.index,
+            "selector": self.selector,
+            "oid": self.oid,
+            "message": self.message,
+            "branch": self.branch
+        }
+
+
+class Description:
+    """
+    A commit named relative to the nearest tag, as by ``git describe --tags --long``.
+
+    Attributes:
+        tag: Nearest tag reachable from the commit
+        distance: Commits between the tag and the commit (0 if tagged)
+        oid: Commit SHA
+        abbreviated: Unambiguous abbreviated commit SHA
+    """
+
+    def __init__(self, tag: str, distance: int, oid: str, abbreviated: str) -> None:
+        self.tag = tag
+        self.distance = distance
+        self.oid = oid
+        self.abbreviated = abbreviated
+
+    @property
+    def name(self) -> str:
+        """Describe string, e.g. 'v1.2.0-3-gabc123d'."""
+        return f"{self.tag}-{self.distance}-g{self.abbreviated}"
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "tag": self.tag,
+            "distance": self.distance,
+            "oid": self.oid,
+            "abbreviated": self.abbreviated,
+            "name": self.name
+        }
+
+
+class TagInfo:
+    """
+    A tag and the object it points at.
+
+    Attributes:
+        name: Tag name, e.g. 'v1.0.0'
+        oid: SHA the tag ref points at (the tag object for an annotated tag,
+            the target itself for a lightweight tag)
+        target: SHA of the tagged object
+        target_type: Type of the tagged object: 'commit', 'tree', 'blob' or
+            'tag' (a tag of a tag)
+        tagger_name: Tagger name (None for a lightweight tag)
+        tagger_email: Tagger email (None for a lightweight tag)
+        tagger_date: Tag date as an ISO 8601 string (None for a lightweight
+            tag)
+    """
+
+    def __init__(
+        self,
+        name: str,
+        oid: str,
+        target: str,
+        target_type: str,
+        tagger_name: Optional[str] = None,
+        tagger_email: Optional[str] = None,
+        tagger_date: Optional[str] = None
+    ) -> None:
+        self.name = name
+        self.oid = oid
+        self.target = target
+        self.target_type = target_type
+        self.tagger_name = tagger_name
+        self.tagger_email = tagger_email
+        self.tagger_date = tagger_date
+
+    @property
+    def annotated(self) -> bool:
+        """Whether the tag is a tag object rather than a plain ref."""
+        return self.oid != self.target
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "name": self.name,
+            "oid": self.oid,
+            "target": self.target,
+            "targetType": self.target_type,
+            "annotated": self.annotated,
+            "taggerName": self.tagger_name,
+            "taggerEmail": self.tagger_email,
+            "taggerDate": self.tagger_date
+        }
+
+
+class HeadInfo:
+    """
+    What HEAD points at.
+
+    Attributes:
+        oid: Commit SHA HEAD resolves to (None if the branch has no commits
+            yet)
+        branch: Checked out branch name (None if detached)
+        upstream: Upstream branch, e.g. 'origin/main' (None if detached or
+            not tracking)
+    """
+
+    def __init__(
+        self,
+        oid: Optional[str],
+        branch: Optional[str] = None,
+        upstream: Optional[str] = None
+    ) -> None:
+        self.oid = oid
+        self.branch = branch
+        self.upstream = upstream
+
+    @property
+    def detached(self) -> bool:
+        """Whether HEAD points directly at a commit rather than a branch."""
+        return self.branch is None
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "branch": self.branch,
+            "oid": self.oid,
+            "detached": self.detached,
+            "upstream": self.upstream
+        }
+
+
+def get_head_info(repo_path: str = ".") -> HeadInfo:
+    """
+    Report the branch, commit and upstream of HEAD.
+
+    Unlike ``get_current_branch``, which returns the string 'HEAD' when
+    detached, the branch and commit are kept apart. On a branch with no
+    commits yet (a fresh ``git init`` or orphan branch) the branch is still
+    reported and oid is None.
+
+    Args:
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        HeadInfo
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> get_head_info("/path/to/repo").to_dict()
+        {'branch': 'main', 'oid': 'abc123...', 'detached': False, 'upstream': 'origin/main'}
+        >>> get_head_info("/path/to/new-repo").to_dict()
+        {'branch': 'main', 'oid': None, 'detached': False, 'upstream': None}
+    """
+    repo_root = get_repository_root(repo_path)
+
+    oid = None if is_head_unborn(repo_root) else get_head_commit(repo_root)
+    branch = get_current_branch(repo_root)
+    if branch == "HEAD":
+        return HeadInfo(oid)
+
+    try:
+        upstream: Optional[str] = execute_git_command(
+            "git rev-parse --abbrev-ref --symbolic-full-name @{upstream}",
+            cwd=repo_root
+        )
+    except GitCommandError:
+        upstream = None
+
+    return HeadInfo(oid, branch, upstream)
+
+
+def get_repo_fingerprint(rev: str = "HEAD", repo_path: str = ".") -> Optional[str]:
+    """
+    Identify a repository by the root commit of a revision's history.
+
+    The root commit is the same in every clone, fork and mirror and does
+    not depend on remote URLs, so it serves as a stable cache or namespace
+    key. When the history has several roots (unrelated histories merged
+    together) the lowest SHA is used: commit dates can tie or be rewritten,
+    so picking by date would not give every clone the same answer.
+
+    Args:
+        rev: Commit SHA, branch, tag or other revision whose history is
+            searched (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        Root commit SHA, or None if rev is HEAD and it has no commits yet, or
+        the clone is shallow, where the root commit is not available
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If rev does not name a commit
+
+    Examples:
+        >>> get_repo_fingerprint(repo_path="/path/to/repo")
+        'e83c5163316f89bfbde7d9ab23ca2e25604af290'
+        >>> get_repo_fingerprint("v1.0.0", repo_path="/path/to/repo")
+        'e83c5163316f89bfbde7d9ab23ca2e25604af290'
+    """
+    repo_root = get_repository_root(repo_path)
+    if (rev == "HEAD" and is_head_unborn(repo_root)) or is_shallow(repo_root):
+        return None
+
+    roots = execute_git_command(
+        ["git", "rev-list", "--max-parents=0", f"{rev}^{{commit}}"],
+        cwd=repo_root
+    ).split()
+    return min(roots)
+
+
+def list_refs(pattern: Optional[str] = None, repo_path: str = ".") -> list[RefInfo]:
+    """
+    List references with their targets in one call.
+
+    Args:
+        pattern: Only list refs under this prefix or matching this glob,
+            e.g. 'refs/tags' or 'refs/remotes/origin/*' (default: all refs)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        RefInfo list sorted by ref name
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> [(r.shorthand, r.kind) for r in list_refs(repo_path="/path/to/repo")]
+        [('main', 'branch'), ('origin/main', 'remote'), ('v1.0.0', 'tag')]
+        >>> list_refs("refs/tags", repo_path="/path/to/repo")[0].peeled
+        'abc123def456...'
+    """
+    repo_root = get_repository_root(repo_path)
+
+    return _for_each_ref(repo_root, [pattern] if pattern else [])
+
+
+def refs_containing(commit: str, repo_path: str = ".") -> list[RefInfo]:
+    """
+    List the branches, remote-tracking branches and tags that contain a commit.
+
+    Mirrors ``git branch -a --contains`` and ``git tag --contains``.
+    Symbolic refs such as 'refs/remotes/origin/HEAD' are left out, since
+    the branch they point at is listed itself.
+
+    Args:
+        commit: Commit SHA or revision
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        RefInfo list sorted by name
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit
+
+    Examples:
+        >>> [(r.kind, r.shorthand) for r in refs_containing("abc123d", "/path/to/repo")]
+        [('branch', 'main'), ('remote', 'origin/main'), ('tag', 'v1.3.0')]
+    """
+    repo_root = get_repository_root(repo_path)
+
+    refs = _for_each_ref(
+        repo_root,
+        ["--contains", commit, "refs/heads", "refs/remotes", "refs/tags"]
+    )
+    return [ref for ref in refs if ref.symref is None]
+
+
+def _for_each_ref(repo_root: str, arguments: list[str]) -> list[RefInfo]:
+    """
+    Read refs with ``git for-each-ref``.
+
+    Args:
+        repo_root: Repository root path
+        arguments: Filter options and patterns passed to for-each-ref
+
+    Returns:
+        RefInfo list sorted by name
+    """
+    format_string = "%(refname)%00%(refname:short)%00%(objectname)%00%(*objectname)%00%(symref)"
+    command = ["git", "for-each-ref", f"--format={format_string}", *arguments]
+
+    refs: list[RefInfo] = []
+    for line in execute_git_command(command, cwd=repo_root).splitlines():
+        name, shorthand, oid, peeled, symref = line.split("\0")
+        kind = next((kind for prefix, kind in REF_KINDS if name.startswith(prefix)), "other")
+        refs.append(RefInfo(name, shorthand, oid, kind, peeled or None, symref or None))
+
+    return refs
+
+
+def get_reflog(refname: str = "HEAD", repo_path: str = ".") -> list[ReflogEntry]:
+    """
+    Read the reflog of a reference, latest update first.
+
+    The reflog records every value a ref has held locally, so it answers
+    what HEAD or a branch pointed at when a link was generated, even after
+    a reset or rebase. HEAD's reflog is read per working tree.
+
+    Args:
+        refname: 'HEAD', a full ref name or a branch, tag or remote name
+            that resolves to one (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        ReflogEntry list, empty if the ref has no reflog
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If refname does not resolve
+        ValueError: If refname names an object rather than a ref
+
+    Examples:
+        >>> entry = get_reflog("HEAD", "/path/to/repo")[1]
+        >>> entry.selector, entry.new_oid, entry.message
+        ('HEAD@{1}', 'abc123...', 'checkout: moving from main to feature')
+        >>> entry.timestamp
+        '2024-01-15T10:30:00+01:00'
+    """
+    repo_root = get_repository_root(repo_path)
+
+    if refname != "HEAD" and not refname.startswith("refs/"):
+        full_name = execute_git_command(
+            ["git", "rev-parse", "--symbolic-full-name", refname],
+            cwd=repo_root
+        )
+        if not full_name:
+            raise ValueError(f"Not a ref: {refname}")
+        refname = full_name
+
+    log_path = execute_git_command(
+        ["git", "rev-parse", "--git-path", f"logs/{refname}"],
+        cwd=repo_root
+    )
+    try:
+        with open(os.path.join(repo_root, log_path), "rb") as f:
+            lines = f.read().decode("utf-8", errors="replace").splitlines()
+    except OSError:
+        return []
+
+    entries: list[ReflogEntry] = []
+    for line in reversed(lines):
+        match = REFLOG_LINE_PATTERN.match(line)
+        if not match:
+            continue
+        timestamp = _format_timestamp(match.group("time"), match.group("offset"))
+        entries.append(ReflogEntry(
+            refname,
+            len(entries),
+            match.group("old"),
+            match.group("new"),
+            match.group("name"),
+            match.group("email"),
+            timestamp,
+            match.group("message") or ""
+        ))
+
+    return entries
+
+
+def _format_timestamp(seconds: str, offset: str) -> str:
+    """
+    Format a Git raw date as ISO 8601 in its own time zone.
+
+    Args:
+        seconds: Seconds since the epoch
+        offset: Time zone offset as Git records it, e.g. '+0200'
+
+    Returns:
+        Timestamp such as '2024-01-15T10:30:00+02:00'
+    """
+    value = int(offset)
+    minutes = (abs(value) // 100) * 60 + abs(value) % 100
+    zone = timezone(timedelta(minutes=-minutes if value < 0 else minutes))
+    return datetime.fromtimestamp(int(seconds), zone).isoformat()
+
+
+def list_stashes(repo_path: str = ".") -> list[StashEntry]:
+    """
+    List stash entries, latest first.
+
+    Args:
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        StashEntry list, empty if nothing is stashed
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> [s.to_dict() for s in list_stashes("/path/to/repo")]
+        [{'index': 0, 'selector': 'stash@{0}', 'oid': 'abc123...',
+          'message': 'WIP on main: 9fceb02 Fix parser', 'branch': 'main'}]
+    """
+    stashes: list[StashEntry] = []
+    for entry in get_reflog("refs/stash", repo_path):
+        match = STASH_MESSAGE_PATTERN.match(entry.message)
+        branch = match.group("branch") if match else None
+        stashes.append(StashEntry(
+            entry.index,
+            entry.new_oid,
+            entry.message,
+            None if branch == "(no branch)" else branch
+        ))
+    return stashes
+
+
+def get_stash_file_hash(file_path: str, index: int = 0, repo_path: str = ".") -> Optional[str]:
+    """
+    Get the blob SHA of a file as saved in a stash entry.
+
+    Looks in the stashed working tree, then among the untracked files the
+    entry saved (``git stash -u``), so unsaved work can be identified.
+
+    Args:
+        file_path: File path (absolute or relative to repo root)
+        index: Stash entry, 0 for the latest (default: 0)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        Blob SHA, or None if the entry does not hold the file
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitError: If there is no stash entry at index
+        ValueError: If index is negative
+
+    Examples:
+        >>> get_stash_file_hash("src/file.py", 0, "/path/to/repo")
+        'def456abc789...'
+    """
+    if index < 0:
+        raise ValueError(f"Invalid stash index: {index}. Must be non-negative")
+
+    repo_root = get_repository_root(repo_path)
+    selector = f"stash@{{{index}}}"
+
+    blob_hash = get_file_hash_at_commit(repo_root, file_path, selector)
+    if blob_hash is not None:
+        return blob_hash
+
+    # Untracked files are saved in a third parent
+    try:
+        execute_git_command(
+            ["git", "rev-parse", "--verify", "--quiet", f"{selector}^3"],
+            cwd=repo_root
+        )
+    except GitCommandError:
+        return None
+    return get_file_hash_at_commit(repo_root, file_path, f"{selector}^3")
+
+
+def resolve_revspec(spec: str, repo_path: str = ".") -> Optional[ResolvedRevision]:
+    """
+    Resolve any revision expression Git understands to an object.
+
+    Accepts everything ``git rev-parse`` does: ref names, ancestry
+    (``HEAD~3``, ``main^2``), reflog and upstream forms (``main@{upstream}``),
+    peeling (``v1.0^{commit}``) and paths in a tree (``main:src/app.py``).
+
+    Args:
+        spec: Revision expression
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        ResolvedRevision, or None if the expression names no object
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> resolve_revspec("main@{upstream}", "/path/to/repo").to_dict()
+        {'spec': 'main@{upstream}', 'oid': 'abc123...', 'type': 'commit', 'ref': 'refs/remotes/origin/main'}
+        >>> resolve_revspec("v1.0:README.md", "/path/to/repo").type
+        'blob'
+    """
+    repo_root = get_repository_root(repo_path)
+
+    try:
+        oid = execute_git_command(["git", "rev-parse", "--verify", "--quiet", spec], cwd=repo_root)
+    except GitCommandError:
+        return None
+
+    object_type = execute_git_command(["git", "cat-file", "-t", oid], cwd=repo_root)
+    ref = execute_git_command(["git", "rev-parse", "--symbolic-full-name", spec], cwd=repo_root)
+    return ResolvedRevision(spec, oid, object_type, ref or None)
+
+
+def describe(commit: str = "HEAD", repo_path: str = ".") -> Optional[Description]:
+    """
+    Name a commit by the nearest tag reachable from it, like ``git describe --tags --long``.
+
+    Lightweight and annotated tags are both considered.
+
+    Args:
+        commit: Commit SHA, branch, tag or other revision (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        Description, or None if no tag is reachable from the commit
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit
+
+    Examples:
+        >>> describe("HEAD", "/path/to/repo").to_dict()
+        {'tag': 'v1.2.0', 'distance': 3, 'oid': 'abc123d...', 'abbreviated': 'abc123d',
+         'name': 'v1.2.0-3-gabc123d'}
+    """
+    repo_root = get_repository_root(repo_path)
+
+    oid = execute_git_command(
+        ["git", "rev-parse", "--verify", f"{commit}^{{commit}}"],
+        cwd=repo_root
+    )
+
+    try:
+        output = execute_git_command(["git", "describe", "--tags", "--long", oid], cwd=repo_root)
+    except GitCommandError:
+        return None
+
+    # "<tag>-<distance>-g<abbreviated>"; the tag itself may contain dashes
+    tag, distance, abbreviated = output.rsplit("-", 2)
+    return Description(tag, int(distance), oid, abbreviated[1:])
+
+
+def get_latest_tag(
+    commit: str = "HEAD",
+    semver: bool = False,
+    repo_path: str = "."
+) -> Optional[TagInfo]:
+    """
+    Find the nearest tag reachable from a commit: the release it belongs to.
+
+    "Nearest" is by history, as ``git describe`` counts it, so a tag on the
+    commit itself wins over older ones.
+
+    Args:
+        commit: Commit SHA, branch, tag or other revision (default: 'HEAD')
+        semver: Only consider tags that look like semantic versions, such
+            as 'v1.2.3' or '1.2.3-rc.1' (default: False)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        TagInfo, or None if no (matching) tag is reachable
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit
+
+    Examples:
+        >>> get_latest_tag(repo_path="/path/to/repo").name
+        'nightly'
+        >>> get_latest_tag(repo_path="/path/to/repo", semver=True).name
+        'v1.2.0'
+    """
+    repo_root = get_repository_root(repo_path)
+
+    oid = execute_git_command(
+        ["git", "rev-parse", "--verify", f"{commit}^{{commit}}"],
+        cwd=repo_root
+    )
+
+    command = ["git", "describe", "--tags", "--abbrev=0"]
+    if semver:
+        command += [f"--match={glob}" for glob in SEMVER_TAG_GLOBS]
+
+    try:
+        name = execute_git_command([*command, oid], cwd=repo_root)
+    except GitCommandError:
+        return None
+
+    return resolve_tag(name, repo_root)
+
+
+def resolve_tag(name: str, repo_path: str = ".") -> Optional[TagInfo]:
+    """
+    Resolve a tag, annotated or lightweight, to the object it tags.
+
+    Annotated tags are peeled to their target and report the tagger;
+    lightweight tags resolve to the object the ref points at directly.
+
+    Args:
+        name: Tag name, with or without the 'refs/tags/' prefix
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        TagInfo, or None if there is no such tag
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> resolve_tag("v1.0.0", "/path/to/repo").to_dict()
+        {'name': 'v1.0.0', 'oid': 'e4f5a6b...', 'target': 'abc123...', 'targetType': 'commit',
+         'annotated': True, 'taggerName': 'Jane Doe', 'taggerEmail': 'jane@example.com',
+         'taggerDate': '2024-01-15T10:30:00+00:00'}
+        >>> resolve_tag("nightly", "/path/to/repo").annotated
+        False
+    """
+    repo_root = get_repository_root(repo_path)
+
+    ref_name = name if name.startswith("refs/tags/") else f"refs/tags/{name}"
+    format_string = (
+        "%(refname)%00%(objectname)%00%(objecttype)%00%(*objectname)%00%(*objecttype)"
+        "%00%(taggername)%00%(taggeremail:trim)%00%(taggerdate:iso-strict)"
+    )
+    output = execute_git_command(
+        ["git", "for-each-ref", f"--format={format_string}", ref_name],
+        cwd=repo_root
+    )
+
+    for line in output.splitlines():
+        refname, oid, object_type, target, target_type, tagger, email, date = line.split("\0")
+        # The pattern also matches tags below refname/ as a directory
+        if refname != ref_name:
+            continue
+        # Only annotated tags have a peeled target and a tagger
+        return TagInfo(
+            refname[len("refs/tags/"):],
+            oid,
+            target or oid,
+            target_type or object_type,
+            tagger or None,
+            email or None,
+            date or None
+        )
+
+    return None
+
+
+__all__ = [
+    "HeadInfo",
+    "get_head_info",
+    "get_repo_fingerprint",
+    "RefKind",
+    "RefInfo",
+    "list_refs",
+    "refs_containing",
+    "ReflogEntry",
+    "get_reflog",
+    "StashEntry",
+    "list_stashes",
+    "get_stash_file_hash",
+    "ResolvedRevision",
+    "resolve_revspec",
+    "Description",
+    "describe",
+    "get_latest_tag",
+    "TagInfo",
+    "resolve_tag",
+]
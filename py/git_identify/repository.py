@@ -0,0 +1,307 @@
+"""
+Repository layout inspection.
+
+Provides information about the working trees and submodules of a local
+Git repository so file lookups can be routed to the right checkout, and
+about operations in progress in it.
+"""
+
+import os
+import re
+from typing import Any, Literal, Optional
+
+from .errors import GitCommandError
+from .utils.git import (
+    execute_git_command,
+    get_remote_url,
+    get_repository_root,
+    resolve_repository_path,
+)
+from .utils.path import resolve_file_path
+
+RepositoryState = Literal[
+    "clean", "merge", "revert", "cherry-pick", "bisect", "rebase", "apply-mailbox"
+]
+
+REPOSITORY_STATES = (
+    "clean", "merge", "revert", "cherry-pick", "bisect", "rebase", "apply-mailbox"
+)
+
+# Git directory markers for operations in progress, checked in order
+STATE_MARKERS = (
+    ("rebase-merge", "rebase"),
+    ("rebase-apply/applying", "apply-mailbox"),
+    ("rebase-apply", "rebase"),
+    ("MERGE_HEAD", "merge"),
+    ("REVERT_HEAD", "revert"),
+    ("CHERRY_PICK_HEAD", "cherry-pick"),
+    ("BISECT_LOG", "bisect"),
+)
+
+
+class Worktree:
+    """
+    A working tree attached to a repository.
+
+    Attributes:
+        path: Absolute path of the working tree (the Git directory for bare repos)
+        head: Commit SHA checked out in the working tree (None for bare repos)
+        branch: Checked out branch name (None if detached or bare)
+        is_main: Whether this is the main working tree
+        is_bare: Whether this entry is a bare repository
+        is_detached: Whether HEAD is detached
+        is_locked: Whether the worktree is locked against pruning
+        is_prunable: Whether the worktree's directory is gone and can be pruned
+    """
+
+    def __init__(
+        self,
+        path: str,
+        head: Optional[str] = None,
+        branch: Optional[str] = None,
+        is_main: bool = False,
+        is_bare: bool = False,
+        is_detached: bool = False,
+        is_locked: bool = False,
+        is_prunable: bool = False
+    ) -> None:
+        self.path = path
+        self.head = head
+        self.branch = branch
+        self.is_main = is_main
+        self.is_bare = is_bare
+        self.is_detached = is_detached
+        self.is_locked = is_locked
+        self.is_prunable = is_prunable
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "head": self.head,
+            "branch": self.branch,
+            "isMain": self.is_main,
+            "isBare": self.is_bare,
+            "isDetached": self.is_detached,
+            "isLocked": self.is_locked,
+            "isPrunable": self.is_prunable
+        }
+
+
+class SubmoduleIdentity:
+    """
+    Identity of a path inside a submodule, seen from both repositories.
+
+    Attributes:
+        superproject_root: Root of the superproject working tree
+        submodule_path: Gitlink path of the submodule in the superproject
+        gitlink_commit: Submodule commit recorded in the superproject's HEAD
+            (None if the submodule is not committed yet)
+        submodule_root: Root of the submodule working tree
+        head_commit: Commit checked out in the submodule (None if unborn)
+        remote_url: Submodule origin remote URL (None if not configured)
+        file_path: Path relative to the submodule root
+    """
+
+    def __init__(
+        self,
+        superproject_root: str,
+        submodule_path: str,
+        submodule_root: str,
+        file_path: str,
+        gitlink_commit: Optional[str] = None,
+        head_commit: Optional[str] = None,
+        remote_url: Optional[str] = None
+    ) -> None:
+        self.superproject_root = superproject_root
+        self.submodule_path = submodule_path
+        self.submodule_root = submodule_root
+        self.file_path = file_path
+        self.gitlink_commit = gitlink_commit
+        self.head_commit = head_commit
+        self.remote_url = remote_url
+
+    @property
+    def is_modified(self) -> bool:
+        """Whether the submodule has moved away from the commit the superproject pins."""
+        return self.head_commit != self.gitlink_commit
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "superproject": {
+                "root": self.superproject_root,
+                "gitlinkPath": self.submodule_path,
+                "gitlinkCommit": self.gitlink_commit
+            },
+            "submodule": {
+                "root": self.submodule_root,
+                "headCommit": self.head_commit,
+                "remoteUrl": self.remote_url,
+                "filePath": self.file_path
+            },
+            "isModified": self.is_modified
+        }
+
+
+def get_submodule_identity(file_path: str, repo_path: str = ".") -> Optional[SubmoduleIdentity]:
+    """
+    Report the superproject gitlink and submodule-level identity of a path.
+
+    Identification functions already resolve paths inside submodules against
+    the submodule itself; this adds the superproject's view, i.e. which
+    submodule commit the superproject pins.
+
+    Args:
+        file_path: File or directory path (absolute or relative to repo root)
+        repo_path: Path within the superproject (default: current directory)
+
+    Returns:
+        SubmoduleIdentity, or None if the path is not inside a submodule
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> identity = get_submodule_identity("vendor/lib/src/file.py", "/path/to/repo")
+        >>> identity.submodule_path, identity.file_path
+        ('vendor/lib', 'src/file.py')
+    """
+    submodule_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    superproject_root = execute_git_command(
+        "git rev-parse --show-superproject-working-tree",
+        cwd=submodule_root
+    )
+    if not superproject_root:
+        return None
+
+    submodule_path = resolve_file_path(superproject_root, submodule_root)
+
+    gitlink_commit = None
+    try:
+        ls_tree_output = execute_git_command(
+            ["git", "ls-tree", "HEAD", "--", submodule_path],
+            cwd=superproject_root
+        )
+        # "160000 commit <hash>\t<path>"
+        match = re.match(r"^160000 commit ([0-9a-f]{40})\t", ls_tree_output)
+        if match:
+            gitlink_commit = match.group(1)
+    except GitCommandError:
+        pass
+
+    try:
+        head_commit: Optional[str] = execute_git_command(
+            "git rev-parse --verify --quiet HEAD",
+            cwd=submodule_root
+        )
+    except GitCommandError:
+        head_commit = None
+
+    return SubmoduleIdentity(
+        superproject_root=superproject_root,
+        submodule_path=submodule_path,
+        submodule_root=submodule_root,
+        file_path=relative_path,
+        gitlink_commit=gitlink_commit,
+        head_commit=head_commit,
+        remote_url=get_remote_url(submodule_root)
+    )
+
+
+def list_worktrees(repo_path: str = ".") -> list[Worktree]:
+    """
+    List the main and linked working trees of a repository.
+
+    Works from any of the repository's working trees; the main working tree
+    is always listed first.
+
+    Args:
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        List of Worktree entries
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> [(w.path, w.branch) for w in list_worktrees("/path/to/repo")]
+        [('/path/to/repo', 'main'), ('/path/to/repo-feature', 'feature/x')]
+    """
+    repo_root = get_repository_root(repo_path)
+    output = execute_git_command("git worktree list --porcelain", cwd=repo_root)
+
+    worktrees: list[Worktree] = []
+    for block in output.split("\n\n"):
+        lines = block.strip().splitlines()
+        if not lines or not lines[0].startswith("worktree "):
+            continue
+
+        worktree = Worktree(path=lines[0][len("worktree "):], is_main=not worktrees)
+        for line in lines[1:]:
+            key, _, value = line.partition(" ")
+            if key == "HEAD":
+                worktree.head = value
+            elif key == "branch":
+                worktree.branch = value.removeprefix("refs/heads/")
+            elif key == "bare":
+                worktree.is_bare = True
+            elif key == "detached":
+                worktree.is_detached = True
+            elif key == "locked":
+                worktree.is_locked = True
+            elif key == "prunable":
+                worktree.is_prunable = True
+
+        worktrees.append(worktree)
+
+    return worktrees
+
+
+def get_repo_state(repo_path: str = ".") -> RepositoryState:
+    """
+    Report which multi-step operation, if any, is in progress.
+
+    While a merge, rebase, cherry-pick, revert or bisect is in progress HEAD
+    is transient, so URLs generated against it may not survive the
+    operation. State is read per working tree.
+
+    Args:
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        'clean' or the operation in progress: 'merge', 'revert',
+        'cherry-pick', 'bisect', 'rebase' or 'apply-mailbox' (``git am``)
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> get_repo_state("/path/to/repo")
+        'clean'
+        >>> get_repo_state("/path/to/repo-mid-rebase")
+        'rebase'
+    """
+    repo_root = get_repository_root(repo_path)
+
+    markers = [argument for marker, _ in STATE_MARKERS for argument in ("--git-path", marker)]
+    paths = execute_git_command(["git", "rev-parse", *markers], cwd=repo_root).splitlines()
+
+    for path, (_, state) in zip(paths, STATE_MARKERS):
+        if os.path.exists(os.path.join(repo_root, path)):
+            return state
+
+    return "clean"
+
+
+__all__ = [
+    "RepositoryState",
+    "REPOSITORY_STATES",
+    "get_repo_state",
+    "Worktree",
+    "list_worktrees",
+    "SubmoduleIdentity",
+    "get_submodule_identity",
+]
@@ -0,0 +1,259 @@
+"""
+Commit signature inspection.
+
+Extracts the GPG, SSH or X.509 signature embedded in a commit object and
+verifies it through Git's own signature checking, so that tooling can
+report who signed a commit and whether the signature holds.
+"""
+
+import os
+from typing import Any, Literal, Optional
+
+from .utils.git import execute_git_command, execute_git_command_bytes, get_repository_root
+
+SignatureFormat = Literal["openpgp", "ssh", "x509"]
+
+SIGNATURE_FORMATS = ("openpgp", "ssh", "x509")
+
+SignatureStatus = Literal[
+    "good",
+    "unknown-validity",
+    "bad",
+    "expired-signature",
+    "expired-key",
+    "revoked-key",
+    "unverifiable",
+    "unsigned",
+]
+
+# Git's %G? verification codes and the status each reports
+SIGNATURE_STATUS_CODES: dict[str, SignatureStatus] = {
+    "G": "good",
+    "U": "unknown-validity",
+    "B": "bad",
+    "X": "expired-signature",
+    "Y": "expired-key",
+    "R": "revoked-key",
+    "E": "unverifiable",
+    "N": "unsigned",
+}
+
+# Armor headers identifying each signature format
+SIGNATURE_ARMOR: tuple[tuple[str, SignatureFormat], ...] = (
+    ("-----BEGIN PGP SIGNATURE-----", "openpgp"),
+    ("-----BEGIN SSH SIGNATURE-----", "ssh"),
+    ("-----BEGIN SIGNED MESSAGE-----", "x509"),
+)
+
+# Commit headers holding the signature (gpgsig-sha256 in SHA-256 repositories)
+SIGNATURE_HEADERS = ("gpgsig", "gpgsig-sha256")
+
+
+class CommitSignature:
+    """
+    A signature embedded in a commit.
+
+    Attributes:
+        format: 'openpgp', 'ssh' or 'x509' (None if the armor is unrecognized)
+        signature: ASCII-armored signature block
+        payload: Signed data: the commit object without its signature header
+    """
+
+    def __init__(self, format: Optional[SignatureFormat], signature: str, payload: str) -> None:
+        self.format = format
+        self.signature = signature
+        self.payload = payload
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "format": self.format,
+            "signature": self.signature,
+            "payload": self.payload
+        }
+
+
+class SignatureVerification:
+    """
+    The outcome of checking a commit signature.
+
+    Attributes:
+        status: 'good'; 'unknown-validity' (intact, but the key is not
+            trusted or not an allowed signer); 'bad'; 'expired-signature';
+            'expired-key'; 'revoked-key'; 'unverifiable' (e.g. the key is
+            missing, or no SSH allowed signers file is configured) or
+            'unsigned'
+        signer: Signer identity, e.g. 'Jane Doe <jane@example.com>' for
+            OpenPGP or the allowed-signers principal for SSH (None if unknown)
+        key: Signing key ID (None if unsigned)
+        fingerprint: Signing key fingerprint (None if unknown)
+    """
+
+    def __init__(
+        self,
+        status: SignatureStatus,
+        signer: Optional[str] = None,
+        key: Optional[str] = None,
+        fingerprint: Optional[str] = None
+    ) -> None:
+        self.status = status
+        self.signer = signer
+        self.key = key
+        self.fingerprint = fingerprint
+
+    @property
+    def valid(self) -> bool:
+        """Whether the signature is intact and made by a trusted key."""
+        return self.status == "good"
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "status": self.status,
+            "valid": self.valid,
+            "signer": self.signer,
+            "key": self.key,
+            "fingerprint": self.fingerprint
+        }
+
+
+def extract_signature(commit: str = "HEAD", repo_path: str = ".") -> Optional[CommitSignature]:
+    """
+    Extract the signature block and signed payload of a commit.
+
+    No keys are needed; the signature is returned as stored, so it can be
+    checked with external tools (``gpg --verify``, ``ssh-keygen -Y verify``)
+    against the payload.
+
+    Args:
+        commit: Commit SHA, branch, tag or other revision (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        CommitSignature, or None if the commit is not signed
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit
+
+    Examples:
+        >>> sig = extract_signature("HEAD", "/path/to/repo")
+        >>> sig.format
+        'ssh'
+        >>> sig.signature.splitlines()[0]
+        '-----BEGIN SSH SIGNATURE-----'
+    """
+    repo_root = get_repository_root(repo_path)
+
+    content = execute_git_command_bytes(
+        ["git", "cat-file", "commit", f"{commit}^{{commit}}"],
+        cwd=repo_root
+    ).decode("utf-8", errors="replace")
+
+    header, separator, message = content.partition("\n\n")
+
+    signature_lines: list[str] = []
+    payload_lines: list[str] = []
+    in_signature = False
+    for line in header.split("\n"):
+        # Multi-line header values continue on lines starting with a space
+        if in_signature and line.startswith(" "):
+            signature_lines.append(line[1:])
+            continue
+        name, _, value = line.partition(" ")
+        in_signature = name in SIGNATURE_HEADERS and not signature_lines
+        if in_signature:
+            signature_lines.append(value)
+        else:
+            payload_lines.append(line)
+
+    if not signature_lines:
+        return None
+
+    signature = "\n".join(signature_lines)
+    signature_format = next(
+        (fmt for armor, fmt in SIGNATURE_ARMOR if signature.startswith(armor)),
+        None
+    )
+    payload = "\n".join(payload_lines) + separator + message
+    return CommitSignature(signature_format, signature, payload)
+
+
+def verify_signature(
+    commit: str = "HEAD",
+    keyring: Optional[str] = None,
+    repo_path: str = "."
+) -> SignatureVerification:
+    """
+    Verify a commit signature with Git and report the signer.
+
+    Requires ``gpg`` (OpenPGP), ``gpgsm`` (X.509) or ``ssh-keygen`` (SSH)
+    as configured for Git. Keys come from the user's keyring unless another
+    is given.
+
+    Args:
+        commit: Commit SHA, branch, tag or other revision (default: 'HEAD')
+        keyring: For OpenPGP and X.509 signatures, a GnuPG home directory;
+            for SSH signatures, an allowed signers file (default: Git's
+            configuration, i.e. ``~/.gnupg`` or
+            ``gpg.ssh.allowedSignersFile``)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        SignatureVerification; status 'unsigned' if the commit has no
+        signature
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit
+
+    Examples:
+        >>> verify_signature("HEAD", repo_path="/path/to/repo", keyring="~/.ssh/allowed_signers").to_dict()
+        {'status': 'good', 'valid': True, 'signer': 'jane@example.com',
+         'key': 'SHA256:BNTK5keE...', 'fingerprint': 'SHA256:BNTK5keE...'}
+    """
+    signature = extract_signature(commit, repo_path)
+    if signature is None:
+        return SignatureVerification("unsigned")
+
+    repo_root = get_repository_root(repo_path)
+
+    config: list[str] = []
+    env: dict[str, str] = {}
+    if keyring is not None:
+        keyring = os.path.expanduser(keyring)
+        if signature.format == "ssh":
+            config = ["-c", f"gpg.ssh.allowedSignersFile={keyring}"]
+        else:
+            env["GNUPGHOME"] = keyring
+
+    output = execute_git_command(
+        ["git", *config, "show", "-s", "--format=%G?%x00%GS%x00%GK%x00%GF", f"{commit}^{{commit}}"],
+        cwd=repo_root,
+        env=env
+    )
+
+    code, signer, key, fingerprint = output.split("\0")
+    # Git reports N when it cannot check the signature at all, e.g. an SSH
+    # signature with no allowed signers file configured
+    status = SIGNATURE_STATUS_CODES.get(code, "unverifiable")
+    if status == "unsigned":
+        status = "unverifiable"
+
+    return SignatureVerification(
+        status,
+        signer or None,
+        key or None,
+        fingerprint or None
+    )
+
+
+__all__ = [
+    "SignatureFormat",
+    "SIGNATURE_FORMATS",
+    "SignatureStatus",
+    "CommitSignature",
+    "SignatureVerification",
+    "extract_signature",
+    "verify_signature",
+]
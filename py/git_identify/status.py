@@ -0,0 +1,813 @@
+"""
+Working-tree status.
+
+Reports the index and working-tree status of files and directories, the
+index entries Git records for them, and the ignore rules and attributes
+that apply to a path.
+"""
+
+import os
+import tempfile
+from typing import Any, Literal, Optional, Union
+
+from .errors import FileNotFoundError, GitCommandError
+from .utils.git import execute_git_command, get_repository_root, resolve_repository_path
+
+FileChange = Literal[
+    "unmodified", "modified", "type-changed", "added", "deleted", "renamed",
+    "copied", "unmerged", "untracked", "ignored"
+]
+
+# git status --porcelain=v2 XY codes
+STATUS_CODES: dict[str, FileChange] = {
+    ".": "unmodified",
+    "M": "modified",
+    "T": "type-changed",
+    "A": "added",
+    "D": "deleted",
+    "R": "renamed",
+    "C": "copied",
+    "U": "unmerged",
+}
+
+# Attribute value: True (set), False (unset, "-attr"), a string ("attr=value")
+# or None (unspecified)
+AttributeValue = Optional[Union[bool, str]]
+
+
+class FileStatus:
+    """
+    Status of a file in the index and in the working tree.
+
+    Staged and unstaged changes are reported separately, so a file that was
+    staged and then edited again has both ``index`` and ``worktree`` set.
+
+    Git does not compare files flagged skip-worktree (sparse checkout,
+    ``git update-index --skip-worktree``) or assume-unchanged against the
+    working tree, so they can report 'unmodified' while edited on disk;
+    ``may_be_stale`` flags them.
+
+    Attributes:
+        path: Path relative to the repository root
+        index: Change staged in the index relative to HEAD
+        worktree: Change in the working tree relative to the index
+        rename_source: Original path when the file was renamed or copied
+        skip_worktree: Whether the index entry has the skip-worktree bit
+        assume_unchanged: Whether the index entry has the assume-unchanged bit
+    """
+
+    def __init__(
+        self,
+        path: str,
+        index: FileChange = "unmodified",
+        worktree: FileChange = "unmodified",
+        rename_source: Optional[str] = None,
+        skip_worktree: bool = False,
+        assume_unchanged: bool = False
+    ) -> None:
+        self.path = path
+        self.index = index
+        self.worktree = worktree
+        self.rename_source = rename_source
+        self.skip_worktree = skip_worktree
+        self.assume_unchanged = assume_unchanged
+
+    @property
+    def is_staged(self) -> bool:
+        """Whether the index has a change to commit."""
+        return self.index not in ("unmodified", "untracked", "ignored", "unmerged")
+
+    @property
+    def is_unstaged(self) -> bool:
+        """Whether the working tree differs from the index."""
+        return self.worktree not in ("unmodified", "untracked", "ignored", "unmerged")
+
+    @property
+    def is_conflicted(self) -> bool:
+        """Whether the file has unresolved merge conflicts."""
+        return self.index == "unmerged"
+
+    @property
+    def may_be_stale(self) -> bool:
+        """Whether Git skips checking the working tree copy, so changes may go unreported."""
+        return self.skip_worktree or self.assume_unchanged
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "index": self.index,
+            "worktree": self.worktree,
+            "renameSource": self.rename_source,
+            "isStaged": self.is_staged,
+            "isUnstaged": self.is_unstaged,
+            "isConflicted": self.is_conflicted,
+            "skipWorktree": self.skip_worktree,
+            "assumeUnchanged": self.assume_unchanged,
+            "mayBeStale": self.may_be_stale
+        }
+
+
+class IndexEntry:
+    """
+    A staged entry for a path in the index.
+
+    Attributes:
+        path: Path relative to the repository root
+        oid: Object SHA (a blob, or a commit for a submodule gitlink)
+        mode: Octal file mode, e.g. '100644', '100755', '120000' (symlink)
+            or '160000' (gitlink)
+        stage: 0 for a merged entry; 1 (common ancestor), 2 (ours) or
+            3 (theirs) while the path has a merge conflict
+    """
+
+    def __init__(self, path: str, oid: str, mode: str, stage: int = 0) -> None:
+        self.path = path
+        self.oid = oid
+        self.mode = mode
+        self.stage = stage
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "oid": self.oid,
+            "mode": self.mode,
+            "stage": self.stage
+        }
+
+
+class DirectoryStatus:
+    """
+    File counts by status below a directory.
+
+    A file that is staged and then edited again counts as both staged and
+    modified; every other file is counted once.
+
+    Attributes:
+        path: Directory path relative to the repository root ('' for the root)
+        clean: Tracked files with no staged or unstaged changes
+        modified: Files with unstaged changes (including deletions)
+        staged: Files with staged changes
+        untracked: Untracked files
+        conflicted: Files with unresolved merge conflicts
+    """
+
+    def __init__(
+        self,
+        path: str,
+        clean: int = 0,
+        modified: int = 0,
+        staged: int = 0,
+        untracked: int = 0,
+        conflicted: int = 0
+    ) -> None:
+        self.path = path
+        self.clean = clean
+        self.modified = modified
+        self.staged = staged
+        self.untracked = untracked
+        self.conflicted = conflicted
+
+    @property
+    def is_clean(self) -> bool:
+        """Whether nothing below the directory is changed or untracked."""
+        return not (self.modified or self.staged or self.untracked or self.conflicted)
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "clean": self.clean,
+            "modified": self.modified,
+            "staged": self.staged,
+            "untracked": self.untracked,
+            "conflicted": self.conflicted,
+            "isClean": self.is_clean
+        }
+
+
+class IgnoreRule:
+    """
+    The ignore pattern that excludes a path.
+
+    Attributes:
+        path: Ignored path relative to the repository root
+        source: File the pattern is defined in (e.g. ``.gitignore``,
+            ``src/.gitignore``, ``.git/info/exclude`` or the global
+            ``core.excludesFile``)
+        line: Line number of the pattern in the source
+        pattern: The matching pattern as written
+    """
+
+    def __init__(self, path: str, source: str, line: int, pattern: str) -> None:
+        self.path = path
+        self.source = source
+        self.line = line
+        self.pattern = pattern
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "source": self.source,
+            "line": self.line,
+            "pattern": self.pattern
+        }
+
+
+def get_file_status(file_path: str, repo_path: str = ".") -> FileStatus:
+    """
+    Report the index and working-tree status of a file.
+
+    Renames are detected across the whole repository, so a staged
+    ``git mv old.py new.py`` reports ``new.py`` as renamed from ``old.py``
+    (querying ``old.py`` returns the same entry). To check many paths, use
+    ``get_repo_status`` once instead.
+
+    Args:
+        file_path: File path (absolute or relative to repo root)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        FileStatus; a conflicted file reports 'unmerged' on both sides, an
+        untracked or ignored file reports 'untracked' or 'ignored' on both
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        FileNotFoundError: If the file is neither tracked nor on disk
+
+    Examples:
+        >>> status = get_file_status("src/file.py", "/path/to/repo")
+        >>> status.index, status.worktree
+        ('modified', 'modified')
+        >>> get_file_status("new.py", "/path/to/repo").rename_source
+        'old.py'
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    for status in _read_status(repo_root):
+        if relative_path in (status.path, status.rename_source):
+            return status
+
+    try:
+        execute_git_command(
+            ["git", "ls-files", "--error-unmatch", "--", relative_path],
+            cwd=repo_root
+        )
+        skip_worktree, assume_unchanged = _read_index_flags(repo_root, relative_path).get(
+            relative_path, (False, False)
+        )
+        return FileStatus(
+            relative_path,
+            skip_worktree=skip_worktree,
+            assume_unchanged=assume_unchanged
+        )
+    except GitCommandError:
+        pass
+
+    if not os.path.lexists(os.path.join(repo_root, relative_path)):
+        raise FileNotFoundError(
+            f"File not found in repository: {relative_path}",
+            file_path=relative_path
+        )
+
+    # Ignored files are not listed by --untracked-files
+    return FileStatus(relative_path, "ignored", "ignored")
+
+
+def get_index_entries(file_path: str, repo_path: str = ".") -> list[IndexEntry]:
+    """
+    List the index entries of a path, including conflict stages.
+
+    Args:
+        file_path: File path (absolute or relative to repo root)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        IndexEntry list ordered by stage: one stage 0 entry for a merged
+        file, stages 1-3 for a conflicted one, empty if the path is not in
+        the index
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> [(e.stage, e.oid[:7]) for e in get_index_entries("src/file.py", "/path/to/repo")]
+        [(1, 'a1b2c3d'), (2, 'e4f5a6b'), (3, 'c7d8e9f')]
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    output = execute_git_command(
+        ["git", "ls-files", "--stage", "-z", "--", relative_path],
+        cwd=repo_root
+    )
+
+    entries: list[IndexEntry] = []
+    for line in output.split("\0"):
+        # "<mode> <oid> <stage>\t<path>"
+        info, _, path = line.partition("\t")
+        if path != relative_path:
+            continue
+        mode, oid, stage = info.split(" ")
+        entries.append(IndexEntry(path, oid, mode, int(stage)))
+
+    return sorted(entries, key=lambda entry: entry.stage)
+
+
+def get_index_entry(file_path: str, stage: int = 0, repo_path: str = ".") -> Optional[IndexEntry]:
+    """
+    Get the staged entry of a path: what the next commit will contain.
+
+    Unlike the HEAD blob, this reflects ``git add`` since the last commit,
+    so tools can identify exactly what is about to be committed.
+
+    Args:
+        file_path: File path (absolute or relative to repo root)
+        stage: Index stage: 0 (default) for a merged entry, or 1-3 to read a
+            side of a merge conflict
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        IndexEntry, or None if the path has no entry at that stage
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        ValueError: If stage is not 0-3
+
+    Examples:
+        >>> entry = get_index_entry("src/file.py", repo_path="/path/to/repo")
+        >>> entry.oid, entry.mode
+        ('fedcba987654...', '100644')
+        >>> get_index_entry("conflicted.py", repo_path="/path/to/repo", stage=3).oid
+        'c7d8e9f...'
+    """
+    if stage not in (0, 1, 2, 3):
+        raise ValueError(f"Invalid index stage: {stage}. Must be 0, 1, 2 or 3")
+
+    for entry in get_index_entries(file_path, repo_path):
+        if entry.stage == stage:
+            return entry
+    return None
+
+
+def get_repo_status(
+    include_ignored: bool = False,
+    pathspec: Optional[str] = None,
+    repo_path: str = "."
+) -> list[FileStatus]:
+    """
+    List the status of every changed, untracked (and optionally ignored) file.
+
+    Runs ``git status`` once for the whole repository; unmodified tracked
+    files are not listed. A pathspec such as ``src/**/*.py`` limits the
+    entries using Git's own matching: it is relative to the repository root
+    and read with ``:(glob)`` magic (``*`` stays within a directory, ``**``
+    crosses them) unless it carries its own magic, e.g. ``:(icase)*.md``.
+    Renames are only paired when both paths match the pathspec.
+
+    Args:
+        include_ignored: Also list ignored files (default: False)
+        pathspec: Only list paths matching this pathspec (default: all)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        List of FileStatus entries in ``git status`` order
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> [(s.path, s.index, s.worktree) for s in get_repo_status(repo_path="/path/to/repo")]
+        [('src/file.py', 'modified', 'unmodified'), ('notes.txt', 'untracked', 'untracked')]
+        >>> [s.path for s in get_repo_status(repo_path="/path/to/repo", pathspec="src/**/*.py")]
+        ['src/file.py']
+    """
+    repo_root = get_repository_root(repo_path)
+    return _read_status(repo_root, include_ignored, pathspec)
+
+
+def get_directory_status(dir_path: str, repo_path: str = ".") -> DirectoryStatus:
+    """
+    Summarize the status of every file below a directory.
+
+    Uses one ``git status`` and one ``git ls-files`` run regardless of the
+    number of files, so file-tree views can badge folders cheaply.
+
+    Args:
+        dir_path: Directory path (absolute or relative to repo root)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        DirectoryStatus with file counts
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> summary = get_directory_status("src", "/path/to/repo")
+        >>> summary.clean, summary.modified, summary.untracked
+        (42, 2, 1)
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, dir_path)
+    if relative_path == ".":
+        relative_path = ""
+
+    prefix = f"{relative_path}/" if relative_path else ""
+    pathspec = ["--", relative_path] if relative_path else []
+    output = execute_git_command(["git", "ls-files", "-z", *pathspec], cwd=repo_root)
+    tracked = set(filter(None, output.split("\0")))
+
+    summary = DirectoryStatus(relative_path)
+    for status in _read_status(repo_root):
+        if not status.path.startswith(prefix):
+            continue
+
+        tracked.discard(status.path)
+        if status.index == "untracked":
+            summary.untracked += 1
+        elif status.is_conflicted:
+            summary.conflicted += 1
+        else:
+            summary.staged += status.is_staged
+            summary.modified += status.is_unstaged
+
+    summary.clean = len(tracked)
+    return summary
+
+
+def list_untracked(
+    directory: Optional[str] = None,
+    directories: bool = False,
+    repo_path: str = "."
+) -> list[str]:
+    """
+    List untracked files that are not ignored.
+
+    Such files have no commit, so no commit-pinned link can be produced for
+    them until they are added.
+
+    Args:
+        directory: Only list files below this directory (absolute or relative
+            to repo root; default: the whole repository)
+        directories: List a wholly untracked directory once, with a trailing
+            slash, instead of every file in it (default: False)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        Paths relative to the repository root
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> list_untracked(repo_path="/path/to/repo")
+        ['notes.txt', 'scratch/a.py']
+        >>> list_untracked(repo_path="/path/to/repo", directories=True)
+        ['notes.txt', 'scratch/']
+    """
+    return _list_others(directory, directories, False, repo_path)
+
+
+def list_ignored(
+    directory: Optional[str] = None,
+    directories: bool = False,
+    repo_path: str = "."
+) -> list[str]:
+    """
+    List untracked files excluded by ignore rules.
+
+    Args:
+        directory: Only list files below this directory (absolute or relative
+            to repo root; default: the whole repository)
+        directories: List a wholly ignored directory (e.g. ``node_modules``)
+            once, with a trailing slash, instead of every file in it
+            (default: False)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        Paths relative to the repository root
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> list_ignored(repo_path="/path/to/repo", directories=True)
+        ['build/', 'debug.log', 'node_modules/']
+    """
+    return _list_others(directory, directories, True, repo_path)
+
+
+def _list_others(
+    directory: Optional[str],
+    directories: bool,
+    ignored: bool,
+    repo_path: str
+) -> list[str]:
+    """
+    List untracked or ignored files with ``git ls-files --others``.
+
+    Args:
+        directory: Only list files below this directory
+        directories: Collapse wholly untracked or ignored directories
+        ignored: List ignored instead of untracked files
+        repo_path: Path within the repository
+
+    Returns:
+        Paths relative to the repository root
+    """
+    if directory is None:
+        repo_root, relative_path = get_repository_root(repo_path), ""
+    else:
+        repo_root, relative_path = resolve_repository_path(repo_path, directory)
+        if relative_path == ".":
+            relative_path = ""
+
+    command = ["git", "ls-files", "-z", "--others", "--exclude-standard"]
+    if ignored:
+        command.append("--ignored")
+    if directories:
+        command.append("--directory")
+    if relative_path:
+        command += ["--", relative_path]
+
+    output = execute_git_command(command, cwd=repo_root)
+    return [path for path in output.split("\0") if path]
+
+
+def is_path_ignored(file_path: str, repo_path: str = ".") -> bool:
+    """
+    Check whether a path is excluded by an ignore rule.
+
+    Tracked files are never reported as ignored, since ignore rules only
+    apply to untracked files.
+
+    Args:
+        file_path: File or directory path (absolute or relative to repo root)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        True if the path is ignored
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> is_path_ignored("build/output.js", "/path/to/repo")
+        True
+    """
+    return explain_ignore(file_path, repo_path) is not None
+
+
+def explain_ignore(file_path: str, repo_path: str = ".") -> Optional[IgnoreRule]:
+    """
+    Report which ignore file and pattern exclude a path.
+
+    Useful to tell users why a file cannot be identified or linked: an
+    ignored file is never committed, so it has no GitHub URL.
+
+    Args:
+        file_path: File or directory path (absolute or relative to repo root)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        IgnoreRule, or None if the path is not ignored (including tracked
+        files and paths re-included by a ``!pattern``)
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> rule = explain_ignore("build/output.js", "/path/to/repo")
+        >>> rule.source, rule.line, rule.pattern
+        ('.gitignore', 3, 'build/')
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    try:
+        output = execute_git_command(
+            ["git", "check-ignore", "--verbose", "--", relative_path],
+            cwd=repo_root
+        )
+    except GitCommandError as e:
+        # Exit code 1: not ignored
+        if e.context.get("exit_code") == 1:
+            return None
+        raise
+
+    # "<source>:<line>:<pattern>\t<path>"
+    rule, _, _ = output.partition("\t")
+    source, line, pattern = rule.split(":", 2)
+
+    if pattern.startswith("!"):
+        return None
+
+    return IgnoreRule(relative_path, source, int(line), pattern)
+
+
+def get_attributes(
+    file_path: str,
+    names: Optional[list[str]] = None,
+    commit: Optional[str] = None,
+    repo_path: str = "."
+) -> dict[str, AttributeValue]:
+    """
+    Look up the gitattributes that apply to a path.
+
+    Attributes such as ``linguist-generated``, ``export-ignore``, ``diff``
+    and ``eol`` tell consumers whether a file should be linked, diffed or
+    skipped. Lookup is delegated to ``git check-attr``, so every
+    ``.gitattributes`` file, ``.git/info/attributes`` and macros such as
+    ``binary`` are taken into account.
+
+    With ``commit``, the ``.gitattributes`` files are read from that
+    commit's tree rather than the working tree, so attributes added or
+    removed since then do not leak in. Git 2.40 and later read the tree
+    with ``--source``; older versions read it into a scratch index.
+
+    Args:
+        file_path: File path (absolute or relative to repo root)
+        names: Attributes to look up (default: every attribute specified
+            for the path)
+        commit: Read attributes as of this revision (default: the working
+            tree)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        Mapping of attribute name to True (set), False (unset), a string
+        value, or None (unspecified; only returned for requested names)
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit cannot be resolved
+
+    Examples:
+        >>> get_attributes("dist/bundle.js", repo_path="/path/to/repo")
+        {'linguist-generated': True, 'diff': False}
+        >>> get_attributes("src/file.py", ["eol", "export-ignore"], repo_path="/path/to/repo")
+        {'eol': 'lf', 'export-ignore': None}
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    selector = list(names) if names else ["--all"]
+    output = _check_attr(repo_root, [*selector, "--", relative_path], commit)
+
+    # "<path>\0<attribute>\0<info>\0" per attribute
+    fields = output.split("\0")
+    attributes: dict[str, AttributeValue] = {}
+    for index in range(0, len(fields) - 2, 3):
+        name, info = fields[index + 1], fields[index + 2]
+        if info == "set":
+            attributes[name] = True
+        elif info == "unset":
+            attributes[name] = False
+        elif info == "unspecified":
+            attributes[name] = None
+        else:
+            attributes[name] = info
+
+    return attributes
+
+
+def _check_attr(repo_root: str, arguments: list[str], commit: Optional[str]) -> str:
+    """
+    Run git check-attr -z against the working tree or a commit's tree.
+
+    Args:
+        repo_root: Repository root path
+        arguments: Attribute selector, "--" and the path
+        commit: Revision whose tree holds the .gitattributes files, or None
+            for the working tree
+
+    Returns:
+        check-attr output
+    """
+    if commit is None:
+        return execute_git_command(["git", "check-attr", "-z", *arguments], cwd=repo_root)
+
+    tree = f"{commit}^{{tree}}"
+    try:
+        return execute_git_command(
+            ["git", "check-attr", "-z", f"--source={tree}", *arguments],
+            cwd=repo_root
+        )
+    except GitCommandError:
+        # Git before 2.40 has no --source; --cached reads a scratch index of the tree instead
+        with tempfile.TemporaryDirectory() as scratch:
+            env = {"GIT_INDEX_FILE": os.path.join(scratch, "index")}
+            execute_git_command(["git", "read-tree", tree], cwd=repo_root, env=env)
+            return execute_git_command(
+                ["git", "check-attr", "-z", "--cached", *arguments],
+                cwd=repo_root,
+                env=env
+            )
+
+
+def _read_status(
+    repo_root: str,
+    include_ignored: bool = False,
+    pathspec: Optional[str] = None
+) -> list[FileStatus]:
+    """
+    Run ``git status --porcelain=v2`` and parse its entries.
+
+    Args:
+        repo_root: Repository root path
+        include_ignored: Also list ignored files
+        pathspec: Only list paths matching this pathspec (glob magic by default)
+
+    Returns:
+        List of FileStatus entries
+    """
+    command = ["git", "status", "--porcelain=v2", "-z", "--untracked-files=all"]
+    if include_ignored:
+        command.append("--ignored=matching")
+    if pathspec:
+        if not pathspec.startswith(":"):
+            pathspec = f":(glob){pathspec}"
+        command += ["--", pathspec]
+
+    output = execute_git_command(command, cwd=repo_root)
+
+    statuses: list[FileStatus] = []
+    entries = iter(output.split("\0"))
+    for entry in entries:
+        kind, _, rest = entry.partition(" ")
+
+        if kind in ("?", "!"):
+            change: FileChange = "untracked" if kind == "?" else "ignored"
+            statuses.append(FileStatus(rest, change, change))
+        elif kind == "1":
+            # "1 XY sub mH mI mW hH hI path"
+            fields = rest.split(" ", 7)
+            statuses.append(FileStatus(
+                fields[7],
+                STATUS_CODES[fields[0][0]],
+                STATUS_CODES[fields[0][1]]
+            ))
+        elif kind == "2":
+            # "2 XY sub mH mI mW hH hI Xscore path", then the original path
+            fields = rest.split(" ", 8)
+            statuses.append(FileStatus(
+                fields[8],
+                STATUS_CODES[fields[0][0]],
+                STATUS_CODES[fields[0][1]],
+                rename_source=next(entries)
+            ))
+        elif kind == "u":
+            # "u XY sub m1 m2 m3 mW h1 h2 h3 path"
+            statuses.append(FileStatus(rest.split(" ", 9)[9], "unmerged", "unmerged"))
+
+    flags = _read_index_flags(repo_root, pathspec)
+    for status in statuses:
+        status.skip_worktree, status.assume_unchanged = flags.get(status.path, (False, False))
+
+    return statuses
+
+
+def _read_index_flags(repo_root: str, pathspec: Optional[str] = None) -> dict[str, tuple[bool, bool]]:
+    """
+    Read the skip-worktree and assume-unchanged bits of index entries.
+
+    Args:
+        repo_root: Repository root path
+        pathspec: Only read entries matching this pathspec
+
+    Returns:
+        Mapping of path to (skip_worktree, assume_unchanged) for flagged
+        entries only
+    """
+    command = ["git", "ls-files", "-v", "-z"]
+    if pathspec:
+        command += ["--", pathspec]
+
+    # "<tag> <path>": 'S' marks skip-worktree, a lowercase tag assume-unchanged
+    flags: dict[str, tuple[bool, bool]] = {}
+    for entry in execute_git_command(command, cwd=repo_root).split("\0"):
+        tag, _, path = entry.partition(" ")
+        skip_worktree = tag.upper() == "S"
+        assume_unchanged = tag.islower()
+        if path and (skip_worktree or assume_unchanged):
+            flags[path] = (skip_worktree, assume_unchanged)
+
+    return flags
+
+
+__all__ = [
+    "FileChange",
+    "FileStatus",
+    "get_file_status",
+    "get_repo_status",
+    "IndexEntry",
+    "get_index_entries",
+    "get_index_entry",
+    "DirectoryStatus",
+    "get_directory_status",
+    "list_untracked",
+    "list_ignored",
+    "IgnoreRule",
+    "is_path_ignored",
+    "explain_ignore",
+    "AttributeValue",
+    "get_attributes",
+]
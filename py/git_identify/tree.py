@@ -0,0 +1,1020 @@
+"""
+Tree and blob inspection.
+
+Reads the trees and blobs of a commit without checking it out: file
+text, LFS pointers, tree entries, manifests, blob lookups, grep and
+symbolic links.
+"""
+
+import fnmatch
+import os
+from typing import Any, Iterator, Optional
+
+from .errors import FileNotFoundError, GitCommandError
+from .status import AttributeValue, get_attributes
+from .utils.encoding import decode_text, detect_encoding
+from .utils.git import (
+    execute_git_command,
+    execute_git_command_bytes,
+    get_blob_bytes,
+    get_blob_size,
+    get_file_hash_at_commit,
+    get_repository_root,
+    resolve_repository_path,
+)
+from .utils.hash import validate_git_hash
+from .utils.lfs import LFS_POINTER_MAX_SIZE, LfsInfo, parse_lfs_pointer
+from .utils.path import normalize_file_path
+
+# Tree entry mode of a symbolic link
+SYMLINK_MODE = "120000"
+
+# Links followed before a target is treated as a loop, as on Linux
+MAX_SYMLINK_HOPS = 40
+
+
+class FileText:
+    """
+    The decoded text of a file as stored at a commit.
+
+    Attributes:
+        path: File path relative to repo root
+        oid: Blob SHA
+        text: Decoded content, without a byte order mark
+        encoding: Encoding the blob was decoded from: 'utf-8', 'utf-16-le',
+            'utf-16-be', 'utf-32-le', 'utf-32-be' or 'latin-1' (None if the
+            content looks binary; it is then decoded as UTF-8 with invalid
+            sequences replaced by U+FFFD)
+        working_tree_encoding: The file's ``working-tree-encoding``
+            attribute, e.g. 'UTF-16LE-BOM' (None if unset)
+        binary: Whether Git treats the file as binary, by its gitattributes
+            or else its content (see ``is_binary``)
+    """
+
+    def __init__(
+        self,
+        path: str,
+        oid: str,
+        text: str,
+        encoding: Optional[str],
+        working_tree_encoding: Optional[str] = None,
+        binary: bool = False
+    ) -> None:
+        self.path = path
+        self.oid = oid
+        self.text = text
+        self.encoding = encoding
+        self.working_tree_encoding = working_tree_encoding
+        self.binary = binary
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "oid": self.oid,
+            "text": self.text,
+            "encoding": self.encoding,
+            "workingTreeEncoding": self.working_tree_encoding,
+            "binary": self.binary
+        }
+
+
+class TreeEntry:
+    """
+    An entry of a Git tree object.
+
+    Attributes:
+        name: Entry name within the tree (a single path component)
+        mode: Octal file mode, e.g. '100644', '100755', '120000' (symlink),
+            '040000' (tree) or '160000' (gitlink)
+        type: Object type: 'blob', 'tree' or 'commit' (a submodule gitlink)
+        oid: Object SHA
+        path: Full path of the entry; the same as name unless it comes from
+            ``walk_tree``
+    """
+
+    def __init__(
+        self,
+        name: str,
+        mode: str,
+        type: str,
+        oid: str,
+        path: Optional[str] = None
+    ) -> None:
+        self.name = name
+        self.mode = mode
+        self.type = type
+        self.oid = oid
+        self.path = name if path is None else path
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "name": self.name,
+            "path": self.path,
+            "mode": self.mode,
+            "type": self.type,
+            "oid": self.oid
+        }
+
+
+class DuplicateGroup:
+    """
+    Paths whose files have identical content.
+
+    Attributes:
+        oid: Blob SHA the paths share
+        size: Size of the content in bytes
+        paths: Paths holding the content, relative to repo root, in tree
+            order
+    """
+
+    def __init__(self, oid: str, size: int, paths: list[str]) -> None:
+        self.oid = oid
+        self.size = size
+        self.paths = paths
+
+    @property
+    def redundant_bytes(self) -> int:
+        """Bytes taken by the copies beyond the first."""
+        return self.size * (len(self.paths) - 1)
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "oid": self.oid,
+            "size": self.size,
+            "paths": self.paths
+        }
+
+
+class ManifestEntry:
+    """
+    The identity of a tracked file.
+
+    Attributes:
+        path: File path relative to repo root
+        blob_oid: Blob SHA of the file's content
+        mode: Octal file mode, e.g. '100644', '100755' or '120000' (symlink)
+        size: Size of the content in bytes
+    """
+
+    def __init__(self, path: str, blob_oid: str, mode: str, size: int) -> None:
+        self.path = path
+        self.blob_oid = blob_oid
+        self.mode = mode
+        self.size = size
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "blobOid": self.blob_oid,
+            "mode": self.mode,
+            "size": self.size
+        }
+
+
+class Manifest:
+    """
+    The identities of every tracked file at a commit.
+
+    Attributes:
+        commit: Commit SHA
+        tree: Root tree SHA of the commit
+        entries: ManifestEntry list sorted by path
+    """
+
+    def __init__(self, commit: str, tree: str, entries: list[ManifestEntry]) -> None:
+        self.commit = commit
+        self.tree = tree
+        self.entries = entries
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "commit": self.commit,
+            "tree": self.tree,
+            "entries": [entry.to_dict() for entry in self.entries]
+        }
+
+
+class ManifestChange:
+    """
+    A tracked file whose content or mode differs between two commits.
+
+    Attributes:
+        path: File path relative to repo root
+        old_blob_oid: Blob SHA at the older commit
+        new_blob_oid: Blob SHA at the newer commit
+        old_mode: File mode at the older commit
+        new_mode: File mode at the newer commit
+    """
+
+    def __init__(
+        self,
+        path: str,
+        old_blob_oid: str,
+        new_blob_oid: str,
+        old_mode: str,
+        new_mode: str
+    ) -> None:
+        self.path = path
+        self.old_blob_oid = old_blob_oid
+        self.new_blob_oid = new_blob_oid
+        self.old_mode = old_mode
+        self.new_mode = new_mode
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "oldBlobOid": self.old_blob_oid,
+            "newBlobOid": self.new_blob_oid,
+            "oldMode": self.old_mode,
+            "newMode": self.new_mode
+        }
+
+
+class ManifestDiff:
+    """
+    The file identities that differ between the manifests of two commits.
+
+    Attributes:
+        from_commit: Older commit SHA
+        to_commit: Newer commit SHA
+        added: ManifestEntry list of files only at to_commit
+        removed: ManifestEntry list of files only at from_commit
+        changed: ManifestChange list of files at both whose blob or mode
+            differs
+    """
+
+    def __init__(
+        self,
+        from_commit: str,
+        to_commit: str,
+        added: list[ManifestEntry],
+        removed: list[ManifestEntry],
+        changed: list[ManifestChange]
+    ) -> None:
+        self.from_commit = from_commit
+        self.to_commit = to_commit
+        self.added = added
+        self.removed = removed
+        self.changed = changed
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "fromCommit": self.from_commit,
+            "toCommit": self.to_commit,
+            "added": [entry.to_dict() for entry in self.added],
+            "removed": [entry.to_dict() for entry in self.removed],
+            "changed": [change.to_dict() for change in self.changed]
+        }
+
+
+class GrepMatch:
+    """
+    A line matching a search.
+
+    Attributes:
+        path: File path relative to repo root
+        line_number: Line number (1-based)
+        line: Line text without the line terminator
+    """
+
+    def __init__(self, path: str, line_number: int, line: str) -> None:
+        self.path = path
+        self.line_number = line_number
+        self.line = line
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "lineNumber": self.line_number,
+            "line": self.line
+        }
+
+
+class SymlinkInfo:
+    """
+    A symbolic link stored in a tree (mode 120000) and what it points at.
+
+    Attributes:
+        path: Path of the link, relative to repo root
+        oid: Blob SHA of the link, whose content is the target
+        target: Link target as stored, e.g. '../shared/config.yml'
+        resolved_path: Path the link leads to after following every link
+            on the way, relative to repo root (None if not resolved, or if
+            it leads outside the repository, to a missing path or into a
+            loop)
+        resolved_type: Type of the object at resolved_path: 'blob', 'tree'
+            or 'commit' (None if not resolved)
+        resolved_oid: SHA of the object at resolved_path (None if not
+            resolved)
+    """
+
+    def __init__(
+        self,
+        path: str,
+        oid: str,
+        target: str,
+        resolved_path: Optional[str] = None,
+        resolved_type: Optional[str] = None,
+        resolved_oid: Optional[str] = None
+    ) -> None:
+        self.path = path
+        self.oid = oid
+        self.target = target
+        self.resolved_path = resolved_path
+        self.resolved_type = resolved_type
+        self.resolved_oid = resolved_oid
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "path": self.path,
+            "oid": self.oid,
+            "target": self.target,
+            "resolvedPath": self.resolved_path,
+            "resolvedType": self.resolved_type,
+            "resolvedOid": self.resolved_oid
+        }
+
+
+def get_file_text(file_path: str, commit: str = "HEAD", repo_path: str = ".") -> Optional[FileText]:
+    """
+    Read a file at a commit as text, detecting its encoding.
+
+    Files with a ``working-tree-encoding`` attribute are stored by Git as
+    UTF-8 and only converted on checkout; the attribute, as set in the
+    ``.gitattributes`` files at ``commit``, is reported so the text can be
+    re-encoded to match a checkout of that commit. Files committed in
+    another encoding are recognized by their byte order mark or, for
+    UTF-16, their NUL bytes, so UTF-16 content is not returned as mojibake.
+
+    Args:
+        file_path: File path (absolute or relative to repo root)
+        commit: Commit SHA, branch, tag or other revision (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        FileText, or None if the path is not a file at that commit
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitError: If the commit cannot be resolved
+
+    Examples:
+        >>> text = get_file_text("data/export.csv", "HEAD", "/path/to/repo")
+        >>> text.encoding, text.text[:10]
+        ('utf-16-le', 'name,value')
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    blob_hash = get_file_hash_at_commit(repo_root, relative_path, commit)
+    if blob_hash is None:
+        return None
+
+    text, encoding = decode_text(get_blob_bytes(repo_root, blob_hash))
+    attributes = get_attributes(relative_path, ["working-tree-encoding", "diff"], commit, repo_root)
+    attribute = attributes.get("working-tree-encoding")
+    binary = _binary_from_attributes(repo_root, attributes)
+
+    return FileText(
+        relative_path,
+        blob_hash,
+        text,
+        encoding,
+        attribute if isinstance(attribute, str) else None,
+        encoding is None if binary is None else binary
+    )
+
+
+def get_lfs_info(file_path: str, commit: str = "HEAD", repo_path: str = ".") -> Optional[LfsInfo]:
+    """
+    Read the Git LFS pointer stored for a file at a commit.
+
+    For a file tracked by Git LFS the blob SHA identifies the pointer, not
+    the content; the LFS object ID and size identify the content and
+    locate it on the LFS server (see ``build_github_lfs_media_url``). Blobs
+    larger than a pointer can be are not read.
+
+    Args:
+        file_path: File path (absolute or relative to repo root)
+        commit: Commit SHA, branch, tag or other revision (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        LfsInfo, or None if the path is not a file at that commit or its
+        blob is not an LFS pointer
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitError: If the commit cannot be resolved
+
+    Examples:
+        >>> get_lfs_info("assets/video.mp4", "HEAD", "/path/to/repo").to_dict()
+        {'oid': '4d7a2146...', 'size': 314572800, 'pointerOid': 'abc123...',
+         'path': 'assets/video.mp4'}
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    blob_hash = get_file_hash_at_commit(repo_root, relative_path, commit)
+    if blob_hash is None or get_blob_size(repo_root, blob_hash) > LFS_POINTER_MAX_SIZE:
+        return None
+
+    info = parse_lfs_pointer(get_blob_bytes(repo_root, blob_hash))
+    if info is None:
+        return None
+
+    info.pointer_oid = blob_hash
+    info.path = relative_path
+    return info
+
+
+def is_binary(file_path: str, commit: str = "HEAD", repo_path: str = ".") -> bool:
+    """
+    Tell whether Git treats a file as binary, as ``git diff`` does.
+
+    gitattributes decide first, as in Git: ``-diff`` (which the ``binary``
+    macro sets) marks a file binary and ``diff`` marks it text, while a
+    ``diff=<driver>`` is binary if ``diff.<driver>.binary`` is configured
+    true. ``text`` and ``-text`` only control line-ending conversion, so
+    they leave the decision to the content. Attributes are read from the
+    ``.gitattributes`` files at ``commit``, not the working tree. Content is binary if it has
+    NUL bytes, except that UTF-16 and UTF-32 text is recognized as text
+    (see ``detect_encoding``).
+
+    Args:
+        file_path: File path (absolute or relative to repo root)
+        commit: Commit SHA, branch, tag or other revision (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        True if the file is binary
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        FileNotFoundError: If the path is not a file at that commit
+        GitError: If the commit cannot be resolved
+
+    Examples:
+        >>> is_binary("assets/logo.png", "HEAD", "/path/to/repo")
+        True
+        >>> is_binary("data/export.csv", "HEAD", "/path/to/repo")  # UTF-16, marked diff
+        False
+    """
+    repo_root, relative_path = resolve_repository_path(repo_path, file_path)
+
+    blob_hash = get_file_hash_at_commit(repo_root, relative_path, commit)
+    if blob_hash is None:
+        raise FileNotFoundError(
+            f"File not found in repository: {relative_path}",
+            file_path=relative_path
+        )
+
+    attributes = get_attributes(relative_path, ["diff"], commit, repo_root)
+    binary = _binary_from_attributes(repo_root, attributes)
+    if binary is not None:
+        return binary
+
+    return detect_encoding(get_blob_bytes(repo_root, blob_hash)) is None
+
+
+def _binary_from_attributes(
+    repo_root: str,
+    attributes: dict[str, AttributeValue]
+) -> Optional[bool]:
+    """
+    Classify a file as binary or text from its diff attribute.
+
+    Args:
+        repo_root: Repository root path
+        attributes: Attributes of the file, including 'diff'
+
+    Returns:
+        True (binary), False (text), or None if the content decides
+    """
+    diff = attributes.get("diff")
+    if isinstance(diff, bool):
+        return not diff
+    if diff is None:
+        return None
+
+    try:
+        return execute_git_command(
+            ["git", "config", "--type=bool", "--get", f"diff.{diff}.binary"],
+            cwd=repo_root
+        ) == "true"
+    except GitCommandError:
+        # Driver does not set binary
+        return None
+
+
+def find_paths_for_blob(blob_oid: str, at_commit: str = "HEAD", repo_path: str = ".") -> list[str]:
+    """
+    Find every path at which a commit's tree holds a blob.
+
+    Answers where a piece of content lives when only its blob SHA is known;
+    identical files share one blob, so several paths can match.
+
+    Args:
+        blob_oid: Blob SHA (full 40 hex characters)
+        at_commit: Commit SHA, branch, tag or other revision whose tree is
+            searched (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        Paths relative to repo root, in tree order; empty if the blob is
+        not in the tree
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If at_commit does not exist
+        InvalidHashError: If blob_oid is not a valid Git hash
+
+    Examples:
+        >>> find_paths_for_blob("def456abc789...", "HEAD", "/path/to/repo")
+        ['LICENSE', 'vendor/lib/LICENSE']
+    """
+    validate_git_hash(blob_oid, "blobOid")
+    blob_oid = blob_oid.lower()
+
+    return [
+        entry.path
+        for entry in walk_tree(at_commit, repo_path=repo_path)
+        if entry.oid == blob_oid
+    ]
+
+
+def grep_at(
+    commit: str,
+    pattern: str,
+    pathspec: Optional[str] = None,
+    ignore_case: bool = False,
+    fixed_strings: bool = False,
+    repo_path: str = "."
+) -> list[GrepMatch]:
+    """
+    Search the files of a commit without checking it out.
+
+    Runs ``git grep`` against the commit's tree, so the results reflect that
+    exact snapshot regardless of the working tree. Binary files are skipped.
+
+    Args:
+        commit: Commit SHA, branch, tag or other revision
+        pattern: Extended regular expression, or a literal string with
+            fixed_strings
+        pathspec: Only search matching paths, relative to repo root, in Git
+            pathspec syntax (e.g. 'src/', '*.py'; default: all files)
+        ignore_case: Match case-insensitively (default: False)
+        fixed_strings: Treat pattern as a literal string (default: False)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        GrepMatch list ordered by path, then line number
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit or pattern is not
+            a valid regular expression
+        ValueError: If pattern is empty
+
+    Examples:
+        >>> [m.to_dict() for m in grep_at("v1.0.0", "TODO", "*.py", repo_path="/path/to/repo")]
+        [{'path': 'src/app.py', 'lineNumber': 42, 'line': '    # TODO: retry'}]
+    """
+    if not pattern:
+        raise ValueError("Invalid pattern: must not be empty")
+
+    repo_root = get_repository_root(repo_path)
+    oid = execute_git_command(["git", "rev-parse", f"{commit}^{{commit}}"], cwd=repo_root)
+
+    command = ["git", "grep", "-n", "-z", "-I", "--full-name", "--no-color"]
+    command.append("-F" if fixed_strings else "-E")
+    if ignore_case:
+        command.append("-i")
+    command += ["-e", pattern, oid]
+    if pathspec is not None:
+        command += ["--", pathspec]
+
+    try:
+        output = execute_git_command_bytes(command, cwd=repo_root).decode("utf-8", errors="replace")
+    except GitCommandError as e:
+        # Exit code 1: no matches
+        if e.context.get("exit_code") == 1:
+            return []
+        raise
+
+    matches: list[GrepMatch] = []
+    for record in output.split("\n"):
+        # "<commit>:<path>\0<line number>\0<text>"
+        fields = record.split("\0", 2)
+        if len(fields) == 3:
+            matches.append(GrepMatch(fields[0][len(oid) + 1:], int(fields[1]), fields[2]))
+    return matches
+
+
+def get_tree_entries(tree: str = "HEAD", repo_path: str = ".") -> list[TreeEntry]:
+    """
+    List the entries of a tree without recursing into subtrees.
+
+    Args:
+        tree: Tree SHA, or any revision naming a tree: a commit or tag
+            (its root tree) or ``<rev>:<dir>`` such as ``HEAD:src``
+            (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        TreeEntry list in tree order
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If tree does not exist or does not name a tree
+
+    Examples:
+        >>> [(e.type, e.name) for e in get_tree_entries("HEAD:src", "/path/to/repo")]
+        [('blob', 'main.py'), ('tree', 'utils')]
+    """
+    repo_root = get_repository_root(repo_path)
+
+    output = execute_git_command(["git", "ls-tree", "-z", tree], cwd=repo_root)
+
+    entries: list[TreeEntry] = []
+    for line in output.split("\0"):
+        if not line:
+            continue
+        # "<mode> <type> <oid>\t<name>"
+        info, _, name = line.partition("\t")
+        mode, object_type, oid = info.split(" ")
+        entries.append(TreeEntry(name, mode, object_type, oid))
+
+    return entries
+
+
+def walk_tree(
+    tree: str = "HEAD",
+    prefix: str = "",
+    max_depth: Optional[int] = None,
+    pathspec: Optional[list[str]] = None,
+    repo_path: str = "."
+) -> Iterator[TreeEntry]:
+    """
+    Walk every blob below a tree, with full paths.
+
+    Submodule gitlinks are skipped. Pathspecs follow Git's default
+    matching: a pattern matches a path it names, any path below a directory
+    it names, or a path it matches as a wildcard (``*`` also matches ``/``).
+
+    Args:
+        tree: Tree SHA, or any revision naming a tree (default: 'HEAD')
+        prefix: Directory prepended to each path, e.g. 'src' when walking
+            ``HEAD:src`` so that paths are relative to the repository root
+        max_depth: Deepest directory level to descend into below the tree;
+            0 yields only the tree's own blobs (default: unlimited)
+        pathspec: Only yield blobs whose full path matches one of these
+            patterns (default: all blobs)
+        repo_path: Path within the repository (default: current directory)
+
+    Yields:
+        TreeEntry for each blob, in tree order
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If tree does not exist or does not name a tree
+        ValueError: If max_depth is negative
+
+    Examples:
+        >>> [(e.path, e.oid[:7]) for e in walk_tree("HEAD:src", "src", repo_path="/path/to/repo")]
+        [('src/main.py', 'a1b2c3d'), ('src/utils/io.py', 'e4f5a6b')]
+        >>> [e.path for e in walk_tree(pathspec=["*.md"], repo_path="/path/to/repo")]
+        ['README.md', 'docs/guide.md']
+    """
+    if max_depth is not None and max_depth < 0:
+        raise ValueError(f"Invalid max depth: {max_depth}. Must not be negative")
+
+    repo_root = get_repository_root(repo_path)
+    prefix = prefix.strip("/")
+
+    output = execute_git_command(["git", "ls-tree", "-r", "-z", tree], cwd=repo_root)
+
+    for line in output.split("\0"):
+        if not line:
+            continue
+        # "<mode> <type> <oid>\t<path>"
+        info, _, relative_path = line.partition("\t")
+        mode, object_type, oid = info.split(" ")
+        if object_type != "blob":
+            continue
+        if max_depth is not None and relative_path.count("/") > max_depth:
+            continue
+
+        path = f"{prefix}/{relative_path}" if prefix else relative_path
+        if pathspec is not None and not any(
+            path == pattern
+            or path.startswith(pattern.rstrip("/") + "/")
+            or fnmatch.fnmatchcase(path, pattern)
+            for pattern in pathspec
+        ):
+            continue
+
+        yield TreeEntry(os.path.basename(relative_path), mode, object_type, oid, path)
+
+
+def find_duplicate_files(
+    tree: str = "HEAD",
+    pathspec: Optional[list[str]] = None,
+    min_size: int = 1,
+    repo_path: str = "."
+) -> list[DuplicateGroup]:
+    """
+    Find files with identical content, by grouping paths on their blob OID.
+
+    Symbolic links are left out, since links with the same target are not
+    copies of a file. Empty files all share one blob and are left out
+    unless min_size is 0.
+
+    Args:
+        tree: Tree SHA, or any revision naming a tree (default: 'HEAD')
+        pathspec: Only consider files matching one of these patterns, as
+            in ``walk_tree`` (default: all files)
+        min_size: Smallest file size in bytes to report (default: 1)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        DuplicateGroup list, most redundant bytes first, then by first path
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If tree does not exist or does not name a tree
+        ValueError: If min_size is negative
+
+    Examples:
+        >>> [g.paths for g in find_duplicate_files(repo_path="/path/to/repo")]
+        [['assets/logo.png', 'docs/img/logo.png'], ['LICENSE', 'packages/cli/LICENSE']]
+    """
+    if min_size < 0:
+        raise ValueError(f"Invalid min size: {min_size}. Must not be negative")
+
+    repo_root = get_repository_root(repo_path)
+
+    paths_by_oid: dict[str, list[str]] = {}
+    for entry in walk_tree(tree, repo_path=repo_root, pathspec=pathspec):
+        if entry.mode != SYMLINK_MODE:
+            paths_by_oid.setdefault(entry.oid, []).append(entry.path)
+
+    groups: list[DuplicateGroup] = []
+    for oid, paths in paths_by_oid.items():
+        if len(paths) < 2:
+            continue
+        size = get_blob_size(repo_root, oid)
+        if size >= min_size:
+            groups.append(DuplicateGroup(oid, size, paths))
+
+    groups.sort(key=lambda group: (-group.redundant_bytes, group.paths[0]))
+    return groups
+
+
+def generate_manifest(commit: str = "HEAD", repo_path: str = ".") -> Manifest:
+    """
+    List the path, blob OID, mode and size of every tracked file at a commit.
+
+    The bulk form of file identification, read from the commit's tree in a
+    single git invocation, for CI provenance and release records. Submodule
+    gitlinks are not files of the repository and are left out.
+
+    Args:
+        commit: Commit SHA, branch, tag or other revision (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        Manifest; ``json.dumps(manifest.to_dict())`` exports it as JSON
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit
+
+    Examples:
+        >>> manifest = generate_manifest("v1.2.0", "/path/to/repo")
+        >>> manifest.entries[0].to_dict()
+        {'path': '.gitignore', 'blobOid': 'a1b2c3d...', 'mode': '100644', 'size': 112}
+    """
+    repo_root = get_repository_root(repo_path)
+    oid = execute_git_command(["git", "rev-parse", f"{commit}^{{commit}}"], cwd=repo_root)
+    tree = execute_git_command(["git", "rev-parse", f"{oid}^{{tree}}"], cwd=repo_root)
+
+    output = execute_git_command(["git", "ls-tree", "-r", "-l", "-z", oid], cwd=repo_root)
+
+    entries: list[ManifestEntry] = []
+    for line in output.split("\0"):
+        if not line:
+            continue
+        # "<mode> <type> <oid> <padded size>\t<path>"
+        info, _, path = line.partition("\t")
+        mode, object_type, blob_oid, size = info.split()
+        if object_type == "blob":
+            entries.append(ManifestEntry(path, blob_oid, mode, int(size)))
+
+    entries.sort(key=lambda entry: entry.path)
+    return Manifest(oid, tree, entries)
+
+
+def diff_manifests(
+    from_commit: str,
+    to_commit: str = "HEAD",
+    repo_path: str = "."
+) -> ManifestDiff:
+    """
+    Report which file identities were added, removed or changed between commits.
+
+    Compares the ``generate_manifest`` of each commit path by path, so a
+    renamed file shows up as removed under its old path and added under the
+    new one, as a per-file change report between release tags should.
+
+    Args:
+        from_commit: Older commit SHA, branch, tag or other revision
+        to_commit: Newer revision (default: 'HEAD')
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        ManifestDiff with each list sorted by path
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If from_commit or to_commit does not name a commit
+
+    Examples:
+        >>> report = diff_manifests("v1.2.0", "v1.3.0", "/path/to/repo")
+        >>> [change.to_dict() for change in report.changed]
+        [{'path': 'src/app.py', 'oldBlobOid': 'a1b2c3d...', 'newBlobOid': 'e4f5a6b...', ...}]
+    """
+    old = generate_manifest(from_commit, repo_path)
+    new = generate_manifest(to_commit, repo_path)
+
+    old_entries = {entry.path: entry for entry in old.entries}
+    new_paths = {entry.path for entry in new.entries}
+
+    added: list[ManifestEntry] = []
+    changed: list[ManifestChange] = []
+    for entry in new.entries:
+        previous = old_entries.get(entry.path)
+        if previous is None:
+            added.append(entry)
+        elif previous.blob_oid != entry.blob_oid or previous.mode != entry.mode:
+            changed.append(ManifestChange(
+                entry.path,
+                previous.blob_oid,
+                entry.blob_oid,
+                previous.mode,
+                entry.mode
+            ))
+    removed = [entry for entry in old.entries if entry.path not in new_paths]
+
+    return ManifestDiff(old.commit, new.commit, added, removed, changed)
+
+
+def get_symlink(
+    file_path: str,
+    commit: str = "HEAD",
+    resolve: bool = True,
+    repo_path: str = "."
+) -> Optional[SymlinkInfo]:
+    """
+    Read a symbolic link at a commit and optionally resolve it in the tree.
+
+    Resolution follows the target through the commit's tree, including
+    links in intermediate directories and chains of links, so URLs can
+    point at the real file rather than the one-line blob holding the
+    target path. Absolute targets and targets leaving the repository are
+    not resolved.
+
+    Args:
+        file_path: Path of the link (absolute or relative to repo root)
+        commit: Commit SHA, branch, tag or other revision (default: 'HEAD')
+        resolve: Follow the link to the object it points at (default: True)
+        repo_path: Path within the repository (default: current directory)
+
+    Returns:
+        SymlinkInfo, or None if the path is not a symbolic link at that
+        commit
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitCommandError: If commit does not name a commit
+
+    Examples:
+        >>> get_symlink("docs/README.md", "HEAD", repo_path="/path/to/repo").to_dict()
+        {'path': 'docs/README.md', 'oid': 'abc123...', 'target': '../README.md',
+         'resolvedPath': 'README.md', 'resolvedType': 'blob', 'resolvedOid': 'def456...'}
+    """
+    repo_root = get_repository_root(repo_path)
+    # Not resolve_file_path: that follows links in the working tree
+    relative_path = normalize_file_path(
+        os.path.relpath(os.path.join(repo_root, file_path), repo_root)
+    )
+
+    entry = _tree_entry_at(repo_root, commit, relative_path)
+    if entry is None or entry.mode != SYMLINK_MODE:
+        return None
+
+    target = get_blob_bytes(repo_root, entry.oid).decode("utf-8", errors="replace")
+    link = SymlinkInfo(relative_path, entry.oid, target)
+    if not resolve or target.startswith("/"):
+        return link
+
+    # Walk the target one component at a time from the link's directory,
+    # splicing in the target of every link met on the way
+    resolved = relative_path.split("/")[:-1]
+    remaining = target.split("/")
+    hops = 1
+    resolved_entry: Optional[TreeEntry] = None
+    while remaining:
+        name = remaining.pop(0)
+        if name in ("", "."):
+            continue
+        if name == "..":
+            if not resolved:
+                # Leaves the repository
+                return link
+            resolved.pop()
+            resolved_entry = None
+            continue
+
+        resolved_entry = _tree_entry_at(repo_root, commit, "/".join(resolved + [name]))
+        if resolved_entry is None:
+            return link
+        if resolved_entry.mode == SYMLINK_MODE:
+            hops += 1
+            next_target = get_blob_bytes(repo_root, resolved_entry.oid).decode("utf-8", errors="replace")
+            if hops > MAX_SYMLINK_HOPS or next_target.startswith("/"):
+                return link
+            remaining = next_target.split("/") + remaining
+            resolved_entry = None
+            continue
+        resolved.append(name)
+
+    if not resolved:
+        # The repository root itself
+        return link
+    if resolved_entry is None:
+        resolved_entry = _tree_entry_at(repo_root, commit, "/".join(resolved))
+        if resolved_entry is None:
+            return link
+
+    link.resolved_path = "/".join(resolved)
+    link.resolved_type = resolved_entry.type
+    link.resolved_oid = resolved_entry.oid
+    return link
+
+
+def _tree_entry_at(repo_root: str, commit: str, path: str) -> Optional[TreeEntry]:
+    """
+    Look up the tree entry for an exact path at a commit.
+
+    Args:
+        repo_root: Repository root path
+        commit: Commit SHA, branch, tag or other revision
+        path: Path relative to repo root
+
+    Returns:
+        TreeEntry, or None if nothing exists at that path
+
+    Raises:
+        GitCommandError: If commit does not name a commit
+    """
+    output = execute_git_command(["git", "ls-tree", "-z", commit, "--", path], cwd=repo_root)
+
+    for line in output.split("\0"):
+        # "<mode> <type> <oid>\t<path>"
+        info, _, entry_path = line.partition("\t")
+        if entry_path == path:
+            mode, object_type, oid = info.split(" ")
+            return TreeEntry(os.path.basename(path), mode, object_type, oid, path)
+    return None
+
+
+__all__ = [
+    "TreeEntry",
+    "DuplicateGroup",
+    "ManifestEntry",
+    "Manifest",
+    "ManifestChange",
+    "ManifestDiff",
+    "get_tree_entries",
+    "walk_tree",
+    "find_duplicate_files",
+    "generate_manifest",
+    "diff_manifests",
+    "find_paths_for_blob",
+    "GrepMatch",
+    "grep_at",
+    "SymlinkInfo",
+    "get_symlink",
+    "FileText",
+    "get_file_text",
+    "is_binary",
+    "get_lfs_info",
+]
@@ -0,0 +1,104 @@
+"""
+Conventional Commits parsing.
+
+Decomposes commit messages written to the Conventional Commits
+specification (https://www.conventionalcommits.org/) into their type,
+scope, description and breaking-change marker, for changelog generation.
+"""
+
+import re
+from typing import Any, Optional
+
+# "<type>[(<scope>)][!]: <description>" on the first line
+HEADER_PATTERN = re.compile(
+    r"^(?P<type>[A-Za-z][\w-]*)"
+    r"(?:\((?P<scope>[^()\r\n]*)\))?"
+    r"(?P<breaking>!)?"
+    r": +(?P<description>\S.*)$"
+)
+
+# "BREAKING CHANGE: <text>" footer; the text runs to the end of its paragraph
+BREAKING_FOOTER_PATTERN = re.compile(
+    r"^BREAKING[ -]CHANGE: (?P<note>.+(?:\n(?!\n).+)*)",
+    re.MULTILINE
+)
+
+
+class ConventionalCommit:
+    """
+    A commit message decomposed per the Conventional Commits specification.
+
+    Attributes:
+        type: Commit type as written, e.g. 'feat' or 'fix'
+        scope: Scope in parentheses after the type (None if absent)
+        breaking: Whether the header has '!' or a BREAKING CHANGE footer
+        description: Header text after the colon
+        breaking_change: Text of the BREAKING CHANGE footer (None if absent)
+    """
+
+    def __init__(
+        self,
+        type: str,
+        description: str,
+        scope: Optional[str] = None,
+        breaking: bool = False,
+        breaking_change: Optional[str] = None
+    ) -> None:
+        self.type = type
+        self.scope = scope
+        self.breaking = breaking
+        self.description = description
+        self.breaking_change = breaking_change
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "type": self.type,
+            "scope": self.scope,
+            "breaking": self.breaking,
+            "description": self.description,
+            "breakingChange": self.breaking_change
+        }
+
+
+def parse_conventional_commit(message: str) -> Optional[ConventionalCommit]:
+    """
+    Parse a commit message written to the Conventional Commits specification.
+
+    Args:
+        message: Full commit message
+
+    Returns:
+        ConventionalCommit, or None if the first line is not a conventional
+        commit header
+
+    Examples:
+        >>> parse_conventional_commit("feat(parser)!: drop legacy syntax").to_dict()
+        {'type': 'feat', 'scope': 'parser', 'breaking': True, 'description': 'drop legacy syntax',
+         'breakingChange': None}
+        >>> parse_conventional_commit("fix: handle empty input\\n\\nBREAKING CHANGE: returns None").breaking
+        True
+        >>> parse_conventional_commit("Update README") is None
+        True
+    """
+    header, _, rest = message.strip().partition("\n")
+    match = HEADER_PATTERN.match(header.strip())
+    if not match:
+        return None
+
+    footer = BREAKING_FOOTER_PATTERN.search(rest)
+    breaking_change = footer.group("note").strip() if footer else None
+
+    return ConventionalCommit(
+        match.group("type"),
+        match.group("description").strip(),
+        match.group("scope") or None,
+        bool(match.group("breaking")) or breaking_change is not None,
+        breaking_change
+    )
+
+
+__all__ = [
+    "ConventionalCommit",
+    "parse_conventional_commit",
+]
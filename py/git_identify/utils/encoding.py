@@ -0,0 +1,133 @@
+"""
+Text encoding detection.
+
+Recognizes the encodings text files are commonly committed in (UTF-8,
+UTF-16 and UTF-32, with or without a byte order mark) so blob content can
+be decoded without producing mojibake.
+"""
+
+from typing import Optional
+
+# Byte order marks, longest first so UTF-32 LE is not taken for UTF-16 LE
+BYTE_ORDER_MARKS: tuple[tuple[bytes, str], ...] = (
+    (b"\xff\xfe\x00\x00", "utf-32-le"),
+    (b"\x00\x00\xfe\xff", "utf-32-be"),
+    (b"\xef\xbb\xbf", "utf-8"),
+    (b"\xff\xfe", "utf-16-le"),
+    (b"\xfe\xff", "utf-16-be"),
+)
+
+# Encodings detect_encoding reports
+TEXT_ENCODINGS = ("utf-8", "utf-16-le", "utf-16-be", "utf-32-le", "utf-32-be", "latin-1")
+
+# Bytes inspected when guessing, as Git does when deciding a file is binary
+DETECTION_SAMPLE_SIZE = 8000
+
+
+def detect_encoding(data: bytes) -> Optional[str]:
+    """
+    Guess the encoding of text content.
+
+    A byte order mark decides; otherwise the pattern of NUL bytes
+    identifies UTF-16 (mostly ASCII text has a NUL in every other byte).
+    Other content is UTF-8 if it decodes as such, else Latin-1, unless it
+    contains NUL bytes, which marks it binary.
+
+    Args:
+        data: Content to inspect
+
+    Returns:
+        One of TEXT_ENCODINGS, or None if the content looks binary
+
+    Examples:
+        >>> detect_encoding("café".encode("utf-8"))
+        'utf-8'
+        >>> detect_encoding("\\ufeffname,value\\n".encode("utf-16-le"))
+        'utf-16-le'
+        >>> detect_encoding(b"\\x89PNG\\r\\n\\x1a\\n\\x00\\x00")
+    """
+    for bom, encoding in BYTE_ORDER_MARKS:
+        if data.startswith(bom):
+            return encoding
+
+    sample = data[:DETECTION_SAMPLE_SIZE]
+    if b"\0" in sample:
+        return _detect_utf16(data, sample)
+
+    try:
+        data.decode("utf-8")
+    except UnicodeDecodeError:
+        return "latin-1"
+    return "utf-8"
+
+
+def _detect_utf16(data: bytes, sample: bytes) -> Optional[str]:
+    """
+    Tell BOM-less UTF-16 from binary content by where its NUL bytes fall.
+
+    Args:
+        data: Content to inspect
+        sample: Leading bytes of data to count NUL bytes in
+
+    Returns:
+        'utf-16-le' or 'utf-16-be', or None if the content looks binary
+    """
+    pairs = len(sample) // 2
+    if len(data) % 2 or not pairs:
+        return None
+
+    even_nuls = sample[0::2].count(0)
+    odd_nuls = sample[1::2].count(0)
+    if odd_nuls >= pairs * 0.3 and even_nuls <= pairs * 0.05:
+        encoding = "utf-16-le"
+    elif even_nuls >= pairs * 0.3 and odd_nuls <= pairs * 0.05:
+        encoding = "utf-16-be"
+    else:
+        return None
+
+    try:
+        data.decode(encoding)
+    except UnicodeDecodeError:
+        return None
+    return encoding
+
+
+def decode_text(data: bytes, encoding: Optional[str] = None) -> tuple[str, Optional[str]]:
+    """
+    Decode text content, detecting its encoding unless one is given.
+
+    A leading byte order mark is removed. Content that looks binary is
+    decoded as UTF-8 with invalid sequences replaced by U+FFFD.
+
+    Args:
+        data: Content to decode
+        encoding: Python codec name to decode with (default: detect)
+
+    Returns:
+        Tuple of (text, encoding used), the encoding None for binary content
+
+    Raises:
+        LookupError: If encoding is not a known codec
+
+    Examples:
+        >>> decode_text("\\ufeffhi".encode("utf-16-be"))
+        ('hi', 'utf-16-be')
+        >>> decode_text(b"caf\\xe9")
+        ('café', 'latin-1')
+    """
+    if encoding is None:
+        encoding = detect_encoding(data)
+        if encoding is None:
+            return data.decode("utf-8", errors="replace"), None
+
+    text = data.decode(encoding, errors="replace")
+    if text.startswith("\ufeff"):
+        text = text[1:]
+    return text, encoding
+
+
+__all__ = [
+    "TEXT_ENCODINGS",
+    "detect_encoding",
+    "decode_text",
+]
@@ -0,0 +1,191 @@
+"""
+Link formatting utilities.
+
+Renders generated GitHub URLs in embeddable output formats such as HTML
+anchors, terminal hyperlinks and Org-mode links, with link text derived
+from the URL itself.
+"""
+
+import html
+import re
+from typing import Literal, Optional
+
+from .hash import is_valid_git_hash
+from .url import parse_github_link
+
+LinkFormat = Literal["url", "html", "osc8", "org"]
+
+LINK_FORMATS = ("url", "html", "osc8", "org")
+
+# Abbreviated commit SHA length used in link text
+SHORT_SHA_LENGTH = 7
+
+# C0/C1 control characters, which would break out of an OSC 8 sequence
+CONTROL_CHARACTERS = re.compile(r"[\x00-\x1f\x7f-\x9f]")
+
+
+def link_text(url: str, sha_length: int = SHORT_SHA_LENGTH) -> str:
+    """
+    Derive ``path@shortsha`` link text from a GitHub blob or tree URL.
+
+    Commit SHAs are abbreviated; branch and tag refs are kept as-is. The
+    repository root is labelled with the repository name.
+
+    Args:
+        url: GitHub URL
+        sha_length: Commit SHA characters to keep (default: 7); pass the
+            length of ``abbreviate_oid`` for a prefix that is unambiguous in
+            the local repository
+
+    Returns:
+        Link text, or the URL itself if it is not a blob or tree URL
+
+    Examples:
+        >>> link_text("https://github.com/user/repo/blob/abc123def456.../src/file.py#L10")
+        'src/file.py@abc123d'
+        >>> link_text("https://github.com/user/repo/tree/main/src")
+        'src@main'
+    """
+    link = parse_github_link(url)
+    if not link:
+        return url
+
+    ref = link.ref[:sha_length] if is_valid_git_hash(link.ref) else link.ref
+    return f"{link.path or link.repo}@{ref}"
+
+
+def format_html_link(
+    url: str,
+    text: Optional[str] = None,
+    sha_length: int = SHORT_SHA_LENGTH
+) -> str:
+    """
+    Format a URL as an HTML anchor element.
+
+    Both the ``href`` attribute and the link text are HTML-escaped.
+
+    Args:
+        url: Link target
+        text: Link text (default: ``path@shortsha`` derived from the URL)
+        sha_length: Commit SHA characters in the default text (default: 7)
+
+    Returns:
+        HTML anchor string
+
+    Examples:
+        >>> format_html_link("https://github.com/user/repo/blob/abc123def456.../a&b.py")
+        '<a href="https://github.com/user/repo/blob/abc123def456.../a&amp;b.py">a&amp;b.py@abc123d</a>'
+    """
+    if text is None:
+        text = link_text(url, sha_length)
+    return f'<a href="{html.escape(url)}">{html.escape(text)}</a>'
+
+
+def format_osc8_link(url: str, text: Optional[str] = None) -> str:
+    """
+    Format a URL as an OSC 8 terminal hyperlink.
+
+    Terminals that support OSC 8 show the text as a clickable link; others
+    print the text alone. Control characters are stripped from the URL and
+    text so they cannot terminate the escape sequence early.
+
+    Args:
+        url: Link target
+        text: Link text (default: the path relative to the repository root)
+
+    Returns:
+        Escape-sequence wrapped link
+
+    Examples:
+        >>> print(format_osc8_link("https://github.com/user/repo/blob/abc123.../src/file.py"))
+        src/file.py
+    """
+    if text is None:
+        link = parse_github_link(url)
+        text = (link.path or link.repo) if link else url
+
+    url = CONTROL_CHARACTERS.sub("", url)
+    text = CONTROL_CHARACTERS.sub("", text)
+    return f"\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\"
+
+
+def format_org_link(
+    url: str,
+    text: Optional[str] = None,
+    sha_length: int = SHORT_SHA_LENGTH
+) -> str:
+    """
+    Format a URL as an Org-mode link.
+
+    Square brackets in the URL are percent-encoded so they cannot terminate
+    the link early. Org has no escape for brackets in the description, so
+    there they become braces, as Org itself does when inserting a link.
+
+    Args:
+        url: Link target
+        text: Link description (default: ``path@shortsha`` derived from the URL)
+        sha_length: Commit SHA characters in the default text (default: 7)
+
+    Returns:
+        Org-mode link string
+
+    Examples:
+        >>> format_org_link("https://github.com/user/repo/tree/main/src")
+        '[[https://github.com/user/repo/tree/main/src][src@main]]'
+    """
+    if text is None:
+        text = link_text(url, sha_length)
+    target = url.replace("[", "%5B").replace("]", "%5D")
+    description = text.replace("[", "{").replace("]", "}")
+    return f"[[{target}][{description}]]"
+
+
+def format_link(
+    url: str,
+    link_format: LinkFormat = "url",
+    text: Optional[str] = None,
+    sha_length: int = SHORT_SHA_LENGTH
+) -> str:
+    """
+    Format a URL in one of the supported output formats.
+
+    Args:
+        url: Link target
+        link_format: 'url' (plain URL, default), 'html', 'osc8' or 'org'
+        text: Link text (default: derived from the URL)
+        sha_length: Commit SHA characters in default ``path@shortsha`` text
+            (default: 7)
+
+    Returns:
+        Formatted link
+
+    Raises:
+        ValueError: If link_format is invalid
+
+    Examples:
+        >>> format_link("https://github.com/user/repo/tree/main/src", "html")
+        '<a href="https://github.com/user/repo/tree/main/src">src@main</a>'
+    """
+    if link_format == "url":
+        return url
+    if link_format == "html":
+        return format_html_link(url, text, sha_length)
+    if link_format == "osc8":
+        return format_osc8_link(url, text)
+    if link_format == "org":
+        return format_org_link(url, text, sha_length)
+
+    raise ValueError(
+        f"Invalid link format: {link_format}. Must be 'url', 'html', 'osc8' or 'org'"
+    )
+
+
+__all__ = [
+    "LinkFormat",
+    "LINK_FORMATS",
+    "link_text",
+    "format_html_link",
+    "format_osc8_link",
+    "format_org_link",
+    "format_link",
+]
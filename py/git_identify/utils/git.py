@@ -5,59 +5,176 @@ Provides functions for executing Git commands safely and checking repository sta
 """
 
 import os
+import re
 import subprocess
 from pathlib import Path
-from typing import Optional
+from typing import Any, Iterator, Optional, Union
 
-from ..errors import GitCommandError, RepositoryNotFoundError
+from ..errors import (
+    GitCommandError,
+    GitError,
+    InvalidHashError,
+    ObjectNotLocalError,
+    RepositoryNotFoundError,
+    UnbornHeadError,
+)
+from .path import resolve_file_path
+
+# Abbreviated object ID: 4 (Git's minimum) to 40 hexadecimal characters
+SHORT_OID_PATTERN = re.compile(r"^[0-9a-f]{4,40}$", re.IGNORECASE)
+
+# Environment variables that select a repository instead of discovery from the working directory
+REPOSITORY_ENV_VARS = (
+    "GIT_DIR",
+    "GIT_WORK_TREE",
+    "GIT_COMMON_DIR",
+    "GIT_INDEX_FILE",
+    "GIT_OBJECT_DIRECTORY",
+    "GIT_ALTERNATE_OBJECT_DIRECTORIES",
+    "GIT_IMPLICIT_WORK_TREE",
+    "GIT_PREFIX",
+)
+
+# Repository environment variables holding a single path
+REPOSITORY_PATH_ENV_VARS = (
+    "GIT_DIR",
+    "GIT_WORK_TREE",
+    "GIT_COMMON_DIR",
+    "GIT_INDEX_FILE",
+    "GIT_OBJECT_DIRECTORY",
+)
+
+# stderr of an on-demand partial clone fetch that failed
+PROMISOR_FETCH_FAILURE_PATTERN = re.compile(r"could not fetch ([0-9a-f]{40}) from promisor remote")
+
+# Chunk size for streamed blob reads
+BLOB_CHUNK_SIZE = 64 * 1024
+
+# Branch names tried when a remote has no HEAD ref
+DEFAULT_BRANCH_CANDIDATES = ("main", "master", "trunk", "develop")
+
+# Whether GIT_DIR / GIT_WORK_TREE from the environment are honored (see set_honor_git_env)
+_honor_git_env = True
+
+# git ls-tree -z entry: "<mode> blob <blob>\t<path>"
+TREE_ENTRY_PATTERN = re.compile(r"^\d+ blob ([0-9a-f]{40})\t(.*)$", re.DOTALL)
+
+# git ls-files --stage -z entry for a merged file or symlink: "<mode> <blob> 0\t<path>"
+INDEX_ENTRY_PATTERN = re.compile(r"^(?:100644|100755|120000) ([0-9a-f]{40}) 0\t(.*)$", re.DOTALL)
 
 
 def execute_git_command(
-    command: str,
+    command: Union[str, list[str]],
     cwd: Optional[str] = None,
-    timeout: int = 30
+    timeout: int = 30,
+    env: Optional[dict[str, str]] = None
 ) -> str:
     """
     Execute a Git command and return its output.
 
+    No shell is invoked. A string command is split on whitespace, so it
+    suits fixed commands only; pass an argument list when any argument is a
+    path, revision or other caller-supplied value, so it reaches Git as-is.
+
     Args:
-        command: Git command to execute (e.g., "git status")
+        command: Git command to execute (e.g., "git status" or
+            ["git", "log", "--", path])
         cwd: Working directory for command execution (defaults to current directory)
         timeout: Command timeout in seconds (default: 30)
+        env: Extra environment variables for this command
 
     Returns:
         Command output (stdout) as string, stripped of whitespace
 
     Raises:
         GitCommandError: If command execution fails
+        ObjectNotLocalError: If an object missing from a partial clone
+            cannot be fetched from the promisor remote
         RepositoryNotFoundError: If not in a Git repository
 
     Examples:
         >>> execute_git_command("git rev-parse HEAD", "/path/to/repo")
         'abc123def456...'
 
+        >>> execute_git_command(["git", "ls-files", "--", "routes/$id.tsx"], "/path/to/repo")
+        'routes/$id.tsx'
+
         >>> execute_git_command("git status", "/invalid/path")
         # Raises RepositoryNotFoundError
     """
+    return _run_git_command(command, cwd, timeout, env, text=True).strip()
+
+
+def execute_git_command_bytes(
+    command: Union[str, list[str]],
+    cwd: Optional[str] = None,
+    timeout: int = 30,
+    env: Optional[dict[str, str]] = None
+) -> bytes:
+    """
+    Execute a Git command and return its raw output.
+
+    Like ``execute_git_command``, but stdout is returned as bytes without
+    decoding or stripping, for binary content such as blobs.
+
+    Args:
+        command: Git command to execute (e.g., ["git", "cat-file", "blob", sha])
+        cwd: Working directory for command execution (defaults to current directory)
+        timeout: Command timeout in seconds (default: 30)
+        env: Extra environment variables for this command
+
+    Returns:
+        Command output (stdout) as bytes
+
+    Raises:
+        GitCommandError: If command execution fails
+        ObjectNotLocalError: If an object missing from a partial clone
+            cannot be fetched from the promisor remote
+        RepositoryNotFoundError: If not in a Git repository
+    """
+    return _run_git_command(command, cwd, timeout, env, text=False)
+
+
+def _run_git_command(
+    command: Union[str, list[str]],
+    cwd: Optional[str],
+    timeout: int,
+    env: Optional[dict[str, str]],
+    text: bool
+) -> Any:
+    """
+    Run a Git command and translate failures into GitError subclasses.
+
+    Args:
+        command: Git command to execute, as a string split on whitespace or
+            an argument list
+        cwd: Working directory (defaults to current directory)
+        timeout: Command timeout in seconds
+        env: Extra environment variables for this command
+        text: Decode stdout as text (str) instead of returning bytes
+
+    Returns:
+        Unstripped stdout
+    """
     if cwd is None:
         cwd = os.getcwd()
 
-    try:
-        # Set LC_ALL=C for consistent output format
-        env = os.environ.copy()
-        env["LC_ALL"] = "C"
+    arguments = command.split() if isinstance(command, str) else list(command)
+    # Error context keeps a readable form of the command
+    command = " ".join(arguments)
 
+    try:
         result = subprocess.run(
-            command.split(),
+            arguments,
             cwd=cwd,
             capture_output=True,
-            text=True,
+            text=text,
             timeout=timeout,
-            env=env,
+            env={**_git_environment(), **(env or {})},
             check=True
         )
 
-        return result.stdout.strip()
+        return result.stdout
 
     except subprocess.TimeoutExpired as e:
         raise GitCommandError(
@@ -67,9 +184,24 @@ def execute_git_command(
         ) from e
 
     except subprocess.CalledProcessError as e:
+        stderr = e.stderr if text else e.stderr.decode("utf-8", errors="replace")
+
+        # Object missing from a partial clone that could not be fetched on demand
+        fetch_failure = PROMISOR_FETCH_FAILURE_PATTERN.search(stderr)
+        if fetch_failure:
+            promisors = get_promisor_remotes(cwd)
+            raise ObjectNotLocalError(
+                f"Object not available locally (partial clone): {fetch_failure.group(1)}",
+                object_id=fetch_failure.group(1),
+                remote_url=get_remote_url(cwd, promisors[0]) if promisors else None,
+                command=command,
+                exit_code=e.returncode,
+                stderr=stderr.strip(),
+                cause=e
+            ) from e
+
         # Check for "not a git repository" error
-        stderr = e.stderr.lower()
-        if "not a git repository" in stderr or "not found" in stderr:
+        if "not a git repository" in stderr.lower() or "not found" in stderr.lower():
             raise RepositoryNotFoundError(
                 f"Not a Git repository: {cwd}",
                 path=cwd,
@@ -78,10 +210,10 @@ def execute_git_command(
 
         # Other Git command error
         raise GitCommandError(
-            f"Git command failed: {e.stderr.strip()}",
+            f"Git command failed: {stderr.strip()}",
             command=command,
             exit_code=e.returncode,
-            stderr=e.stderr.strip(),
+            stderr=stderr.strip(),
             cause=e
         ) from e
 
@@ -93,6 +225,51 @@ def execute_git_command(
         ) from e
 
 
+def set_honor_git_env(enabled: bool) -> None:
+    """
+    Choose whether repository environment variables are honored.
+
+    Git hooks and CI wrappers often set ``GIT_DIR`` / ``GIT_WORK_TREE``,
+    which Git uses instead of discovering the repository from the working
+    directory. They are honored by default, with relative values resolved
+    against the current directory so they keep working when commands run
+    from another directory. Disabling this removes them (and related
+    variables such as ``GIT_INDEX_FILE``) so every path is discovered on
+    its own.
+
+    Args:
+        enabled: Honor the environment (True, default) or ignore it (False)
+
+    Examples:
+        >>> set_honor_git_env(False)  # Discover repositories from paths only
+    """
+    global _honor_git_env
+    _honor_git_env = enabled
+
+
+def _git_environment() -> dict[str, str]:
+    """
+    Build the environment for Git subprocesses.
+
+    Returns:
+        Environment with LC_ALL=C and repository variables resolved or removed
+    """
+    env = os.environ.copy()
+
+    # Set LC_ALL=C for consistent output format
+    env["LC_ALL"] = "C"
+
+    for name in REPOSITORY_ENV_VARS:
+        if name not in env:
+            continue
+        if not _honor_git_env:
+            del env[name]
+        elif name in REPOSITORY_PATH_ENV_VARS and env[name]:
+            env[name] = os.path.abspath(env[name])
+
+    return env
+
+
 def is_git_repository(path: str) -> bool:
     """
     Check if a path is within a Git repository.
@@ -120,6 +297,11 @@ def get_repository_root(path: str) -> str:
     """
     Get the root directory of a Git repository.
 
+    Discovery is delegated to Git, so linked worktrees and submodules (where
+    ``.git`` is a file containing ``gitdir: ...``) resolve to their own
+    working tree rather than the Git directory they point to. For bare
+    repositories the Git directory itself is returned.
+
     Args:
         path: Path within the repository
 
@@ -132,28 +314,82 @@ def get_repository_root(path: str) -> str:
     Examples:
         >>> get_repository_root("/path/to/repo/src")
         '/path/to/repo'
+        >>> get_repository_root("/path/to/worktree")
+        '/path/to/worktree'
+    """
+    return _discover_repository_root(path)
+
+
+def find_repository_with_ceiling(path: str, ceiling_dirs: list[str]) -> Optional[str]:
+    """
+    Find the repository containing a path without walking above ceiling directories.
+
+    Discovery walks up from ``path`` but never looks in a ceiling directory
+    or its parents, so scanning a vendored tree that is not itself a
+    repository does not resolve to an unrelated parent repository. Ceilings
+    from ``GIT_CEILING_DIRECTORIES`` in the environment are applied too.
+
+    Args:
+        path: Path to start discovery from
+        ceiling_dirs: Directories discovery must not enter
+
+    Returns:
+        Absolute path to repository root, or None if no repository is found
+        below the ceilings
+
+    Examples:
+        >>> find_repository_with_ceiling("/path/to/repo/vendor/lib", ["/path/to/repo/vendor"])
+        None
+        >>> find_repository_with_ceiling("/path/to/repo/src", ["/path/to"])
+        '/path/to/repo'
     """
+    ceilings = [os.path.abspath(ceiling) for ceiling in ceiling_dirs]
+    inherited = os.environ.get("GIT_CEILING_DIRECTORIES")
+    if inherited:
+        ceilings.append(inherited)
+
     try:
-        # Get the .git directory location
-        git_dir = execute_git_command(
-            "git rev-parse --git-dir",
-            cwd=path,
-            timeout=5
+        return _discover_repository_root(
+            path,
+            env={"GIT_CEILING_DIRECTORIES": os.pathsep.join(ceilings)}
         )
+    except RepositoryNotFoundError:
+        return None
+
+
+def _discover_repository_root(path: str, env: Optional[dict[str, str]] = None) -> str:
+    """
+    Run Git repository discovery from a path.
+
+    Args:
+        path: Path within the repository
+        env: Extra environment variables (e.g. GIT_CEILING_DIRECTORIES)
+
+    Returns:
+        Absolute path to repository root (the Git directory for bare repositories)
 
-        # Convert to absolute path
-        git_path = Path(git_dir)
-        if not git_path.is_absolute():
-            git_path = Path(path) / git_path
+    Raises:
+        RepositoryNotFoundError: If path is not in a Git repository
+    """
+    try:
+        is_bare, git_dir = execute_git_command(
+            "git rev-parse --is-bare-repository --absolute-git-dir",
+            cwd=path,
+            timeout=5,
+            env=env
+        ).splitlines()
 
-        git_path = git_path.resolve()
+        # Bare repository - the Git directory IS the repo
+        if is_bare == "true":
+            return str(Path(git_dir).resolve())
 
-        # Repository root is parent of .git (unless it's a bare repo)
-        if git_path.name == ".git":
-            return str(git_path.parent)
-        else:
-            # Bare repository - the .git dir IS the repo
-            return str(git_path)
+        toplevel = execute_git_command(
+            "git rev-parse --show-toplevel",
+            cwd=path,
+            timeout=5,
+            env=env
+        )
+        return str(Path(toplevel).resolve())
 
     except GitCommandError as e:
         raise RepositoryNotFoundError(
@@ -163,15 +399,57 @@ def get_repository_root(path: str) -> str:
         ) from e
 
 
+def resolve_repository_path(repo_path: str, file_path: str) -> tuple[str, str]:
+    """
+    Find the repository that owns a path and the path relative to its root.
+
+    Relative paths are resolved against the root of the repository containing
+    ``repo_path``. Discovery then starts from the path itself, so a file inside
+    a submodule (or a nested repository) resolves to that repository rather
+    than the superproject. Paths that do not exist yet are resolved from their
+    nearest existing parent directory.
+
+    Args:
+        repo_path: Path within the (super)repository
+        file_path: File or directory path (absolute or relative to repo root)
+
+    Returns:
+        Tuple of (repository root, path relative to that root)
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> resolve_repository_path("/path/to/repo", "vendor/lib/src/file.py")
+        ('/path/to/repo/vendor/lib', 'src/file.py')
+    """
+    base_root = get_repository_root(repo_path)
+    absolute_path = os.path.normpath(os.path.join(base_root, file_path))
+
+    probe = absolute_path
+    while not os.path.isdir(probe) and os.path.dirname(probe) != probe:
+        probe = os.path.dirname(probe)
+
+    try:
+        repo_root = get_repository_root(probe)
+    except RepositoryNotFoundError:
+        repo_root = base_root
+
+    return repo_root, resolve_file_path(repo_root, absolute_path)
+
+
 def get_current_branch(repo_path: str) -> str:
     """
     Get the current branch name.
 
+    HEAD is read as a symbolic ref, so this also works on an unborn branch
+    (a fresh ``git init`` with no commits yet).
+
     Args:
         repo_path: Repository path
 
     Returns:
-        Current branch name
+        Current branch name, or 'HEAD' if HEAD is detached
 
     Raises:
         GitCommandError: If unable to determine branch
@@ -180,19 +458,304 @@ def get_current_branch(repo_path: str) -> str:
         >>> get_current_branch("/path/to/repo")
         'main'
     """
+    try:
+        return execute_git_command(
+            "git symbolic-ref --quiet --short HEAD",
+            cwd=repo_path
+        )
+    except GitCommandError as e:
+        # Exit code 1: HEAD is not a symbolic ref (detached)
+        if e.context.get("exit_code") == 1:
+            return "HEAD"
+        raise
+
+
+def is_head_unborn(repo_path: str) -> bool:
+    """
+    Check whether HEAD points at a branch with no commits yet.
+
+    Args:
+        repo_path: Repository path
+
+    Returns:
+        True for a fresh ``git init`` or orphan branch, False otherwise
+
+    Examples:
+        >>> is_head_unborn("/path/to/new-repo")
+        True
+    """
+    try:
+        execute_git_command("git rev-parse --verify --quiet HEAD", cwd=repo_path)
+        return False
+    except GitCommandError:
+        return True
+
+
+def get_head_commit(repo_path: str) -> str:
+    """
+    Get the commit SHA HEAD points at.
+
+    Args:
+        repo_path: Repository path
+
+    Returns:
+        Commit SHA
+
+    Raises:
+        UnbornHeadError: If the current branch has no commits yet
+
+    Examples:
+        >>> get_head_commit("/path/to/repo")
+        'abc123def456...'
+    """
+    try:
+        return execute_git_command("git rev-parse --verify --quiet HEAD", cwd=repo_path)
+    except GitCommandError as e:
+        branch = get_current_branch(repo_path)
+        raise UnbornHeadError(
+            f"Branch {branch} has no commits yet",
+            branch=branch,
+            path=repo_path,
+            cause=e
+        ) from e
+
+
+def abbreviate_oid(repo_path: str, oid: str, min_length: Optional[int] = None) -> str:
+    """
+    Abbreviate an object ID to the shortest prefix that is unambiguous in the repository.
+
+    Args:
+        repo_path: Repository path
+        oid: Full object SHA (or any revision)
+        min_length: Shortest prefix to return (default: Git's ``core.abbrev``,
+            usually 7 or more depending on repository size)
+
+    Returns:
+        Unambiguous abbreviated SHA
+
+    Raises:
+        GitCommandError: If the object does not exist
+        ValueError: If min_length is not between 4 and 40
+
+    Examples:
+        >>> abbreviate_oid("/path/to/repo", "abc123def4567890abc123def4567890abc123de")
+        'abc123d'
+        >>> abbreviate_oid("/path/to/repo", "abc123def4567890abc123def4567890abc123de", 4)
+        'abc1'
+    """
+    if min_length is not None and not 4 <= min_length <= 40:
+        raise ValueError(f"Invalid min length: {min_length}. Must be between 4 and 40")
+
+    option = "--short" if min_length is None else f"--short={min_length}"
+    # ^{object} makes a full SHA that names no object fail instead of being echoed
     return execute_git_command(
-        "git rev-parse --abbrev-ref HEAD",
+        ["git", "rev-parse", "--verify", option, f"{oid}^{{object}}"],
         cwd=repo_path
     )
 
 
-def get_remote_url(repo_path: str, remote: str = "origin") -> Optional[str]:
+def expand_oid(repo_path: str, prefix: str) -> str:
+    """
+    Expand an abbreviated object ID to the full SHA.
+
+    Args:
+        repo_path: Repository path
+        prefix: Abbreviated SHA, at least 4 hexadecimal characters
+
+    Returns:
+        Full object SHA
+
+    Raises:
+        InvalidHashError: If prefix is not 4-40 hexadecimal characters
+        GitError: If no object matches (code OBJECT_NOT_FOUND) or several
+            do (code AMBIGUOUS_OID, with the candidates in the context)
+
+    Examples:
+        >>> expand_oid("/path/to/repo", "abc12")
+        'abc123def4567890abc123def4567890abc123de'
+    """
+    if not SHORT_OID_PATTERN.match(prefix):
+        raise InvalidHashError(
+            "Invalid abbreviated hash: must be 4 to 40 hexadecimal characters",
+            hash_value=prefix
+        )
+
+    candidates = execute_git_command(
+        ["git", "rev-parse", f"--disambiguate={prefix.lower()}"],
+        cwd=repo_path
+    ).split()
+
+    if not candidates:
+        raise GitError(
+            f"No object matches {prefix}",
+            code="OBJECT_NOT_FOUND",
+            context={"prefix": prefix, "path": repo_path}
+        )
+    if len(candidates) > 1:
+        raise GitError(
+            f"Abbreviated hash {prefix} is ambiguous: {len(candidates)} objects match",
+            code="AMBIGUOUS_OID",
+            context={"prefix": prefix, "candidates": candidates}
+        )
+
+    return candidates[0]
+
+
+def list_remotes(repo_path: str) -> list[str]:
+    """
+    List the configured remotes in configuration order.
+
+    Args:
+        repo_path: Repository path
+
+    Returns:
+        Remote names
+
+    Examples:
+        >>> list_remotes("/path/to/repo")
+        ['origin', 'upstream']
+    """
+    output = execute_git_command("git remote", cwd=repo_path)
+    return output.splitlines()
+
+
+def get_tracking_branch(repo_path: str) -> Optional[tuple[str, str]]:
+    """
+    Get the remote and branch the current branch tracks.
+
+    Read from ``branch.<name>.remote`` and ``branch.<name>.merge``, so a
+    local ``feature`` branch tracking ``upstream/main`` yields
+    ``('upstream', 'main')``.
+
+    Args:
+        repo_path: Repository path
+
+    Returns:
+        Tuple of (remote, remote branch name), or None if HEAD is detached
+        or the branch does not track a remote branch
+
+    Examples:
+        >>> get_tracking_branch("/path/to/repo")
+        ('upstream', 'main')
+    """
+    branch = get_current_branch(repo_path)
+    if branch == "HEAD":
+        return None
+
+    try:
+        remote = execute_git_command(
+            ["git", "config", "--get", f"branch.{branch}.remote"],
+            cwd=repo_path
+        )
+        merge = execute_git_command(
+            ["git", "config", "--get", f"branch.{branch}.merge"],
+            cwd=repo_path
+        )
+    except GitCommandError:
+        return None
+
+    # "." tracks a branch of the local repository
+    if remote == ".":
+        return None
+
+    return remote, merge.removeprefix("refs/heads/")
+
+
+def get_upstream_divergence(repo_path: str) -> Optional[tuple[int, int]]:
+    """
+    Count commits between HEAD and the current branch's upstream.
+
+    Args:
+        repo_path: Repository path
+
+    Returns:
+        Tuple of (ahead, behind): commits on HEAD not yet on the upstream,
+        and upstream commits not yet on HEAD; None if HEAD is detached or
+        the branch has no upstream
+
+    Examples:
+        >>> get_upstream_divergence("/path/to/repo")
+        (3, 0)
+    """
+    try:
+        output = execute_git_command(
+            "git rev-list --left-right --count HEAD...@{upstream}",
+            cwd=repo_path
+        )
+    except GitCommandError:
+        return None
+
+    ahead, behind = output.split()
+    return int(ahead), int(behind)
+
+
+def get_default_remote(repo_path: str, prefer_upstream: bool = False) -> Optional[str]:
+    """
+    Pick the remote used when none is named.
+
+    The remote the current branch tracks (``branch.<name>.remote``) wins,
+    then ``origin``, then the first configured remote, so repositories
+    without an ``origin`` remote still resolve.
+
+    With ``prefer_upstream``, a clone of a fork (both ``origin`` and
+    ``upstream`` remotes configured) resolves to ``upstream`` regardless of
+    tracking, so shared links point at the canonical repository where
+    everyone can see the commit.
+
+    Args:
+        repo_path: Repository path
+        prefer_upstream: Pick ``upstream`` when it exists alongside ``origin``
+            (default: False)
+
+    Returns:
+        Remote name, or None if no remotes are configured
+
+    Examples:
+        >>> get_default_remote("/path/to/repo")
+        'origin'
+        >>> get_default_remote("/path/to/fork-clone", prefer_upstream=True)
+        'upstream'
+    """
+    remotes = list_remotes(repo_path)
+    if not remotes:
+        return None
+
+    if prefer_upstream and "origin" in remotes and "upstream" in remotes:
+        return "upstream"
+
+    branch = get_current_branch(repo_path)
+    if branch != "HEAD":
+        try:
+            tracked = execute_git_command(
+                ["git", "config", "--get", f"branch.{branch}.remote"],
+                cwd=repo_path
+            )
+            if tracked in remotes:
+                return tracked
+        except GitCommandError:
+            pass
+
+    return "origin" if "origin" in remotes else remotes[0]
+
+
+def get_remote_url(
+    repo_path: str,
+    remote: Optional[str] = None,
+    push: bool = False
+) -> Optional[str]:
     """
     Get the URL of a Git remote.
 
+    ``url.<base>.insteadOf`` rewrites from git config are applied, so a
+    remote stored as ``gh:owner/repo`` resolves to the real URL. With
+    ``push=True`` the push URL is returned instead (``remote.<name>.pushurl``
+    if set, otherwise the fetch URL with ``pushInsteadOf`` rewrites).
+
     Args:
         repo_path: Repository path
-        remote: Remote name (default: 'origin')
+        remote: Remote name (default: ``get_default_remote``, i.e. the
+            current branch's remote, then 'origin', then the first remote)
+        push: Return the push URL instead of the fetch URL (default: False)
 
     Returns:
         Remote URL, or None if remote doesn't exist
@@ -200,20 +763,746 @@ def get_remote_url(repo_path: str, remote: str = "origin") -> Optional[str]:
     Examples:
         >>> get_remote_url("/path/to/repo")
         'git@github.com:user/repo.git'
+        >>> get_remote_url("/path/to/repo", "upstream")
+        'git@github.com:org/repo.git'
     """
+    if remote is None:
+        remote = get_default_remote(repo_path)
+        if remote is None:
+            return None
+
+    if push:
+        try:
+            push_url = execute_git_command(
+                ["git", "config", "--get", f"remote.{remote}.pushurl"],
+                cwd=repo_path
+            )
+            return apply_url_rewrites(push_url, repo_path)
+        except GitCommandError:
+            pass
+
     try:
-        return execute_git_command(
-            f"git remote get-url {remote}",
+        url = execute_git_command(
+            ["git", "config", "--get", f"remote.{remote}.url"],
             cwd=repo_path
         )
     except GitCommandError:
         return None
 
+    return apply_url_rewrites(url, repo_path, push=push)
+
+
+def get_default_branch(repo_path: str, remote: Optional[str] = None) -> Optional[str]:
+    """
+    Get the default branch of a remote.
+
+    Read from ``refs/remotes/<remote>/HEAD`` (set by ``git clone`` or
+    ``git remote set-head``). When that ref is missing, the first of
+    ``main``, ``master``, ``trunk`` and ``develop`` that exists as a
+    remote-tracking branch, then as a local branch, is used.
+
+    Args:
+        repo_path: Repository path
+        remote: Remote name (default: ``get_default_remote``)
+
+    Returns:
+        Branch name without the remote prefix, or None if it cannot be
+        determined
+
+    Examples:
+        >>> get_default_branch("/path/to/repo")
+        'main'
+        >>> get_default_branch("/path/to/repo", "upstream")
+        'develop'
+    """
+    if remote is None:
+        remote = get_default_remote(repo_path)
+
+    if remote is not None:
+        try:
+            head = execute_git_command(
+                ["git", "symbolic-ref", "--quiet", "--short", f"refs/remotes/{remote}/HEAD"],
+                cwd=repo_path
+            )
+            return head.removeprefix(f"{remote}/")
+        except GitCommandError:
+            pass
+
+    prefixes = [f"refs/remotes/{remote}/"] if remote is not None else []
+    prefixes.append("refs/heads/")
+
+    for prefix in prefixes:
+        for name in DEFAULT_BRANCH_CANDIDATES:
+            try:
+                execute_git_command(
+                    ["git", "show-ref", "--verify", "--quiet", f"{prefix}{name}"],
+                    cwd=repo_path
+                )
+                return name
+            except GitCommandError:
+                continue
+
+    return None
+
+
+def is_commit_on_remote(repo_path: str, commit: str, remote: Optional[str] = None) -> bool:
+    """
+    Check whether a commit is reachable from a remote's tracking branches.
+
+    A commit that only exists locally 404s for everyone else, so permalinks
+    should only point at commits this returns True for. The check is made
+    against ``refs/remotes/<remote>/*`` as of the last fetch or push.
+
+    Args:
+        repo_path: Repository path
+        commit: Commit SHA or revision
+        remote: Remote name (default: ``get_default_remote``)
+
+    Returns:
+        True if a remote-tracking branch of the remote contains the commit
+
+    Raises:
+        GitCommandError: If the commit does not exist
+
+    Examples:
+        >>> is_commit_on_remote("/path/to/repo", "abc123...")
+        True
+    """
+    if remote is None:
+        remote = get_default_remote(repo_path)
+        if remote is None:
+            return False
+
+    output = execute_git_command(
+        [
+            "git", "for-each-ref", "--contains", commit, "--count=1", "--format=%(refname)",
+            f"refs/remotes/{remote}/"
+        ],
+        cwd=repo_path
+    )
+    return bool(output)
+
+
+def get_nearest_pushed_ancestor(
+    repo_path: str,
+    commit: str,
+    remote: Optional[str] = None
+) -> Optional[str]:
+    """
+    Get the closest ancestor of a commit that is on a remote.
+
+    The commit itself is returned when it is already pushed. Otherwise the
+    newest commit where the unpushed history branches off the remote's
+    tracking branches is returned.
+
+    Args:
+        repo_path: Repository path
+        commit: Commit SHA or revision
+        remote: Remote name (default: ``get_default_remote``)
+
+    Returns:
+        Full SHA of the pushed ancestor, or None if no ancestor is pushed
+
+    Raises:
+        GitCommandError: If the commit does not exist
+
+    Examples:
+        >>> get_nearest_pushed_ancestor("/path/to/repo", "HEAD")
+        'def456...'
+    """
+    if remote is None:
+        remote = get_default_remote(repo_path)
+        if remote is None:
+            return None
+
+    # Boundary commits ("-<sha>") are the pushed parents of the unpushed commits
+    output = execute_git_command(
+        ["git", "rev-list", "--boundary", commit, "--not", f"--remotes={remote}"],
+        cwd=repo_path
+    )
+    if not output:
+        return execute_git_command(
+            ["git", "rev-parse", "--verify", f"{commit}^{{commit}}"],
+            cwd=repo_path
+        )
+
+    for line in output.splitlines():
+        if line.startswith("-"):
+            return line[1:]
+
+    return None
+
+
+def merge_base(repo_path: str, a: str, b: str) -> Optional[str]:
+    """
+    Get the best common ancestor of two commits.
+
+    With the default branch from ``get_default_branch`` this gives the
+    point where a feature branch forked, which stays fixed while both
+    branches move on.
+
+    Args:
+        repo_path: Repository path
+        a: Commit SHA or revision
+        b: Commit SHA or revision
+
+    Returns:
+        Full SHA of the merge base, or None if the histories are unrelated
+
+    Raises:
+        GitCommandError: If either commit does not exist
+
+    Examples:
+        >>> merge_base("/path/to/repo", "HEAD", "origin/main")
+        'def456...'
+    """
+    try:
+        return execute_git_command(["git", "merge-base", a, b], cwd=repo_path)
+    except GitCommandError as e:
+        # Exit code 1: no common ancestor
+        if e.context.get("exit_code") == 1:
+            return None
+        raise
+
+
+def is_ancestor(repo_path: str, ancestor: str, descendant: str) -> bool:
+    """
+    Check whether one commit is reachable from another.
+
+    A commit counts as its own ancestor.
+
+    Args:
+        repo_path: Repository path
+        ancestor: Commit SHA or revision that may be contained
+        descendant: Commit SHA or revision whose history is searched
+
+    Returns:
+        True if ancestor is in the history of descendant
+
+    Raises:
+        GitCommandError: If either commit does not exist
+
+    Examples:
+        >>> is_ancestor("/path/to/repo", "abc123def456...", "origin/main")
+        True
+    """
+    try:
+        execute_git_command(
+            ["git", "merge-base", "--is-ancestor", ancestor, descendant],
+            cwd=repo_path
+        )
+        return True
+    except GitCommandError as e:
+        # Exit code 1: not an ancestor
+        if e.context.get("exit_code") == 1:
+            return False
+        raise
+
+
+def apply_url_rewrites(url: str, repo_path: str, push: bool = False) -> str:
+    """
+    Apply ``url.<base>.insteadOf`` rewrites from git config to a URL.
+
+    As in git, the longest matching prefix wins. With ``push=True``,
+    ``pushInsteadOf`` rewrites take precedence over ``insteadOf``.
+
+    Args:
+        url: Remote URL as configured
+        repo_path: Repository path (for repository-level config)
+        push: Also consider pushInsteadOf rewrites (default: False)
+
+    Returns:
+        Rewritten URL, or the URL unchanged if no rewrite applies
+
+    Examples:
+        >>> # git config url."git@github.com:".insteadOf "gh:"
+        >>> apply_url_rewrites("gh:owner/repo", "/path/to/repo")
+        'git@github.com:owner/repo'
+    """
+    try:
+        output = execute_git_command(
+            ["git", "config", "--get-regexp", r"^url\..*\.(push)?insteadof$"],
+            cwd=repo_path
+        )
+    except GitCommandError:
+        return url
+
+    rewrites: dict[str, list[tuple[str, str]]] = {"insteadof": [], "pushinsteadof": []}
+    for line in output.splitlines():
+        key, _, prefix = line.partition(" ")
+        base, _, variable = key[len("url."):].rpartition(".")
+        if prefix:
+            rewrites[variable.lower()].append((prefix, base))
+
+    kinds = ["pushinsteadof", "insteadof"] if push else ["insteadof"]
+    for kind in kinds:
+        matches = [(prefix, base) for prefix, base in rewrites[kind] if url.startswith(prefix)]
+        if matches:
+            prefix, base = max(matches, key=lambda match: len(match[0]))
+            return base + url[len(prefix):]
+
+    return url
+
+
+def get_last_commit_for_file(
+    repo_path: str,
+    file_path: str,
+    rev: str = "HEAD"
+) -> Optional[str]:
+    """
+    Get the most recent commit that modified a file or directory.
+
+    Unlike HEAD, this only changes when the path itself changes, so links
+    built from it stay stable across unrelated commits. In a shallow clone
+    the search stops at the shallow boundary: if the path was last changed
+    before it, the boundary commit is returned (check it against
+    ``get_shallow_boundary``).
+
+    Args:
+        repo_path: Repository path
+        file_path: Path relative to the repository root ('' or '.' for everything)
+        rev: Revision whose history is searched (default: 'HEAD')
+
+    Returns:
+        Commit SHA, or None if no commit in the history touches the path
+
+    Examples:
+        >>> get_last_commit_for_file("/path/to/repo", "src/file.py")
+        'abc123def456...'
+    """
+    try:
+        output = execute_git_command(
+            ["git", "log", "-1", "--format=%H", rev, "--", file_path or "."],
+            cwd=repo_path
+        )
+    except GitCommandError:
+        return None
+
+    return output or None
+
+
+def get_promisor_remotes(repo_path: str) -> list[str]:
+    """
+    Get the promisor remotes of a partial clone.
+
+    Partial clones (``git clone --filter=...``) leave objects out and fetch
+    them from these remotes when first needed.
+
+    Args:
+        repo_path: Repository path
+
+    Returns:
+        Promisor remote names (empty if the repository is not a partial clone)
+
+    Examples:
+        >>> get_promisor_remotes("/path/to/blobless-clone")
+        ['origin']
+    """
+    try:
+        output = execute_git_command(
+            ["git", "config", "--get-regexp", r"^remote\..*\.promisor$"],
+            cwd=repo_path
+        )
+    except GitCommandError:
+        return []
+
+    remotes = []
+    for line in output.splitlines():
+        key, _, value = line.partition(" ")
+        if value.lower() in ("true", "yes", "on", "1"):
+            remotes.append(key[len("remote."):-len(".promisor")])
+    return remotes
+
+
+def is_object_local(repo_path: str, object_id: str) -> bool:
+    """
+    Check whether an object is present in the local object database.
+
+    Unlike most Git commands this never fetches from a promisor remote, so
+    it is safe to call offline in a partial clone.
+
+    Args:
+        repo_path: Repository path
+        object_id: Object SHA
+
+    Returns:
+        True if the object is stored locally, False otherwise
+
+    Examples:
+        >>> is_object_local("/path/to/blobless-clone", "abc123def456...")
+        False
+    """
+    # --missing disables on-demand fetching; tree:0 stops the walk at the object itself
+    try:
+        execute_git_command(
+            [
+                "git", "rev-list", "--objects", "--no-walk", "--missing=print", "--filter=tree:0",
+                object_id
+            ],
+            cwd=repo_path
+        )
+        return True
+    except GitCommandError:
+        return False
+
+
+def is_shallow(repo_path: str) -> bool:
+    """
+    Check whether the repository is a shallow clone.
+
+    Args:
+        repo_path: Repository path
+
+    Returns:
+        True if history was truncated (e.g. ``git clone --depth``), False otherwise
+
+    Examples:
+        >>> is_shallow("/path/to/ci-checkout")
+        True
+    """
+    return execute_git_command(
+        "git rev-parse --is-shallow-repository",
+        cwd=repo_path
+    ) == "true"
+
+
+def get_shallow_boundary(repo_path: str) -> list[str]:
+    """
+    Get the commits at which a shallow clone's history is cut off.
+
+    History walks treat these commits as parentless, so results that land on
+    one of them may be truncated rather than exact.
+
+    Args:
+        repo_path: Repository path
+
+    Returns:
+        Boundary commit SHAs (empty if the repository is not shallow)
+
+    Examples:
+        >>> get_shallow_boundary("/path/to/ci-checkout")
+        ['abc123def456...']
+    """
+    shallow_file = execute_git_command(
+        "git rev-parse --git-path shallow",
+        cwd=repo_path
+    )
+
+    try:
+        with open(os.path.join(repo_path, shallow_file), "r", encoding="utf-8") as f:
+            return f.read().split()
+    except OSError:
+        return []
+
+
+def get_blob_oid(repo_path: str, file_path: str, index: bool = False) -> Optional[str]:
+    """
+    Get the Git blob SHA of a file as stored in HEAD's tree or the index.
+
+    This is the per-file content identifier: it changes only when the file
+    content changes, unlike the commit hash.
+
+    Args:
+        repo_path: Path within the repository
+        file_path: File path (absolute or relative to repo root)
+        index: Read the staged version from the index instead of HEAD
+            (default: False)
+
+    Returns:
+        Blob SHA, or None if the path is not a file in HEAD (or the index)
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+
+    Examples:
+        >>> get_blob_oid("/path/to/repo", "src/file.py")
+        'def456abc789...'
+        >>> get_blob_oid("/path/to/repo", "src/file.py", index=True)
+        'fedcba987654...'
+    """
+    repo_root = get_repository_root(repo_path)
+    relative_path = resolve_file_path(repo_root, file_path)
+
+    if not index:
+        try:
+            return get_file_hash_at_commit(repo_root, relative_path, "HEAD")
+        except GitError:
+            # Unborn HEAD (no commits yet)
+            return None
+
+    try:
+        output = execute_git_command(
+            ["git", "ls-files", "--stage", "-z", "--", relative_path],
+            cwd=repo_root
+        )
+    except GitCommandError:
+        return None
+
+    return _find_blob_entry(output, INDEX_ENTRY_PATTERN, relative_path)
+
+
+def get_blob_bytes(repo_path: str, blob_hash: str) -> bytes:
+    """
+    Read the raw content of a blob.
+
+    Args:
+        repo_path: Repository path
+        blob_hash: Blob SHA, or any revision naming a blob such as
+            ``HEAD:src/file.py``
+
+    Returns:
+        Blob content as bytes
+
+    Raises:
+        GitCommandError: If the object does not exist or is not a blob
+        ObjectNotLocalError: If the blob is missing from a partial clone and
+            cannot be fetched
+
+    Examples:
+        >>> get_blob_bytes("/path/to/repo", "def456abc789...")[:4]
+        b'\\x89PNG'
+    """
+    return execute_git_command_bytes(["git", "cat-file", "blob", blob_hash], cwd=repo_path)
+
+
+def get_blob_content(repo_path: str, blob_hash: str) -> str:
+    """
+    Read the content of a blob as text.
+
+    Decoded as UTF-8; invalid byte sequences (binary files, other
+    encodings) are replaced with U+FFFD rather than raising.
+
+    Args:
+        repo_path: Repository path
+        blob_hash: Blob SHA, or any revision naming a blob such as
+            ``HEAD:src/file.py``
+
+    Returns:
+        Blob content as text
+
+    Raises:
+        GitCommandError: If the object does not exist or is not a blob
+        ObjectNotLocalError: If the blob is missing from a partial clone and
+            cannot be fetched
+
+    Examples:
+        >>> get_blob_content("/path/to/repo", "HEAD:README.md")
+        '# Project\\n...'
+    """
+    return get_blob_bytes(repo_path, blob_hash).decode("utf-8", errors="replace")
+
+
+def get_blob_size(repo_path: str, blob_hash: str) -> int:
+    """
+    Get the size of a blob in bytes without reading its content.
+
+    Args:
+        repo_path: Repository path
+        blob_hash: Blob SHA, or any revision naming a blob
+
+    Returns:
+        Size in bytes
+
+    Raises:
+        GitCommandError: If the object does not exist
+
+    Examples:
+        >>> get_blob_size("/path/to/repo", "HEAD:assets/video.mp4")
+        314572800
+    """
+    return int(execute_git_command(["git", "cat-file", "-s", blob_hash], cwd=repo_path))
+
+
+def stream_blob(
+    repo_path: str,
+    blob_hash: str,
+    chunk_size: int = BLOB_CHUNK_SIZE
+) -> Iterator[bytes]:
+    """
+    Read a blob in chunks instead of loading it whole.
+
+    Chunks are read from ``git cat-file`` as they are consumed, so memory
+    use stays at one chunk; combine with ``get_blob_size`` to report
+    progress. The object is checked before the first chunk, so missing
+    objects raise the same errors as ``get_blob_bytes``.
+
+    Args:
+        repo_path: Repository path
+        blob_hash: Blob SHA, or any revision naming a blob
+        chunk_size: Chunk size in bytes (default: 64 KiB); the last chunk
+            may be shorter
+
+    Yields:
+        Consecutive chunks of the blob content
+
+    Raises:
+        GitCommandError: If the object does not exist or is not a blob
+        ObjectNotLocalError: If the blob is missing from a partial clone and
+            cannot be fetched
+        ValueError: If chunk_size is not positive
+
+    Examples:
+        >>> total = get_blob_size("/path/to/repo", "HEAD:assets/video.mp4")
+        >>> with open("video.mp4", "wb") as f:
+        ...     for chunk in stream_blob("/path/to/repo", "HEAD:assets/video.mp4"):
+        ...         f.write(chunk)
+    """
+    if chunk_size <= 0:
+        raise ValueError(f"Invalid chunk size: {chunk_size}. Must be positive")
+
+    # Resolve through execute_git_command for its error handling
+    object_type = execute_git_command(["git", "cat-file", "-t", blob_hash], cwd=repo_path)
+    if object_type != "blob":
+        raise GitCommandError(
+            f"Not a blob: {blob_hash} is a {object_type}",
+            command=f'git cat-file blob "{blob_hash}"'
+        )
+
+    command = ["git", "cat-file", "blob", blob_hash]
+    with subprocess.Popen(
+        command,
+        cwd=repo_path,
+        stdout=subprocess.PIPE,
+        stderr=subprocess.PIPE,
+        env=_git_environment()
+    ) as process:
+        while True:
+            chunk = process.stdout.read(chunk_size)
+            if not chunk:
+                break
+            yield chunk
+
+        stderr = process.stderr.read().decode("utf-8", errors="replace")
+        if process.wait() != 0:
+            raise GitCommandError(
+                f"Git command failed: {stderr.strip()}",
+                command=" ".join(command),
+                exit_code=process.returncode,
+                stderr=stderr.strip()
+            )
+
+
+def get_file_hash_at_commit(repo_path: str, file_path: str, commit: str) -> Optional[str]:
+    """
+    Get the Git blob SHA of a file as stored at a given commit.
+
+    Args:
+        repo_path: Path within the repository
+        file_path: File path (absolute or relative to repo root)
+        commit: Commit SHA, branch, tag or other revision
+
+    Returns:
+        Blob SHA, or None if the path is not a file at that commit
+
+    Raises:
+        RepositoryNotFoundError: If repo_path is not in a Git repository
+        GitError: If the commit cannot be resolved
+
+    Examples:
+        >>> get_file_hash_at_commit("/path/to/repo", "src/file.py", "v1.0.0")
+        'def456abc789...'
+    """
+    repo_root = get_repository_root(repo_path)
+    relative_path = resolve_file_path(repo_root, file_path)
+
+    try:
+        output = execute_git_command(
+            ["git", "ls-tree", "-z", commit, "--", relative_path],
+            cwd=repo_root
+        )
+    except GitCommandError as e:
+        raise GitError(
+            f"Cannot resolve commit: {commit}",
+            code="REF_NOT_FOUND",
+            context={"path": repo_root, "ref": commit},
+            cause=e
+        ) from e
+
+    return _find_blob_entry(output, TREE_ENTRY_PATTERN, relative_path)
+
+
+def _find_blob_entry(output: str, pattern: re.Pattern[str], relative_path: str) -> Optional[str]:
+    """
+    Find the blob SHA for an exact path in NUL-separated ls-tree/ls-files output.
+
+    Args:
+        output: Command output produced with -z
+        pattern: Entry pattern capturing (blob SHA, path)
+        relative_path: Path relative to the repository root
+
+    Returns:
+        Blob SHA, or None if no entry matches the path
+    """
+    for entry in output.split("\0"):
+        match = pattern.match(entry)
+        if match and match.group(2) == relative_path:
+            return match.group(1)
+    return None
+
+
+def get_head_tag(repo_path: str) -> Optional[str]:
+    """
+    Get the tag pointing exactly at HEAD.
+
+    When several tags point at HEAD, the highest version-sorted tag wins
+    (e.g. ``v1.10.0`` over ``v1.9.0``).
+
+    Args:
+        repo_path: Repository path
+
+    Returns:
+        Tag name, or None if HEAD is not tagged
+
+    Examples:
+        >>> get_head_tag("/path/to/repo")
+        'v1.2.3'
+    """
+    try:
+        output = execute_git_command(
+            "git tag --points-at HEAD --sort=-v:refname",
+            cwd=repo_path
+        )
+    except GitCommandError:
+        return None
+
+    tags = output.splitlines()
+    return tags[0] if tags else None
+
 
 __all__ = [
     "execute_git_command",
+    "execute_git_command_bytes",
+    "set_honor_git_env",
     "is_git_repository",
     "get_repository_root",
+    "find_repository_with_ceiling",
+    "resolve_repository_path",
     "get_current_branch",
+    "is_head_unborn",
+    "get_head_commit",
+    "abbreviate_oid",
+    "expand_oid",
+    "list_remotes",
+    "get_tracking_branch",
+    "get_upstream_divergence",
+    "get_default_remote",
     "get_remote_url",
+    "get_default_branch",
+    "is_commit_on_remote",
+    "get_nearest_pushed_ancestor",
+    "merge_base",
+    "is_ancestor",
+    "apply_url_rewrites",
+    "get_last_commit_for_file",
+    "get_promisor_remotes",
+    "is_object_local",
+    "is_shallow",
+    "get_shallow_boundary",
+    "get_blob_oid",
+    "get_blob_bytes",
+    "get_blob_content",
+    "get_blob_size",
+    "stream_blob",
+    "get_file_hash_at_commit",
+    "get_head_tag",
 ]
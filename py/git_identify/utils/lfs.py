@@ -0,0 +1,121 @@
+"""
+Git LFS pointer parsing.
+
+Recognizes the small pointer files Git LFS commits in place of large
+content (https://github.com/git-lfs/git-lfs/blob/main/docs/spec.md), so
+the blob OID of a tracked file can be told apart from the identity of the
+content it stands for.
+"""
+
+import re
+from typing import Any, Optional
+
+# Pointer files are at most this many bytes
+LFS_POINTER_MAX_SIZE = 1024
+
+# Spec versions accepted on a pointer's first line; the second is the
+# pre-release name of Git LFS
+LFS_SPEC_VERSIONS = (
+    "https://git-lfs.github.com/spec/v1",
+    "https://hawser.github.com/spec/v1",
+)
+
+# "oid sha256:<64 hex digits>" pointer line
+LFS_OID_PATTERN = re.compile(r"^sha256:([0-9a-f]{64})$")
+
+
+class LfsInfo:
+    """
+    A Git LFS pointer: the content a pointer blob stands for.
+
+    Attributes:
+        oid: SHA-256 of the real content, as hex (the LFS object ID)
+        size: Size of the real content in bytes
+        pointer_oid: Git blob SHA of the pointer file (None if parsed from
+            bytes rather than read from a repository)
+        path: Path of the pointer file relative to repo root (None if parsed
+            from bytes)
+    """
+
+    def __init__(
+        self,
+        oid: str,
+        size: int,
+        pointer_oid: Optional[str] = None,
+        path: Optional[str] = None
+    ) -> None:
+        self.oid = oid
+        self.size = size
+        self.pointer_oid = pointer_oid
+        self.path = path
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        return {
+            "oid": self.oid,
+            "size": self.size,
+            "pointerOid": self.pointer_oid,
+            "path": self.path
+        }
+
+
+def parse_lfs_pointer(content: bytes) -> Optional[LfsInfo]:
+    """
+    Parse the content of a Git LFS pointer file.
+
+    Args:
+        content: Blob content
+
+    Returns:
+        LfsInfo, or None if the content is not a valid pointer
+
+    Examples:
+        >>> pointer = (
+        ...     b"version https://git-lfs.github.com/spec/v1\\n"
+        ...     b"oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\\n"
+        ...     b"size 12345\\n"
+        ... )
+        >>> parse_lfs_pointer(pointer).to_dict()
+        {'oid': '4d7a2146...', 'size': 12345, 'pointerOid': None, 'path': None}
+        >>> parse_lfs_pointer(b"plain text\\n") is None
+        True
+    """
+    if len(content) > LFS_POINTER_MAX_SIZE:
+        return None
+
+    try:
+        text = content.decode("utf-8")
+    except UnicodeDecodeError:
+        return None
+
+    # "<key> <value>" lines, version first
+    lines = text.split("\n")
+    if lines[-1] == "":
+        lines.pop()
+    if not lines:
+        return None
+
+    key, _, version = lines[0].partition(" ")
+    if key != "version" or version not in LFS_SPEC_VERSIONS:
+        return None
+
+    fields: dict[str, str] = {}
+    for line in lines[1:]:
+        key, separator, value = line.partition(" ")
+        if not separator:
+            return None
+        fields[key] = value
+
+    match = LFS_OID_PATTERN.match(fields.get("oid", ""))
+    size = fields.get("size", "")
+    if not match or not size.isdigit():
+        return None
+
+    return LfsInfo(match.group(1), int(size))
+
+
+__all__ = [
+    "LFS_POINTER_MAX_SIZE",
+    "LfsInfo",
+    "parse_lfs_pointer",
+]
@@ -85,7 +85,9 @@ def resolve_file_path(repo_path: str, file_path: str) -> str:
     # If file path is absolute, make it relative to repo
     if file.is_absolute():
         try:
-            file = file.resolve().relative_to(repo)
+            # Resolve the parent only: a symbolic link is tracked as itself,
+            # not as the file it points to
+            file = (file.parent.resolve() / file.name).relative_to(repo)
         except ValueError:
             # File is not under repo - use as-is
             pass
@@ -0,0 +1,102 @@
+"""
+SSH configuration utilities.
+
+Provides host alias resolution from OpenSSH client configuration so that
+remotes like ``git@github-work:owner/repo.git`` map to their real host.
+"""
+
+import fnmatch
+import os
+import re
+from typing import Optional
+
+# "Key value" or "Key=value" configuration lines
+CONFIG_LINE_PATTERN = re.compile(r"^(\S+?)(?:\s*=\s*|\s+)(.+)$")
+
+
+def resolve_ssh_host(alias: str, config_path: Optional[str] = None) -> str:
+    """
+    Resolve an SSH host alias to its real hostname.
+
+    Reads ``Host`` blocks from the SSH client configuration and returns the
+    first matching ``HostName`` (as ssh does, the first obtained value wins).
+    Wildcards (``*``, ``?``) and negated patterns (``!``) are supported;
+    ``Match`` blocks and ``Include`` directives are not.
+
+    Args:
+        alias: Host alias as written in the remote URL
+        config_path: SSH config file (default: ~/.ssh/config)
+
+    Returns:
+        Resolved hostname, or the alias itself if no HostName applies
+
+    Examples:
+        >>> # ~/.ssh/config:  Host github-work
+        >>> #                     HostName github.com
+        >>> resolve_ssh_host("github-work")
+        'github.com'
+        >>> resolve_ssh_host("example.com")
+        'example.com'
+    """
+    if config_path is None:
+        config_path = os.path.expanduser("~/.ssh/config")
+
+    try:
+        with open(config_path, "r", encoding="utf-8") as f:
+            lines = f.read().splitlines()
+    except OSError:
+        return alias
+
+    # Options before the first Host block apply to every host
+    matched = True
+
+    for raw_line in lines:
+        line = raw_line.strip()
+        if not line or line.startswith("#"):
+            continue
+
+        match = CONFIG_LINE_PATTERN.match(line)
+        if not match:
+            continue
+
+        key = match.group(1).lower()
+        value = match.group(2).strip().strip('"')
+
+        if key == "host":
+            matched = _host_matches(alias, value.split())
+        elif key == "match":
+            # Match criteria are not evaluated
+            matched = False
+        elif key == "hostname" and matched:
+            return value.replace("%h", alias)
+
+    return alias
+
+
+def _host_matches(alias: str, patterns: list[str]) -> bool:
+    """
+    Check whether a host alias matches a Host line's pattern list.
+
+    Args:
+        alias: Host alias
+        patterns: Patterns from a Host line
+
+    Returns:
+        True if a pattern matches and no negated pattern matches
+    """
+    alias = alias.lower()
+    found = False
+
+    for pattern in patterns:
+        negated = pattern.startswith("!")
+        if fnmatch.fnmatchcase(alias, pattern.lstrip("!").lower()):
+            if negated:
+                return False
+            found = True
+
+    return found
+
+
+__all__ = [
+    "resolve_ssh_host",
+]
@@ -5,81 +5,451 @@ Provides functions for parsing Git remote URLs and building GitHub permalinks.
 """
 
 import re
-from typing import Optional
+from typing import Any, Literal, Optional
+from urllib.parse import quote, unquote
 
 from .path import normalize_file_path
+from .ssh import resolve_ssh_host
 
+Provider = Literal["github", "gitlab", "bitbucket"]
 
-def parse_github_url(remote_url: str) -> Optional[dict[str, str]]:
+ArchiveFormat = Literal["tar.gz", "zip"]
+
+ARCHIVE_FORMATS = ("tar.gz", "zip")
+
+# RFC 3986 path characters that are safe to leave unencoded in a segment
+PATH_SEGMENT_SAFE = "!$&'()*+,;=:@"
+
+# https://github.com/<owner>/<repo>/<blob|tree>/<ref and path>[?query][#fragment]
+GITHUB_LINK_PATTERN = re.compile(
+    r"^https?://(?:www\.)?github\.com/([^/]+)/([^/]+)/(blob|tree)/([^?#]+)(\?[^#]*)?(#.*)?$"
+)
+
+# GitHub line anchors: #L10, #L10-L25, with optional column suffixes (#L10C5-L25C3)
+LINE_ANCHOR_PATTERN = re.compile(r"^#L(\d+)(?:C\d+)?(?:-L(\d+)(?:C\d+)?)?$")
+
+# GitHub URL serving the content behind a Git LFS pointer
+LFS_MEDIA_URL_TEMPLATE = "https://media.githubusercontent.com/media/{owner}/{repo}/{ref}/{path}"
+
+# Markup formats GitHub renders instead of showing source lines
+RENDERED_EXTENSIONS = frozenset({
+    ".md", ".markdown", ".mdown", ".mkdn", ".mkd",
+    ".adoc", ".asciidoc", ".asc",
+    ".rst", ".org", ".textile", ".rdoc",
+    ".creole", ".mediawiki", ".wiki", ".pod",
+})
+
+
+def detect_provider(hostname: str) -> Optional[Provider]:
+    """
+    Detect the hosting provider from a remote hostname.
+
+    Self-hosted instances are recognized when the provider name appears
+    in the hostname (e.g. ``gitlab.example.com``).
+
+    Args:
+        hostname: Remote hostname
+
+    Returns:
+        'github', 'gitlab', 'bitbucket', or None if unrecognized
+
+    Examples:
+        >>> detect_provider("ssh.github.com")
+        'github'
+        >>> detect_provider("gitlab.example.com")
+        'gitlab'
+        >>> detect_provider("git.example.com") is None
+        True
+    """
+    hostname = hostname.lower()
+    if hostname in ("github.com", "ssh.github.com", "www.github.com"):
+        return "github"
+    if "gitlab" in hostname:
+        return "gitlab"
+    if "bitbucket" in hostname:
+        return "bitbucket"
+    return None
+
+
+def parse_github_url(
+    remote_url: str,
+    resolve_ssh_aliases: bool = False
+) -> Optional[dict[str, str]]:
     """
     Parse a GitHub remote URL to extract owner and repository name.
 
-    Supports both SSH and HTTPS URL formats:
+    Supports SSH and HTTPS URL formats:
     - SSH: git@github.com:owner/repo.git
-    - HTTPS: https://github.com/owner/repo.git
+    - SSH URL: ssh://git@github.com:2222/owner/repo.git (user and port optional)
+    - HTTPS: https://github.com/owner/repo.git (``http://`` and
+      credentials such as ``https://user@host/...`` also accepted)
 
-    Also supports GitLab and Bitbucket URLs with similar formats.
+    Also supports GitLab and Bitbucket URLs with similar formats. SSH and
+    HTTP(S) remotes are accepted for any host, including self-hosted ones
+    and ``~/.ssh/config`` aliases such as ``git@github-work:owner/repo.git``.
+    A trailing slash is ignored, also after ``.git``.
 
     Args:
         remote_url: Git remote URL
+        resolve_ssh_aliases: Resolve unrecognized SSH hosts through
+            ``~/.ssh/config`` HostName entries (default: False)
 
     Returns:
-        Dictionary with 'owner' and 'repo' keys, or None if parsing fails
+        Dictionary with 'owner', 'repo' and 'hostname' keys ('port' when the
+        URL has one, and 'host' set to the provider for non-GitHub remotes:
+        'gitlab', 'bitbucket' or 'unknown'), or None if parsing fails
 
     Examples:
         >>> parse_github_url("git@github.com:user/myrepo.git")
-        {'owner': 'user', 'repo': 'myrepo'}
-
-        >>> parse_github_url("https://github.com/user/myrepo.git")
-        {'owner': 'user', 'repo': 'myrepo'}
+        {'owner': 'user', 'repo': 'myrepo', 'hostname': 'github.com'}
 
         >>> parse_github_url("https://github.com/user/myrepo")
-        {'owner': 'user', 'repo': 'myrepo'}
+        {'owner': 'user', 'repo': 'myrepo', 'hostname': 'github.com'}
+
+        >>> parse_github_url("ssh://git@github.com:2222/user/myrepo.git")
+        {'owner': 'user', 'repo': 'myrepo', 'hostname': 'github.com', 'port': '2222'}
+
+        >>> parse_github_url("ssh://git@gitlab.example.com/group/repo.git")["host"]
+        'gitlab'
     """
     # SSH format: git@github.com:owner/repo.git
     ssh_pattern = re.compile(
-        r"^git@(?:github\.com|gitlab\.com|bitbucket\.org):([^/]+)/(.+?)(?:\.git)?$"
+        r"^git@(github\.com|gitlab\.com|bitbucket\.org):([^/]+)/(.+?)(?:\.git)?/?$"
     )
 
-    # HTTPS format: https://github.com/owner/repo.git
+    # HTTP(S) format for any host: http[s]://[user@]host[:port]/owner/repo.git
     https_pattern = re.compile(
-        r"^https://(?:github\.com|gitlab\.com|bitbucket\.org)/([^/]+)/(.+?)(?:\.git)?$"
+        r"^https?://(?:[^@/]+@)?([^:/]+)(?::(\d+))?/([^/]+)/(.+?)(?:\.git)?/?$"
+    )
+
+    # SSH URL format: ssh://[user@]host[:port]/owner/repo.git
+    ssh_url_pattern = re.compile(
+        r"^ssh://(?:[^@/]+@)?([^:/]+)(?::(\d+))?/([^/]+)/(.+?)(?:\.git)?/?$"
     )
 
+    # SSH format for any host or alias: [user@]host:owner/repo.git
+    scp_pattern = re.compile(r"^(?:[^@/]+@)?([^:/]+):([^/]+)/(.+?)(?:\.git)?/?$")
+
     # Try SSH pattern
     match = ssh_pattern.match(remote_url)
     if match:
-        return {
-            "owner": match.group(1),
-            "repo": match.group(2)
-        }
+        return _remote_info(match.group(1), match.group(2), match.group(3))
 
-    # Try HTTPS pattern
+    # Try HTTP(S) pattern
     match = https_pattern.match(remote_url)
     if match:
-        return {
-            "owner": match.group(1),
-            "repo": match.group(2)
-        }
+        return _remote_info(match.group(1), match.group(3), match.group(4), port=match.group(2))
+
+    # Try SSH URL pattern
+    match = ssh_url_pattern.match(remote_url)
+    if match:
+        return _remote_info(
+            match.group(1),
+            match.group(3),
+            match.group(4),
+            port=match.group(2),
+            resolve_ssh_aliases=resolve_ssh_aliases
+        )
+
+    # Try SSH pattern for other hosts
+    match = scp_pattern.match(remote_url)
+    if match:
+        return _remote_info(
+            match.group(1),
+            match.group(2),
+            match.group(3),
+            resolve_ssh_aliases=resolve_ssh_aliases
+        )
 
     # Unable to parse
     return None
 
 
+def _remote_info(
+    hostname: str,
+    owner: str,
+    repo: str,
+    port: Optional[str] = None,
+    resolve_ssh_aliases: bool = False
+) -> dict[str, str]:
+    """
+    Build the parse_github_url result for a matched remote.
+
+    Args:
+        hostname: Remote hostname or SSH alias
+        owner: Repository owner
+        repo: Repository name
+        port: Port from an ssh:// or http(s):// URL (optional)
+        resolve_ssh_aliases: Resolve an unrecognized hostname through ~/.ssh/config
+
+    Returns:
+        Remote info dictionary
+    """
+    if resolve_ssh_aliases and detect_provider(hostname) is None:
+        hostname = resolve_ssh_host(hostname)
+
+    info = {"owner": owner, "repo": repo, "hostname": hostname}
+
+    if port:
+        info["port"] = port
+
+    # GitHub remotes carry no 'host' key, matching the original format
+    provider = detect_provider(hostname)
+    if provider != "github":
+        info["host"] = provider or "unknown"
+
+    return info
+
+
+def normalize_remote_url(remote_url: str) -> str:
+    """
+    Normalize a remote URL into a canonical ``https://host/owner/repo`` form.
+
+    Any form accepted by :func:`parse_github_url` (scp-like SSH, ``ssh://``,
+    HTTP(S) with or without credentials, with or without ``.git`` and a
+    trailing slash) maps to the same string, which makes the result usable
+    as a cache key. The hostname is lowercased, GitHub host variants
+    (``ssh.github.com``, ``www.github.com``) collapse to ``github.com``,
+    and ports and credentials are dropped.
+
+    Args:
+        remote_url: Git remote URL
+
+    Returns:
+        Canonical HTTPS repository URL
+
+    Raises:
+        ValueError: If the remote URL cannot be parsed
+
+    Examples:
+        >>> normalize_remote_url("git@github.com:owner/repo.git")
+        'https://github.com/owner/repo'
+        >>> normalize_remote_url("ssh://git@ssh.github.com:443/owner/repo")
+        'https://github.com/owner/repo'
+        >>> normalize_remote_url("https://user@GitLab.example.com/group/repo.git/")
+        'https://gitlab.example.com/group/repo'
+    """
+    parsed = parse_github_url(remote_url)
+    if not parsed:
+        raise ValueError(f"Unsupported remote URL: {remote_url}")
+
+    hostname = parsed["hostname"].lower()
+    if "host" not in parsed:
+        hostname = "github.com"
+
+    return f"https://{hostname}/{parsed['owner']}/{parsed['repo']}"
+
+
+class ParsedLink:
+    """
+    Components of a GitHub blob or tree URL.
+
+    Attributes:
+        owner: Repository owner
+        repo: Repository name
+        kind: 'blob' for files, 'tree' for directories
+        ref: Commit SHA, branch or tag name
+        path: Decoded file or directory path ('' for the repository root)
+        line_range: (start, end) line numbers from the anchor, or None
+    """
+
+    def __init__(
+        self,
+        owner: str,
+        repo: str,
+        kind: str,
+        ref: str,
+        path: str,
+        line_range: Optional[tuple[int, int]] = None
+    ) -> None:
+        self.owner = owner
+        self.repo = repo
+        self.kind = kind
+        self.ref = ref
+        self.path = path
+        self.line_range = line_range
+
+    def to_dict(self) -> dict[str, Any]:
+        """Convert to dictionary."""
+        line_range = None
+        if self.line_range:
+            line_range = {"start": self.line_range[0], "end": self.line_range[1]}
+
+        return {
+            "owner": self.owner,
+            "repo": self.repo,
+            "kind": self.kind,
+            "ref": self.ref,
+            "path": self.path,
+            "lineRange": line_range
+        }
+
+
+def parse_github_link(url: str) -> Optional[ParsedLink]:
+    """
+    Decompose a GitHub blob or tree URL into its components.
+
+    The first segment after ``blob/`` or ``tree/`` is taken as the ref.
+    Branch names containing slashes cannot be told apart from the path
+    without a repository; use ``canonicalize_github_url`` to resolve them
+    against a local clone.
+
+    Args:
+        url: GitHub blob or tree URL
+
+    Returns:
+        ParsedLink, or None if the URL is not a GitHub blob or tree URL
+
+    Examples:
+        >>> link = parse_github_link("https://github.com/user/repo/blob/main/src/a.py#L10-L25")
+        >>> (link.owner, link.repo, link.ref, link.path, link.line_range)
+        ('user', 'repo', 'main', 'src/a.py', (10, 25))
+    """
+    match = GITHUB_LINK_PATTERN.match(url)
+    if not match:
+        return None
+
+    owner, repo, kind, ref_and_path, _query, fragment = match.groups()
+    ref, _, path = ref_and_path.strip("/").partition("/")
+
+    line_range = None
+    anchor = LINE_ANCHOR_PATTERN.match(fragment) if fragment else None
+    if anchor:
+        start = int(anchor.group(1))
+        end = int(anchor.group(2)) if anchor.group(2) else start
+        line_range = (start, end)
+
+    return ParsedLink(
+        owner=owner,
+        repo=repo,
+        kind=kind,
+        ref=unquote(ref),
+        path=unquote(path),
+        line_range=line_range
+    )
+
+
+def encode_url_path(file_path: str) -> str:
+    """
+    Percent-encode each segment of a repository path for use in a URL.
+
+    Spaces, ``#``, ``?``, ``%`` and non-ASCII characters are encoded while
+    the ``/`` separators between segments are preserved.
+
+    Args:
+        file_path: POSIX path relative to the repository root
+
+    Returns:
+        URL-safe path
+
+    Examples:
+        >>> encode_url_path("docs/my file#1.md")
+        'docs/my%20file%231.md'
+        >>> encode_url_path("src/café.py")
+        'src/caf%C3%A9.py'
+    """
+    return "/".join(quote(segment, safe=PATH_SEGMENT_SAFE) for segment in file_path.split("/"))
+
+
+def is_rendered_file(file_path: str) -> bool:
+    """
+    Check if GitHub renders a file as markup rather than showing its source.
+
+    Line anchors on rendered files are lost unless the URL requests the
+    plain source view with ``?plain=1``.
+
+    Args:
+        file_path: File path
+
+    Returns:
+        True if the file extension is a rendered markup format
+
+    Examples:
+        >>> is_rendered_file("docs/README.md")
+        True
+        >>> is_rendered_file("src/file.py")
+        False
+    """
+    _, dot, extension = file_path.rpartition(".")
+    return bool(dot) and f".{extension.lower()}" in RENDERED_EXTENSIONS
+
+
+def build_line_anchor(
+    line_start: int,
+    line_end: Optional[int] = None,
+    provider: Provider = "github"
+) -> str:
+    """
+    Build a line or line-range anchor in the syntax of a hosting provider.
+
+    Args:
+        line_start: First line number (1-based)
+        line_end: Last line number (inclusive), or None for a single line
+        provider: Hosting provider ('github', 'gitlab' or 'bitbucket')
+
+    Returns:
+        URL fragment including the leading '#'
+
+    Raises:
+        ValueError: If the line range or provider is invalid
+
+    Examples:
+        >>> build_line_anchor(10)
+        '#L10'
+        >>> build_line_anchor(10, 25)
+        '#L10-L25'
+        >>> build_line_anchor(10, 25, provider="gitlab")
+        '#L10-25'
+        >>> build_line_anchor(10, 25, provider="bitbucket")
+        '#lines-10:25'
+    """
+    if line_start < 1:
+        raise ValueError(f"Invalid line number: {line_start}. Must be >= 1")
+
+    if line_end is not None and line_end < line_start:
+        raise ValueError(f"Invalid line range: {line_start}-{line_end}. End must be >= start")
+
+    # A range ending on its first line is a single-line anchor
+    if line_end == line_start:
+        line_end = None
+
+    if provider == "github":
+        return f"#L{line_start}" if line_end is None else f"#L{line_start}-L{line_end}"
+    if provider == "gitlab":
+        return f"#L{line_start}" if line_end is None else f"#L{line_start}-{line_end}"
+    if provider == "bitbucket":
+        return f"#lines-{line_start}" if line_end is None else f"#lines-{line_start}:{line_end}"
+
+    raise ValueError(f"Invalid provider: {provider}. Must be 'github', 'gitlab' or 'bitbucket'")
+
+
 def build_github_url(
     owner: str,
     repo: str,
     commit_hash: str,
-    file_path: str
+    file_path: str,
+    is_directory: bool = False,
+    line_start: Optional[int] = None,
+    line_end: Optional[int] = None,
+    plain: Optional[bool] = None
 ) -> str:
     """
-    Build a GitHub permalink URL for a file at a specific commit.
+    Build a GitHub permalink URL for a file or directory at a specific commit.
+
+    Files are linked with ``/blob/`` and directories with ``/tree/``.
+    Rendered markup files (Markdown, AsciiDoc, ...) get ``?plain=1`` when a
+    line anchor is requested so the anchor lands on the source line.
 
     Args:
         owner: Repository owner
         repo: Repository name
-        commit_hash: Full commit SHA
-        file_path: File path relative to repository root
+        commit_hash: Full commit SHA (or branch/tag name for non-pinned links)
+        file_path: File or directory path relative to repository root
+        is_directory: Whether the path is a directory (default: False)
+        line_start: First line to anchor (optional)
+        line_end: Last line of the anchored range (optional)
+        plain: Force (True) or suppress (False) ``?plain=1``; when None it is
+            added for rendered files with a line anchor
 
     Returns:
         GitHub permalink URL
@@ -87,27 +457,199 @@ def build_github_url(
     Examples:
         >>> build_github_url("user", "repo", "abc123...", "src/file.py")
         'https://github.com/user/repo/blob/abc123.../src/file.py'
+
+        >>> build_github_url("user", "repo", "abc123...", "src", is_directory=True)
+        'https://github.com/user/repo/tree/abc123.../src'
+
+        >>> build_github_url("user", "repo", "abc123...", "src/file.py", line_start=10, line_end=25)
+        'https://github.com/user/repo/blob/abc123.../src/file.py#L10-L25'
+
+        >>> build_github_url("user", "repo", "abc123...", "README.md", line_start=10)
+        'https://github.com/user/repo/blob/abc123.../README.md?plain=1#L10'
     """
     # Normalize file path and remove leading slash
     normalized_path = normalize_file_path(file_path).lstrip("/")
 
-    return f"https://github.com/{owner}/{repo}/blob/{commit_hash}/{normalized_path}"
+    kind = "tree" if is_directory else "blob"
+    url = f"https://github.com/{owner}/{repo}/{kind}/{encode_url_path(commit_hash)}"
+
+    # Repository root directory has no path component
+    if normalized_path:
+        url = f"{url}/{encode_url_path(normalized_path)}"
+
+    has_anchor = line_start is not None and not is_directory
+
+    if plain is None:
+        plain = has_anchor and is_rendered_file(normalized_path)
+
+    # Query string must precede the fragment
+    if plain and not is_directory:
+        url += "?plain=1"
+
+    # Line anchors only apply to files
+    if line_start is not None and not is_directory:
+        url += build_line_anchor(line_start, line_end, provider="github")
+
+    return url
+
+
+def build_github_api_contents_url(
+    owner: str,
+    repo: str,
+    file_path: str,
+    ref: Optional[str] = None
+) -> str:
+    """
+    Build a GitHub REST API contents URL for a file or directory.
+
+    Args:
+        owner: Repository owner
+        repo: Repository name
+        file_path: File or directory path relative to repository root
+        ref: Commit SHA, branch or tag name (default: repository default branch)
+
+    Returns:
+        GitHub REST API contents URL
+
+    Examples:
+        >>> build_github_api_contents_url("user", "repo", "src/file.py", "abc123...")
+        'https://api.github.com/repos/user/repo/contents/src/file.py?ref=abc123...'
+    """
+    normalized_path = normalize_file_path(file_path).strip("/")
+    if normalized_path == ".":
+        normalized_path = ""
+
+    url = f"https://api.github.com/repos/{owner}/{repo}/contents"
+    if normalized_path:
+        url = f"{url}/{encode_url_path(normalized_path)}"
+    if ref:
+        url = f"{url}?ref={quote(ref, safe='/')}"
+    return url
+
+
+def build_github_pull_request_url(owner: str, repo: str, number: int) -> str:
+    """
+    Build a GitHub pull request URL.
+
+    Args:
+        owner: Repository owner
+        repo: Repository name
+        number: Pull request number
+
+    Returns:
+        GitHub pull request URL
+
+    Examples:
+        >>> build_github_pull_request_url("user", "repo", 123)
+        'https://github.com/user/repo/pull/123'
+    """
+    return f"https://github.com/{owner}/{repo}/pull/{number}"
+
+
+def build_github_compare_url(owner: str, repo: str, base: str, head: str) -> str:
+    """
+    Build a GitHub compare URL between two revisions.
+
+    Args:
+        owner: Repository owner
+        repo: Repository name
+        base: Base commit SHA (or branch/tag name)
+        head: Head commit SHA (or branch/tag name)
+
+    Returns:
+        GitHub compare URL
+
+    Examples:
+        >>> build_github_compare_url("user", "repo", "abc123", "def456")
+        'https://github.com/user/repo/compare/abc123...def456'
+    """
+    return (
+        f"https://github.com/{owner}/{repo}/compare/"
+        f"{encode_url_path(base)}...{encode_url_path(head)}"
+    )
+
+
+def build_github_archive_url(
+    owner: str,
+    repo: str,
+    ref: str,
+    archive_format: ArchiveFormat = "tar.gz"
+) -> str:
+    """
+    Build a GitHub source archive (tarball or zip) URL for a revision.
+
+    Args:
+        owner: Repository owner
+        repo: Repository name
+        ref: Commit SHA (or branch/tag name)
+        archive_format: 'tar.gz' (default) or 'zip'
+
+    Returns:
+        GitHub archive URL
+
+    Raises:
+        ValueError: If archive_format is invalid
+
+    Examples:
+        >>> build_github_archive_url("user", "repo", "abc123")
+        'https://github.com/user/repo/archive/abc123.tar.gz'
+        >>> build_github_archive_url("user", "repo", "abc123", "zip")
+        'https://github.com/user/repo/archive/abc123.zip'
+    """
+    if archive_format not in ARCHIVE_FORMATS:
+        raise ValueError(f"Invalid archive format: {archive_format}. Must be 'tar.gz' or 'zip'")
+
+    return f"https://github.com/{owner}/{repo}/archive/{encode_url_path(ref)}.{archive_format}"
+
+
+def build_github_lfs_media_url(owner: str, repo: str, ref: str, file_path: str) -> str:
+    """
+    Build the GitHub URL serving the real content of a Git LFS file.
+
+    GitHub's raw URLs return the pointer file for LFS-tracked paths; the
+    media host resolves the pointer and serves the content.
+
+    Args:
+        owner: Repository owner
+        repo: Repository name
+        ref: Commit SHA (or branch/tag name)
+        file_path: File path relative to repository root
+
+    Returns:
+        GitHub LFS media URL
+
+    Examples:
+        >>> build_github_lfs_media_url("user", "repo", "abc123", "assets/video.mp4")
+        'https://media.githubusercontent.com/media/user/repo/abc123/assets/video.mp4'
+    """
+    return LFS_MEDIA_URL_TEMPLATE.format(
+        owner=owner,
+        repo=repo,
+        ref=encode_url_path(ref),
+        path=encode_url_path(normalize_file_path(file_path).strip("/"))
+    )
 
 
 def build_gitlab_url(
     owner: str,
     repo: str,
     commit_hash: str,
-    file_path: str
+    file_path: str,
+    is_directory: bool = False,
+    line_start: Optional[int] = None,
+    line_end: Optional[int] = None
 ) -> str:
     """
-    Build a GitLab permalink URL for a file at a specific commit.
+    Build a GitLab permalink URL for a file or directory at a specific commit.
 
     Args:
         owner: Repository owner
         repo: Repository name
         commit_hash: Full commit SHA
-        file_path: File path relative to repository root
+        file_path: File or directory path relative to repository root
+        is_directory: Whether the path is a directory (default: False)
+        line_start: First line to anchor (optional)
+        line_end: Last line of the anchored range (optional)
 
     Returns:
         GitLab permalink URL
@@ -117,14 +659,22 @@ def build_gitlab_url(
         'https://gitlab.com/user/repo/-/blob/abc123.../src/file.py'
     """
     normalized_path = normalize_file_path(file_path).lstrip("/")
-    return f"https://gitlab.com/{owner}/{repo}/-/blob/{commit_hash}/{normalized_path}"
+    kind = "tree" if is_directory else "blob"
+    url = f"https://gitlab.com/{owner}/{repo}/-/{kind}/{commit_hash}"
+    if normalized_path:
+        url = f"{url}/{encode_url_path(normalized_path)}"
+    if line_start is not None and not is_directory:
+        url += build_line_anchor(line_start, line_end, provider="gitlab")
+    return url
 
 
 def build_bitbucket_url(
     owner: str,
     repo: str,
     commit_hash: str,
-    file_path: str
+    file_path: str,
+    line_start: Optional[int] = None,
+    line_end: Optional[int] = None
 ) -> str:
     """
     Build a Bitbucket permalink URL for a file at a specific commit.
@@ -134,6 +684,8 @@ def build_bitbucket_url(
         repo: Repository name
         commit_hash: Full commit SHA
         file_path: File path relative to repository root
+        line_start: First line to anchor (optional)
+        line_end: Last line of the anchored range (optional)
 
     Returns:
         Bitbucket permalink URL
@@ -143,12 +695,33 @@ def build_bitbucket_url(
         'https://bitbucket.org/user/repo/src/abc123.../src/file.py'
     """
     normalized_path = normalize_file_path(file_path).lstrip("/")
-    return f"https://bitbucket.org/{owner}/{repo}/src/{commit_hash}/{normalized_path}"
+    url = f"https://bitbucket.org/{owner}/{repo}/src/{commit_hash}/{encode_url_path(normalized_path)}"
+    if line_start is not None:
+        url += build_line_anchor(line_start, line_end, provider="bitbucket")
+    return url
 
 
 __all__ = [
+    "Provider",
+    "ArchiveFormat",
+    "ARCHIVE_FORMATS",
+    "RENDERED_EXTENSIONS",
+    "GITHUB_LINK_PATTERN",
+    "ParsedLink",
+    "detect_provider",
     "parse_github_url",
+    "normalize_remote_url",
+    "parse_github_link",
+    "encode_url_path",
+    "is_rendered_file",
+    "build_line_anchor",
     "build_github_url",
+    "build_github_api_contents_url",
+    "build_github_pull_request_url",
+    "build_github_compare_url",
+    "build_github_archive_url",
+    "LFS_MEDIA_URL_TEMPLATE",
+    "build_github_lfs_media_url",
     "build_gitlab_url",
     "build_bitbucket_url",
 ]
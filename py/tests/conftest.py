@@ -0,0 +1,76 @@
+"""
+Shared fixtures: throwaway Git repositories built with the git CLI.
+"""
+
+import os
+import subprocess
+from pathlib import Path
+from typing import Callable
+
+import pytest
+
+# Fixed identity and dates so commit SHAs do not depend on the machine
+GIT_ENV = {
+    "GIT_AUTHOR_NAME": "Test Author",
+    "GIT_AUTHOR_EMAIL": "author@example.com",
+    "GIT_AUTHOR_DATE": "2024-01-15T10:30:00Z",
+    "GIT_COMMITTER_NAME": "Test Author",
+    "GIT_COMMITTER_EMAIL": "author@example.com",
+    "GIT_COMMITTER_DATE": "2024-01-15T10:30:00Z",
+    "GIT_CONFIG_NOSYSTEM": "1",
+}
+
+
+def git(repo: Path, *args: str) -> str:
+    """Run a Git command in repo and return its stripped stdout."""
+    result = subprocess.run(
+        ["git", *args],
+        cwd=repo,
+        capture_output=True,
+        text=True,
+        check=True,
+        env={**os.environ, **GIT_ENV, "HOME": str(repo)}
+    )
+    return result.stdout.strip()
+
+
+def write(repo: Path, path: str, content: str) -> None:
+    """Write a file in repo, creating parent directories."""
+    target = repo / path
+    target.parent.mkdir(parents=True, exist_ok=True)
+    target.write_text(content)
+
+
+def commit_files(repo: Path, files: dict[str, str], message: str = "Update") -> str:
+    """Write files, commit them all and return the new commit SHA."""
+    for path, content in files.items():
+        write(repo, path, content)
+    git(repo, "add", "-A")
+    git(repo, "commit", "-q", "-m", message)
+    return git(repo, "rev-parse", "HEAD")
+
+
+def shallow_clone(source: Path, target: Path) -> Path:
+    """Clone source with only its newest commit."""
+    git(source.parent, "clone", "-q", "--depth=1", f"file://{source}", str(target))
+    return target
+
+
+@pytest.fixture
+def git_repo(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> Path:
+    """An empty repository on branch main with a GitHub origin."""
+    for name, value in GIT_ENV.items():
+        monkeypatch.setenv(name, value)
+    monkeypatch.setenv("HOME", str(tmp_path))
+
+    repo = tmp_path / "repo"
+    repo.mkdir()
+    git(repo, "init", "-q", "-b", "main")
+    git(repo, "remote", "add", "origin", "https://github.com/owner/repo.git")
+    return repo
+
+
+@pytest.fixture
+def make_commit(git_repo: Path) -> Callable[..., str]:
+    """Commit files to git_repo: make_commit({"path": "content"}, "message")."""
+    return lambda files, message="Update": commit_files(git_repo, files, message)
@@ -0,0 +1,78 @@
+"""Tests for line mapping and blame."""
+
+from pathlib import Path
+
+import pytest
+
+from conftest import git, shallow_clone
+from git_identify.blame import blame_file, map_line
+from git_identify.errors import FileNotFoundError
+
+
+def test_map_line_shifts_past_inserted_lines(git_repo: Path, make_commit) -> None:
+    first = make_commit({"app.py": "a\nb\nc\n"})
+    second = make_commit({"app.py": "new\na\nb\nc\n"})
+
+    mapping = map_line("app.py", 3, first, repo_path=str(git_repo))
+
+    assert mapping is not None
+    assert mapping.to_dict() == {"path": "app.py", "line": 4, "commit": second}
+
+
+def test_map_line_changed_line_has_no_counterpart(git_repo: Path, make_commit) -> None:
+    first = make_commit({"app.py": "a\nb\nc\n"})
+    make_commit({"app.py": "a\nB\nc\n"})
+
+    assert map_line("app.py", 2, first, repo_path=str(git_repo)) is None
+
+
+def test_map_line_follows_rename(git_repo: Path, make_commit) -> None:
+    first = make_commit({"old.py": "a\nb\nc\nd\ne\n"})
+    git(git_repo, "mv", "old.py", "new.py")
+    make_commit({})
+
+    mapping = map_line("old.py", 2, first, repo_path=str(git_repo))
+
+    assert mapping is not None
+    assert (mapping.path, mapping.line) == ("new.py", 2)
+
+
+@pytest.mark.parametrize("content", ["a\nb\nc\n", "a\nb\nc"])
+@pytest.mark.parametrize("changed", [False, True])
+def test_map_line_rejects_line_past_end(
+    git_repo: Path, make_commit, content: str, changed: bool
+) -> None:
+    first = make_commit({"app.py": content})
+    if changed:
+        make_commit({"app.py": "new\n" + content})
+
+    assert map_line("app.py", 3, first, repo_path=str(git_repo)) is not None
+    with pytest.raises(ValueError, match="has 3 lines"):
+        map_line("app.py", 4, first, repo_path=str(git_repo))
+
+
+def test_map_line_rejects_empty_file(git_repo: Path, make_commit) -> None:
+    first = make_commit({"empty.txt": ""})
+
+    with pytest.raises(ValueError, match="has 0 lines"):
+        map_line("empty.txt", 1, first, repo_path=str(git_repo))
+
+
+def test_map_line_missing_file(git_repo: Path, make_commit) -> None:
+    first = make_commit({"app.py": "a\n"})
+
+    with pytest.raises(FileNotFoundError):
+        map_line("other.py", 1, first, repo_path=str(git_repo))
+
+
+def test_blame_flags_shallow_boundary(git_repo: Path, make_commit, tmp_path: Path) -> None:
+    root = make_commit({"app.py": "a\n"})
+    head = make_commit({"app.py": "a\nb\n"})
+    clone = shallow_clone(git_repo, tmp_path / "clone")
+
+    def flags(repo: Path) -> list[tuple[str, bool, bool]]:
+        hunks = blame_file("app.py", repo_path=str(repo))
+        return [(hunk.oid, hunk.boundary, hunk.shallow_boundary) for hunk in hunks]
+
+    assert flags(clone) == [(head, True, True)]
+    assert flags(git_repo) == [(root, True, False), (head, False, False)]
@@ -0,0 +1,72 @@
+"""Tests for git_identify.codeowners."""
+
+from pathlib import Path
+
+import pytest
+
+from git_identify.codeowners import CodeOwnersRule, get_code_owners, parse_codeowners
+
+
+@pytest.mark.parametrize(
+    ("pattern", "path", "expected"),
+    [
+        ("apps/", "apps", True),
+        ("apps/", "apps/web/index.js", True),
+        ("apps/", "lib/apps/index.js", True),
+        ("apps/", "apps.js", False),
+        ("/apps/", "lib/apps", False),
+        ("docs/*", "docs/readme.md", True),
+        ("docs/*", "docs/guide/intro.md", False),
+        ("*.js", "src/app.js", True),
+        ("*.js", "src/app.jsx", False),
+        ("/build/logs", "build/logs/out.txt", True),
+        ("/build/logs", "src/build/logs", False),
+        ("docs/**/*.md", "docs/a/b/c.md", True),
+        ("docs/**/*.md", "docs/c.md", True),
+        ("a?c", "abc", True),
+        ("a?c", "a/c", False),
+    ]
+)
+def test_rule_matches(pattern: str, path: str, expected: bool) -> None:
+    assert CodeOwnersRule(pattern, [], 1).matches(path) is expected
+
+
+def test_parse_codeowners_skips_comments() -> None:
+    rules = parse_codeowners("# Owners\n\n*.js @org/web # web team\n\\#notes @a\n/docs/\n")
+
+    assert [(rule.pattern, rule.owners, rule.line) for rule in rules] == [
+        ("*.js", ["@org/web"], 3),
+        ("#notes", ["@a"], 4),
+        ("/docs/", [], 5),
+    ]
+
+
+def test_last_matching_rule_decides(git_repo: Path, make_commit) -> None:
+    make_commit({
+        ".github/CODEOWNERS": "* @org/all\napps/ @org/apps\n/apps/legacy/\n",
+        "CODEOWNERS": "* @ignored\n",
+        "apps/web/index.js": "",
+    })
+    repo = str(git_repo)
+
+    owners = get_code_owners("apps/web/index.js", repo_path=repo)
+    assert owners is not None and owners.rule is not None
+    assert (owners.owners, owners.rule.line) == (["@org/apps"], 2)
+    assert owners.source == ".github/CODEOWNERS"
+
+    owners = get_code_owners("apps", repo_path=repo)
+    assert owners is not None and owners.owners == ["@org/apps"]
+
+    owners = get_code_owners("apps/legacy/old.js", repo_path=repo)
+    assert owners is not None and owners.rule is not None
+    assert (owners.owners, owners.rule.line) == ([], 3)
+
+
+def test_working_tree_and_missing_file(git_repo: Path, make_commit) -> None:
+    make_commit({"README.md": "readme\n"})
+
+    assert get_code_owners("README.md", repo_path=str(git_repo)) is None
+
+    (git_repo / "CODEOWNERS").write_text("*.md @docs\n")
+    owners = get_code_owners("README.md", None, str(git_repo))
+    assert owners is not None and owners.owners == ["@docs"]
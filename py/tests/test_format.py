@@ -0,0 +1,19 @@
+"""Tests for git_identify.utils.format."""
+
+from git_identify.utils.format import format_org_link
+
+
+def test_org_link_protects_brackets() -> None:
+    url = "https://github.com/owner/repo/blob/abc1234/routes/[[id]].tsx"
+
+    assert format_org_link(url, "routes/[[id]].tsx") == (
+        "[[https://github.com/owner/repo/blob/abc1234/routes/%5B%5Bid%5D%5D.tsx]"
+        "[routes/{{id}}.tsx]]"
+    )
+
+
+def test_org_link_protects_brackets_in_default_text() -> None:
+    link = format_org_link("https://github.com/owner/repo/blob/abc1234/docs/a]]b.md")
+
+    assert link.endswith("[docs/a}}b.md@abc1234]]")
+    assert link.count("]]") == 1
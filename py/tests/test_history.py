@@ -0,0 +1,49 @@
+"""Tests for commit history."""
+
+from pathlib import Path
+
+from conftest import shallow_clone
+from git_identify.history import count_commits, get_commit_info, get_file_history
+
+
+def test_file_history_ends_at_root(git_repo: Path, make_commit) -> None:
+    first = make_commit({"app.py": "one\n"})
+    second = make_commit({"app.py": "two\n"})
+
+    history = list(get_file_history("app.py", repo_path=str(git_repo)))
+
+    assert [info.oid for info in history] == [second, first]
+    assert [info.parents for info in history] == [[first], []]
+    assert not any(info.shallow_boundary for info in history)
+
+
+def test_file_history_flags_shallow_boundary(
+    git_repo: Path, make_commit, tmp_path: Path
+) -> None:
+    make_commit({"app.py": "one\n"})
+    head = make_commit({"app.py": "two\n"})
+    clone = shallow_clone(git_repo, tmp_path / "clone")
+
+    [info] = get_file_history("app.py", repo_path=str(clone))
+
+    assert (info.oid, info.parents, info.shallow_boundary) == (head, [], True)
+    assert info.to_dict()["shallowBoundary"] is True
+    assert get_commit_info(head, repo_path=str(clone)).shallow_boundary
+
+
+def test_unborn_head_has_no_history(git_repo: Path) -> None:
+    (git_repo / "app.py").write_text("one\n")
+
+    assert list(get_file_history("app.py", repo_path=str(git_repo))) == []
+    assert count_commits(repo_path=str(git_repo)) == 0
+    assert count_commits(path_filter="app.py", repo_path=str(git_repo)) == 0
+
+
+def test_count_commits(git_repo: Path, make_commit) -> None:
+    first = make_commit({"app.py": "one\n"})
+    make_commit({"other.py": "one\n"})
+    make_commit({"app.py": "two\n"})
+
+    assert count_commits(repo_path=str(git_repo)) == 3
+    assert count_commits(path_filter="app.py", repo_path=str(git_repo)) == 2
+    assert count_commits(f"{first}..HEAD", repo_path=str(git_repo)) == 2
@@ -0,0 +1,181 @@
+"""Tests for git_identify.permalink."""
+
+import hashlib
+from pathlib import Path
+from urllib.parse import quote
+
+import pytest
+
+from conftest import git
+from git_identify.errors import FileNotFoundError, GitError
+from git_identify.history import get_file_history
+from git_identify.metadata.local import get_local_metadata
+from git_identify.permalink import (
+    canonicalize_github_url,
+    check_drift,
+    find_pull_request_for_commit,
+    generate_github_url,
+    locate_github_link,
+)
+from git_identify.status import get_file_status
+from git_identify.utils.git import get_blob_oid
+
+# File names that shell or shlex quoting would mangle
+SPECIAL_NAMES = ["routes/$id.tsx", "routes/`id`.tsx", 'routes/q"uote.tsx', "routes/it's.tsx"]
+
+
+@pytest.mark.parametrize("name", SPECIAL_NAMES)
+def test_special_characters_in_paths(git_repo: Path, make_commit, name: str) -> None:
+    commit = make_commit({name: "content\n"})
+    repo = str(git_repo)
+
+    assert get_file_status(name, repo).worktree == "unmodified"
+    assert get_blob_oid(repo, name) is not None
+    assert check_drift(name, repo) == "identical"
+    assert [info.oid for info in get_file_history(name, repo_path=repo)] == [commit]
+    assert get_local_metadata(repo, name)["commitHash"] == commit
+
+    url = f"https://github.com/owner/repo/blob/main/{quote(name)}"
+    location = locate_github_link(url, repo_path=repo)
+    assert location.path == name
+    assert location.status == "identical"
+
+
+def test_link_path_is_not_run_by_a_shell(git_repo: Path, make_commit, tmp_path: Path) -> None:
+    make_commit({"README.md": "readme\n"})
+    marker = tmp_path / "pwned"
+
+    url = f"https://github.com/owner/repo/blob/main/{quote(f'$(touch {marker})')}"
+    with pytest.raises(FileNotFoundError):
+        locate_github_link(url, repo_path=str(git_repo))
+
+    assert not marker.exists()
+
+
+def test_generate_github_url_pins_commit(git_repo: Path, make_commit) -> None:
+    commit = make_commit({"src/app.py": "print()\n"})
+
+    url = generate_github_url("src/app.py", repo_path=str(git_repo), line_start=1)
+
+    assert url == f"https://github.com/owner/repo/blob/{commit}/src/app.py#L1"
+
+
+def test_canonicalize_github_url_pins_branch(git_repo: Path, make_commit) -> None:
+    commit = make_commit({"routes/$id.tsx": "content\n"})
+    git(git_repo, "branch", "feature/x")
+
+    url = "https://github.com/owner/repo/blob/feature/x/routes/%24id.tsx?plain=1#L2"
+
+    assert canonicalize_github_url(url, repo_path=str(git_repo)) == (
+        f"https://github.com/owner/repo/blob/{commit}/routes/%24id.tsx?plain=1#L2"
+    )
+
+
+@pytest.mark.parametrize("ref", ["main~1", "main^", "@{-1}", "--all", "HEAD:README.md"])
+def test_canonicalize_github_url_rejects_revision_syntax(
+    git_repo: Path, make_commit, ref: str
+) -> None:
+    make_commit({"README.md": "one\n"})
+    make_commit({"README.md": "two\n"})
+
+    with pytest.raises(GitError, match="Cannot resolve ref"):
+        canonicalize_github_url(
+            f"https://github.com/owner/repo/blob/{quote(ref, safe='')}/README.md",
+            repo_path=str(git_repo)
+        )
+
+
+def test_canonicalize_github_url_ref_is_not_run_by_a_shell(
+    git_repo: Path, make_commit, tmp_path: Path
+) -> None:
+    make_commit({"README.md": "readme\n"})
+    marker = tmp_path / "pwned"
+
+    url = f"https://github.com/owner/repo/blob/{quote(f'$(touch {marker})', safe='')}/README.md"
+    with pytest.raises(GitError):
+        canonicalize_github_url(url, repo_path=str(git_repo))
+
+    assert not marker.exists()
+
+
+def test_symlink_drift_agrees_with_check_drift(git_repo: Path, make_commit) -> None:
+    (git_repo / "target.txt").write_text("target\n")
+    (git_repo / "other.txt").write_text("other\n")
+    (git_repo / "link").symlink_to("target.txt")
+    make_commit({})
+    repo = str(git_repo)
+    url = "https://github.com/owner/repo/blob/main/link"
+
+    location = locate_github_link(url, repo_path=repo)
+    assert (location.status, check_drift("link", repo)) == ("identical", "identical")
+    assert location.working_hash == location.blob_hash
+
+    (git_repo / "link").unlink()
+    (git_repo / "link").symlink_to("other.txt")
+
+    location = locate_github_link(url, repo_path=repo)
+    assert (location.status, check_drift("link", repo)) == ("modified", "modified")
+    assert location.working_hash == hashlib.sha1(b"blob 9\0other.txt").hexdigest()
+
+
+@pytest.fixture
+def unpushed_commit(git_repo: Path, make_commit) -> tuple[str, str]:
+    """A commit on origin/main followed by one that is not pushed."""
+    pushed = make_commit({"app.py": "one\n"})
+    git(git_repo, "update-ref", "refs/remotes/origin/main", pushed)
+    return pushed, make_commit({"app.py": "two\n"})
+
+
+def test_unpushed_warn_links_commit(git_repo: Path, unpushed_commit: tuple[str, str]) -> None:
+    _, local = unpushed_commit
+
+    with pytest.warns(UserWarning, match=f"Commit {local} is not on remote 'origin'"):
+        url = generate_github_url("app.py", repo_path=str(git_repo), unpushed="warn")
+
+    assert url == f"https://github.com/owner/repo/blob/{local}/app.py"
+
+
+def test_unpushed_fallback_links_pushed_ancestor(
+    git_repo: Path, unpushed_commit: tuple[str, str]
+) -> None:
+    pushed, _ = unpushed_commit
+
+    url = generate_github_url("app.py", repo_path=str(git_repo), unpushed="fallback")
+
+    assert url == f"https://github.com/owner/repo/blob/{pushed}/app.py"
+
+
+def test_unpushed_error_raises(git_repo: Path, unpushed_commit: tuple[str, str]) -> None:
+    with pytest.raises(GitError, match="not on remote"):
+        generate_github_url("app.py", repo_path=str(git_repo), unpushed="error")
+
+
+@pytest.fixture
+def merged_pull_request(git_repo: Path, make_commit) -> tuple[str, str]:
+    """A direct commit on main, then a branch commit merged as pull request #5."""
+    make_commit({"README.md": "readme\n"})
+    direct = make_commit({"main.py": "direct\n"}, "Direct commit")
+    git(git_repo, "checkout", "-q", "-b", "feature")
+    branch = make_commit({"feature.py": "feature\n"}, "Add feature")
+    git(git_repo, "checkout", "-q", "main")
+    git(git_repo, "merge", "-q", "--no-ff", "-m", "Merge pull request #5 from owner/feature", "feature")
+    return direct, branch
+
+
+def test_pull_request_from_merge_of_branch(
+    git_repo: Path, merged_pull_request: tuple[str, str]
+) -> None:
+    _, branch = merged_pull_request
+
+    pr = find_pull_request_for_commit(branch, repo_path=str(git_repo))
+
+    assert (pr.number, pr.source) == (5, "merge")
+    assert pr.url == "https://github.com/owner/repo/pull/5"
+
+
+def test_pull_request_not_credited_for_direct_commit(
+    git_repo: Path, merged_pull_request: tuple[str, str]
+) -> None:
+    direct, _ = merged_pull_request
+
+    assert find_pull_request_for_commit(direct, repo_path=str(git_repo)) is None
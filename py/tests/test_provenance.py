@@ -0,0 +1,18 @@
+"""Tests for git_identify.provenance."""
+
+from pathlib import Path
+
+from conftest import git
+from git_identify.provenance import create_provenance_record
+
+
+def test_fingerprint_follows_commit(git_repo: Path, make_commit) -> None:
+    root = make_commit({"app.py": "one\n"})
+    git(git_repo, "checkout", "-q", "--orphan", "other")
+    other = make_commit({"app.py": "other\n"})
+    git(git_repo, "checkout", "-q", "main")
+
+    assert create_provenance_record("app.py", repo_path=str(git_repo)).repo_fingerprint == root
+
+    record = create_provenance_record("app.py", other, repo_path=str(git_repo))
+    assert (record.commit, record.repo_fingerprint, record.dirty) == (other, other, True)
@@ -0,0 +1,54 @@
+"""Tests for HEAD and ref lookups."""
+
+from pathlib import Path
+
+from conftest import git
+from git_identify.refs import get_head_info, get_repo_fingerprint
+
+
+def test_head_info_on_branch(git_repo: Path, make_commit) -> None:
+    commit = make_commit({"app.py": "one\n"})
+    git(git_repo, "update-ref", "refs/remotes/origin/main", commit)
+    git(git_repo, "branch", "--set-upstream-to=origin/main")
+
+    assert get_head_info(str(git_repo)).to_dict() == {
+        "branch": "main",
+        "oid": commit,
+        "detached": False,
+        "upstream": "origin/main"
+    }
+
+
+def test_head_info_detached(git_repo: Path, make_commit) -> None:
+    commit = make_commit({"app.py": "one\n"})
+    git(git_repo, "checkout", "-q", "--detach")
+
+    info = get_head_info(str(git_repo))
+
+    assert (info.branch, info.oid, info.detached, info.upstream) == (None, commit, True, None)
+
+
+def test_head_info_unborn_branch(git_repo: Path) -> None:
+    assert get_head_info(str(git_repo)).to_dict() == {
+        "branch": "main",
+        "oid": None,
+        "detached": False,
+        "upstream": None
+    }
+
+
+def test_repo_fingerprint_picks_lowest_root(git_repo: Path, make_commit) -> None:
+    first = make_commit({"app.py": "one\n"})
+    git(git_repo, "checkout", "-q", "--orphan", "other")
+    git(git_repo, "rm", "-q", "-r", "--cached", ".")
+    second = make_commit({"lib.py": "two\n"})
+    git(git_repo, "checkout", "-q", "main")
+    git(git_repo, "merge", "-q", "--allow-unrelated-histories", "-m", "Merge", "other")
+
+    # Both roots share the fixture's commit date, so only the SHA can order them
+    assert get_repo_fingerprint(repo_path=str(git_repo)) == min(first, second)
+    assert get_repo_fingerprint(second, repo_path=str(git_repo)) == second
+
+
+def test_repo_fingerprint_unborn_head(git_repo: Path) -> None:
+    assert get_repo_fingerprint(repo_path=str(git_repo)) is None
@@ -0,0 +1,104 @@
+"""Tests for working-tree status."""
+
+from pathlib import Path
+
+from conftest import git, write
+from git_identify.status import (
+    explain_ignore,
+    get_directory_status,
+    get_file_status,
+    get_repo_status,
+    is_path_ignored,
+    list_untracked,
+)
+
+
+def test_file_status_unmodified(git_repo: Path, make_commit) -> None:
+    make_commit({"app.py": "one\n"})
+
+    status = get_file_status("app.py", str(git_repo))
+
+    assert (status.index, status.worktree, status.is_staged, status.is_unstaged) == (
+        "unmodified", "unmodified", False, False
+    )
+
+
+def test_file_status_separates_staged_and_unstaged(git_repo: Path, make_commit) -> None:
+    make_commit({"app.py": "one\n"})
+    write(git_repo, "app.py", "two\n")
+    git(git_repo, "add", "app.py")
+    write(git_repo, "app.py", "three\n")
+
+    status = get_file_status("app.py", str(git_repo))
+
+    assert (status.index, status.worktree) == ("modified", "modified")
+    assert status.is_staged and status.is_unstaged
+
+
+def test_file_status_reports_rename_source(git_repo: Path, make_commit) -> None:
+    make_commit({"old.py": "content\n"})
+    git(git_repo, "mv", "old.py", "new.py")
+
+    status = get_file_status("new.py", str(git_repo))
+
+    assert (status.index, status.rename_source) == ("renamed", "old.py")
+
+
+def test_file_status_untracked(git_repo: Path, make_commit) -> None:
+    make_commit({"app.py": "one\n"})
+    write(git_repo, "notes.txt", "draft\n")
+
+    assert get_file_status("notes.txt", str(git_repo)).worktree == "untracked"
+
+
+def test_file_status_flags_skip_worktree(git_repo: Path, make_commit) -> None:
+    make_commit({"app.py": "one\n"})
+    git(git_repo, "update-index", "--skip-worktree", "app.py")
+    write(git_repo, "app.py", "edited\n")
+
+    status = get_file_status("app.py", str(git_repo))
+
+    assert status.worktree == "unmodified"
+    assert status.to_dict()["mayBeStale"] is True
+
+
+def test_repo_status_lists_changed_files(git_repo: Path, make_commit) -> None:
+    make_commit({"a.py": "a\n", "b.py": "b\n"})
+    write(git_repo, "a.py", "changed\n")
+    write(git_repo, "c.py", "new\n")
+
+    statuses = get_repo_status(repo_path=str(git_repo))
+
+    assert [(s.path, s.index, s.worktree) for s in statuses] == [
+        ("a.py", "unmodified", "modified"),
+        ("c.py", "untracked", "untracked"),
+    ]
+
+
+def test_directory_status_counts(git_repo: Path, make_commit) -> None:
+    make_commit({"src/a.py": "a\n", "src/b.py": "b\n", "docs/c.md": "c\n"})
+    write(git_repo, "src/a.py", "changed\n")
+    write(git_repo, "src/new.py", "new\n")
+
+    status = get_directory_status("src", str(git_repo))
+
+    assert (status.clean, status.modified, status.untracked) == (1, 1, 1)
+
+
+def test_list_untracked_skips_ignored(git_repo: Path, make_commit) -> None:
+    make_commit({".gitignore": "*.log\n"})
+    write(git_repo, "notes.txt", "draft\n")
+    write(git_repo, "debug.log", "log\n")
+
+    assert list_untracked(repo_path=str(git_repo)) == ["notes.txt"]
+
+
+def test_explain_ignore_names_rule(git_repo: Path, make_commit) -> None:
+    make_commit({".gitignore": "# build output\ndist/\n*.log\n"})
+
+    rule = explain_ignore("debug.log", str(git_repo))
+
+    assert is_path_ignored("debug.log", str(git_repo))
+    assert not is_path_ignored("app.py", str(git_repo))
+    assert (rule.source, rule.line, rule.pattern) == (".gitignore", 3, "*.log")
+    assert explain_ignore("app.py", str(git_repo)) is None
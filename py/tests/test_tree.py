@@ -0,0 +1,35 @@
+"""Tests for files and blobs at a commit."""
+
+from pathlib import Path
+
+from conftest import git, write
+from git_identify.tree import get_file_text, is_binary
+
+
+def test_is_binary_reads_attributes_at_commit(git_repo: Path, make_commit) -> None:
+    old = make_commit({".gitattributes": "*.dat binary\n", "data.dat": "plain text\n"})
+    git(git_repo, "rm", "-q", ".gitattributes")
+    git(git_repo, "commit", "-q", "-m", "Drop attributes")
+
+    assert is_binary("data.dat", old, str(git_repo))
+    assert not is_binary("data.dat", "HEAD", str(git_repo))
+
+
+def test_is_binary_ignores_uncommitted_attributes(git_repo: Path, make_commit) -> None:
+    make_commit({"data.dat": "plain text\n"})
+    (git_repo / ".gitattributes").write_text("*.dat binary\n")
+
+    assert not is_binary("data.dat", "HEAD", str(git_repo))
+
+
+def test_file_text_reads_encoding_attribute_at_commit(git_repo: Path, make_commit) -> None:
+    make_commit({"data.csv": "name,value\n"})
+    write(git_repo, ".gitattributes", "*.csv working-tree-encoding=UTF-16\n")
+    git(git_repo, "add", ".gitattributes")
+    git(git_repo, "commit", "-q", "-m", "Add attributes")
+    old = git(git_repo, "rev-parse", "HEAD")
+    git(git_repo, "rm", "-q", ".gitattributes")
+    git(git_repo, "commit", "-q", "-m", "Drop attributes")
+
+    assert get_file_text("data.csv", old, str(git_repo)).working_tree_encoding == "UTF-16"
+    assert get_file_text("data.csv", "HEAD", str(git_repo)).working_tree_encoding is None